@@ -1,12 +1,15 @@
 use crate::clipboard::ClipboardManager;
 use crate::events::Action;
-use crate::state::{AppState, MessageLevel};
-use crate::cli::BitwardenCli;
+use crate::item_format::CopyFormat;
+use crate::state::{AppState, DetailField, MessageLevel};
+use crate::backend::Backend;
+use crate::types::VaultItem;
 
 /// Result of copy action handling
 pub enum CopyResult {
     Handled,
     NeedTotpFetch,
+    NeedReprompt, // The master password must be re-entered first
     NotHandled,
 }
 
@@ -15,7 +18,7 @@ pub fn handle_copy(
     action: &Action,
     state: &mut AppState,
     clipboard: Option<&mut ClipboardManager>,
-    cli: Option<&BitwardenCli>,
+    backend: Option<&Backend>,
 ) -> CopyResult {
     match action {
         Action::CopyUsername => {
@@ -23,18 +26,66 @@ pub fn handle_copy(
             CopyResult::Handled
         }
         Action::CopyPassword => {
-            copy_password(state, clipboard);
-            CopyResult::Handled
+            copy_password(state, clipboard, None)
+        }
+        Action::QuickCopyPassword(position) => {
+            copy_password(state, clipboard, Some(*position))
         }
         Action::CopyTotp => {
-            copy_totp(state, clipboard, cli)
+            copy_totp(state, clipboard, backend)
         }
         Action::CopyCardNumber => {
-            copy_card_number(state, clipboard);
-            CopyResult::Handled
+            copy_card_number(state, clipboard)
         }
         Action::CopyCardCvv => {
-            copy_card_cvv(state, clipboard);
+            copy_card_cvv(state, clipboard)
+        }
+        Action::CopyCardExpiry => {
+            copy_card_expiry(state, clipboard);
+            CopyResult::Handled
+        }
+        Action::CopyCardAll => {
+            copy_card_all(state, clipboard)
+        }
+        Action::CopyIdentityName => {
+            copy_identity_field(state, clipboard, DetailField::IdentityName);
+            CopyResult::Handled
+        }
+        Action::CopyIdentityAddress => {
+            copy_identity_field(state, clipboard, DetailField::IdentityAddress);
+            CopyResult::Handled
+        }
+        Action::CopyIdentityPhone => {
+            copy_identity_field(state, clipboard, DetailField::IdentityPhone);
+            CopyResult::Handled
+        }
+        Action::CopyIdentityEmail => {
+            copy_identity_field(state, clipboard, DetailField::IdentityEmail);
+            CopyResult::Handled
+        }
+        Action::CopyFocusedField => {
+            match state.focused_detail_field() {
+                Some(field) => copy_field(state, clipboard, backend, field),
+                None => CopyResult::Handled,
+            }
+        }
+        Action::CopyDetailField(field) => {
+            copy_field(state, clipboard, backend, *field)
+        }
+        Action::ToggleRevealField => {
+            toggle_reveal_focused_field(state)
+        }
+        Action::ShowTotpQrCode => {
+            show_totp_qr_code(state)
+        }
+        Action::ShowFieldQrCode => {
+            show_field_qr_code(state)
+        }
+        Action::CopyItemAs(format) => {
+            copy_item_as(state, clipboard, *format)
+        }
+        Action::CopyDashboardTotp => {
+            copy_dashboard_totp(state, clipboard);
             CopyResult::Handled
         }
         _ => {
@@ -72,26 +123,53 @@ fn copy_username(state: &mut AppState, clipboard: Option<&mut ClipboardManager>)
     }
 }
 
-fn copy_password(state: &mut AppState, clipboard: Option<&mut ClipboardManager>) {
+/// The item at `position` in the filtered list, or the selected item
+fn target_item(state: &AppState, position: Option<usize>) -> Option<&VaultItem> {
+    match position {
+        Some(position) => state.vault.filtered_item(position),
+        None => state.selected_item(),
+    }
+}
+
+/// Whether the master password must be re-entered before using a secret of the target item,
+/// consuming the grant a verified re-prompt left for it
+fn reprompt_pending(state: &mut AppState, position: Option<usize>) -> bool {
+    let Some(item_id) = target_item(state, position).filter(|item| item.requires_reprompt()).map(|item| item.id.clone()) else {
+        return false;
+    };
+    !state.consume_reprompt_grant(&item_id)
+}
+
+/// Copy the password of the item at `position` in the filtered list, or of the selected item
+fn copy_password(state: &mut AppState, clipboard: Option<&mut ClipboardManager>, position: Option<usize>) -> CopyResult {
     if !state.secrets_available() {
         state.set_status(
             "⏳ Please wait, loading vault secrets...",
             MessageLevel::Warning,
         );
-        return;
+        return CopyResult::Handled;
     }
 
-    if let Some(item) = state.selected_item() {
+    let has_password = target_item(state, position)
+        .and_then(|item| item.login.as_ref())
+        .is_some_and(|login| login.password.is_some());
+    if has_password && reprompt_pending(state, position) {
+        return CopyResult::NeedReprompt;
+    }
+
+    if let Some(item) = target_item(state, position) {
+        // Name the item when it isn't the selected one
+        let copied = match position {
+            Some(_) => format!("✓ Password of {} copied to clipboard (hidden for security)", item.name),
+            None => "✓ Password copied to clipboard (hidden for security)".to_string(),
+        };
         if let Some(login) = &item.login {
             if let Some(password) = &login.password {
                 if let Some(cb) = clipboard {
                     match cb.copy(password) {
                         Ok(_) => {
                             crate::logger::Logger::info("Password copied to clipboard");
-                            state.set_status(
-                                "✓ Password copied to clipboard (hidden for security)",
-                                MessageLevel::Success,
-                            );
+                            state.set_status(copied, MessageLevel::Success);
                         }
                         Err(e) => {
                             crate::logger::Logger::error(&format!("Failed to copy password to clipboard: {}", e));
@@ -109,9 +187,10 @@ fn copy_password(state: &mut AppState, clipboard: Option<&mut ClipboardManager>)
             }
         }
     }
+    CopyResult::Handled
 }
 
-fn copy_totp(state: &mut AppState, clipboard: Option<&mut ClipboardManager>, cli: Option<&BitwardenCli>) -> CopyResult {
+fn copy_totp(state: &mut AppState, clipboard: Option<&mut ClipboardManager>, backend: Option<&Backend>) -> CopyResult {
     if !state.secrets_available() {
         state.set_status(
             "⏳ Please wait, loading vault secrets...",
@@ -120,6 +199,13 @@ fn copy_totp(state: &mut AppState, clipboard: Option<&mut ClipboardManager>, cli
         return CopyResult::Handled;
     }
 
+    let has_totp = state.selected_item()
+        .and_then(|item| item.login.as_ref())
+        .is_some_and(|login| login.totp.is_some());
+    if has_totp && reprompt_pending(state, None) {
+        return CopyResult::NeedReprompt;
+    }
+
     if let Some(item) = state.selected_item() {
         if let Some(login) = &item.login {
             if login.totp.is_some() {
@@ -152,7 +238,7 @@ fn copy_totp(state: &mut AppState, clipboard: Option<&mut ClipboardManager>, cli
                 }
 
                 // If we don't have a valid TOTP code, fetch it from CLI
-                if let Some(_cli) = cli {
+                if backend.is_some() {
                     state.set_status(
                         "⏳ Fetching TOTP code...",
                         MessageLevel::Info,
@@ -181,19 +267,26 @@ fn copy_totp(state: &mut AppState, clipboard: Option<&mut ClipboardManager>, cli
     CopyResult::Handled
 }
 
-fn copy_card_number(state: &mut AppState, clipboard: Option<&mut ClipboardManager>) {
+fn copy_card_number(state: &mut AppState, clipboard: Option<&mut ClipboardManager>) -> CopyResult {
     if !state.secrets_available() {
         state.set_status(
             "⏳ Please wait, loading vault secrets...",
             MessageLevel::Warning,
         );
-        return;
+        return CopyResult::Handled;
+    }
+
+    let has_value = state.selected_item()
+        .and_then(|item| item.card.as_ref())
+        .is_some_and(|card| card.number.is_some());
+    if has_value && reprompt_pending(state, None) {
+        return CopyResult::NeedReprompt;
     }
 
     if let Some(item) = state.selected_item() {
         if item.item_type != crate::types::ItemType::Card {
             state.set_status("✗ This is not a card entry", MessageLevel::Warning);
-            return;
+            return CopyResult::Handled;
         }
 
         if let Some(card) = &item.card {
@@ -225,21 +318,29 @@ fn copy_card_number(state: &mut AppState, clipboard: Option<&mut ClipboardManage
             state.set_status("✗ No card data for this entry", MessageLevel::Warning);
         }
     }
+    CopyResult::Handled
 }
 
-fn copy_card_cvv(state: &mut AppState, clipboard: Option<&mut ClipboardManager>) {
+fn copy_card_cvv(state: &mut AppState, clipboard: Option<&mut ClipboardManager>) -> CopyResult {
     if !state.secrets_available() {
         state.set_status(
             "⏳ Please wait, loading vault secrets...",
             MessageLevel::Warning,
         );
-        return;
+        return CopyResult::Handled;
+    }
+
+    let has_value = state.selected_item()
+        .and_then(|item| item.card.as_ref())
+        .is_some_and(|card| card.code.is_some());
+    if has_value && reprompt_pending(state, None) {
+        return CopyResult::NeedReprompt;
     }
 
     if let Some(item) = state.selected_item() {
         if item.item_type != crate::types::ItemType::Card {
             state.set_status("✗ This is not a card entry", MessageLevel::Warning);
-            return;
+            return CopyResult::Handled;
         }
 
         if let Some(card) = &item.card {
@@ -271,5 +372,488 @@ fn copy_card_cvv(state: &mut AppState, clipboard: Option<&mut ClipboardManager>)
             state.set_status("✗ No card data for this entry", MessageLevel::Warning);
         }
     }
+    CopyResult::Handled
+}
+
+fn copy_card_expiry(state: &mut AppState, clipboard: Option<&mut ClipboardManager>) {
+    let Some(item) = state.selected_item() else {
+        return;
+    };
+
+    if item.item_type != crate::types::ItemType::Card {
+        state.set_status("✗ This is not a card entry", MessageLevel::Warning);
+        return;
+    }
+
+    match item.card.as_ref().and_then(|card| card.expiry()) {
+        Some(expiry) => copy_value(state, clipboard, "Expiry", &expiry, false),
+        None => state.set_status("✗ No expiry date for this entry", MessageLevel::Warning),
+    }
+}
+
+fn copy_card_all(state: &mut AppState, clipboard: Option<&mut ClipboardManager>) -> CopyResult {
+    if !state.secrets_available() {
+        state.set_status(
+            "⏳ Please wait, loading vault secrets...",
+            MessageLevel::Warning,
+        );
+        return CopyResult::Handled;
+    }
+
+    let Some(item) = state.selected_item() else {
+        return CopyResult::Handled;
+    };
+
+    if item.item_type != crate::types::ItemType::Card {
+        state.set_status("✗ This is not a card entry", MessageLevel::Warning);
+        return CopyResult::Handled;
+    }
+
+    let Some(line) = item.card.as_ref().and_then(|card| card.autofill_line()) else {
+        state.set_status("✗ No card number for this entry", MessageLevel::Warning);
+        return CopyResult::Handled;
+    };
+
+    if item.requires_reprompt() {
+        let item_id = item.id.clone();
+        if !state.consume_reprompt_grant(&item_id) {
+            return CopyResult::NeedReprompt;
+        }
+    }
+
+    copy_value(state, clipboard, "Card details", &line, true);
+    CopyResult::Handled
+}
+
+fn copy_field(state: &mut AppState, clipboard: Option<&mut ClipboardManager>, backend: Option<&Backend>, field: DetailField) -> CopyResult {
+    match field {
+        DetailField::Username => copy_username(state, clipboard),
+        DetailField::Password => return copy_password(state, clipboard, None),
+        DetailField::Totp => return copy_totp(state, clipboard, backend),
+        DetailField::CardNumber => return copy_card_number(state, clipboard),
+        DetailField::CardExpiry => copy_card_expiry(state, clipboard),
+        DetailField::CardCvv => return copy_card_cvv(state, clipboard),
+        DetailField::IdentityName
+        | DetailField::IdentityAddress
+        | DetailField::IdentityPhone
+        | DetailField::IdentityEmail => copy_identity_field(state, clipboard, field),
+        DetailField::Uri(index) => {
+            let uri = state.selected_item()
+                .and_then(|item| item.login.as_ref())
+                .and_then(|login| login.uris.as_ref())
+                .and_then(|uris| uris.get(index))
+                .map(|uri| uri.uri.clone());
+            if let Some(uri) = uri {
+                copy_value(state, clipboard, "URI", &uri, false);
+            }
+        }
+        DetailField::CustomField(index) => {
+            let field = state.selected_item()
+                .and_then(|item| item.fields.as_ref())
+                .and_then(|fields| fields.get(index))
+                .cloned();
+            let value = field.as_ref().and_then(|field| state.selected_item()?.custom_field_value(field));
+            if let (Some(name), Some(value)) = (field.and_then(|field| field.name), value) {
+                if value.is_secret() && reprompt_pending(state, None) {
+                    return CopyResult::NeedReprompt;
+                }
+                copy_value(state, clipboard, &name, &value.text(), value.is_secret());
+            }
+        }
+    }
+    CopyResult::Handled
+}
+
+/// Copy the whole item in a format other tools understand
+fn copy_item_as(state: &mut AppState, clipboard: Option<&mut ClipboardManager>, format: CopyFormat) -> CopyResult {
+    state.close_copy_as();
+    let Some(item) = state.selected_item() else {
+        return CopyResult::Handled;
+    };
+    let Some(text) = format.format(item) else {
+        state.set_status(format!("✗ This item can't be copied as {}", format.label()), MessageLevel::Warning);
+        return CopyResult::Handled;
+    };
+    let (item_id, label) = (item.id.clone(), format!("{} as {}", item.name, format.label()));
+
+    // Each format can include secrets
+    if item.requires_reprompt() && !state.consume_reprompt_grant(&item_id) {
+        return CopyResult::NeedReprompt;
+    }
+
+    copy_value(state, clipboard, &label, &text, true);
+    CopyResult::Handled
+}
+
+/// Copy the code of the row selected in the TOTP dashboard
+fn copy_dashboard_totp(state: &mut AppState, clipboard: Option<&mut ClipboardManager>) {
+    let Some(row) = state.totp_dashboard.selected_row() else {
+        return;
+    };
+    let Some(code) = row.code.as_ref().map(|totp| totp.code.clone()) else {
+        state.set_status("✗ This TOTP code can't be generated locally, copy it from the item with Ctrl+T", MessageLevel::Warning);
+        return;
+    };
+    let label = format!("TOTP code of {}", row.name);
+    copy_value(state, clipboard, &label, &code, false);
+}
+
+/// Show or mask the value of the focused hidden custom field
+fn toggle_reveal_focused_field(state: &mut AppState) -> CopyResult {
+    let Some(DetailField::CustomField(index)) = state.focused_detail_field() else {
+        state.set_status("✗ Only hidden custom fields can be revealed", MessageLevel::Warning);
+        return CopyResult::Handled;
+    };
+    let Some(item) = state.selected_item() else {
+        return CopyResult::Handled;
+    };
+
+    let secret = item.fields.as_ref()
+        .and_then(|fields| fields.get(index))
+        .and_then(|field| item.custom_field_value(field))
+        .is_some_and(|value| value.is_secret());
+    if !secret {
+        state.set_status("✗ This field is not hidden", MessageLevel::Warning);
+        return CopyResult::Handled;
+    }
+
+    // Masking again never needs the master password
+    if state.revealed_detail_field().is_none() && item.requires_reprompt() {
+        let item_id = item.id.clone();
+        if !state.consume_reprompt_grant(&item_id) {
+            return CopyResult::NeedReprompt;
+        }
+    }
+
+    state.toggle_focused_field_revealed();
+    CopyResult::Handled
+}
+
+/// Show the TOTP secret as a QR code, to enroll it in an authenticator app
+fn show_totp_qr_code(state: &mut AppState) -> CopyResult {
+    let Some(item) = state.selected_item() else {
+        return CopyResult::Handled;
+    };
+    let Some(secret) = item.login.as_ref().and_then(|login| login.totp.clone()) else {
+        state.set_status("✗ No TOTP secret for this item", MessageLevel::Warning);
+        return CopyResult::Handled;
+    };
+    let (item_id, name, requires_reprompt) = (item.id.clone(), item.name.clone(), item.requires_reprompt());
+    let account = item.username().unwrap_or(&item.name).to_string();
+
+    if requires_reprompt && !state.consume_reprompt_grant(&item_id) {
+        return CopyResult::NeedReprompt;
+    }
+
+    let lines = crate::totp_util::otpauth_uri(&secret, &name, &account)
+        .map_err(|e| e.to_string())
+        .and_then(|uri| crate::qr::qr_lines(&uri).map_err(|e| e.to_string()));
+    match lines {
+        Ok(lines) => state.show_qr_popup(format!("TOTP for {}", name), lines),
+        Err(e) => state.set_status(format!("✗ Failed to create QR code: {}", e), MessageLevel::Error),
+    }
+    CopyResult::Handled
+}
+
+/// Show the focused field's value as a QR code, e.g. a Wi-Fi password for a phone
+fn show_field_qr_code(state: &mut AppState) -> CopyResult {
+    let Some(field) = state.focused_detail_field() else {
+        return CopyResult::Handled;
+    };
+    // The TOTP field shows the secret to enroll rather than the current code
+    if field == DetailField::Totp {
+        return show_totp_qr_code(state);
+    }
+    let Some(item) = state.selected_item() else {
+        return CopyResult::Handled;
+    };
+    let Some((value, secret)) = field.value(item) else {
+        return CopyResult::Handled;
+    };
+    let (item_id, title) = (item.id.clone(), format!("{} for {}", field.label(item), item.name));
+
+    if secret && item.requires_reprompt() && !state.consume_reprompt_grant(&item_id) {
+        return CopyResult::NeedReprompt;
+    }
+
+    match crate::qr::qr_lines(&value) {
+        Ok(lines) => state.show_qr_popup(title, lines),
+        Err(e) => state.set_status(format!("✗ Failed to create QR code: {}", e), MessageLevel::Error),
+    }
+    CopyResult::Handled
+}
+
+fn copy_identity_field(state: &mut AppState, clipboard: Option<&mut ClipboardManager>, field: DetailField) {
+    let Some(identity) = state.selected_item().and_then(|item| item.identity.as_ref()) else {
+        return;
+    };
+
+    let (label, value) = match field {
+        DetailField::IdentityName => ("Name", identity.full_name()),
+        DetailField::IdentityAddress => ("Address", identity.formatted_address()),
+        DetailField::IdentityPhone => ("Phone", identity.phone.clone()),
+        DetailField::IdentityEmail => ("Email", identity.email.clone()),
+        _ => return,
+    };
+
+    match value {
+        // Addresses span several lines, so don't echo them in the status bar
+        Some(value) => copy_value(state, clipboard, label, &value, field == DetailField::IdentityAddress),
+        None => state.set_status(
+            format!("✗ No {} for this entry", label.to_lowercase()),
+            MessageLevel::Warning,
+        ),
+    }
+}
+
+/// Copy an arbitrary value, echoing it in the status bar unless it is secret
+fn copy_value(state: &mut AppState, clipboard: Option<&mut ClipboardManager>, label: &str, value: &str, secret: bool) {
+    if let Some(cb) = clipboard {
+        match cb.copy(value) {
+            Ok(_) => {
+                crate::logger::Logger::info("Field value copied to clipboard");
+                let message = if secret {
+                    format!("✓ {} copied to clipboard (hidden for security)", label)
+                } else {
+                    format!("✓ {} copied: {}", label, value)
+                };
+                state.set_status(message, MessageLevel::Success);
+            }
+            Err(e) => {
+                crate::logger::Logger::error(&format!("Failed to copy field value to clipboard: {}", e));
+                state.set_status(
+                    "✗ Failed to copy to clipboard",
+                    MessageLevel::Error,
+                );
+            }
+        }
+    } else {
+        state.set_status("✗ Clipboard not available", MessageLevel::Error);
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CustomField, CustomFieldType, ItemType, VaultItem};
+
+    fn create_item_with_fields(fields: Vec<CustomField>) -> VaultItem {
+        VaultItem {
+            id: "1".to_string(),
+            name: "Server".to_string(),
+            item_type: ItemType::SecureNote,
+            login: None,
+            card: None,
+            identity: None,
+            notes: None,
+            fields: Some(fields),
+            favorite: false,
+            folder_id: None,
+            organization_id: None,
+            revision_date: chrono::Utc::now(),
+            object: None,
+            creation_date: None,
+            deleted_date: None,
+            password_history: None,
+            attachments: None,
+            collection_ids: None,
+            reprompt: None,
+            raw_fields: Default::default(),
+        }
+    }
+
+    fn field(name: &str, value: &str, field_type: CustomFieldType) -> CustomField {
+        CustomField {
+            name: Some(name.to_string()),
+            value: Some(value.to_string()),
+            field_type: Some(field_type),
+            linked_id: None,
+        }
+    }
+
+    #[test]
+    fn test_reveal_focused_hidden_field() {
+        let mut state = AppState::new();
+        state.load_items_with_secrets(vec![create_item_with_fields(vec![
+            field("host", "db.example.com", CustomFieldType::Text),
+            field("pin", "1234", CustomFieldType::Hidden),
+        ])]);
+        state.toggle_details_panel();
+        state.focus_details();
+
+        // Plain text fields have nothing to reveal
+        handle_copy(&Action::ToggleRevealField, &mut state, None, None);
+        assert_eq!(state.revealed_detail_field(), None);
+
+        state.focus_next_field();
+        handle_copy(&Action::ToggleRevealField, &mut state, None, None);
+        assert_eq!(state.revealed_detail_field(), Some(DetailField::CustomField(1)));
+        handle_copy(&Action::ToggleRevealField, &mut state, None, None);
+        assert_eq!(state.revealed_detail_field(), None);
+
+        // Moving the focus masks the value again
+        handle_copy(&Action::ToggleRevealField, &mut state, None, None);
+        state.focus_previous_field();
+        state.focus_next_field();
+        assert_eq!(state.revealed_detail_field(), None);
+    }
+
+    #[test]
+    fn test_quick_copy_targets_result_without_selecting_it() {
+        let login = |id: &str, name: &str, password: Option<&str>| {
+            let mut item = create_item_with_fields(Vec::new());
+            item.id = id.to_string();
+            item.name = name.to_string();
+            item.item_type = ItemType::Login;
+            item.login = Some(crate::types::LoginData {
+                username: None,
+                password: password.map(str::to_string),
+                totp: None,
+                uris: None,
+                password_revision_date: None,
+            });
+            item
+        };
+        let mut state = AppState::new();
+        state.load_items_with_secrets(vec![login("1", "Alpha", Some("secret")), login("2", "Beta", None)]);
+
+        handle_copy(&Action::QuickCopyPassword(1), &mut state, None, None);
+        assert_eq!(state.status_message.as_ref().unwrap().text, "✗ No password for this entry");
+        assert_eq!(state.selected_item().unwrap().id, "1");
+
+        // Positions past the results do nothing
+        state.status_message = None;
+        handle_copy(&Action::QuickCopyPassword(5), &mut state, None, None);
+        assert!(state.status_message.is_none());
+    }
+
+    #[test]
+    fn test_reveal_requires_reprompt() {
+        let mut state = AppState::new();
+        let mut item = create_item_with_fields(vec![field("pin", "1234", CustomFieldType::Hidden)]);
+        item.reprompt = Some(1);
+        state.load_items_with_secrets(vec![item]);
+        state.toggle_details_panel();
+        state.focus_details();
+
+        assert!(matches!(handle_copy(&Action::ToggleRevealField, &mut state, None, None), CopyResult::NeedReprompt));
+        assert_eq!(state.revealed_detail_field(), None);
+
+        state.grant_reprompt("1".to_string());
+        handle_copy(&Action::ToggleRevealField, &mut state, None, None);
+        assert_eq!(state.revealed_detail_field(), Some(DetailField::CustomField(0)));
+    }
+
+    #[test]
+    fn test_totp_qr_code_popup() {
+        let mut state = AppState::new();
+        let mut item = create_item_with_fields(Vec::new());
+        item.item_type = ItemType::Login;
+        item.reprompt = Some(1);
+        item.login = Some(crate::types::LoginData {
+            username: Some("alice".to_string()),
+            password: None,
+            totp: Some("JBSWY3DPEHPK3PXP".to_string()),
+            uris: None,
+            password_revision_date: None,
+        });
+        state.load_items_with_secrets(vec![item]);
+
+        assert!(matches!(handle_copy(&Action::ShowTotpQrCode, &mut state, None, None), CopyResult::NeedReprompt));
+        assert!(state.qr_popup().is_none());
+
+        state.grant_reprompt("1".to_string());
+        handle_copy(&Action::ShowTotpQrCode, &mut state, None, None);
+        let popup = state.qr_popup().unwrap();
+        assert_eq!(popup.title, "TOTP for Server");
+        assert!(!popup.lines.is_empty());
+    }
+
+    #[test]
+    fn test_copying_secrets_requires_reprompt() {
+        let mut login = crate::fake_backend::login("1", "Bank", Some("JBSWY3DPEHPK3PXP"));
+        login.reprompt = Some(1);
+        let mut card = crate::fake_backend::login("2", "Visa", None);
+        card.item_type = ItemType::Card;
+        card.login = None;
+        card.card = Some(crate::types::CardData {
+            brand: Some("Visa".to_string()),
+            card_holder_name: None,
+            number: Some("4111111111111111".to_string()),
+            exp_month: None,
+            exp_year: None,
+            code: Some("123".to_string()),
+        });
+        card.reprompt = Some(1);
+        let mut state = AppState::new();
+        state.load_items_with_secrets(vec![login, card]);
+
+        let secrets = [
+            ("1", Action::CopyPassword),
+            ("1", Action::QuickCopyPassword(0)),
+            ("1", Action::CopyTotp),
+            ("1", Action::CopyDetailField(DetailField::Password)),
+            ("2", Action::CopyCardNumber),
+            ("2", Action::CopyCardCvv),
+        ];
+        for (item_id, action) in secrets {
+            state.vault.select_item(item_id);
+            assert!(matches!(handle_copy(&action, &mut state, None, None), CopyResult::NeedReprompt), "{:?}", action);
+            // A verified re-prompt allows a single copy
+            state.grant_reprompt(item_id.to_string());
+            assert!(!matches!(handle_copy(&action, &mut state, None, None), CopyResult::NeedReprompt), "{:?}", action);
+            assert!(matches!(handle_copy(&action, &mut state, None, None), CopyResult::NeedReprompt), "{:?}", action);
+        }
+
+        // The username isn't a secret
+        state.vault.select_item("1");
+        assert!(matches!(handle_copy(&Action::CopyUsername, &mut state, None, None), CopyResult::Handled));
+    }
+
+    #[test]
+    fn test_focused_field_qr_code_popup() {
+        let mut state = AppState::new();
+        let mut item = create_item_with_fields(vec![
+            field("SSID", "home", CustomFieldType::Text),
+            field("Wi-Fi password", "correct horse", CustomFieldType::Hidden),
+        ]);
+        item.reprompt = Some(1);
+        state.load_items_with_secrets(vec![item]);
+        state.toggle_details_panel();
+        state.focus_details();
+
+        // Plain fields don't need the master password
+        handle_copy(&Action::ShowFieldQrCode, &mut state, None, None);
+        assert_eq!(state.qr_popup().unwrap().title, "SSID for Server");
+        state.close_qr_popup();
+
+        state.focus_next_field();
+        assert!(matches!(handle_copy(&Action::ShowFieldQrCode, &mut state, None, None), CopyResult::NeedReprompt));
+        state.grant_reprompt("1".to_string());
+        handle_copy(&Action::ShowFieldQrCode, &mut state, None, None);
+        assert_eq!(state.qr_popup().unwrap().title, "Wi-Fi password for Server");
+    }
+
+    #[test]
+    fn test_copy_item_as_requires_reprompt_and_closes_picker() {
+        let mut state = AppState::new();
+        let mut item = create_item_with_fields(vec![field("token", "abc", CustomFieldType::Hidden)]);
+        item.reprompt = Some(1);
+        state.load_items_with_secrets(vec![item]);
+
+        state.open_copy_as();
+        assert_eq!(state.copy_formats(), vec![CopyFormat::Json, CopyFormat::Env]);
+        state.select_next_copy_format();
+        state.select_next_copy_format();
+        assert_eq!(state.selected_copy_format(), Some(CopyFormat::Env));
+
+        let result = handle_copy(&Action::CopyItemAs(CopyFormat::Env), &mut state, None, None);
+        assert!(matches!(result, CopyResult::NeedReprompt));
+        assert!(!state.copy_as_open());
+
+        // Without a clipboard the copy itself fails, after the re-prompt check
+        state.grant_reprompt("1".to_string());
+        handle_copy(&Action::CopyItemAs(CopyFormat::Env), &mut state, None, None);
+        assert_eq!(state.status_message.as_ref().unwrap().text, "✗ Clipboard not available");
+    }
+}
@@ -1,5 +1,5 @@
 use crate::events::Action;
-use crate::state::AppState;
+use crate::state::{AppState, MessageLevel};
 
 /// Handle filter/search actions
 pub fn handle_filter(action: &Action, state: &mut AppState) -> bool {
@@ -10,9 +10,39 @@ pub fn handle_filter(action: &Action, state: &mut AppState) -> bool {
         Action::DeleteFilterChar => {
             state.delete_filter_char();
         }
+        Action::PasteFilter(text) => {
+            state.paste_filter(text);
+        }
+        Action::EditFilter(edit) => {
+            state.edit_filter(*edit);
+        }
         Action::ClearFilter => {
             state.clear_filter();
         }
+        Action::ToggleFuzzyMatching => {
+            state.toggle_fuzzy_matching();
+            let mode = if state.vault.fuzzy_enabled() { "fuzzy" } else { "exact" };
+            state.set_status(format!("Search mode: {}", mode), MessageLevel::Info);
+        }
+        Action::ToggleCaseSensitive => {
+            state.toggle_case_sensitive();
+            let mode = if state.vault.case_sensitive() { "case-sensitive" } else { "ignoring case" };
+            state.set_status(format!("Search is now {}", mode), MessageLevel::Info);
+        }
+        Action::CycleOwnerFilter => {
+            state.cycle_owner_filter();
+            state.set_status(format!("Showing items of: {}", state.vault.owner_filter_label()), MessageLevel::Info);
+        }
+        Action::ToggleShowHidden => {
+            state.toggle_show_hidden();
+            let count = state.vault.hidden_count();
+            let items = if count == 1 { "item" } else { "items" };
+            if state.vault.show_hidden() {
+                state.set_status(format!("Showing {} hidden {}", count, items), MessageLevel::Info);
+            } else {
+                state.set_status(format!("Hiding {} {}", count, items), MessageLevel::Info);
+            }
+        }
         _ => {
             return false; // Not a filter action
         }
@@ -24,6 +54,7 @@ pub fn handle_filter(action: &Action, state: &mut AppState) -> bool {
 mod tests {
     use super::*;
     use crate::types::{VaultItem, ItemType};
+    use crate::state::TextEdit;
 
     fn create_test_item(id: &str, name: &str, item_type: ItemType) -> VaultItem {
         VaultItem {
@@ -46,6 +77,7 @@ mod tests {
             attachments: None,
             collection_ids: None,
             reprompt: None,
+            raw_fields: Default::default(),
         }
     }
 
@@ -63,6 +95,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::len_zero)]
     fn test_filter_functionality() {
         let mut state = AppState::new();
         
@@ -76,27 +109,50 @@ mod tests {
         state.load_items_with_secrets(items);
         
         // Initially all items should be visible
-        assert_eq!(state.vault.filtered_items.len(), 4);
+        assert_eq!(state.vault.filtered_len(), 4);
         
         // Filter by text - use "git" which only matches GitHub
         handle_filter(&Action::AppendFilter('g'), &mut state);
         handle_filter(&Action::AppendFilter('i'), &mut state);
         handle_filter(&Action::AppendFilter('t'), &mut state);
         // Filter should match at least GitHub, might also match others with fuzzy matching
-        assert!(state.vault.filtered_items.len() >= 1);
-        assert!(state.vault.filtered_items.iter().any(|item| item.name == "GitHub"));
+        assert!(state.vault.filtered_indices.len() >= 1);
+        assert!(state.vault.filtered_indices.iter().any(|&idx| state.vault.vault_items[idx].name == "GitHub"));
         
         // Clear filter
         handle_filter(&Action::ClearFilter, &mut state);
-        assert_eq!(state.vault.filtered_items.len(), 4);
+        assert_eq!(state.vault.filtered_len(), 4);
         
         // Test with single character filter that should match multiple items
         handle_filter(&Action::AppendFilter('a'), &mut state);
-        assert!(state.vault.filtered_items.len() >= 1); // At least Amazon, might match more with fuzzy
+        assert!(state.vault.filtered_indices.len() >= 1); // At least Amazon, might match more with fuzzy
         
         // Test delete filter character
         handle_filter(&Action::DeleteFilterChar, &mut state);
-        assert_eq!(state.vault.filtered_items.len(), 4); // Back to all items
+        assert_eq!(state.vault.filtered_len(), 4); // Back to all items
+    }
+
+    #[test]
+    fn test_edit_filter_at_cursor() {
+        let mut state = AppState::new();
+        state.load_items_with_secrets(vec![
+            create_test_item("1", "GitHub", ItemType::Login),
+            create_test_item("2", "Gmail", ItemType::Login),
+        ]);
+
+        for c in "hub".chars() {
+            handle_filter(&Action::AppendFilter(c), &mut state);
+        }
+        assert!(handle_filter(&Action::EditFilter(TextEdit::Home), &mut state));
+        for c in "git".chars() {
+            handle_filter(&Action::AppendFilter(c), &mut state);
+        }
+        assert_eq!(state.vault.filter_query.text(), "github");
+        assert_eq!(state.vault.filter_query.cursor(), 3);
+        assert_eq!(state.vault.filtered_len(), 1);
+
+        handle_filter(&Action::EditFilter(TextEdit::DeleteWord), &mut state);
+        assert_eq!(state.vault.filter_query.text(), "hub");
     }
 
     #[test]
@@ -112,15 +168,46 @@ mod tests {
         
         // Filter by type first
         state.set_item_type_filter(Some(ItemType::Login));
-        assert_eq!(state.vault.filtered_items.len(), 2); // GitHub, Amazon
+        assert_eq!(state.vault.filtered_len(), 2); // GitHub, Amazon
         
         // Then filter by text
         handle_filter(&Action::AppendFilter('g'), &mut state);
-        assert_eq!(state.vault.filtered_items.len(), 1); // GitHub
+        assert_eq!(state.vault.filtered_len(), 1); // GitHub
         
         // Clear text filter
         handle_filter(&Action::ClearFilter, &mut state);
-        assert_eq!(state.vault.filtered_items.len(), 2); // Back to Login items
+        assert_eq!(state.vault.filtered_len(), 2); // Back to Login items
     }
-}
 
+    #[test]
+    fn test_toggle_matching_mode() {
+        let mut state = AppState::new();
+
+        let items = vec![
+            create_test_item("1", "GitHub", ItemType::Login),
+            create_test_item("2", "Google", ItemType::Login),
+        ];
+        state.load_items_with_secrets(items);
+
+        // "gh" fuzzy-matches GitHub but isn't a substring of either name
+        handle_filter(&Action::AppendFilter('g'), &mut state);
+        handle_filter(&Action::AppendFilter('h'), &mut state);
+        assert_eq!(state.vault.filtered_len(), 1);
+
+        assert!(handle_filter(&Action::ToggleFuzzyMatching, &mut state));
+        assert!(!state.vault.fuzzy_enabled());
+        assert_eq!(state.vault.filtered_len(), 0);
+
+        // Case-sensitive exact matching
+        handle_filter(&Action::ClearFilter, &mut state);
+        handle_filter(&Action::AppendFilter('G'), &mut state);
+        assert_eq!(state.vault.filtered_len(), 2);
+        assert!(handle_filter(&Action::ToggleCaseSensitive, &mut state));
+        assert!(state.vault.case_sensitive());
+        handle_filter(&Action::AppendFilter('i'), &mut state);
+        assert_eq!(state.vault.filtered_len(), 1);
+        handle_filter(&Action::DeleteFilterChar, &mut state);
+        handle_filter(&Action::AppendFilter('I'), &mut state);
+        assert_eq!(state.vault.filtered_len(), 0);
+    }
+}
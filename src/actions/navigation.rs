@@ -22,6 +22,12 @@ pub fn handle_navigation(action: &Action, state: &mut AppState) -> bool {
         Action::End => {
             state.jump_to_end();
         }
+        Action::ScrollListUp => {
+            state.scroll_list_up();
+        }
+        Action::ScrollListDown => {
+            state.scroll_list_down();
+        }
         Action::SelectIndex(index) => {
             state.select_index(*index);
         }
@@ -64,6 +70,7 @@ mod tests {
             attachments: None,
             collection_ids: None,
             reprompt: None,
+            raw_fields: Default::default(),
         }
     }
 
@@ -80,6 +87,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::unnecessary_min_or_max)]
     fn test_navigation_functionality() {
         let mut state = AppState::new();
         
@@ -98,8 +106,8 @@ mod tests {
         // But we need to verify we can navigate correctly
         
         // Ensure we have items
-        assert!(!state.vault.filtered_items.is_empty());
-        assert_eq!(state.vault.filtered_items.len(), 5);
+        assert!(!state.vault.filtered_indices.is_empty());
+        assert_eq!(state.vault.filtered_len(), 5);
         
         // Reset to first item for consistent testing
         handle_navigation(&Action::Home, &mut state);
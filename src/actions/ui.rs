@@ -1,5 +1,8 @@
 use crate::events::Action;
-use crate::state::AppState;
+use crate::state::{Annotation, AppState, MessageLevel};
+
+/// Percentage points the split moves per resize
+const SPLIT_STEP: i16 = 5;
 
 /// Handle UI actions (details panel, etc.)
 pub fn handle_ui(action: &Action, state: &mut AppState) -> bool {
@@ -24,6 +27,232 @@ pub fn handle_ui(action: &Action, state: &mut AppState) -> bool {
                 state.toggle_details_panel();
             }
         }
+        Action::ToggleDetailsFullscreen => {
+            state.toggle_details_fullscreen();
+        }
+        Action::ToggleRawNotes => {
+            state.toggle_raw_notes();
+        }
+        Action::ToggleTotpWatch => {
+            state.toggle_totp_watch();
+        }
+        Action::ShrinkList => {
+            state.resize_split(-SPLIT_STEP);
+        }
+        Action::GrowList => {
+            state.resize_split(SPLIT_STEP);
+        }
+        Action::ToggleStatusHistory => {
+            state.toggle_status_history();
+        }
+        Action::ToggleSyncDetails => {
+            state.toggle_sync_details();
+        }
+        Action::CancelQuit => {
+            state.ui.confirm_quit = false;
+        }
+        Action::ScrollStatusHistoryUp => {
+            state.scroll_status_history_up();
+        }
+        Action::ScrollStatusHistoryDown => {
+            state.scroll_status_history_down();
+        }
+        Action::CloseQrCode => {
+            state.close_qr_popup();
+        }
+        Action::OpenCopyAs => {
+            if state.copy_formats().is_empty() {
+                state.set_status("✗ No item selected", MessageLevel::Warning);
+            } else {
+                state.open_copy_as();
+            }
+        }
+        Action::CloseCopyAs => {
+            state.close_copy_as();
+        }
+        Action::SelectNextCopyFormat => {
+            state.select_next_copy_format();
+        }
+        Action::SelectPreviousCopyFormat => {
+            state.select_previous_copy_format();
+        }
+        Action::ToggleDuplicates => {
+            state.toggle_duplicates();
+        }
+        Action::SelectNextDuplicate => {
+            state.duplicates.select_next();
+        }
+        Action::SelectPreviousDuplicate => {
+            state.duplicates.select_previous();
+        }
+        Action::OpenLocalNote => {
+            state.open_local_note(Annotation::Note);
+        }
+        Action::OpenLocalTags => {
+            state.open_local_note(Annotation::Tags);
+        }
+        Action::CloseLocalNote => {
+            state.close_local_note();
+        }
+        Action::AppendLocalNoteChar(c) => {
+            if let Some(input) = state.local_note_input() {
+                input.insert(*c);
+            }
+        }
+        Action::DeleteLocalNoteChar => {
+            if let Some(input) = state.local_note_input() {
+                input.delete_back();
+            }
+        }
+        Action::EditLocalNote(edit) => {
+            if let Some(input) = state.local_note_input() {
+                input.apply(*edit);
+            }
+        }
+        Action::PasteLocalNote(text) => {
+            if let Some(input) = state.local_note_input() {
+                // A note is a single line
+                input.insert_str(&text.replace(['\r', '\n'], " "));
+            }
+        }
+        Action::ToggleTagList => {
+            if state.tag_list_open() {
+                state.close_tag_list();
+            } else {
+                state.open_tag_list();
+            }
+        }
+        Action::SelectNextTag => {
+            state.select_next_tag();
+        }
+        Action::SelectPreviousTag => {
+            state.select_previous_tag();
+        }
+        Action::FilterBySelectedTag => {
+            state.filter_by_selected_tag();
+        }
+        Action::ToggleTotpDashboard => {
+            state.toggle_totp_dashboard();
+        }
+        Action::SelectNextTotpRow => {
+            state.totp_dashboard.select_next();
+        }
+        Action::SelectPreviousTotpRow => {
+            state.totp_dashboard.select_previous();
+        }
+        Action::JumpToDuplicate => {
+            state.jump_to_duplicate();
+        }
+        Action::RequestDeleteDuplicate => {
+            if state.ui.readonly {
+                state.set_status("Read-only mode, items can't be deleted", MessageLevel::Warning);
+            } else if state.duplicates.selected_item_id().is_some() && !state.duplicates.deleting {
+                state.duplicates.confirm_delete = true;
+            }
+        }
+        Action::CancelDeleteDuplicate => {
+            state.duplicates.confirm_delete = false;
+        }
+        Action::OpenShare => {
+            if state.ui.readonly {
+                state.set_status("Read-only mode, items can't be shared", MessageLevel::Warning);
+            } else if state.share.busy {
+                state.set_status("⏳ Please wait, sharing the last item...", MessageLevel::Warning);
+            } else {
+                state.open_share();
+            }
+        }
+        Action::CloseShare => {
+            state.share.close();
+        }
+        Action::SelectNextShareCollection => {
+            state.select_next_share_collection();
+        }
+        Action::SelectPreviousShareCollection => {
+            state.share.select_previous();
+        }
+        Action::ToggleShareCollection => {
+            state.toggle_share_collection();
+        }
+        Action::RequestShare => {
+            state.request_share();
+        }
+        Action::CancelShare => {
+            state.share.confirm = false;
+        }
+        Action::ToggleFolders => {
+            state.toggle_folders();
+        }
+        Action::SelectNextFolder => {
+            state.select_next_folder();
+        }
+        Action::SelectPreviousFolder => {
+            state.folders.select_previous();
+        }
+        Action::FilterBySelectedFolder => {
+            state.filter_by_selected_folder();
+        }
+        Action::NewFolder => {
+            if can_change_folders(state) {
+                state.start_new_folder();
+            }
+        }
+        Action::RenameFolder => {
+            if can_change_folders(state) {
+                state.start_rename_folder();
+            }
+        }
+        Action::RequestDeleteFolder => {
+            if can_change_folders(state) {
+                state.request_delete_folder();
+            }
+        }
+        Action::CancelFolderEdit => {
+            state.folders.cancel_edit();
+        }
+        Action::AppendFolderChar(c) => {
+            state.folders.input.insert(*c);
+        }
+        Action::DeleteFolderChar => {
+            state.folders.input.delete_back();
+        }
+        Action::EditFolderName(edit) => {
+            state.folders.input.apply(*edit);
+        }
+        Action::PasteFolderName(text) => {
+            state.folders.input.insert_str(&text.replace(['\r', '\n'], " "));
+        }
+        Action::CancelDeleteFolder => {
+            state.folders.confirm_delete = false;
+        }
+        Action::OpenMoveToFolder => {
+            if state.ui.readonly {
+                state.set_status("Read-only mode, items can't be changed", MessageLevel::Warning);
+            } else {
+                state.open_move_to_folder();
+            }
+        }
+        Action::FocusSearch => {
+            state.focus_search();
+        }
+        Action::FocusList => {
+            state.focus_list();
+        }
+        Action::KeyPrefix(key) => {
+            state.ui.pending_key = *key;
+        }
+        Action::FocusDetails => {
+            state.focus_details();
+        }
+        Action::UnfocusDetails => {
+            state.unfocus_details();
+        }
+        Action::FocusNextField => {
+            state.focus_next_field();
+        }
+        Action::FocusPreviousField => {
+            state.focus_previous_field();
+        }
         Action::SelectItemTypeTab(filter) => {
             state.set_item_type_filter(*filter);
         }
@@ -40,9 +269,23 @@ pub fn handle_ui(action: &Action, state: &mut AppState) -> bool {
     true
 }
 
+/// Whether the folder screen may change a folder now, telling why not otherwise
+fn can_change_folders(state: &mut AppState) -> bool {
+    if state.ui.readonly {
+        state.set_status("Read-only mode, folders can't be changed", MessageLevel::Warning);
+        return false;
+    }
+    if state.folders.busy {
+        state.set_status("⏳ Please wait, saving the last folder change...", MessageLevel::Warning);
+        return false;
+    }
+    true
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::Focus;
     use crate::types::{VaultItem, ItemType};
 
     fn create_test_item(id: &str, name: &str, item_type: ItemType) -> VaultItem {
@@ -66,6 +309,7 @@ mod tests {
             attachments: None,
             collection_ids: None,
             reprompt: None,
+            raw_fields: Default::default(),
         }
     }
 
@@ -99,6 +343,35 @@ mod tests {
         assert!(!state.details_panel_visible());
     }
 
+    #[test]
+    fn test_details_scroll_restored_per_item() {
+        let mut state = AppState::new();
+        state.load_items_with_secrets(vec![
+            create_test_item("1", "Alpha", ItemType::SecureNote),
+            create_test_item("2", "Beta", ItemType::SecureNote),
+        ]);
+        handle_ui(&Action::ToggleDetailsPanel, &mut state);
+        state.set_details_max_scroll(20); // Normally set while rendering
+        for _ in 0..3 {
+            handle_ui(&Action::ScrollDetailsDown, &mut state);
+        }
+
+        // Closing and opening again comes back to the same place
+        handle_ui(&Action::ToggleDetailsPanel, &mut state);
+        assert_eq!(state.ui.details_panel_scroll, 0);
+        handle_ui(&Action::ToggleDetailsPanel, &mut state);
+        assert_eq!(state.ui.details_panel_scroll, 3);
+
+        // Another item starts at the top, and each keeps its own position
+        state.select_next();
+        assert_eq!(state.ui.details_panel_scroll, 0);
+        handle_ui(&Action::ScrollDetailsDown, &mut state);
+        state.select_previous();
+        assert_eq!(state.ui.details_panel_scroll, 3);
+        state.select_next();
+        assert_eq!(state.ui.details_panel_scroll, 1);
+    }
+
     #[test]
     fn test_open_details_panel_only_when_closed() {
         let mut state = AppState::new();
@@ -149,21 +422,21 @@ mod tests {
         state.load_items_with_secrets(items);
         
         // Initially all items should be visible
-        assert_eq!(state.vault.filtered_items.len(), 4);
+        assert_eq!(state.vault.filtered_len(), 4);
         
         // Filter to Login items
         handle_ui(&Action::SelectItemTypeTab(Some(ItemType::Login)), &mut state);
-        assert_eq!(state.vault.filtered_items.len(), 1);
-        assert_eq!(state.vault.filtered_items[0].id, "1");
+        assert_eq!(state.vault.filtered_len(), 1);
+        assert_eq!(state.vault.filtered_item(0).unwrap().id, "1");
         
         // Filter to Card items
         handle_ui(&Action::SelectItemTypeTab(Some(ItemType::Card)), &mut state);
-        assert_eq!(state.vault.filtered_items.len(), 1);
-        assert_eq!(state.vault.filtered_items[0].id, "3");
+        assert_eq!(state.vault.filtered_len(), 1);
+        assert_eq!(state.vault.filtered_item(0).unwrap().id, "3");
         
         // Filter to show all
         handle_ui(&Action::SelectItemTypeTab(None), &mut state);
-        assert_eq!(state.vault.filtered_items.len(), 4);
+        assert_eq!(state.vault.filtered_len(), 4);
     }
 
     #[test]
@@ -178,23 +451,186 @@ mod tests {
         state.load_items_with_secrets(items);
         
         // Initially all items visible
-        assert_eq!(state.vault.filtered_items.len(), 3);
+        assert_eq!(state.vault.filtered_len(), 3);
         
         // Cycle to Login tab
         handle_ui(&Action::CycleNextTab, &mut state);
-        assert_eq!(state.vault.filtered_items.len(), 1);
-        assert_eq!(state.vault.filtered_items[0].item_type, ItemType::Login);
+        assert_eq!(state.vault.filtered_len(), 1);
+        assert_eq!(state.vault.filtered_item(0).unwrap().item_type, ItemType::Login);
         
         // Cycle to SecureNote tab
         handle_ui(&Action::CycleNextTab, &mut state);
-        assert_eq!(state.vault.filtered_items.len(), 1);
-        assert_eq!(state.vault.filtered_items[0].item_type, ItemType::SecureNote);
+        assert_eq!(state.vault.filtered_len(), 1);
+        assert_eq!(state.vault.filtered_item(0).unwrap().item_type, ItemType::SecureNote);
         
         // Cycle back to show all
         handle_ui(&Action::CycleNextTab, &mut state);
         handle_ui(&Action::CycleNextTab, &mut state);
         handle_ui(&Action::CycleNextTab, &mut state);
-        assert_eq!(state.vault.filtered_items.len(), 3);
+        assert_eq!(state.vault.filtered_len(), 3);
     }
-}
 
+    #[test]
+    fn test_details_field_focus() {
+        let mut state = AppState::new();
+
+        let mut item = create_test_item("1", "GitHub", ItemType::Login);
+        item.login = Some(crate::types::LoginData {
+            username: Some("user".to_string()),
+            password: Some("pass".to_string()),
+            totp: None,
+            uris: None,
+            password_revision_date: None,
+        });
+        state.load_items_with_secrets(vec![item, create_test_item("2", "Note", ItemType::SecureNote)]);
+
+        // Focus requires the details panel to be open
+        handle_ui(&Action::FocusDetails, &mut state);
+        assert!(!state.details_focused());
+
+        handle_ui(&Action::OpenDetailsPanel, &mut state);
+        handle_ui(&Action::FocusDetails, &mut state);
+        assert_eq!(state.focused_detail_field(), Some(crate::state::DetailField::Username));
+
+        handle_ui(&Action::FocusNextField, &mut state);
+        assert_eq!(state.focused_detail_field(), Some(crate::state::DetailField::Password));
+
+        // Wraps around in both directions
+        handle_ui(&Action::FocusNextField, &mut state);
+        assert_eq!(state.focused_detail_field(), Some(crate::state::DetailField::Username));
+        handle_ui(&Action::FocusPreviousField, &mut state);
+        assert_eq!(state.focused_detail_field(), Some(crate::state::DetailField::Password));
+
+        handle_ui(&Action::UnfocusDetails, &mut state);
+        assert!(!state.details_focused());
+
+        // Changing the selection drops focus
+        handle_ui(&Action::FocusDetails, &mut state);
+        state.select_next();
+        assert!(!state.details_focused());
+
+        // Items without copyable fields can't be focused
+        handle_ui(&Action::FocusDetails, &mut state);
+        assert!(!state.details_focused());
+    }
+
+    #[test]
+    fn test_focus_returns_to_previous_pane() {
+        let mut state = AppState::new();
+        let mut item = create_test_item("1", "GitHub", ItemType::Login);
+        item.login = Some(crate::types::LoginData {
+            username: Some("user".to_string()),
+            password: None,
+            totp: None,
+            uris: None,
+            password_revision_date: None,
+        });
+        state.load_items_with_secrets(vec![item]);
+        assert_eq!(state.focus(), Focus::Search);
+        assert!(!state.can_focus_details());
+
+        handle_ui(&Action::FocusList, &mut state);
+        handle_ui(&Action::OpenDetailsPanel, &mut state);
+        assert!(state.can_focus_details());
+        handle_ui(&Action::FocusDetails, &mut state);
+        assert_eq!(state.focus(), Focus::Details);
+
+        // Leaving the details panel goes back to the list, not the search box
+        handle_ui(&Action::UnfocusDetails, &mut state);
+        assert_eq!(state.focus(), Focus::List);
+
+        handle_ui(&Action::FocusDetails, &mut state);
+        handle_ui(&Action::FocusSearch, &mut state);
+        assert_eq!(state.focus(), Focus::Search);
+        assert!(!state.details_focused());
+    }
+
+    #[test]
+    fn test_status_history_popup() {
+        let mut state = AppState::new();
+        state.set_status("first", crate::state::MessageLevel::Info);
+        state.set_status("second", crate::state::MessageLevel::Error);
+        assert_eq!(state.status_history.len(), 2);
+        assert_eq!(state.status_history.newest_first().next().map(|e| e.text.as_str()), Some("second"));
+
+        assert!(handle_ui(&Action::ToggleStatusHistory, &mut state));
+        assert!(state.show_status_history());
+
+        // Scrolling stops at the oldest message
+        handle_ui(&Action::ScrollStatusHistoryDown, &mut state);
+        handle_ui(&Action::ScrollStatusHistoryDown, &mut state);
+        assert_eq!(state.ui.status_history_scroll, 1);
+        handle_ui(&Action::ScrollStatusHistoryUp, &mut state);
+        assert_eq!(state.ui.status_history_scroll, 0);
+
+        handle_ui(&Action::ToggleStatusHistory, &mut state);
+        assert!(!state.show_status_history());
+    }
+
+    #[test]
+    fn test_duplicates_report_jumps_to_item() {
+        let mut state = AppState::new();
+        state.load_items_with_secrets(vec![
+            create_test_item("1", "GitHub", ItemType::Login),
+            create_test_item("2", "Bank", ItemType::Card),
+            create_test_item("3", "Bank", ItemType::Login),
+        ]);
+        state.set_item_type_filter(Some(ItemType::Login));
+        state.append_filter('g');
+
+        assert!(handle_ui(&Action::ToggleDuplicates, &mut state));
+        assert!(state.show_duplicates());
+        assert_eq!(state.duplicates.item_count(), 2);
+
+        // Deleting asks for confirmation first
+        handle_ui(&Action::RequestDeleteDuplicate, &mut state);
+        assert!(state.duplicates.confirm_delete);
+        handle_ui(&Action::CancelDeleteDuplicate, &mut state);
+        assert!(!state.duplicates.confirm_delete);
+
+        // Nothing can be deleted in read-only mode
+        state.ui.readonly = true;
+        handle_ui(&Action::RequestDeleteDuplicate, &mut state);
+        assert!(!state.duplicates.confirm_delete);
+
+        // Jumping clears the filters hiding the card
+        handle_ui(&Action::JumpToDuplicate, &mut state);
+        assert!(!state.show_duplicates());
+        assert!(state.details_panel_visible());
+        assert_eq!(state.ui.get_active_filter(), None);
+        assert_eq!(state.selected_item().map(|item| item.id.as_str()), Some("2"));
+
+        // Removing an item refreshes the open report
+        handle_ui(&Action::ToggleDuplicates, &mut state);
+        state.remove_item("3");
+        assert_eq!(state.duplicates.item_count(), 0);
+    }
+
+    #[test]
+    fn test_resize_split_and_fullscreen() {
+        let mut state = AppState::new();
+        assert_eq!(state.ui.layout.list_percent, 50);
+
+        assert!(handle_ui(&Action::GrowList, &mut state));
+        assert_eq!(state.ui.layout.list_percent, 55);
+        assert!(handle_ui(&Action::ShrinkList, &mut state));
+        assert_eq!(state.ui.layout.list_percent, 50);
+
+        // The split stays within bounds
+        for _ in 0..20 {
+            handle_ui(&Action::ShrinkList, &mut state);
+        }
+        assert_eq!(state.ui.layout.list_percent, crate::config::MIN_LIST_PERCENT);
+
+        // Full-screen opens the details panel, closing the panel leaves full-screen
+        assert!(handle_ui(&Action::ToggleDetailsFullscreen, &mut state));
+        assert!(state.details_panel_visible());
+        assert!(state.details_fullscreen());
+        handle_ui(&Action::ToggleDetailsFullscreen, &mut state);
+        assert!(state.details_panel_visible());
+        assert!(!state.details_fullscreen());
+        handle_ui(&Action::ToggleDetailsFullscreen, &mut state);
+        handle_ui(&Action::CloseDetailsPanel, &mut state);
+        assert!(!state.details_fullscreen());
+    }
+}
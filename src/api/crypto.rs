@@ -0,0 +1,262 @@
+use crate::error::{BwError, Result};
+use aes::cipher::{block_padding::Pkcs7, BlockDecryptMut, KeyIvInit};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::{Oaep, RsaPrivateKey};
+use sha2::{Digest, Sha256};
+
+type Aes256CbcDec = cbc::Decryptor<aes::Aes256>;
+type HmacSha256 = Hmac<Sha256>;
+
+/// Key derivation settings of an account, as reported by the server
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kdf {
+    Pbkdf2 { iterations: u32 },
+    Argon2id { iterations: u32, memory_mib: u32, parallelism: u32 },
+}
+
+/// An AES-256 key, with the HMAC-SHA256 key authenticating its ciphertexts
+#[derive(Clone)]
+pub struct SymmetricKey {
+    enc: [u8; 32],
+    mac: Option<[u8; 32]>, // Only missing for keys of very old accounts
+}
+
+impl std::fmt::Debug for SymmetricKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SymmetricKey(..)")
+    }
+}
+
+impl SymmetricKey {
+    /// A 64 byte key (encryption then MAC key), or a 32 byte encryption-only key
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        match bytes.len() {
+            64 => Ok(Self { enc: to_array(&bytes[..32]), mac: Some(to_array(&bytes[32..])) }),
+            32 => Ok(Self { enc: to_array(bytes), mac: None }),
+            len => Err(crypto_error(&format!("invalid key length {}", len))),
+        }
+    }
+
+    /// Decrypt an encrypted string ("2.iv|data|mac"), checking its MAC
+    pub fn decrypt(&self, enc_string: &str) -> Result<Vec<u8>> {
+        let (enc_type, parts) = parse_enc_string(enc_string)?;
+        let (iv, data, mac) = match (enc_type, parts.as_slice()) {
+            (0, [iv, data]) => (iv, data, None),
+            (2, [iv, data, mac]) => (iv, data, Some(mac)),
+            _ => return Err(crypto_error(&format!("unsupported encryption type {}", enc_type))),
+        };
+
+        match (mac, &self.mac) {
+            (Some(mac), Some(mac_key)) => {
+                let mut hmac = <HmacSha256 as Mac>::new_from_slice(mac_key).map_err(|e| crypto_error(&e.to_string()))?;
+                hmac.update(iv);
+                hmac.update(data);
+                hmac.verify_slice(mac).map_err(|_| crypto_error("MAC mismatch"))?;
+            }
+            (None, None) => {}
+            _ => return Err(crypto_error("key and encrypted value don't match")),
+        }
+
+        Aes256CbcDec::new_from_slices(&self.enc, iv)
+            .map_err(|e| crypto_error(&e.to_string()))?
+            .decrypt_padded_vec_mut::<Pkcs7>(data)
+            .map_err(|_| crypto_error("invalid padding"))
+    }
+
+    /// Decrypt an encrypted string holding UTF-8 text
+    pub fn decrypt_to_string(&self, enc_string: &str) -> Result<String> {
+        String::from_utf8(self.decrypt(enc_string)?).map_err(|_| crypto_error("decrypted value is not UTF-8"))
+    }
+
+    /// Decrypt an encrypted string holding another key
+    pub fn decrypt_key(&self, enc_string: &str) -> Result<SymmetricKey> {
+        SymmetricKey::from_bytes(&self.decrypt(enc_string)?)
+    }
+}
+
+/// Derive the master key from the master password
+pub fn derive_master_key(password: &str, email: &str, kdf: Kdf) -> Result<[u8; 32]> {
+    let email = email.trim().to_lowercase();
+    let mut master_key = [0u8; 32];
+    match kdf {
+        Kdf::Pbkdf2 { iterations } => {
+            pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), email.as_bytes(), iterations, &mut master_key);
+        }
+        Kdf::Argon2id { iterations, memory_mib, parallelism } => {
+            let params = argon2::Params::new(memory_mib * 1024, iterations, parallelism, Some(32))
+                .map_err(|e| crypto_error(&format!("invalid Argon2 settings: {}", e)))?;
+            let salt = Sha256::digest(email.as_bytes());
+            argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+                .hash_password_into(password.as_bytes(), &salt, &mut master_key)
+                .map_err(|e| crypto_error(&format!("Argon2 failed: {}", e)))?;
+        }
+    }
+    Ok(master_key)
+}
+
+/// The hash sent to the server instead of the master password
+pub fn master_password_hash(master_key: &[u8; 32], password: &str) -> String {
+    let mut hash = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(master_key, password.as_bytes(), 1, &mut hash);
+    STANDARD.encode(hash)
+}
+
+/// Expand the master key into the key that decrypts the account's user key
+pub fn stretch_master_key(master_key: &[u8; 32]) -> Result<SymmetricKey> {
+    let hkdf = Hkdf::<Sha256>::from_prk(master_key).map_err(|e| crypto_error(&e.to_string()))?;
+    let mut enc = [0u8; 32];
+    let mut mac = [0u8; 32];
+    hkdf.expand(b"enc", &mut enc).map_err(|e| crypto_error(&e.to_string()))?;
+    hkdf.expand(b"mac", &mut mac).map_err(|e| crypto_error(&e.to_string()))?;
+    Ok(SymmetricKey { enc, mac: Some(mac) })
+}
+
+/// Decrypt the account's RSA private key, used to decrypt organization keys
+pub fn decrypt_private_key(user_key: &SymmetricKey, enc_string: &str) -> Result<RsaPrivateKey> {
+    let der = user_key.decrypt(enc_string)?;
+    RsaPrivateKey::from_pkcs8_der(&der).map_err(|e| crypto_error(&format!("invalid private key: {}", e)))
+}
+
+/// Decrypt an organization key encrypted with the account's public key ("4.data")
+pub fn decrypt_org_key(private_key: &RsaPrivateKey, enc_string: &str) -> Result<SymmetricKey> {
+    let (enc_type, parts) = parse_enc_string(enc_string)?;
+    let data = parts.first().ok_or_else(|| crypto_error("missing data"))?;
+    let padding = match enc_type {
+        3 | 5 => Oaep::new::<Sha256>(),
+        4 | 6 => Oaep::new::<sha1::Sha1>(),
+        _ => return Err(crypto_error(&format!("unsupported encryption type {}", enc_type))),
+    };
+    let key = private_key.decrypt(padding, data).map_err(|e| crypto_error(&e.to_string()))?;
+    SymmetricKey::from_bytes(&key)
+}
+
+/// Whether a value looks like an encrypted string rather than plain text
+pub fn is_enc_string(value: &str) -> bool {
+    parse_enc_string(value).is_ok()
+}
+
+/// Split "<type>.<b64>|<b64>|..." into the type and decoded parts
+fn parse_enc_string(enc_string: &str) -> Result<(u8, Vec<Vec<u8>>)> {
+    let (enc_type, rest) = enc_string.split_once('.').ok_or_else(|| crypto_error("not an encrypted value"))?;
+    let enc_type: u8 = enc_type.parse().map_err(|_| crypto_error("not an encrypted value"))?;
+    let parts = rest
+        .split('|')
+        .map(|part| STANDARD.decode(part))
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|_| crypto_error("not an encrypted value"))?;
+    Ok((enc_type, parts))
+}
+
+/// Encrypt text like the Bitwarden clients do ("2.iv|data|mac"), with a random IV
+pub fn encrypt_string(key: &SymmetricKey, plaintext: &str) -> Result<String> {
+    let mut iv = [0u8; 16];
+    rsa::rand_core::RngCore::fill_bytes(&mut rsa::rand_core::OsRng, &mut iv);
+    encrypt_with_iv(key, plaintext.as_bytes(), iv)
+}
+
+fn encrypt_with_iv(key: &SymmetricKey, plaintext: &[u8], iv: [u8; 16]) -> Result<String> {
+    use aes::cipher::BlockEncryptMut;
+
+    // Keys without a MAC key are from very old accounts, new values always get a MAC
+    let mac_key = key.mac.as_ref().ok_or_else(|| crypto_error("the key has no MAC key"))?;
+    let data = cbc::Encryptor::<aes::Aes256>::new_from_slices(&key.enc, &iv)
+        .map_err(|e| crypto_error(&e.to_string()))?
+        .encrypt_padded_vec_mut::<Pkcs7>(plaintext);
+    let mut hmac = <HmacSha256 as Mac>::new_from_slice(mac_key).map_err(|e| crypto_error(&e.to_string()))?;
+    hmac.update(&iv);
+    hmac.update(&data);
+    let mac = hmac.finalize().into_bytes();
+    Ok(format!("2.{}|{}|{}", STANDARD.encode(iv), STANDARD.encode(data), STANDARD.encode(mac)))
+}
+
+/// Encrypt with a fixed IV, to test decryption against
+#[cfg(test)]
+pub fn encrypt(key: &SymmetricKey, plaintext: &[u8], iv: [u8; 16]) -> String {
+    encrypt_with_iv(key, plaintext, iv).unwrap()
+}
+
+fn to_array(bytes: &[u8]) -> [u8; 32] {
+    let mut array = [0u8; 32];
+    array.copy_from_slice(bytes);
+    array
+}
+
+fn crypto_error(message: &str) -> BwError {
+    BwError::CommandFailed(format!("Decryption failed: {}", message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_key() -> SymmetricKey {
+        SymmetricKey::from_bytes(&(0u8..64).collect::<Vec<_>>()).unwrap()
+    }
+
+    #[test]
+    fn test_decrypt_round_trip() {
+        let key = test_key();
+        let enc_string = encrypt(&key, "GitHub".as_bytes(), [7; 16]);
+        assert!(is_enc_string(&enc_string));
+        assert_eq!(key.decrypt_to_string(&enc_string).unwrap(), "GitHub");
+
+        // Item keys are themselves encrypted with the user key
+        let item_key_bytes: Vec<u8> = (100u8..164).collect();
+        let item_key = key.decrypt_key(&encrypt(&key, &item_key_bytes, [1; 16])).unwrap();
+        assert_eq!(item_key.enc.to_vec(), item_key_bytes[..32].to_vec());
+    }
+
+    #[test]
+    fn test_encrypt_string_uses_a_new_iv() {
+        let key = test_key();
+        let first = encrypt_string(&key, "Work").unwrap();
+        assert_ne!(first, encrypt_string(&key, "Work").unwrap());
+        assert_eq!(key.decrypt_to_string(&first).unwrap(), "Work");
+
+        let no_mac = SymmetricKey::from_bytes(&[9u8; 32]).unwrap();
+        assert!(encrypt_string(&no_mac, "Work").is_err());
+    }
+
+    #[test]
+    fn test_decrypt_rejects_tampering() {
+        let key = test_key();
+        let enc_string = encrypt(&key, b"secret", [7; 16]);
+        let (head, mac) = enc_string.rsplit_once('|').unwrap();
+        let mut mac = STANDARD.decode(mac).unwrap();
+        mac[0] ^= 1;
+        let tampered = format!("{}|{}", head, STANDARD.encode(mac));
+        assert!(key.decrypt(&tampered).is_err());
+
+        let other_key = SymmetricKey::from_bytes(&[9u8; 64]).unwrap();
+        assert!(other_key.decrypt(&enc_string).is_err());
+    }
+
+    #[test]
+    fn test_is_enc_string() {
+        assert!(!is_enc_string("GitHub"));
+        assert!(!is_enc_string("2023-01-01T00:00:00.000Z"));
+        assert!(!is_enc_string("1.5"));
+        assert!(!is_enc_string("https://example.com"));
+    }
+
+    #[test]
+    fn test_master_key_derivation() {
+        let kdf = Kdf::Pbkdf2 { iterations: 1000 };
+        let master_key = derive_master_key("password", "User@Example.com", kdf).unwrap();
+        // The email is the salt, regardless of case
+        assert_eq!(master_key, derive_master_key("password", "user@example.com", kdf).unwrap());
+        assert_ne!(master_key, derive_master_key("Password", "user@example.com", kdf).unwrap());
+
+        let hash = master_password_hash(&master_key, "password");
+        assert_eq!(STANDARD.decode(hash).unwrap().len(), 32);
+
+        let stretched = stretch_master_key(&master_key).unwrap();
+        assert_ne!(Some(stretched.enc), stretched.mac);
+
+        let argon2 = Kdf::Argon2id { iterations: 1, memory_mib: 1, parallelism: 1 };
+        assert_ne!(derive_master_key("password", "user@example.com", argon2).unwrap(), master_key);
+    }
+}
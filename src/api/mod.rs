@@ -0,0 +1,851 @@
+mod crypto;
+
+use crate::backend::{Backend, ItemBatchFn, Unlocked, VaultBackend, VaultStatus};
+use crate::config::BackendConfig;
+use crate::error::{BwError, Result};
+use crate::retry::{self, RetryListener, RetryPolicy};
+use crate::types::{Collection, CollectionAccess, EquivalentDomains, Folder, Organization, VaultItem};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use crypto::{Kdf, SymmetricKey};
+use futures_util::future::BoxFuture;
+use rsa::RsaPrivateKey;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Device type the server records for this client
+#[cfg(target_os = "windows")]
+const DEVICE_TYPE: &str = "23"; // Windows CLI
+#[cfg(target_os = "macos")]
+const DEVICE_TYPE: &str = "24"; // macOS CLI
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const DEVICE_TYPE: &str = "25"; // Linux CLI
+
+/// Base URLs of the server's identity and API services
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ServerUrls {
+    identity: String,
+    api: String,
+}
+
+impl ServerUrls {
+    /// Bitwarden's cloud servers use separate hosts, self-hosted servers (and Vaultwarden) use paths
+    fn new(server_url: &str) -> Self {
+        let server_url = server_url.trim().trim_end_matches('/');
+        match server_url {
+            "https://vault.bitwarden.com" | "https://bitwarden.com" => Self {
+                identity: "https://identity.bitwarden.com".to_string(),
+                api: "https://api.bitwarden.com".to_string(),
+            },
+            "https://vault.bitwarden.eu" | "https://bitwarden.eu" => Self {
+                identity: "https://identity.bitwarden.eu".to_string(),
+                api: "https://api.bitwarden.eu".to_string(),
+            },
+            _ => Self {
+                identity: format!("{}/identity", server_url),
+                api: format!("{}/api", server_url),
+            },
+        }
+    }
+}
+
+/// Access token and keys of an unlocked vault
+struct Session {
+    access_token: String,
+    user_key: SymmetricKey,
+    private_key: Option<RsaPrivateKey>,
+}
+
+/// Decrypted contents of the last /sync response
+struct VaultData {
+    items: Vec<VaultItem>,
+    organizations: Vec<Organization>,
+    folders: Vec<Folder>,
+    collections: Vec<Collection>,
+}
+
+/// Backend talking to the Bitwarden (or Vaultwarden) server directly, decrypting the vault locally
+/// Only the read operations bwtui needs, moving items to the trash and changing folders are supported
+#[derive(Clone)]
+pub struct ApiBackend {
+    client: reqwest::Client,
+    urls: ServerUrls,
+    email: String,
+    client_credentials: Option<(String, String)>, // Personal API key (client_id, client_secret)
+    device_id: String,
+    session: Option<Arc<Session>>, // Set once unlocked
+    data: Arc<Mutex<Option<Arc<VaultData>>>>, // Vault fetched by the last sync, shared by the list operations
+    retry_listener: Option<RetryListener>,
+}
+
+impl ApiBackend {
+    /// Create a locked backend for the configured server and account
+    pub fn new(config: &BackendConfig, timeout: Duration) -> Result<Self> {
+        Self::new_with_device_id(config, timeout, device_id())
+    }
+
+    /// Create a locked backend that identifies itself with the given device id
+    /// Tests use it so they don't read or write the device id in the data directory
+    fn new_with_device_id(config: &BackendConfig, timeout: Duration, device_id: String) -> Result<Self> {
+        if config.email.trim().is_empty() {
+            return Err(BwError::CommandFailed("Set email under [backend] to use the api backend".to_string()));
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .user_agent(concat!("bwtui/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .map_err(|e| BwError::CommandFailed(format!("Failed to create HTTP client: {}", e)))?;
+
+        let client_credentials = match (&config.client_id, &config.client_secret) {
+            (Some(id), Some(secret)) => Some((id.clone(), secret.clone())),
+            _ => None,
+        };
+
+        crate::logger::Logger::info(&format!("Using the API backend with {}", config.server_url));
+        Ok(Self {
+            client,
+            urls: ServerUrls::new(&config.server_url),
+            email: config.email.trim().to_string(),
+            client_credentials,
+            device_id,
+            session: None,
+            data: Arc::new(Mutex::new(None)),
+            retry_listener: None,
+        })
+    }
+
+    /// Log in and decrypt the user key with the master password
+    async fn log_in(&self, password: &str) -> Result<Session> {
+        let kdf = self.prelogin().await?;
+        let master_key = crypto::derive_master_key(password, &self.email, kdf)?;
+        let password_hash = crypto::master_password_hash(&master_key, password);
+
+        let device_name = format!("bwtui on {}", whoami::devicename());
+        let mut form = vec![
+            ("deviceType", DEVICE_TYPE.to_string()),
+            ("deviceIdentifier", self.device_id.clone()),
+            ("deviceName", device_name),
+        ];
+        match &self.client_credentials {
+            Some((client_id, client_secret)) => form.extend([
+                ("grant_type", "client_credentials".to_string()),
+                ("scope", "api".to_string()),
+                ("client_id", client_id.clone()),
+                ("client_secret", client_secret.clone()),
+            ]),
+            None => form.extend([
+                ("grant_type", "password".to_string()),
+                ("scope", "api offline_access".to_string()),
+                ("client_id", "cli".to_string()),
+                ("username", self.email.clone()),
+                ("password", password_hash),
+            ]),
+        }
+
+        let response = self.client
+            .post(format!("{}/connect/token", self.urls.identity))
+            .header("Auth-Email", URL_SAFE_NO_PAD.encode(&self.email))
+            .form(&form)
+            .send()
+            .await
+            .map_err(request_error)?;
+        let status = response.status();
+        let body = normalize_keys(response.json::<Value>().await.map_err(request_error)?);
+
+        if !status.is_success() {
+            return Err(login_error(&body));
+        }
+
+        let access_token = body["access_token"]
+            .as_str()
+            .ok_or_else(|| BwError::ParseError("Login response has no access token".to_string()))?
+            .to_string();
+        let encrypted_user_key = body["key"]
+            .as_str()
+            .ok_or_else(|| BwError::ParseError("Login response has no user key".to_string()))?;
+
+        let user_key = crypto::stretch_master_key(&master_key)?
+            .decrypt_key(encrypted_user_key)
+            .map_err(|_| BwError::CommandFailed("Invalid master password".to_string()))?;
+        let private_key = body["privateKey"]
+            .as_str()
+            .map(|private_key| crypto::decrypt_private_key(&user_key, private_key))
+            .transpose()?;
+
+        crate::logger::Logger::info("Logged in to the server");
+        Ok(Session { access_token, user_key, private_key })
+    }
+
+    /// Fetch the account's key derivation settings
+    async fn prelogin(&self) -> Result<Kdf> {
+        let response = self.client
+            .post(format!("{}/accounts/prelogin", self.urls.identity))
+            .json(&serde_json::json!({ "email": self.email }))
+            .send()
+            .await
+            .map_err(request_error)?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(http_error(status));
+        }
+        parse_kdf(&normalize_keys(response.json::<Value>().await.map_err(request_error)?))
+    }
+
+    fn session(&self) -> Result<&Session> {
+        self.session.as_deref().ok_or(BwError::VaultLocked)
+    }
+
+    /// The vault fetched by the last sync, fetching it if needed
+    async fn vault_data(&self) -> Result<Arc<VaultData>> {
+        if let Some(data) = self.data.lock().ok().and_then(|data| data.clone()) {
+            return Ok(data);
+        }
+
+        let data = Arc::new(
+            retry::retry(RetryPolicy::default(), "GET /sync", self.retry_listener.as_ref(), || self.fetch_vault_data()).await?,
+        );
+        if let Ok(mut cached) = self.data.lock() {
+            *cached = Some(data.clone());
+        }
+        Ok(data)
+    }
+
+    /// Download and decrypt the whole vault
+    async fn fetch_vault_data(&self) -> Result<VaultData> {
+        let session = self.session()?;
+        let response = self.client
+            .get(format!("{}/sync?excludeDomains=true", self.urls.api))
+            .bearer_auth(&session.access_token)
+            .send()
+            .await
+            .map_err(request_error)?;
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            // The access token expired, logging in again unlocks the vault again
+            return Err(BwError::VaultLocked);
+        }
+        if !status.is_success() {
+            return Err(http_error(status));
+        }
+
+        let sync = normalize_keys(response.json::<Value>().await.map_err(request_error)?);
+        decrypt_vault(&sync, session)
+    }
+
+    /// Send a request changing the vault, returning the response body (null if there is none)
+    /// The vault fetched by the last sync is dropped, so the next read includes the change
+    async fn send_change(&self, request: reqwest::RequestBuilder) -> Result<Value> {
+        let body = self.send(request).await?;
+        if let Ok(mut data) = self.data.lock() {
+            *data = None;
+        }
+        Ok(body)
+    }
+
+    /// Send an authenticated request, returning the response body (null if there is none)
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<Value> {
+        let session = self.session()?;
+        let response = request
+            .bearer_auth(&session.access_token)
+            .send()
+            .await
+            .map_err(request_error)?;
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(BwError::VaultLocked);
+        }
+        if !status.is_success() {
+            return Err(http_error(status));
+        }
+        Ok(response.json::<Value>().await.map(normalize_keys).unwrap_or(Value::Null))
+    }
+
+    /// Clone of this backend with the given session
+    fn with_session(&self, session: Session) -> Self {
+        Self {
+            session: Some(Arc::new(session)),
+            data: Arc::new(Mutex::new(None)),
+            retry_listener: None,
+            ..self.clone()
+        }
+    }
+}
+
+impl VaultBackend for ApiBackend {
+    fn check_status(&self) -> BoxFuture<'_, Result<VaultStatus>> {
+        // The master password is needed to decrypt the vault on every start
+        let status = if self.session.is_some() { VaultStatus::Unlocked } else { VaultStatus::Locked };
+        Box::pin(async move { Ok(status) })
+    }
+
+    fn unlock<'a>(&'a self, password: &'a str) -> BoxFuture<'a, Result<Unlocked>> {
+        Box::pin(async move {
+            let session = self.log_in(password).await?;
+            // Nothing can be saved, the keys only live in memory
+            Ok(Unlocked { session_token: None, backend: Arc::new(self.with_session(session)) })
+        })
+    }
+
+    fn sync(&self) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move {
+            if let Ok(mut data) = self.data.lock() {
+                *data = None;
+            }
+            self.vault_data().await.map(|_| ())
+        })
+    }
+
+    fn list_items<'a>(&'a self, on_batch: &'a ItemBatchFn) -> BoxFuture<'a, Result<usize>> {
+        Box::pin(async move {
+            let data = self.vault_data().await?;
+            on_batch(data.items.clone());
+            Ok(data.items.len())
+        })
+    }
+
+    fn list_organizations(&self) -> BoxFuture<'_, Result<Vec<Organization>>> {
+        Box::pin(async move { Ok(self.vault_data().await?.organizations.clone()) })
+    }
+
+    fn list_folders(&self) -> BoxFuture<'_, Result<Vec<Folder>>> {
+        Box::pin(async move { Ok(self.vault_data().await?.folders.clone()) })
+    }
+
+    fn list_collections(&self) -> BoxFuture<'_, Result<Vec<Collection>>> {
+        Box::pin(async move { Ok(self.vault_data().await?.collections.clone()) })
+    }
+
+    fn collection_access<'a>(&'a self, organization_id: &'a str, collection_id: &'a str) -> BoxFuture<'a, Result<CollectionAccess>> {
+        Box::pin(async move {
+            let organization = format!("{}/organizations/{}", self.urls.api, organization_id);
+            let mut members = self.send(self.client.get(format!("{}/users", organization))).await?;
+            let details = self.send(self.client.get(format!("{}/collections/{}/details", organization, collection_id))).await?;
+            let parse_error = |e: serde_json::Error| BwError::ParseError(format!("Failed to parse collection access: {}", e));
+            Ok(CollectionAccess::new(
+                serde_json::from_value(details).map_err(parse_error)?,
+                serde_json::from_value(members["data"].take()).map_err(parse_error)?,
+            ))
+        })
+    }
+
+    fn equivalent_domains(&self) -> BoxFuture<'_, Result<EquivalentDomains>> {
+        Box::pin(async move {
+            // The domains aren't encrypted, and are left out of /sync
+            let settings = self.send(self.client.get(format!("{}/settings/domains", self.urls.api))).await?;
+            let settings = serde_json::from_value(settings)
+                .map_err(|e| BwError::ParseError(format!("Failed to parse equivalent domains: {}", e)))?;
+            Ok(EquivalentDomains::from_settings(settings))
+        })
+    }
+
+    fn get_totp<'a>(&'a self, item_id: &'a str) -> BoxFuture<'a, Result<String>> {
+        Box::pin(async move {
+            let data = self.vault_data().await?;
+            let secret = data.items
+                .iter()
+                .find(|item| item.id == item_id)
+                .and_then(|item| item.login.as_ref()?.totp.clone())
+                .ok_or_else(|| BwError::CommandFailed("No TOTP configured for this item".to_string()))?;
+            Ok(crate::totp_util::generate_totp(&secret)?.code)
+        })
+    }
+
+    fn delete_item<'a>(&'a self, item_id: &'a str) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            self.send_change(self.client.put(format!("{}/ciphers/{}/delete", self.urls.api, item_id))).await?;
+            crate::logger::Logger::info(&format!("Item {} moved to trash", item_id));
+            Ok(())
+        })
+    }
+
+    fn share_item<'a>(&'a self, _item_id: &'a str, _organization_id: &'a str, _collection_ids: &'a [String]) -> BoxFuture<'a, Result<()>> {
+        // Every field would have to be encrypted again with the organization's key
+        Box::pin(async { Err(BwError::CommandFailed("Sharing items needs the bw CLI backend".to_string())) })
+    }
+
+    fn set_reprompt<'a>(&'a self, _item_id: &'a str, _enabled: bool) -> BoxFuture<'a, Result<()>> {
+        // The server only takes whole items, which would have to be encrypted again
+        Box::pin(async { Err(BwError::CommandFailed("Changing the re-prompt setting needs the bw CLI backend".to_string())) })
+    }
+
+    fn move_item<'a>(&'a self, item_id: &'a str, folder_id: Option<&'a str>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            // The partial update takes the folder and favorite flag as they are, nothing to encrypt
+            let favorite = self.vault_data().await?
+                .items
+                .iter()
+                .find(|item| item.id == item_id)
+                .is_some_and(|item| item.favorite);
+            let request = self.client
+                .put(format!("{}/ciphers/{}/partial", self.urls.api, item_id))
+                .json(&serde_json::json!({ "folderId": folder_id, "favorite": favorite }));
+            self.send_change(request).await?;
+            crate::logger::Logger::info(&format!("Item {} moved to folder {}", item_id, folder_id.unwrap_or("none")));
+            Ok(())
+        })
+    }
+
+    fn create_folder<'a>(&'a self, name: &'a str) -> BoxFuture<'a, Result<Folder>> {
+        Box::pin(async move {
+            let encrypted_name = crypto::encrypt_string(&self.session()?.user_key, name)?;
+            let request = self.client
+                .post(format!("{}/folders", self.urls.api))
+                .json(&serde_json::json!({ "name": encrypted_name }));
+            let body = self.send_change(request).await?;
+            let id = body["id"]
+                .as_str()
+                .ok_or_else(|| BwError::ParseError("The created folder has no id".to_string()))?;
+            crate::logger::Logger::info(&format!("Folder {} created", id));
+            Ok(Folder { id: id.to_string(), name: name.to_string() })
+        })
+    }
+
+    fn rename_folder<'a>(&'a self, folder_id: &'a str, name: &'a str) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let encrypted_name = crypto::encrypt_string(&self.session()?.user_key, name)?;
+            let request = self.client
+                .put(format!("{}/folders/{}", self.urls.api, folder_id))
+                .json(&serde_json::json!({ "name": encrypted_name }));
+            self.send_change(request).await?;
+            crate::logger::Logger::info(&format!("Folder {} renamed", folder_id));
+            Ok(())
+        })
+    }
+
+    fn delete_folder<'a>(&'a self, folder_id: &'a str) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            self.send_change(self.client.delete(format!("{}/folders/{}", self.urls.api, folder_id))).await?;
+            crate::logger::Logger::info(&format!("Folder {} deleted", folder_id));
+            Ok(())
+        })
+    }
+
+    fn with_retry_listener(&self, listener: RetryListener) -> Backend {
+        Arc::new(Self { retry_listener: Some(listener), ..self.clone() })
+    }
+}
+
+/// Decrypt the folders, organizations, collections and items of a /sync response
+fn decrypt_vault(sync: &Value, session: &Session) -> Result<VaultData> {
+    let mut organizations = Vec::new();
+    let mut org_keys: HashMap<String, SymmetricKey> = HashMap::new();
+    for organization in sync["profile"]["organizations"].as_array().into_iter().flatten() {
+        let (Some(id), Some(name)) = (organization["id"].as_str(), organization["name"].as_str()) else {
+            continue;
+        };
+        organizations.push(Organization { id: id.to_string(), name: name.to_string() });
+
+        let key = match (&session.private_key, organization["key"].as_str()) {
+            (Some(private_key), Some(key)) => crypto::decrypt_org_key(private_key, key),
+            _ => Err(BwError::CommandFailed("Organization key unavailable".to_string())),
+        };
+        match key {
+            Ok(key) => {
+                org_keys.insert(id.to_string(), key);
+            }
+            Err(e) => crate::logger::Logger::warn(&format!("Failed to decrypt key of organization {}: {}", id, e)),
+        }
+    }
+
+    let mut folders = Vec::new();
+    for folder in sync["folders"].as_array().into_iter().flatten() {
+        let (Some(id), Some(name)) = (folder["id"].as_str(), folder["name"].as_str()) else {
+            continue;
+        };
+        match session.user_key.decrypt_to_string(name) {
+            Ok(name) => folders.push(Folder { id: id.to_string(), name }),
+            Err(e) => crate::logger::Logger::warn(&format!("Failed to decrypt folder {}: {}", id, e)),
+        }
+    }
+
+    // Collection names are encrypted with their organization's key
+    let mut collections = Vec::new();
+    for collection in sync["collections"].as_array().into_iter().flatten() {
+        let (Some(id), Some(organization_id), Some(name)) =
+            (collection["id"].as_str(), collection["organizationId"].as_str(), collection["name"].as_str())
+        else {
+            continue;
+        };
+        let Some(key) = org_keys.get(organization_id) else {
+            continue;
+        };
+        match key.decrypt_to_string(name) {
+            Ok(name) => collections.push(Collection { id: id.to_string(), organization_id: organization_id.to_string(), name }),
+            Err(e) => crate::logger::Logger::warn(&format!("Failed to decrypt collection {}: {}", id, e)),
+        }
+    }
+
+    let mut items = Vec::new();
+    for cipher in sync["ciphers"].as_array().into_iter().flatten() {
+        // Items in the trash aren't listed, like with the CLI
+        if !cipher["deletedDate"].is_null() {
+            continue;
+        }
+        let id = cipher["id"].as_str().unwrap_or_default();
+        match decrypt_cipher(cipher.clone(), &session.user_key, &org_keys) {
+            Ok(item) => items.push(item),
+            Err(e) => crate::logger::Logger::warn(&format!("Failed to decrypt item {}: {}", id, e)),
+        }
+    }
+
+    crate::logger::Logger::info(&format!("Decrypted {} vault items", items.len()));
+    Ok(VaultData { items, organizations, folders, collections })
+}
+
+/// Decrypt an item with its own key, its organization's key or the user key
+fn decrypt_cipher(mut cipher: Value, user_key: &SymmetricKey, org_keys: &HashMap<String, SymmetricKey>) -> Result<VaultItem> {
+    let object = cipher.as_object_mut().ok_or_else(|| BwError::ParseError("Item is not an object".to_string()))?;
+
+    let owner_key = match object.get("organizationId").and_then(Value::as_str) {
+        Some(organization_id) => org_keys
+            .get(organization_id)
+            .ok_or_else(|| BwError::CommandFailed(format!("Missing key of organization {}", organization_id)))?,
+        None => user_key,
+    };
+    let item_key = match object.remove("key") {
+        Some(Value::String(key)) => owner_key.decrypt_key(&key)?,
+        _ => owner_key.clone(),
+    };
+
+    // "data" repeats the item's fields, older servers don't send it
+    object.remove("data");
+    drop_empty_uris(object);
+
+    decrypt_values(&mut cipher, &item_key)?;
+    serde_json::from_value(cipher).map_err(|e| BwError::ParseError(format!("Invalid item: {}", e)))
+}
+
+/// URIs without a value can't be shown or opened
+fn drop_empty_uris(cipher: &mut Map<String, Value>) {
+    if let Some(Value::Array(uris)) = cipher.get_mut("login").and_then(|login| login.get_mut("uris")) {
+        uris.retain(|uri| uri["uri"].is_string());
+    }
+}
+
+/// Replace every encrypted string in a value with its plain text
+fn decrypt_values(value: &mut Value, key: &SymmetricKey) -> Result<()> {
+    match value {
+        Value::String(text) if crypto::is_enc_string(text) => {
+            *text = key.decrypt_to_string(text)?;
+        }
+        Value::Array(values) => {
+            for value in values {
+                decrypt_values(value, key)?;
+            }
+        }
+        Value::Object(map) => {
+            for value in map.values_mut() {
+                decrypt_values(value, key)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Lowercase the first letter of every key, as older servers send PascalCase names
+fn normalize_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| {
+                    let mut chars = key.chars();
+                    let key = match chars.next() {
+                        Some(first) => first.to_lowercase().chain(chars).collect(),
+                        None => key,
+                    };
+                    (key, normalize_keys(value))
+                })
+                .collect(),
+        ),
+        Value::Array(values) => Value::Array(values.into_iter().map(normalize_keys).collect()),
+        value => value,
+    }
+}
+
+/// Read the key derivation settings from a prelogin response
+fn parse_kdf(prelogin: &Value) -> Result<Kdf> {
+    let number = |key: &str, default: u32| prelogin[key].as_u64().map_or(default, |value| value as u32);
+    match prelogin["kdf"].as_u64() {
+        Some(0) | None => Ok(Kdf::Pbkdf2 { iterations: number("kdfIterations", 600_000) }),
+        Some(1) => Ok(Kdf::Argon2id {
+            iterations: number("kdfIterations", 3),
+            memory_mib: number("kdfMemory", 64),
+            parallelism: number("kdfParallelism", 4),
+        }),
+        Some(kdf) => Err(BwError::CommandFailed(format!("Unsupported key derivation function {}", kdf))),
+    }
+}
+
+/// Explain a rejected login
+fn login_error(body: &Value) -> BwError {
+    if !body["twoFactorProviders"].is_null() || !body["twoFactorProviders2"].is_null() {
+        return BwError::CommandFailed(
+            "Two-step login is not supported by the api backend, set client_id and client_secret under [backend]".to_string(),
+        );
+    }
+
+    let message = body["errorModel"]["message"]
+        .as_str()
+        .or_else(|| body["error_description"].as_str())
+        .unwrap_or("Login failed");
+    if message.to_lowercase().contains("username or password") {
+        return BwError::CommandFailed("Invalid master password".to_string());
+    }
+    BwError::CommandFailed(message.to_string())
+}
+
+fn request_error(e: reqwest::Error) -> BwError {
+    if e.is_timeout() {
+        BwError::CommandTimedOut(format!("Request to the server timed out: {}", e))
+    } else if e.is_connect() || e.is_request() {
+        // Worded so the retry logic treats it as transient
+        BwError::CommandFailed(format!("network error: {}", e))
+    } else {
+        BwError::ParseError(format!("Invalid server response: {}", e))
+    }
+}
+
+fn http_error(status: reqwest::StatusCode) -> BwError {
+    BwError::CommandFailed(format!("server returned {}", status))
+}
+
+/// Identifier the server recognizes this device by, kept between runs to avoid new-device notices
+fn device_id() -> String {
+    // Ephemeral runs write nothing to disk, so the id only lasts as long as the process
+    if crate::profile::ephemeral() {
+        static EPHEMERAL_ID: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+        return EPHEMERAL_ID.get_or_init(new_device_id).clone();
+    }
+
+    let path = crate::profile::data_dir().map(|dir| dir.join("device_id"));
+    if let Ok(id) = path.as_ref().map_err(|_| ()).and_then(|path| std::fs::read_to_string(path).map_err(|_| ())) {
+        if !id.trim().is_empty() {
+            return id.trim().to_string();
+        }
+    }
+
+    let id = new_device_id();
+    if let Ok(path) = path {
+        if let Err(e) = std::fs::create_dir_all(path.parent().unwrap_or(&path)).and_then(|_| std::fs::write(&path, &id)) {
+            crate::logger::Logger::warn(&format!("Failed to save device id: {}", e));
+        }
+    }
+    id
+}
+
+/// A random version 4 UUID
+fn new_device_id() -> String {
+    let mut bytes = [0u8; 16];
+    rsa::rand_core::RngCore::fill_bytes(&mut rsa::rand_core::OsRng, &mut bytes);
+    bytes[6] = (bytes[6] & 0x0f) | 0x40; // UUID version 4
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Keeps tests from creating the device id file in the user's data directory
+    const TEST_DEVICE_ID: &str = "00000000-0000-4000-8000-000000000000";
+
+    #[test]
+    fn test_server_urls() {
+        let cloud = ServerUrls::new("https://vault.bitwarden.com/");
+        assert_eq!(cloud.api, "https://api.bitwarden.com");
+        assert_eq!(cloud.identity, "https://identity.bitwarden.com");
+
+        let self_hosted = ServerUrls::new("https://vw.example.com/");
+        assert_eq!(self_hosted.api, "https://vw.example.com/api");
+        assert_eq!(self_hosted.identity, "https://vw.example.com/identity");
+    }
+
+    #[test]
+    fn test_parse_kdf() {
+        let prelogin = normalize_keys(serde_json::json!({ "Kdf": 0, "KdfIterations": 600000 }));
+        assert_eq!(parse_kdf(&prelogin).unwrap(), Kdf::Pbkdf2 { iterations: 600_000 });
+
+        let prelogin = serde_json::json!({ "kdf": 1, "kdfIterations": 3, "kdfMemory": 64, "kdfParallelism": 4 });
+        assert_eq!(parse_kdf(&prelogin).unwrap(), Kdf::Argon2id { iterations: 3, memory_mib: 64, parallelism: 4 });
+
+        assert!(parse_kdf(&serde_json::json!({ "kdf": 7 })).is_err());
+    }
+
+    #[test]
+    fn test_login_error() {
+        let body = normalize_keys(serde_json::json!({ "TwoFactorProviders": [0] }));
+        assert!(login_error(&body).to_string().contains("Two-step login"));
+
+        let body = normalize_keys(serde_json::json!({ "ErrorModel": { "Message": "Username or password is incorrect. Try again." } }));
+        assert!(login_error(&body).to_string().contains("Invalid master password"));
+    }
+
+    /// Serve canned responses to the login and sync requests of one unlock and load
+    async fn serve_vault(listener: tokio::net::TcpListener, password: &str, email: &str) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let kdf = Kdf::Pbkdf2 { iterations: 1000 };
+        let master_key = crypto::derive_master_key(password, email, kdf).unwrap();
+        let user_key_bytes = [5u8; 64];
+        let user_key = SymmetricKey::from_bytes(&user_key_bytes).unwrap();
+        let stretched = crypto::stretch_master_key(&master_key).unwrap();
+        let expected_hash = crypto::master_password_hash(&master_key, password);
+
+        for _ in 0..3 {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0u8; 4096];
+            // Requests are small, read until the body announced by Content-Length arrived
+            loop {
+                let read = stream.read(&mut buffer).await.unwrap();
+                request.extend_from_slice(&buffer[..read]);
+                let text = String::from_utf8_lossy(&request).to_lowercase();
+                if let Some(end) = text.find("\r\n\r\n") {
+                    let length = text.lines()
+                        .find_map(|line| line.strip_prefix("content-length: ").and_then(|value| value.trim().parse().ok()))
+                        .unwrap_or(0usize);
+                    if request.len() >= end + 4 + length {
+                        break;
+                    }
+                }
+            }
+            let request = String::from_utf8_lossy(&request).to_string();
+
+            let body = if request.starts_with("POST /identity/accounts/prelogin") {
+                serde_json::json!({ "Kdf": 0, "KdfIterations": 1000 })
+            } else if request.starts_with("POST /identity/connect/token") {
+                let encoded_hash = expected_hash.replace('+', "%2B").replace('/', "%2F").replace('=', "%3D");
+                assert!(request.contains(&format!("password={}", encoded_hash)));
+                serde_json::json!({
+                    "access_token": "access",
+                    "Key": crypto::encrypt(&stretched, &user_key_bytes, [1; 16]),
+                })
+            } else if request.starts_with("GET /api/sync") {
+                assert!(request.to_lowercase().contains("authorization: bearer access"));
+                serde_json::json!({
+                    "Profile": { "Organizations": [] },
+                    "Folders": [{ "Id": "f-1", "Name": crypto::encrypt(&user_key, b"Work", [2; 16]) }],
+                    "Ciphers": [
+                        {
+                            "Id": "1", "Type": 1, "FolderId": "f-1", "Favorite": false,
+                            "RevisionDate": "2024-01-01T00:00:00Z",
+                            "Name": crypto::encrypt(&user_key, b"GitHub", [3; 16]),
+                            "Login": { "Password": crypto::encrypt(&user_key, b"hunter2", [4; 16]) },
+                        },
+                        { "Id": "2", "Type": 2, "Name": "trashed", "DeletedDate": "2024-01-02T00:00:00Z", "RevisionDate": "2024-01-01T00:00:00Z" },
+                    ],
+                })
+            } else {
+                panic!("unexpected request: {}", request);
+            };
+
+            let body = body.to_string();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unlock_and_list_items() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let server_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(serve_vault(listener, "correct horse", "me@example.com"));
+
+        let config = BackendConfig {
+            server_url,
+            email: "me@example.com".to_string(),
+            ..BackendConfig::default()
+        };
+        let backend = ApiBackend::new_with_device_id(&config, Duration::from_secs(10), TEST_DEVICE_ID.to_string()).unwrap();
+        assert_eq!(VaultBackend::check_status(&backend).await.unwrap(), VaultStatus::Locked);
+
+        let unlocked = backend.unlock("correct horse").await.unwrap();
+        assert_eq!(unlocked.session_token, None);
+        let backend = unlocked.backend;
+        assert_eq!(backend.check_status().await.unwrap(), VaultStatus::Unlocked);
+
+        let items = Arc::new(Mutex::new(Vec::new()));
+        let batches = items.clone();
+        let count = backend.list_items(&move |batch: Vec<VaultItem>| batches.lock().unwrap().extend(batch)).await.unwrap();
+        assert_eq!(count, 1);
+        let items = items.lock().unwrap().clone();
+        assert_eq!(items[0].name, "GitHub");
+        assert_eq!(items[0].login.as_ref().and_then(|login| login.password.as_deref()), Some("hunter2"));
+
+        // Folders come from the same sync, without another request
+        let folders = backend.list_folders().await.unwrap();
+        assert_eq!(folders, vec![Folder { id: "f-1".to_string(), name: "Work".to_string() }]);
+
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_folder_encrypts_its_name() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let config = BackendConfig {
+            server_url: format!("http://{}", listener.local_addr().unwrap()),
+            email: "me@example.com".to_string(),
+            ..BackendConfig::default()
+        };
+        let user_key = SymmetricKey::from_bytes(&[5u8; 64]).unwrap();
+        let backend = ApiBackend::new_with_device_id(&config, Duration::from_secs(10), TEST_DEVICE_ID.to_string())
+            .unwrap()
+            .with_session(Session { access_token: "access".to_string(), user_key: user_key.clone(), private_key: None });
+
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buffer = [0u8; 4096];
+            let read = stream.read(&mut buffer).await.unwrap();
+            let request = String::from_utf8_lossy(&buffer[..read]).to_string();
+            let body = r#"{"Id":"f-2","Name":"2.encrypted","Object":"folder"}"#;
+            let response = format!("HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", body.len(), body);
+            stream.write_all(response.as_bytes()).await.unwrap();
+            request
+        });
+
+        let folder = backend.create_folder("Work").await.unwrap();
+        assert_eq!(folder, Folder { id: "f-2".to_string(), name: "Work".to_string() });
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /api/folders"));
+        // The server only ever sees the encrypted name
+        let body: Value = serde_json::from_str(&request[request.find("\r\n\r\n").unwrap() + 4..]).unwrap();
+        assert_eq!(user_key.decrypt_to_string(body["name"].as_str().unwrap()).unwrap(), "Work");
+    }
+
+    #[test]
+    fn test_decrypt_cipher_keeps_plain_fields() {
+        // Only encrypted strings are decrypted, so a plain item parses unchanged
+        let key = SymmetricKey::from_bytes(&[3u8; 64]).unwrap();
+        let cipher = normalize_keys(serde_json::json!({
+            "Id": "1",
+            "Type": 1,
+            "Name": "GitHub",
+            "Favorite": false,
+            "RevisionDate": "2024-01-01T00:00:00Z",
+            "Login": { "Username": "octocat", "Uris": [{ "Uri": null, "Match": null }, { "Uri": "https://github.com", "Match": null }] },
+            "Data": { "Name": "GitHub" },
+            "Edit": true,
+        }));
+
+        let item = decrypt_cipher(cipher, &key, &HashMap::new()).unwrap();
+        assert_eq!(item.name, "GitHub");
+        assert_eq!(item.username(), Some("octocat"));
+        assert_eq!(item.login.as_ref().and_then(|login| login.uris.as_ref()).map(Vec::len), Some(1));
+        assert!(!item.raw_fields.contains_key("data"));
+
+        // Organization items need their organization's key
+        let cipher = serde_json::json!({ "id": "2", "type": 2, "name": "Note", "organizationId": "org-1" });
+        assert!(decrypt_cipher(cipher, &key, &HashMap::new()).is_err());
+    }
+}
@@ -1,93 +1,292 @@
 use crate::actions;
 use crate::actions::CopyResult;
 use crate::cache;
-use crate::cli::{self, BitwardenCli};
+use crate::backend::{self, Backend, VaultStatus};
 use crate::clipboard::ClipboardManager;
-use crate::error::Result;
+use crate::config::{Config, SingleResultAction};
+use crate::error::{BwError, Result};
 use crate::events::Action;
-use crate::state::{AppState, MessageLevel};
-use crate::types::VaultItem;
+use crate::retry::RetryListener;
+use crate::session::SessionOptions;
+use crate::state::{AccessLookup, Annotation, AppState, FetchedTotp, FolderEdit, MessageLevel, SyncOutcome};
+use crate::totp_util;
+use crate::types::{Collection, CollectionAccess, EquivalentDomains, Folder, Organization, VaultItem};
+use chrono::{DateTime, Utc};
+use std::future::Future;
+use std::time::Instant;
 use tokio::sync::mpsc;
 
 /// Result type for sync operations
 pub enum SyncResult {
-    Success(Vec<VaultItem>),
+    Organizations(Vec<Organization>), // Sent before the items, which may refer to them
+    Folders(Vec<Folder>), // Sent before the items, which may refer to them
+    Collections(Vec<Collection>), // Sent before the items, which may refer to them
+    EquivalentDomains(EquivalentDomains),
+    Batch(Vec<VaultItem>), // Items parsed so far, more follow until Complete or Error
+    Complete,
     Error(String),
+    Locked(LockedOperation), // The session locked, the operation runs again once unlocked
+    Retrying(u32, u32), // (attempt, max_attempts), the operation is still in progress
 }
 
 /// Result type for unlock operations
 pub enum UnlockResult {
-    PasswordRequired(BitwardenCli),
-    Success(String, BitwardenCli), // (session_token, cli_with_token)
+    PasswordRequired(Backend),
+    SessionExpired(Backend), // The vault is locked although a session token was used
+    Success(Option<String>, Backend), // (session_token to save, if any; unlocked backend)
     Error(String),
     NotLoggedIn,
+    RepromptVerified(Backend), // Master password re-entered, the backend uses the new session
+    RepromptFailed(String),
 }
 
 /// Result type for TOTP operations
 pub enum TotpResult {
-    Success(String, u64), // (code, expires_at)
+    Success(String, u64, u64), // (code, expires_at, period)
+    Fetched(String, DateTime<Utc>, FetchedTotp), // Code fetched from the backend for an item (id, revision_date)
     Error(String),
+    Locked, // The session locked, the code is fetched again once unlocked
+}
+
+/// An operation that failed because the vault locked, to run again after unlocking
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockedOperation {
+    LoadItems,
+    Refresh,
+    FetchTotp,
+}
+
+/// Result type for delete operations
+pub enum DeleteResult {
+    Success(String), // item_id
+    Error(String),
+}
+
+/// Result type for folder changes
+pub enum FolderResult {
+    Created(Folder),
+    Renamed(String, String), // (folder_id, name)
+    Deleted(String), // folder_id
+    Moved(String, Option<String>), // (item_id, folder_id)
+    Error(String),
+}
+
+/// Result type for sharing an item with an organization
+pub enum ShareResult {
+    Success(String, String, Vec<String>), // (item_id, organization_id, collection_ids)
+    Error(String),
+}
+
+/// Message sent by a background task back to the main loop
+pub enum AppMessage {
+    CliReady(Result<Backend>),
+    Unlock(UnlockResult),
+    Sync(SyncResult),
+    Totp(TotpResult),
+    Delete(DeleteResult),
+    Folder(FolderResult),
+    Share(ShareResult),
+    CollectionAccess(String, Result<CollectionAccess>), // (collection_id, who can access it)
+    Reprompt(String, bool, Result<()>), // (item_id, re-prompt turned on, result of the change)
+    SessionStatus(Result<VaultStatus>), // Result of a keep-alive session check
+    Warning(String), // Something the user should know that doesn't stop the app
+}
+
+impl From<Result<Backend>> for AppMessage {
+    fn from(result: Result<Backend>) -> Self {
+        AppMessage::CliReady(result)
+    }
+}
+
+impl From<UnlockResult> for AppMessage {
+    fn from(result: UnlockResult) -> Self {
+        AppMessage::Unlock(result)
+    }
+}
+
+impl From<SyncResult> for AppMessage {
+    fn from(result: SyncResult) -> Self {
+        AppMessage::Sync(result)
+    }
+}
+
+impl From<TotpResult> for AppMessage {
+    fn from(result: TotpResult) -> Self {
+        AppMessage::Totp(result)
+    }
+}
+
+impl From<DeleteResult> for AppMessage {
+    fn from(result: DeleteResult) -> Self {
+        AppMessage::Delete(result)
+    }
+}
+
+impl From<ShareResult> for AppMessage {
+    fn from(result: ShareResult) -> Self {
+        AppMessage::Share(result)
+    }
+}
+
+impl From<FolderResult> for AppMessage {
+    fn from(result: FolderResult) -> Self {
+        AppMessage::Folder(result)
+    }
 }
 
 /// Main application controller
 pub struct App {
     pub state: AppState,
+    pub config: Config,
+    session: SessionOptions, // Where the session token comes from and whether it may be stored
+    use_cache: bool, // Whether the vault cache is read and written (off with --no-cache)
+    read_only_storage: bool, // Another instance owns the cache and saved session, so they are only read
+    session_file: Option<std::path::PathBuf>, // Where the session token is stored, the profile's file if None
+    use_local_notes: bool, // Whether local notes are read and written (not for --demo items)
     pub clipboard: Option<ClipboardManager>,
-    bw_cli: Option<BitwardenCli>,
-    sync_tx: mpsc::UnboundedSender<SyncResult>,
-    sync_rx: mpsc::UnboundedReceiver<SyncResult>,
-    cli_tx: mpsc::UnboundedSender<Result<BitwardenCli>>,
-    cli_rx: mpsc::UnboundedReceiver<Result<BitwardenCli>>,
-    unlock_tx: mpsc::UnboundedSender<UnlockResult>,
-    unlock_rx: mpsc::UnboundedReceiver<UnlockResult>,
-    totp_tx: mpsc::UnboundedSender<TotpResult>,
-    totp_rx: mpsc::UnboundedReceiver<TotpResult>,
+    backend: Option<Backend>,
+    message_tx: mpsc::UnboundedSender<AppMessage>,
+    message_rx: mpsc::UnboundedReceiver<AppMessage>,
     session_token_to_save: Option<String>,
+    sync_task: Option<tokio::task::JoinHandle<()>>, // In-flight vault load or sync, so it can be cancelled
+    loading_items: Vec<VaultItem>, // Items received so far by a sync replacing an already displayed list
+    last_keep_alive: Instant, // When the session was last checked
+    keep_alive_pending: bool, // Whether a session check is running
+    resume_after_unlock: Option<LockedOperation>, // Operation interrupted by a locked vault
+    picked: bool, // Whether the picker copied its field and can exit
 }
 
 impl App {
     /// Create a new App instance
-    pub fn new() -> Self {
-        let state = AppState::new();
+    pub fn new(session: SessionOptions) -> Self {
+        Self::with_config(session, Config::load())
+    }
+
+    /// Create an App with the given configuration instead of the config file
+    pub fn with_config(session: SessionOptions, config: Config) -> Self {
+        let mut state = AppState::new();
+        state.ui.layout = crate::config::LayoutConfig {
+            list_percent: config.layout.list_percent(),
+            ..config.layout.clone()
+        };
+        state.ui.list = config.list.clone();
+        state.ui.accessible = config.accessibility.enabled;
+        state.ui.max_unlock_attempts = config.unlock.max_attempts;
+        // The vim scheme starts in normal mode
+        state.ui.list_focused = config.keys.scheme == crate::config::KeyScheme::Vim;
         
         // Initialize clipboard
-        let clipboard = match ClipboardManager::new() {
-            Ok(cb) => Some(cb),
-            Err(_) => None,
-        };
+        let clipboard = ClipboardManager::from_config(&config.clipboard).ok();
 
-        // Create channels
-        let (sync_tx, sync_rx) = mpsc::unbounded_channel::<SyncResult>();
-        let (cli_tx, cli_rx) = mpsc::unbounded_channel::<Result<BitwardenCli>>();
-        let (unlock_tx, unlock_rx) = mpsc::unbounded_channel::<UnlockResult>();
-        let (totp_tx, totp_rx) = mpsc::unbounded_channel::<TotpResult>();
+        // Create the channel background tasks report back on
+        let (message_tx, message_rx) = mpsc::unbounded_channel::<AppMessage>();
 
         Self {
             state,
+            config,
+            session,
+            use_cache: true,
+            read_only_storage: false,
+            session_file: None,
+            use_local_notes: true,
             clipboard,
-            bw_cli: None,
-            sync_tx,
-            sync_rx,
-            cli_tx,
-            cli_rx,
-            unlock_tx,
-            unlock_rx,
-            totp_tx,
-            totp_rx,
+            backend: None,
+            message_tx,
+            message_rx,
             session_token_to_save: None,
+            sync_task: None,
+            loading_items: Vec::new(),
+            last_keep_alive: Instant::now(),
+            keep_alive_pending: false,
+            resume_after_unlock: None,
+            picked: false,
+        }
+    }
+
+    /// Only read the cache and saved session, because another instance is writing them
+    pub fn use_read_only_storage(&mut self) {
+        crate::logger::Logger::warn("Another instance holds the profile lock, cache and session are read-only");
+        self.read_only_storage = true;
+        self.state.set_status(
+            "⚠ Another bwtui instance is running, the cache and session token won't be saved",
+            MessageLevel::Warning,
+        );
+    }
+
+    /// The stored session token
+    fn session_manager(&self) -> Result<crate::session::SessionManager> {
+        match &self.session_file {
+            Some(path) => Ok(crate::session::SessionManager::at(path.clone())),
+            None => crate::session::SessionManager::new(),
+        }
+    }
+
+    /// Whether a new session token may be written to storage
+    fn can_store_token(&self) -> bool {
+        self.session.use_keyring() && !self.read_only_storage
+    }
+
+    /// Apply the startup options given on the command line
+    pub fn apply_args(&mut self, args: &crate::args::Args) {
+        self.config.apply_args(args);
+        self.state.ui.layout.compact = self.config.layout.compact;
+        // The synthetic vault of --demo must not replace the cached real one
+        self.use_cache = !args.no_cache && !args.ephemeral && args.demo_items().is_none();
+        self.use_local_notes = args.demo_items().is_none();
+        self.state.ui.readonly = args.readonly;
+        if let Some(tab) = args.item_type {
+            self.state.set_item_type_filter(tab.item_type());
         }
+        if let Some(folder) = &args.folder {
+            self.state.set_folder_filter(Some(folder.clone()));
+        }
+        if let Some(query) = &args.query {
+            self.state.set_filter_query(query.clone());
+        }
+        // The picker is typed into right away, whatever the key scheme
+        self.state.ui.pick = args.pick_field();
+        if self.state.ui.pick.is_some() {
+            self.state.ui.list_focused = false;
+        }
+    }
+
+    /// Whether the picker copied its field, so bwtui can exit
+    pub fn picked(&self) -> bool {
+        self.picked
     }
 
     /// Try to load cached vault data
     pub fn load_from_cache(&mut self) {
+        if !self.use_cache {
+            crate::logger::Logger::info("Vault cache disabled with --no-cache");
+            return;
+        }
+
         match cache::load_cache() {
             Ok(Some(cached_data)) => {
+                let now = chrono::Utc::now();
+                let age = cache::describe_age(cached_data.cached_at, now);
+                let expired = self.config.cache.is_expired(cached_data.cached_at, now);
+                if expired && self.config.cache.ignore_expired {
+                    crate::logger::Logger::info(&format!("Ignoring expired vault cache from {}", age));
+                    return;
+                }
+
                 let cached_items = cached_data.to_vault_items();
-                self.state.load_cached_items(cached_items);
-                self.state.set_status(
-                    format!("✓ Loaded {} items from cache (syncing in background...)", cached_data.items.len()),
-                    MessageLevel::Info,
-                );
+                self.state.load_cached_items(cached_items, cached_data.cached_at, expired);
+                self.state.set_organizations(cached_data.organizations);
+                self.state.set_folders(cached_data.folders);
+                if expired {
+                    self.state.set_status(
+                        format!("⚠ Showing an expired cache from {} (syncing in background...)", age),
+                        MessageLevel::Warning,
+                    );
+                } else {
+                    self.state.set_status(
+                        format!("✓ Loaded {} items from cache (syncing in background...)", cached_data.items.len()),
+                        MessageLevel::Info,
+                    );
+                }
             }
             Ok(None) => {
                 // No cache available, will load from vault
@@ -100,41 +299,58 @@ impl App {
 
     /// Start background vault initialization and loading
     pub fn start_vault_initialization(&mut self) {
+        let config = self.config.clone();
+        let session = self.session.clone();
+        self.initialize_vault(async move { backend::connect(&config, &session).await });
+    }
+
+    /// Initialize the vault with the backend `connect` resolves to, then load the items
+    /// or ask for what is missing (master password, login)
+    fn initialize_vault(&mut self, connect: impl Future<Output = Result<Backend>> + Send + 'static) {
         self.state.start_sync();
-        
-        let sync_tx_clone = self.sync_tx.clone();
-        let cli_tx = self.cli_tx.clone();
-        let unlock_tx_clone = self.unlock_tx.clone();
-        
-        tokio::spawn(async move {
-            // Initialize Bitwarden CLI
-            let bw_cli = match BitwardenCli::new().await {
-                Ok(cli) => cli,
+
+        let message_tx = self.message_tx.clone();
+        // A stored token that no longer unlocks the vault would fail the same way on every start
+        let stale_token_store = (self.session.uses_stored_token() && !self.read_only_storage).then(|| self.session_manager());
+
+        self.sync_task = Some(tokio::spawn(async move {
+            // Initialize the configured backend
+            let backend = match connect.await {
+                Ok(backend) => backend,
                 Err(crate::error::BwError::CliNotFound) => {
-                    let error_msg = "Bitwarden CLI not found. Please install: npm install -g @bitwarden/cli";
+                    let error_msg = "Bitwarden CLI not found. Please install: npm install -g @bitwarden/cli (or set bw_path under [cli])";
                     crate::logger::Logger::error(&format!("Vault initialization failed: {}", error_msg));
-                    if let Err(e) = sync_tx_clone.send(SyncResult::Error(error_msg.to_string())) {
+                    if let Err(e) = message_tx.send(SyncResult::Error(error_msg.to_string()).into()) {
                         crate::logger::Logger::error(&format!("Failed to send sync error: {}", e));
                     }
                     return;
                 }
                 Err(e) => {
-                    let error_msg = format!("CLI error: {}", e);
+                    let error_msg = format!("Backend error: {}", e);
                     crate::logger::Logger::error(&format!("Vault initialization failed: {}", error_msg));
-                    if let Err(e) = sync_tx_clone.send(SyncResult::Error(error_msg.clone())) {
+                    if let Err(e) = message_tx.send(SyncResult::Error(error_msg.clone()).into()) {
                         crate::logger::Logger::error(&format!("Failed to send sync error: {}", e));
                     }
                     return;
                 }
             };
 
+            if let Some(warning) = backend.version_warning() {
+                if let Err(e) = message_tx.send(AppMessage::Warning(warning)) {
+                    crate::logger::Logger::error(&format!("Failed to send version warning: {}", e));
+                }
+            }
+
+            // Report retries of the status check and item listing in the spinner
+            let retrying_cli = backend.with_retry_listener(sync_retry_listener(message_tx.clone()));
+
             // Check vault status
-            let status = match bw_cli.check_status().await {
+            let status = match retrying_cli.check_status().await {
                 Ok(s) => s,
                 Err(e) => {
                     let error_msg = format!("Failed to check vault status: {}", e);
                     crate::logger::Logger::error(&format!("Vault initialization failed: {}", error_msg));
-                    if let Err(e) = sync_tx_clone.send(SyncResult::Error(error_msg.clone())) {
+                    if let Err(e) = message_tx.send(SyncResult::Error(error_msg.clone()).into()) {
                         crate::logger::Logger::error(&format!("Failed to send sync error: {}", e));
                     }
                     return;
@@ -143,112 +359,201 @@ impl App {
 
             // Handle vault status
             match status {
-                cli::VaultStatus::Unlocked => {
+                VaultStatus::Unlocked => {
                     // Already unlocked, proceed normally
-                    if let Err(e) = cli_tx.send(Ok(bw_cli.clone())) {
+                    if let Err(e) = message_tx.send(Ok(backend.clone()).into()) {
                         crate::logger::Logger::error(&format!("Failed to send CLI initialization: {}", e));
                     }
-                    let result = match bw_cli.list_items().await {
-                        Ok(items) => {
-                            crate::logger::Logger::info(&format!("Successfully loaded {} vault items", items.len()));
-                            SyncResult::Success(items)
+                    send_item_metadata(&retrying_cli, &message_tx).await;
+                    let result = match retrying_cli.list_items(&item_batch_sender(message_tx.clone())).await {
+                        Ok(count) => {
+                            crate::logger::Logger::info(&format!("Successfully loaded {} vault items", count));
+                            SyncResult::Complete
                         }
+                        Err(BwError::VaultLocked) => SyncResult::Locked(LockedOperation::LoadItems),
                         Err(e) => {
                             let error_msg = format!("Failed to load vault items: {}", e);
                             crate::logger::Logger::error(&format!("Vault sync failed: {}", error_msg));
                             SyncResult::Error(error_msg)
                         }
                     };
-                    if let Err(e) = sync_tx_clone.send(result) {
+                    if let Err(e) = message_tx.send(result.into()) {
                         crate::logger::Logger::error(&format!("Failed to send sync result: {}", e));
                     }
                 }
-                cli::VaultStatus::Locked => {
+                VaultStatus::Locked => {
                     // Vault is locked - prompt for password
                     crate::logger::Logger::info("Vault is locked, prompting for password");
-                    if let Err(e) = unlock_tx_clone.send(UnlockResult::PasswordRequired(bw_cli)) {
+                    let result = if backend.has_session_token() {
+                        if let Some(store) = stale_token_store {
+                            clear_stale_session_token(store);
+                        }
+                        UnlockResult::SessionExpired(backend)
+                    } else {
+                        UnlockResult::PasswordRequired(backend)
+                    };
+                    if let Err(e) = message_tx.send(result.into()) {
                         crate::logger::Logger::error(&format!("Failed to send unlock prompt: {}", e));
                     }
                 }
-                cli::VaultStatus::Unauthenticated => {
+                VaultStatus::Unauthenticated => {
                     // Vault is not logged in - show error popup
                     crate::logger::Logger::warn("Vault is not logged in");
-                    if let Err(e) = unlock_tx_clone.send(UnlockResult::NotLoggedIn) {
+                    if let Err(e) = message_tx.send(UnlockResult::NotLoggedIn.into()) {
                         crate::logger::Logger::error(&format!("Failed to send not logged in error: {}", e));
                     }
                 }
             }
-        });
+        }));
     }
 
-    /// Check for and handle incoming messages from background tasks
-    pub fn process_background_messages(&mut self) {
-        // Check for CLI initialization result
-        if let Ok(result) = self.cli_rx.try_recv() {
-            match result {
-                Ok(cli) => {
-                    self.bw_cli = Some(cli);
-                }
-                Err(e) => {
-                    self.state.set_status(format!("✗ {}", e), MessageLevel::Error);
-                }
-            }
-        }
-
-        // Check for unlock results
-        if let Ok(result) = self.unlock_rx.try_recv() {
-            self.handle_unlock_result(result);
-        }
+    /// Wait for the next message from a background task
+    pub async fn next_message(&mut self) -> Option<AppMessage> {
+        self.message_rx.recv().await
+    }
 
-        // Check for sync results
-        if let Ok(result) = self.sync_rx.try_recv() {
-            self.handle_sync_result(result);
+    /// Handle a message received from a background task
+    pub fn handle_message(&mut self, message: AppMessage) {
+        match message {
+            AppMessage::CliReady(result) => self.handle_cli_result(result),
+            AppMessage::Unlock(result) => self.handle_unlock_result(result),
+            AppMessage::Sync(result) => self.handle_sync_result(result),
+            AppMessage::Totp(result) => self.handle_totp_result(result),
+            AppMessage::Delete(result) => self.handle_delete_result(result),
+            AppMessage::Folder(result) => self.handle_folder_result(result),
+            AppMessage::Share(result) => self.handle_share_result(result),
+            AppMessage::CollectionAccess(collection_id, result) => self.handle_collection_access(&collection_id, result),
+            AppMessage::Reprompt(item_id, enabled, result) => self.handle_reprompt_change(&item_id, enabled, result),
+            AppMessage::SessionStatus(result) => self.handle_session_status(result),
+            AppMessage::Warning(warning) => self.state.set_status(format!("⚠ {}", warning), MessageLevel::Warning),
         }
+        self.state.mark_dirty();
+    }
 
-        // Check for TOTP results
-        if let Ok(result) = self.totp_rx.try_recv() {
-            self.handle_totp_result(result);
+    /// Handle CLI initialization result from background task
+    fn handle_cli_result(&mut self, result: Result<Backend>) {
+        match result {
+            Ok(cli) => {
+                self.backend = Some(cli);
+            }
+            Err(e) => {
+                self.state.set_status(format!("✗ {}", e), MessageLevel::Error);
+            }
         }
     }
 
     /// Handle unlock result from background task
     fn handle_unlock_result(&mut self, result: UnlockResult) {
-        // Clear loading state regardless of result
-        self.state.sync.stop();
+        // Clear loading state for everything but re-prompts, which don't use it
+        if !matches!(result, UnlockResult::RepromptVerified(_) | UnlockResult::RepromptFailed(_)) {
+            self.state.sync.stop();
+        }
         
         match result {
-            UnlockResult::PasswordRequired(cli) => {
-                // Store the CLI temporarily and prompt for password
-                self.bw_cli = Some(cli);
+            UnlockResult::PasswordRequired(backend) => {
+                // Store the backend temporarily and prompt for password
+                self.backend = Some(backend);
                 self.state.stop_sync();
                 self.state.enter_password_mode();
             }
-            UnlockResult::Success(token, cli) => {
+            UnlockResult::SessionExpired(backend) => {
+                crate::logger::Logger::warn("Session token no longer unlocks the vault");
+                self.backend = Some(backend);
+                self.state.stop_sync();
+                self.state.enter_password_mode();
+                self.state.ui.session_expired = true;
+                self.state.set_status("⚠ Session expired, enter your master password", MessageLevel::Warning);
+            }
+            UnlockResult::Success(token, backend) => {
                 // Vault unlocked successfully
-                self.bw_cli = Some(cli);
+                self.backend = Some(backend);
                 self.state.exit_password_mode();
+                self.state.ui.reset_unlock_failures();
+
+                // The vault is still loaded after a lock, only the session changed
+                if self.state.ui.take_relocked() {
+                    if let Some(token) = token.filter(|_| self.can_store_token()) {
+                        resave_session_token(self.session_manager(), &token);
+                    }
+                    self.last_keep_alive = Instant::now();
+                    self.state.set_status("✓ Vault unlocked", MessageLevel::Success);
+                    self.resume_locked_operation();
+                    return;
+                }
                 
+                // The token can't be stored, go straight to loading
+                let Some(token) = token.filter(|_| self.can_store_token()) else {
+                    self.load_vault_items();
+                    return;
+                };
+
                 // Store token and offer to save it
                 self.session_token_to_save = Some(token);
                 self.state.enter_save_token_prompt();
             }
             UnlockResult::Error(error) => {
                 // Unlock failed
-                self.state.set_unlock_error(error);
+                self.show_unlock_error(error);
             }
             UnlockResult::NotLoggedIn => {
                 // Vault is not logged in - show error popup
                 self.state.stop_sync();
+                self.state.ui.unlock_in_progress = false;
                 self.state.show_not_logged_in_popup();
             }
+            UnlockResult::RepromptVerified(cli) => {
+                // Verifying the password issued a new session, the previous one is no longer valid
+                self.backend = Some(cli);
+                self.state.ui.reset_unlock_failures();
+                if let Some(action) = self.state.exit_reprompt() {
+                    // Quick copy acts on a numbered search result rather than the selected item
+                    let item = match action {
+                        Action::QuickCopyPassword(position) => self.state.vault.filtered_item(position),
+                        _ => self.state.selected_item(),
+                    };
+                    if let Some(item_id) = item.map(|item| item.id.clone()) {
+                        self.state.grant_reprompt(item_id);
+                        if matches!(action, Action::ToggleReprompt) {
+                            self.toggle_reprompt();
+                        } else {
+                            match actions::handle_copy(&action, &mut self.state, self.clipboard.as_mut(), self.backend.as_ref()) {
+                                CopyResult::NeedTotpFetch => self.fetch_totp_code(),
+                                _ if self.state.ui.pick.is_some() => self.finish_pick(),
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+            }
+            UnlockResult::RepromptFailed(error) => {
+                self.show_unlock_error(error);
+            }
+        }
+    }
+
+    /// Show why an unlock failed, counting it when the master password was wrong
+    /// Other errors, e.g. a timeout, say nothing about the password and don't count
+    fn show_unlock_error(&mut self, error: String) {
+        self.state.ui.unlock_in_progress = false;
+        if error.contains("Invalid master password") {
+            self.state.ui.record_unlock_failure(Instant::now());
+            if self.state.ui.unlock_attempts_exhausted() {
+                crate::logger::Logger::warn(&format!("{} wrong master passwords, exiting", self.state.ui.unlock_failures));
+            }
         }
+        self.state.set_unlock_error(error);
+    }
+
+    /// Whether the wrong master passwords reached `max_attempts`, so bwtui exits
+    pub fn unlock_attempts_exhausted(&self) -> bool {
+        self.state.ui.unlock_attempts_exhausted()
     }
 
     /// Handle TOTP result from background task
     fn handle_totp_result(&mut self, result: TotpResult) {
         self.state.set_totp_loading(false);
         match result {
-            TotpResult::Success(code, expires_at) => {
+            TotpResult::Success(code, expires_at, period) => {
                 // Get the current item ID to associate the TOTP code with it
                 let item_id = self.state.selected_item()
                     .map(|item| item.id.clone())
@@ -257,7 +562,7 @@ impl App {
                 // Check if we were copying TOTP before setting the code (which clears the flag)
                 let was_copying = self.state.ui.totp_copy_pending;
                 
-                self.state.set_totp_code(code.clone(), expires_at, item_id);
+                self.state.set_totp_code(code.clone(), expires_at, period, item_id);
                 
                 // If we were copying TOTP, copy it now
                 if was_copying {
@@ -279,131 +584,540 @@ impl App {
                     } else {
                         self.state.set_status("✗ Clipboard not available", MessageLevel::Error);
                     }
+                    if self.state.ui.pick.is_some() {
+                        self.finish_pick();
+                    }
                 }
                 // No message when just loading for display purposes
             }
+            TotpResult::Fetched(item_id, revision_date, totp) => {
+                self.state.item_details.store_totp(&item_id, revision_date, totp.clone());
+                if let Some(watch) = self.state.ui.totp_watch.as_mut().filter(|watch| watch.item_id == item_id) {
+                    watch.code = Some(totp.clone());
+                    watch.fetching = false;
+                }
+                // The selection may have moved on while the code was fetched
+                if self.state.selected_item().is_some_and(|item| item.id == item_id) {
+                    self.handle_totp_result(TotpResult::Success(totp.code, totp.expires_at, totp.period));
+                }
+            }
             TotpResult::Error(error) => {
+                // Fetching again on every tick would repeat the error, stop watching
+                if self.state.ui.totp_watch.as_ref().is_some_and(|watch| watch.fetching) {
+                    self.state.ui.totp_watch = None;
+                }
                 self.state.set_status(
                     format!("✗ Failed to fetch TOTP: {}", error),
                     MessageLevel::Error,
                 );
                 crate::logger::Logger::error(&format!("Failed to fetch TOTP: {}", error));
             }
+            TotpResult::Locked => self.handle_vault_locked(LockedOperation::FetchTotp),
+        }
+    }
+
+    /// Handle delete result from background task
+    fn handle_delete_result(&mut self, result: DeleteResult) {
+        self.state.duplicates.deleting = false;
+        match result {
+            DeleteResult::Success(item_id) => {
+                let name = self.state.vault.vault_items
+                    .iter()
+                    .find(|item| item.id == item_id)
+                    .map(|item| item.name.clone())
+                    .unwrap_or_default();
+                self.state.remove_item(&item_id);
+                self.save_cache();
+                self.state.set_status(format!("✓ Moved '{}' to trash", name), MessageLevel::Success);
+            }
+            DeleteResult::Error(error) => {
+                self.state.set_status(format!("✗ Failed to delete item: {}", error), MessageLevel::Error);
+            }
+        }
+    }
+
+    /// Handle the result of a folder change from the folder screen
+    fn handle_folder_result(&mut self, result: FolderResult) {
+        self.state.folders.busy = false;
+        match result {
+            FolderResult::Created(folder) => {
+                let message = format!("✓ Created folder '{}'", folder.name);
+                self.state.add_folder(folder);
+                self.save_cache();
+                self.state.set_status(message, MessageLevel::Success);
+            }
+            FolderResult::Renamed(folder_id, name) => {
+                self.state.rename_folder(&folder_id, &name);
+                self.save_cache();
+                self.state.set_status(format!("✓ Renamed folder to '{}'", name), MessageLevel::Success);
+            }
+            FolderResult::Deleted(folder_id) => {
+                let name = self.state.vault.folders()
+                    .iter()
+                    .find(|folder| folder.id == folder_id)
+                    .map(|folder| folder.name.clone())
+                    .unwrap_or_default();
+                self.state.remove_folder(&folder_id);
+                self.save_cache();
+                self.state.set_status(format!("✓ Deleted folder '{}'", name), MessageLevel::Success);
+            }
+            FolderResult::Moved(item_id, folder_id) => {
+                let item_name = self.state.vault.vault_items
+                    .iter()
+                    .find(|item| item.id == item_id)
+                    .map(|item| item.name.clone())
+                    .unwrap_or_default();
+                let folder_name = folder_id.as_ref().and_then(|folder_id| {
+                    self.state.vault.folders().iter().find(|folder| &folder.id == folder_id).map(|folder| folder.name.clone())
+                });
+                self.state.set_item_folder(&item_id, folder_id);
+                self.save_cache();
+                let message = match folder_name {
+                    Some(folder_name) => format!("✓ Moved '{}' to folder '{}'", item_name, folder_name),
+                    None => format!("✓ Moved '{}' out of its folder", item_name),
+                };
+                self.state.set_status(message, MessageLevel::Success);
+            }
+            FolderResult::Error(error) => {
+                self.state.set_status(format!("✗ Failed to change folder: {}", error), MessageLevel::Error);
+            }
+        }
+    }
+
+    /// Handle the result of sharing an item with an organization
+    fn handle_share_result(&mut self, result: ShareResult) {
+        self.state.share.busy = false;
+        match result {
+            ShareResult::Success(item_id, organization_id, collection_ids) => {
+                self.state.share.close();
+                self.state.mark_item_shared(&item_id, &organization_id, collection_ids);
+                self.save_cache();
+                let name = self.state.vault.vault_items
+                    .iter()
+                    .find(|item| item.id == item_id)
+                    .map(|item| item.name.clone())
+                    .unwrap_or_default();
+                let organization = self.state.vault.organization_name(&organization_id).unwrap_or("the organization");
+                let message = format!("✓ Shared '{}' with {}", name, organization);
+                self.state.set_status(message, MessageLevel::Success);
+            }
+            ShareResult::Error(error) => {
+                self.state.set_status(format!("✗ Failed to share item: {}", error), MessageLevel::Error);
+            }
+        }
+    }
+
+    /// Handle who can access a collection, looked up for the details panel
+    fn handle_collection_access(&mut self, collection_id: &str, result: Result<CollectionAccess>) {
+        match result {
+            Ok(access) => self.state.collection_access.store(collection_id, AccessLookup::Loaded(access)),
+            Err(e) => {
+                crate::logger::Logger::warn(&format!("Failed to look up access to collection {}: {}", collection_id, e));
+                self.state.collection_access.store(collection_id, AccessLookup::Failed(e.to_string()));
+            }
+        }
+    }
+
+    /// Handle a change of an item's master password re-prompt
+    fn handle_reprompt_change(&mut self, item_id: &str, enabled: bool, result: Result<()>) {
+        match result {
+            Ok(()) => {
+                self.state.set_item_reprompt(item_id, enabled);
+                self.save_cache();
+                let name = self.state.vault.vault_items
+                    .iter()
+                    .find(|item| item.id == item_id)
+                    .map(|item| item.name.clone())
+                    .unwrap_or_default();
+                let message = format!("✓ Master password re-prompt turned {} for '{}'", if enabled { "on" } else { "off" }, name);
+                self.state.set_status(message, MessageLevel::Success);
+            }
+            Err(e) => {
+                self.state.set_status(format!("✗ Failed to change the re-prompt setting: {}", e), MessageLevel::Error);
+            }
+        }
+    }
+
+    /// Handle the result of a keep-alive session check
+    fn handle_session_status(&mut self, result: Result<VaultStatus>) {
+        self.keep_alive_pending = false;
+        match result {
+            Ok(VaultStatus::Unlocked) => {}
+            Ok(VaultStatus::Locked) => {
+                crate::logger::Logger::warn("Vault locked while in use, prompting for the master password");
+                if !self.state.password_input_mode() {
+                    self.state.enter_relock_prompt();
+                }
+            }
+            Ok(VaultStatus::Unauthenticated) => {
+                crate::logger::Logger::warn("Vault logged out while in use");
+                self.state.show_not_logged_in_popup();
+            }
+            Err(e) => {
+                // Transient, the next check or command will tell
+                crate::logger::Logger::warn(&format!("Session check failed: {}", e));
+            }
+        }
+    }
+
+    /// Check the session with a cheap `bw status` once the keep-alive interval passed,
+    /// so a lock is noticed (and the password asked for) before the next command fails
+    fn keep_alive_if_due(&mut self) {
+        let Some(interval) = self.config.cli.keep_alive_interval() else {
+            return;
+        };
+        if self.keep_alive_pending || self.last_keep_alive.elapsed() < interval {
+            return;
+        }
+
+        // Only while the vault is unlocked and idle
+        if self.state.syncing() || self.state.password_input_mode() || !self.state.secrets_available() {
+            return;
         }
+        let Some(cli) = self.backend.clone() else {
+            return;
+        };
+
+        self.last_keep_alive = Instant::now();
+        self.keep_alive_pending = true;
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let result = cli.check_status().await;
+            if let Err(e) = message_tx.send(AppMessage::SessionStatus(result)) {
+                crate::logger::Logger::error(&format!("Failed to send session status: {}", e));
+            }
+        });
     }
 
     /// Handle sync result from background task
     fn handle_sync_result(&mut self, result: SyncResult) {
-        self.state.stop_sync();
         match result {
-            SyncResult::Success(items) => {
-                // Save cache (without secrets)
-                let cache_data = cache::CachedVaultData::from_vault_items(&items);
-                if let Err(e) = cache::save_cache(&cache_data) {
-                    crate::logger::Logger::warn(&format!("Failed to save cache: {}", e));
+            // Retries are progress updates, the sync is still running but starts over
+            SyncResult::Retrying(attempt, max_attempts) => {
+                self.discard_loading_items();
+                self.state.sync.set_retry(attempt, max_attempts);
+            }
+            SyncResult::Organizations(organizations) => {
+                self.state.set_organizations(organizations);
+            }
+            SyncResult::Folders(folders) => {
+                self.state.set_folders(folders);
+            }
+            SyncResult::Collections(collections) => {
+                self.state.set_collections(collections);
+            }
+            SyncResult::EquivalentDomains(domains) => {
+                self.state.set_equivalent_domains(domains);
+            }
+            SyncResult::Batch(items) => {
+                if self.state.initial_load_complete() {
+                    // Keep showing the current list until the new one is complete
+                    self.loading_items.extend(items);
                 } else {
-                    crate::logger::Logger::info("Cache saved successfully");
+                    // Nothing to show yet, display items as they arrive
+                    self.state.append_loading_items(items);
                 }
+            }
+            SyncResult::Complete => {
+                self.state.stop_sync();
+                let changes = if self.state.initial_load_complete() {
+                    let items = std::mem::take(&mut self.loading_items);
+                    Some(self.state.load_items_with_secrets(items))
+                } else {
+                    self.state.finish_loading_items();
+                    None
+                };
 
-                // Load items with secrets available
-                self.state.load_items_with_secrets(items);
-                self.state.set_status("✓ Vault synced successfully", MessageLevel::Success);
+                self.save_cache();
+
+                let items = self.state.vault.vault_items.len();
+                self.state.sync.record(SyncOutcome::Synced { items, changes: changes.clone() });
+                match changes.and_then(|changes| changes.summary()) {
+                    Some(changes) => self.state.set_status(format!("✓ Vault synced: {}", changes), MessageLevel::Success),
+                    None => self.state.set_status("✓ Vault synced successfully", MessageLevel::Success),
+                }
             }
             SyncResult::Error(error) => {
+                self.state.stop_sync();
+                self.discard_loading_items();
+                self.state.sync.record(SyncOutcome::Failed(error.clone()));
                 self.state.set_status(
                     format!("✗ Sync failed: {}", error),
                     MessageLevel::Error,
                 );
                 crate::logger::Logger::error(&format!("Sync failed: {}", error));
             }
+            SyncResult::Locked(operation) => {
+                self.state.stop_sync();
+                self.discard_loading_items();
+                self.handle_vault_locked(operation);
+            }
         }
     }
 
-    /// Attempt to unlock the vault with a password
-    pub fn unlock_with_password(&mut self, password: String) {
-        if password.is_empty() {
-            self.state.set_unlock_error("Password cannot be empty".to_string());
-            return;
+    /// Ask for the master password after a command found the vault locked,
+    /// remembering the operation to run again once it is unlocked
+    fn handle_vault_locked(&mut self, operation: LockedOperation) {
+        crate::logger::Logger::warn(&format!("Vault locked during {:?}, prompting for the master password", operation));
+        self.resume_after_unlock = Some(operation);
+        self.state.item_details.clear(); // Values fetched for the locked session
+        if let Some(watch) = self.state.ui.totp_watch.as_mut() {
+            watch.code = None;
+            watch.fetching = false;
         }
+        self.state.close_qr_popup();
+        self.state.totp_dashboard.close();
+        if !self.state.password_input_mode() {
+            self.state.enter_relock_prompt();
+        }
+    }
 
-        // Set loading state and clear any previous error
-        self.state.sync.start();
-        self.state.set_unlock_error("".to_string()); // Clear previous error
+    /// Run the operation interrupted by a locked vault, if any
+    fn resume_locked_operation(&mut self) {
+        match self.resume_after_unlock.take() {
+            Some(LockedOperation::LoadItems) => self.load_vault_items(),
+            Some(LockedOperation::Refresh) => self.refresh_vault(),
+            Some(LockedOperation::FetchTotp) => self.fetch_totp_code(),
+            None => {}
+        }
+    }
 
-        // Attempt unlock in background
-        if let Some(ref cli) = self.bw_cli {
-            let cli_clone = cli.clone();
-            let unlock_tx_clone = self.unlock_tx.clone();
-            tokio::spawn(async move {
-                match cli_clone.unlock(&password).await {
-                    Ok(token) => {
-                        let new_cli = BitwardenCli::with_session_token(token.clone());
-                        crate::logger::Logger::info("Vault unlocked successfully");
-                        if let Err(e) = unlock_tx_clone.send(UnlockResult::Success(token, new_cli)) {
-                            crate::logger::Logger::error(&format!("Failed to send unlock success: {}", e));
-                        }
-                    }
-                    Err(e) => {
-                        let error_msg = e.to_string();
-                        crate::logger::Logger::error(&format!("Failed to unlock vault: {}", error_msg));
-                        if let Err(e) = unlock_tx_clone.send(UnlockResult::Error(error_msg)) {
-                            crate::logger::Logger::error(&format!("Failed to send unlock error: {}", e));
-                        }
-                    }
-                }
-            });
+    /// Read the notes and tags attached to items on this computer
+    pub fn load_local_notes(&mut self) {
+        if self.use_local_notes {
+            self.state.set_local_notes(crate::local_notes::load());
+            self.state.set_local_tags(crate::local_notes::load_tags());
+            self.state.set_hidden_items(crate::local_notes::load_hidden());
         }
     }
 
-    /// Handle save token response (yes/no)
-    pub fn handle_save_token_response(&mut self, save: bool, session_manager: &crate::session::SessionManager) {
-        self.state.set_save_token_response(save);
-        self.state.exit_save_token_prompt();
-        
-        if save {
-            // Save the token
-            if let Some(token) = &self.session_token_to_save {
-                match session_manager.save_token(token) {
-                    Ok(()) => {
-                        self.state.set_status("✓ Session token saved successfully", MessageLevel::Success);
-                    }
-                    Err(e) => {
-                        self.state.set_status(format!("⚠ Failed to save token: {}", e), MessageLevel::Warning);
-                    }
-                }
-            }
+    /// Switch to the saved view after the current one, or back to all items after the last
+    fn cycle_view(&mut self) {
+        let views = &self.config.views;
+        if views.is_empty() {
+            self.state.set_status("No saved views, add [[views]] to the config file", MessageLevel::Info);
+            return;
+        }
+        let current = self.state.ui.active_view.as_deref()
+            .and_then(|name| views.iter().position(|view| view.name == name));
+        let next = match current {
+            Some(index) => views.get(index + 1),
+            None => views.first(),
+        }
+        .cloned();
+
+        let message = match &next {
+            Some(view) => format!("View: {}", view.name),
+            None => "View: all items".to_string(),
+        };
+        self.state.set_status(message, MessageLevel::Info);
+        // A warning about the view's owner replaces the message
+        self.state.apply_view(next.as_ref());
+    }
+
+    /// Why the local notes, tags and hidden items can't be stored, if they can't
+    fn local_storage_unavailable(&self) -> Option<&'static str> {
+        if !self.use_local_notes {
+            Some("demo items aren't stored")
+        } else if crate::profile::ephemeral() {
+            Some("nothing is stored with --ephemeral")
+        } else if self.read_only_storage {
+            Some("another bwtui instance stores them")
         } else {
-            self.state.set_status("Session token not saved", MessageLevel::Info);
+            None
         }
-        
-        self.session_token_to_save = None;
+    }
 
-        // Now load vault items
-        self.load_vault_items();
+    /// Hide the selected item or list it again, and store the hidden items
+    fn toggle_item_hidden(&mut self) {
+        if !self.state.toggle_item_hidden() {
+            return;
+        }
+        if let Some(reason) = self.local_storage_unavailable() {
+            self.state.set_status(format!("⚠ Hidden items kept until exit, {}", reason), MessageLevel::Warning);
+            return;
+        }
+        if let Err(e) = crate::local_notes::save_hidden(self.state.vault.hidden_items()) {
+            crate::logger::Logger::error(&format!("Failed to save hidden items: {}", e));
+            self.state.set_status(format!("✗ Failed to save hidden items: {}", e), MessageLevel::Error);
+        }
+    }
+
+    /// Attach the note or tags written in the dialog to their item and store them
+    fn save_local_note(&mut self) {
+        let Some(annotation) = self.state.save_local_note() else {
+            return;
+        };
+        let what = match annotation {
+            Annotation::Note => "Local note",
+            Annotation::Tags => "Local tags",
+        };
+        if let Some(reason) = self.local_storage_unavailable() {
+            self.state.set_status(format!("⚠ {} kept until exit, {}", what, reason), MessageLevel::Warning);
+            return;
+        }
+        let result = match annotation {
+            Annotation::Note => crate::local_notes::save(self.state.vault.local_notes()),
+            Annotation::Tags => crate::local_notes::save_tags(self.state.vault.local_tags()),
+        };
+        match result {
+            Ok(()) => self.state.set_status(format!("✓ {} saved", what), MessageLevel::Success),
+            Err(e) => {
+                crate::logger::Logger::error(&format!("Failed to save {}: {}", what.to_lowercase(), e));
+                self.state.set_status(format!("✗ Failed to save {}: {}", what.to_lowercase(), e), MessageLevel::Error);
+            }
+        }
+    }
+
+    /// Save the displayed items to the cache (without secrets)
+    fn save_cache(&self) {
+        if !self.use_cache || self.read_only_storage {
+            return;
+        }
+        let cache_data = cache::CachedVaultData::from_vault_items(&self.state.vault.vault_items)
+            .with_organizations(self.state.vault.organizations())
+            .with_folders(self.state.vault.folders());
+        if let Err(e) = cache::save_cache(&cache_data) {
+            crate::logger::Logger::warn(&format!("Failed to save cache: {}", e));
+        } else {
+            crate::logger::Logger::info("Cache saved successfully");
+        }
+    }
+
+    /// Drop the items of an incomplete sync
+    fn discard_loading_items(&mut self) {
+        self.loading_items.clear();
+        if !self.state.initial_load_complete() {
+            self.state.discard_loading_items();
+        }
+    }
+
+    /// Attempt to unlock the vault with a password
+    pub fn unlock_with_password(&mut self, password: String) {
+        if password.is_empty() {
+            self.state.set_unlock_error("Password cannot be empty".to_string());
+            return;
+        }
+
+        // The dialog ignores input until the password is checked
+        let Some(ref backend) = self.backend else {
+            return;
+        };
+        self.state.ui.unlock_in_progress = true;
+        self.state.set_unlock_error("".to_string()); // Clear previous error
+
+        // Attempt unlock in background
+        let backend = backend.clone();
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            match backend.unlock(&password).await {
+                Ok(unlocked) => {
+                    crate::logger::Logger::info("Vault unlocked successfully");
+                    if let Err(e) = message_tx.send(UnlockResult::Success(unlocked.session_token, unlocked.backend).into()) {
+                        crate::logger::Logger::error(&format!("Failed to send unlock success: {}", e));
+                    }
+                }
+                Err(e) => {
+                    let error_msg = e.to_string();
+                    crate::logger::Logger::error(&format!("Failed to unlock vault: {}", error_msg));
+                    if let Err(e) = message_tx.send(UnlockResult::Error(error_msg).into()) {
+                        crate::logger::Logger::error(&format!("Failed to send unlock error: {}", e));
+                    }
+                }
+            }
+        });
+    }
+
+    /// Verify the master password before resuming a re-prompt protected action
+    fn verify_reprompt(&mut self, password: String) {
+        if password.is_empty() {
+            self.state.set_unlock_error("Password cannot be empty".to_string());
+            return;
+        }
+
+        let Some(ref backend) = self.backend else {
+            self.state.set_unlock_error("Bitwarden CLI not available".to_string());
+            return;
+        };
+
+        self.state.ui.unlock_in_progress = true;
+        self.state.set_unlock_error("".to_string()); // Clear previous error
+
+        let backend = backend.clone();
+        let message_tx = self.message_tx.clone();
+        let token_store = self.can_store_token().then(|| self.session_manager());
+        tokio::spawn(async move {
+            let result = match backend.unlock(&password).await {
+                Ok(unlocked) => {
+                    crate::logger::Logger::info("Master password re-prompt verified");
+                    if let (Some(token), Some(store)) = (unlocked.session_token, token_store) {
+                        resave_session_token(store, &token);
+                    }
+                    UnlockResult::RepromptVerified(unlocked.backend)
+                }
+                Err(e) => {
+                    let error_msg = e.to_string();
+                    crate::logger::Logger::warn(&format!("Master password re-prompt failed: {}", error_msg));
+                    UnlockResult::RepromptFailed(error_msg)
+                }
+            };
+            if let Err(e) = message_tx.send(result.into()) {
+                crate::logger::Logger::error(&format!("Failed to send re-prompt result: {}", e));
+            }
+        });
+    }
+
+    /// Handle save token response (yes/no)
+    pub fn handle_save_token_response(&mut self, save: bool, session_manager: &crate::session::SessionManager) {
+        self.state.set_save_token_response(save);
+        self.state.exit_save_token_prompt();
+        
+        if save {
+            // Save the token
+            if let Some(token) = &self.session_token_to_save {
+                match session_manager.save_token(token) {
+                    Ok(()) => {
+                        self.state.set_status("✓ Session token saved successfully", MessageLevel::Success);
+                    }
+                    Err(e) => {
+                        self.state.set_status(format!("⚠ Failed to save token: {}", e), MessageLevel::Warning);
+                    }
+                }
+            }
+        } else {
+            self.state.set_status("Session token not saved", MessageLevel::Info);
+        }
+        
+        self.session_token_to_save = None;
+
+        // Now load vault items
+        self.load_vault_items();
     }
 
     /// Start loading vault items from the CLI
     fn load_vault_items(&mut self) {
-        if let Some(ref cli) = self.bw_cli {
+        if let Some(ref cli) = self.backend {
             self.state.start_sync();
-            let cli_clone = cli.clone();
-            let sync_tx_clone = self.sync_tx.clone();
-            tokio::spawn(async move {
-                let result = match cli_clone.list_items().await {
-                    Ok(items) => {
-                        crate::logger::Logger::info(&format!("Successfully loaded {} vault items", items.len()));
-                        SyncResult::Success(items)
+            let message_tx = self.message_tx.clone();
+            let cli_clone = cli.with_retry_listener(sync_retry_listener(message_tx.clone()));
+            self.sync_task = Some(tokio::spawn(async move {
+                send_item_metadata(&cli_clone, &message_tx).await;
+                let result = match cli_clone.list_items(&item_batch_sender(message_tx.clone())).await {
+                    Ok(count) => {
+                        crate::logger::Logger::info(&format!("Successfully loaded {} vault items", count));
+                        SyncResult::Complete
                     }
+                    Err(BwError::VaultLocked) => SyncResult::Locked(LockedOperation::LoadItems),
                     Err(e) => {
                         let error_msg = format!("Failed to load vault items: {}", e);
                         crate::logger::Logger::error(&format!("Failed to load vault items: {}", error_msg));
                         SyncResult::Error(error_msg)
                     }
                 };
-                if let Err(e) = sync_tx_clone.send(result) {
+                if let Err(e) = message_tx.send(result.into()) {
                     crate::logger::Logger::error(&format!("Failed to send vault items result: {}", e));
                 }
-            });
+            }));
         }
     }
 
@@ -417,42 +1131,40 @@ impl App {
             return;
         }
 
+        // Generate the code locally when the secret is already in memory
+        match self.generate_local_totp() {
+            Some(Ok(totp)) => {
+                self.handle_totp_result(TotpResult::Success(totp.code, totp.expires_at, totp.period));
+                return;
+            }
+            Some(Err(e)) => {
+                crate::logger::Logger::warn(&format!("Failed to generate TOTP locally, falling back to bw CLI: {}", e));
+                if let Some(item) = self.state.selected_item().cloned() {
+                    self.state.item_details.mark_local_totp_failed(&item);
+                }
+            }
+            None => {}
+        }
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        // Reuse a code fetched on an earlier visit while it is still valid
+        if let Some(totp) = self.state.selected_item().and_then(|item| self.state.item_details.totp(item, now)).cloned() {
+            self.handle_totp_result(TotpResult::Success(totp.code, totp.expires_at, totp.period));
+            return;
+        }
+
         if let Some(item) = self.state.selected_item() {
             if let Some(login) = &item.login {
                 if login.totp.is_some() {
-                    if let Some(ref cli) = self.bw_cli {
-                        let item_id = item.id.clone();
+                    if let Some(ref cli) = self.backend {
+                        spawn_totp_fetch(cli.clone(), item.id.clone(), item.revision_date, self.message_tx.clone());
                         self.state.set_totp_loading(true);
                         // Record the timestamp when we start fetching
-                        let now = std::time::SystemTime::now()
-                            .duration_since(std::time::UNIX_EPOCH)
-                            .unwrap_or_default()
-                            .as_secs();
                         self.state.set_last_totp_fetch(now);
-                        let cli_clone = cli.clone();
-                        let totp_tx_clone = self.totp_tx.clone();
-                        
-                        tokio::spawn(async move {
-                            let result = match cli_clone.get_totp(&item_id).await {
-                                Ok(code) => {
-                                    // Calculate expiration time (TOTP codes are valid for 30 seconds)
-                                    let now = std::time::SystemTime::now()
-                                        .duration_since(std::time::UNIX_EPOCH)
-                                        .unwrap_or_default()
-                                        .as_secs();
-                                    let expires_at = ((now / 30) + 1) * 30; // Next 30-second boundary
-                                    TotpResult::Success(code, expires_at)
-                                }
-                                Err(e) => {
-                                    let error_msg = e.to_string();
-                                    crate::logger::Logger::error(&format!("Failed to fetch TOTP for item {}: {}", item_id, error_msg));
-                                    TotpResult::Error(error_msg)
-                                }
-                            };
-                            if let Err(e) = totp_tx_clone.send(result) {
-                                crate::logger::Logger::error(&format!("Failed to send TOTP result: {}", e));
-                            }
-                        });
                     } else {
                         self.state.set_status(
                             "✗ Bitwarden CLI not available",
@@ -469,6 +1181,282 @@ impl App {
         }
     }
 
+    /// Fetch the TOTP code of an item that stayed selected for a moment, so it is ready when the details open
+    /// Attachments and the other values come with the item list, the code is the only round trip
+    fn prefetch_selected_item(&mut self) {
+        let now = Instant::now();
+        let selected = self.state.selected_item().map(|item| item.id.clone());
+        self.state.prefetch.select(selected.as_deref(), now);
+        let ready = self.backend.is_some() && self.state.secrets_available() && !self.state.totp_loading();
+        if !ready || self.state.prefetch.due(now).is_none() {
+            return;
+        }
+        if self.state.selected_item().is_some_and(|item| item.login.as_ref().is_some_and(|login| login.totp.is_some())) {
+            self.fetch_totp_code();
+        }
+    }
+
+    /// Keep the watched TOTP code current: generate it locally, reuse a fetched one or fetch it
+    fn refresh_totp_watch(&mut self) {
+        let Some(watch) = self.state.ui.totp_watch.as_ref() else {
+            return;
+        };
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        if watch.fetching || watch.code.as_ref().is_some_and(|totp| totp.expires_at > now) || !self.state.secrets_available() {
+            return;
+        }
+        // A sync may have removed the item
+        let Some(item) = self.state.vault.vault_items.iter().find(|item| item.id == watch.item_id).cloned() else {
+            let name = self.state.ui.totp_watch.take().map(|watch| watch.name).unwrap_or_default();
+            self.state.set_status(format!("Stopped watching the TOTP code of {}, it was removed", name), MessageLevel::Warning);
+            return;
+        };
+
+        let secret = item.login.as_ref().and_then(|login| login.totp.as_deref());
+        let code = match secret.filter(|_| !self.state.item_details.local_totp_failed(&item)).map(totp_util::generate_totp) {
+            Some(Ok(totp)) => Some(FetchedTotp { code: totp.code, expires_at: totp.expires_at, period: totp.period }),
+            Some(Err(e)) => {
+                crate::logger::Logger::warn(&format!("Failed to generate TOTP locally, falling back to bw CLI: {}", e));
+                self.state.item_details.mark_local_totp_failed(&item);
+                None
+            }
+            None => None,
+        };
+        let code = code.or_else(|| self.state.item_details.totp(&item, now).cloned());
+
+        let Some(watch) = self.state.ui.totp_watch.as_mut() else {
+            return;
+        };
+        match (code, self.backend.as_ref()) {
+            (Some(code), _) => watch.code = Some(code),
+            (None, Some(backend)) => {
+                watch.fetching = true;
+                spawn_totp_fetch(backend.clone(), item.id, item.revision_date, self.message_tx.clone());
+            }
+            (None, None) => {}
+        }
+    }
+
+    /// Generate the TOTP code for the selected item from its in-memory secret
+    /// Returns None if the secret is not available (e.g. items loaded from cache) or can't be used locally
+    fn generate_local_totp(&self) -> Option<Result<totp_util::TotpCode>> {
+        let item = self.state.selected_item()?;
+        if self.state.item_details.local_totp_failed(item) {
+            return None;
+        }
+        let secret = item.login.as_ref()?.totp.as_deref()?;
+        Some(totp_util::generate_totp(secret))
+    }
+
+    /// Move the item selected in the duplicates report to the trash
+    fn delete_selected_duplicate(&mut self) {
+        self.state.duplicates.confirm_delete = false;
+        if self.state.ui.readonly {
+            return;
+        }
+        let Some(item_id) = self.state.duplicates.selected_item_id().map(str::to_string) else {
+            return;
+        };
+
+        let Some(cli) = self.backend.clone() else {
+            self.state.set_status("✗ Vault is not unlocked yet", MessageLevel::Error);
+            return;
+        };
+
+        self.state.duplicates.deleting = true;
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let result = match cli.delete_item(&item_id).await {
+                Ok(()) => DeleteResult::Success(item_id),
+                Err(e) => DeleteResult::Error(e.to_string()),
+            };
+            if let Err(e) = message_tx.send(result.into()) {
+                crate::logger::Logger::error(&format!("Failed to send delete result: {}", e));
+            }
+        });
+    }
+
+    /// Create or rename the folder whose name was typed in the folder screen
+    fn save_folder(&mut self) {
+        let name = self.state.folders.input.text().trim().to_string();
+        if name.is_empty() {
+            self.state.set_status("✗ A folder needs a name", MessageLevel::Warning);
+            return;
+        }
+        let Some(edit) = self.state.folders.edit.take() else {
+            return;
+        };
+        if self.state.ui.readonly {
+            return;
+        }
+        if let FolderEdit::Rename(folder_id) = &edit {
+            if self.state.vault.folders().iter().any(|folder| &folder.id == folder_id && folder.name == name) {
+                return;
+            }
+        }
+
+        let Some(cli) = self.backend.clone() else {
+            self.state.set_status("✗ Vault is not unlocked yet", MessageLevel::Error);
+            return;
+        };
+
+        self.state.folders.busy = true;
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let result = match edit {
+                FolderEdit::Create => cli.create_folder(&name).await.map(FolderResult::Created),
+                FolderEdit::Rename(folder_id) => cli
+                    .rename_folder(&folder_id, &name)
+                    .await
+                    .map(|()| FolderResult::Renamed(folder_id, name)),
+            };
+            let result = result.unwrap_or_else(|e| FolderResult::Error(e.to_string()));
+            if let Err(e) = message_tx.send(result.into()) {
+                crate::logger::Logger::error(&format!("Failed to send folder result: {}", e));
+            }
+        });
+    }
+
+    /// Delete the folder selected in the folder screen, its items stay in the vault
+    fn delete_selected_folder(&mut self) {
+        self.state.folders.confirm_delete = false;
+        if self.state.ui.readonly {
+            return;
+        }
+        let Some(folder_id) = self.state.selected_folder().map(|(folder, _)| folder.id.clone()) else {
+            return;
+        };
+
+        let Some(cli) = self.backend.clone() else {
+            self.state.set_status("✗ Vault is not unlocked yet", MessageLevel::Error);
+            return;
+        };
+
+        self.state.folders.busy = true;
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let result = match cli.delete_folder(&folder_id).await {
+                Ok(()) => FolderResult::Deleted(folder_id),
+                Err(e) => FolderResult::Error(e.to_string()),
+            };
+            if let Err(e) = message_tx.send(result.into()) {
+                crate::logger::Logger::error(&format!("Failed to send folder result: {}", e));
+            }
+        });
+    }
+
+    /// Move the item of the move picker to the folder selected in it
+    fn move_to_selected_folder(&mut self) {
+        let target = self.state.move_target();
+        self.state.folders.close();
+        if self.state.ui.readonly {
+            return;
+        }
+        let Some((item_id, folder_id)) = target else {
+            return;
+        };
+        if self.state.vault.vault_items.iter().any(|item| item.id == item_id && item.folder_id == folder_id) {
+            return;
+        }
+
+        let Some(cli) = self.backend.clone() else {
+            self.state.set_status("✗ Vault is not unlocked yet", MessageLevel::Error);
+            return;
+        };
+
+        self.state.folders.busy = true;
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let result = match cli.move_item(&item_id, folder_id.as_deref()).await {
+                Ok(()) => FolderResult::Moved(item_id, folder_id),
+                Err(e) => FolderResult::Error(e.to_string()),
+            };
+            if let Err(e) = message_tx.send(result.into()) {
+                crate::logger::Logger::error(&format!("Failed to send folder result: {}", e));
+            }
+        });
+    }
+
+    /// Share the item of the share dialog in the chosen collections
+    fn share_item(&mut self) {
+        self.state.share.confirm = false;
+        if self.state.ui.readonly {
+            return;
+        }
+        let Some((item_id, organization_id, collection_ids)) = self.state.share_target() else {
+            return;
+        };
+
+        let Some(cli) = self.backend.clone() else {
+            self.state.set_status("✗ Vault is not unlocked yet", MessageLevel::Error);
+            return;
+        };
+
+        self.state.share.busy = true;
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let result = match cli.share_item(&item_id, &organization_id, &collection_ids).await {
+                Ok(()) => ShareResult::Success(item_id, organization_id, collection_ids),
+                Err(e) => ShareResult::Error(e.to_string()),
+            };
+            if let Err(e) = message_tx.send(result.into()) {
+                crate::logger::Logger::error(&format!("Failed to send share result: {}", e));
+            }
+        });
+    }
+
+    /// Turn the master password re-prompt of the selected item on or off
+    /// Turning it off needs the master password, like using the item's secrets does
+    fn toggle_reprompt(&mut self) {
+        if self.state.ui.readonly {
+            self.state.set_status("Read-only mode, items can't be changed", MessageLevel::Warning);
+            return;
+        }
+        let Some(item) = self.state.selected_item() else {
+            self.state.set_status("✗ No item selected", MessageLevel::Warning);
+            return;
+        };
+        let (item_id, enabled) = (item.id.clone(), !item.requires_reprompt());
+        if !enabled && !self.state.consume_reprompt_grant(&item_id) {
+            self.state.enter_reprompt(Action::ToggleReprompt);
+            return;
+        }
+
+        let Some(cli) = self.backend.clone() else {
+            self.state.set_status("✗ Vault is not unlocked yet", MessageLevel::Error);
+            return;
+        };
+
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let result = cli.set_reprompt(&item_id, enabled).await;
+            if let Err(e) = message_tx.send(AppMessage::Reprompt(item_id, enabled, result)) {
+                crate::logger::Logger::error(&format!("Failed to send re-prompt change: {}", e));
+            }
+        });
+    }
+
+    /// Look up who can access the selected item's collections
+    fn fetch_collection_access(&mut self) {
+        let Some(cli) = self.backend.clone() else {
+            self.state.set_status("✗ Vault is not unlocked yet", MessageLevel::Error);
+            return;
+        };
+        for (organization_id, collection_id) in self.state.start_access_lookup() {
+            let cli = cli.clone();
+            let message_tx = self.message_tx.clone();
+            tokio::spawn(async move {
+                let result = cli.collection_access(&organization_id, &collection_id).await;
+                if let Err(e) = message_tx.send(AppMessage::CollectionAccess(collection_id, result)) {
+                    crate::logger::Logger::error(&format!("Failed to send collection access: {}", e));
+                }
+            });
+        }
+    }
+
     /// Trigger a vault refresh/sync
     pub fn refresh_vault(&mut self) {
         // Don't start a new sync if one is already in progress
@@ -477,21 +1465,23 @@ impl App {
             return;
         }
 
-        if let Some(ref bw_cli) = self.bw_cli {
+        if let Some(ref backend) = self.backend {
             self.state.start_sync();
             
-            let bw_cli_clone = bw_cli.clone();
-            let sync_tx_clone = self.sync_tx.clone();
+            let message_tx = self.message_tx.clone();
+            let backend_clone = backend.with_retry_listener(sync_retry_listener(message_tx.clone()));
             
-            tokio::spawn(async move {
-                let result = match bw_cli_clone.sync().await {
+            self.sync_task = Some(tokio::spawn(async move {
+                let result = match backend_clone.sync().await {
                     Ok(_) => {
                         crate::logger::Logger::info("Vault sync completed");
-                        match bw_cli_clone.list_items().await {
-                            Ok(items) => {
-                                crate::logger::Logger::info(&format!("Successfully loaded {} vault items after sync", items.len()));
-                                SyncResult::Success(items)
+                        send_item_metadata(&backend_clone, &message_tx).await;
+                        match backend_clone.list_items(&item_batch_sender(message_tx.clone())).await {
+                            Ok(count) => {
+                                crate::logger::Logger::info(&format!("Successfully loaded {} vault items after sync", count));
+                                SyncResult::Complete
                             }
+                            Err(BwError::VaultLocked) => SyncResult::Locked(LockedOperation::Refresh),
                             Err(e) => {
                                 let error_msg = format!("Failed to load items: {}", e);
                                 crate::logger::Logger::error(&format!("Vault refresh failed: {}", error_msg));
@@ -499,6 +1489,7 @@ impl App {
                             }
                         }
                     }
+                    Err(BwError::VaultLocked) => SyncResult::Locked(LockedOperation::Refresh),
                     Err(e) => {
                         let error_msg = e.to_string();
                         crate::logger::Logger::error(&format!("Vault sync failed: {}", error_msg));
@@ -506,17 +1497,56 @@ impl App {
                     }
                 };
                 
-                if let Err(e) = sync_tx_clone.send(result) {
+                if let Err(e) = message_tx.send(result.into()) {
                     crate::logger::Logger::error(&format!("Failed to send sync result: {}", e));
                 }
-            });
+            }));
+        } else {
+            // Initialization failed or was cancelled before the CLI was ready, start over
+            self.start_vault_initialization();
+        }
+    }
+
+    /// Cancel the in-flight vault load or sync, killing the running bw command
+    pub fn cancel_sync(&mut self) {
+        if !self.state.syncing() {
+            return;
+        }
+
+        if let Some(task) = self.sync_task.take() {
+            // Aborting drops the command future, which kills the child process
+            task.abort();
+            crate::logger::Logger::info("Vault sync cancelled by user");
+        }
+
+        self.state.stop_sync();
+        self.discard_loading_items();
+        self.state.set_status("Sync cancelled (Ctrl+R to retry)", MessageLevel::Warning);
+    }
+
+    /// Stop the in-flight load or sync before quitting
+    /// Other commands are killed when the runtime drops their tasks on exit
+    fn stop_background_work(&mut self) {
+        if let Some(task) = self.sync_task.take() {
+            // Aborting drops the command future, which kills the child process
+            task.abort();
+            crate::logger::Logger::info("Vault sync stopped to quit");
         }
     }
 
     /// Handle an action - returns false if app should quit
     pub async fn handle_action(&mut self, action: Action, session_manager: &crate::session::SessionManager) -> bool {
-        // Handle quit action
+        // Handle quit action, asking first if it would cut off an operation
         if matches!(action, Action::Quit) {
+            if self.state.operation_in_progress().is_some() {
+                self.state.ui.confirm_quit = true;
+                self.state.mark_dirty();
+                return true;
+            }
+            return false;
+        }
+        if matches!(action, Action::ConfirmQuit) {
+            self.stop_background_work();
             return false;
         }
 
@@ -524,9 +1554,11 @@ impl App {
         if matches!(action, Action::LockAndQuit) {
             let mut errors = Vec::new();
             
-            // Clear the session token
-            if let Err(e) = session_manager.clear_token() {
-                errors.push(format!("Failed to clear session token: {}", e));
+            // Clear the session token, unless the keyring is off limits
+            if self.session.use_keyring() {
+                if let Err(e) = session_manager.clear_token() {
+                    errors.push(format!("Failed to clear session token: {}", e));
+                }
             }
             
             // Clear the vault cache
@@ -538,29 +1570,66 @@ impl App {
             if errors.is_empty() {
                 self.state.set_status("Session token and cache cleared", crate::state::MessageLevel::Info);
             } else {
-                self.state.set_status(&format!("Lock and quit completed with errors: {}", errors.join(", ")), crate::state::MessageLevel::Warning);
+                self.state.set_status(format!("Lock and quit completed with errors: {}", errors.join(", ")), crate::state::MessageLevel::Warning);
             }
             
             return false;
         }
 
+        // Anything but a tick is a user interaction that can change what's on screen
+        if !matches!(action, Action::Tick) {
+            self.state.mark_dirty();
+        }
+
+        // Any other key ends a two-key sequence
+        if !matches!(action, Action::Tick | Action::KeyPrefix(_)) {
+            self.state.ui.pending_key = None;
+        }
+
         // Handle tick action (periodic UI updates)
         if matches!(action, Action::Tick) {
+            // Clear old status messages
+            self.state.expire_old_status();
+
+            // Notice a locked session early
+            self.keep_alive_if_due();
+
+            self.clear_clipboard_if_due();
+            if self.state.has_countdown() {
+                self.state.mark_dirty();
+            }
+
+            self.refresh_totp_watch();
+            self.prefetch_selected_item();
+            if self.state.show_totp_dashboard() {
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                self.state.totp_dashboard.refresh(now);
+            }
+
             // Check if we need to refresh TOTP code
             if self.state.details_panel_visible() {
+                // The TOTP countdown changes every tick
+                self.state.mark_dirty();
+
+                // Regenerate locally on every tick when the secret is in memory
+                if let Some(Ok(totp)) = self.generate_local_totp() {
+                    self.handle_totp_result(TotpResult::Success(totp.code, totp.expires_at, totp.period));
+                    return true;
+                }
+
                 if let Some(item) = self.state.selected_item() {
                     if let Some(login) = &item.login {
                         if login.totp.is_some() {
                             // Only fetch TOTP if we're not already loading one and enough time has passed
-                            if !self.state.totp_loading() && self.state.can_fetch_totp() {
-                                // If we have a TOTP code but it's expired, refresh it
-                                if self.state.current_totp_code().is_some() && self.state.is_totp_expired() {
-                                    self.fetch_totp_code();
-                                }
-                                // If we don't have a TOTP code yet, fetch it
-                                else if self.state.current_totp_code().is_none() {
-                                    self.fetch_totp_code();
-                                }
+                            // Fetch if we don't have a code yet, or if the current one has expired
+                            if !self.state.totp_loading()
+                                && self.state.can_fetch_totp()
+                                && (self.state.current_totp_code().is_none() || self.state.is_totp_expired())
+                            {
+                                self.fetch_totp_code();
                             }
                         }
                     }
@@ -569,6 +1638,12 @@ impl App {
             return true;
         }
 
+        // Resizing affects the screen behind any dialog
+        if matches!(action, Action::Resize) {
+            self.state.handle_resize();
+            return true;
+        }
+
         // Handle password input modal actions
         if self.state.password_input_mode() {
             return self.handle_password_input_action(action);
@@ -584,15 +1659,29 @@ impl App {
             return true;
         }
 
+        let results_before = self.state.vault.filtered_len();
         if actions::handle_filter(&action, &mut self.state) {
+            // Typing down to a single result can act on it right away
+            let typed = matches!(action, Action::AppendFilter(_) | Action::DeleteFilterChar | Action::EditFilter(_) | Action::PasteFilter(_));
+            if typed && results_before != 1 && self.state.vault.filtered_len() == 1 && self.state.ui.pick.is_none() {
+                self.handle_single_result();
+            }
             return true;
         }
 
         if actions::handle_ui(&action, &mut self.state) {
+            if matches!(action, Action::ShrinkList | Action::GrowList) {
+                self.save_list_percent();
+            }
             return true;
         }
 
-        match actions::handle_copy(&action, &mut self.state, self.clipboard.as_mut(), self.bw_cli.as_ref()) {
+        if matches!(action, Action::Pick) {
+            self.pick();
+            return true;
+        }
+
+        match actions::handle_copy(&action, &mut self.state, self.clipboard.as_mut(), self.backend.as_ref()) {
             CopyResult::Handled => {
                 return true;
             }
@@ -601,6 +1690,11 @@ impl App {
                 self.fetch_totp_code();
                 return true;
             }
+            CopyResult::NeedReprompt => {
+                // Ask for the master password, the action is resumed once verified
+                self.state.enter_reprompt(action);
+                return true;
+            }
             CopyResult::NotHandled => {
                 // Continue to other action handlers
             }
@@ -618,9 +1712,106 @@ impl App {
             return true;
         }
 
+        // Handle sync cancellation
+        if matches!(action, Action::CancelSync) {
+            self.cancel_sync();
+            return true;
+        }
+
+        // Handle deleting an item from the duplicates report
+        if matches!(action, Action::ConfirmDeleteDuplicate) {
+            self.delete_selected_duplicate();
+            return true;
+        }
+
+        if matches!(action, Action::ConfirmShare) {
+            self.share_item();
+            return true;
+        }
+
+        if matches!(action, Action::ToggleReprompt) {
+            self.toggle_reprompt();
+            return true;
+        }
+
+        if matches!(action, Action::ShowCollectionAccess) {
+            self.fetch_collection_access();
+            return true;
+        }
+
+        // Handle changes to folders from the folder screen
+        if matches!(action, Action::SaveFolder) {
+            self.save_folder();
+            return true;
+        }
+
+        if matches!(action, Action::ConfirmDeleteFolder) {
+            self.delete_selected_folder();
+            return true;
+        }
+
+        if matches!(action, Action::MoveToSelectedFolder) {
+            self.move_to_selected_folder();
+            return true;
+        }
+
+        if matches!(action, Action::SaveLocalNote) {
+            self.save_local_note();
+            return true;
+        }
+
+        if matches!(action, Action::CycleView) {
+            self.cycle_view();
+            return true;
+        }
+
+        if matches!(action, Action::ToggleItemHidden) {
+            self.toggle_item_hidden();
+            return true;
+        }
+
         true
     }
 
+    /// Run the configured action on the only search result, which filtering already selected
+    fn handle_single_result(&mut self) {
+        match self.config.search.single_result {
+            SingleResultAction::Select => {}
+            SingleResultAction::CopyPassword => {
+                let result = actions::handle_copy(&Action::CopyPassword, &mut self.state, self.clipboard.as_mut(), self.backend.as_ref());
+                if let CopyResult::NeedReprompt = result {
+                    self.state.enter_reprompt(Action::CopyPassword);
+                }
+            }
+            SingleResultAction::OpenDetails => {
+                if !self.state.details_panel_visible() {
+                    self.state.toggle_details_panel();
+                }
+            }
+        }
+    }
+
+    /// Copy the picker's field of the selected item
+    fn pick(&mut self) {
+        let Some(field) = self.state.ui.pick else {
+            return;
+        };
+        // Only a new success message tells that the copy worked
+        self.state.status_message = None;
+        match actions::handle_copy(&field.copy_action(), &mut self.state, self.clipboard.as_mut(), self.backend.as_ref()) {
+            // Finished once the code arrives
+            CopyResult::NeedTotpFetch => self.fetch_totp_code(),
+            // Finished once the master password is verified
+            CopyResult::NeedReprompt => self.state.enter_reprompt(field.copy_action()),
+            _ => self.finish_pick(),
+        }
+    }
+
+    /// Let the picker exit if its copy succeeded, otherwise it stays open showing why not
+    fn finish_pick(&mut self) {
+        self.picked = self.state.status_message.as_ref().is_some_and(|status| status.level == MessageLevel::Success);
+    }
+
     /// Handle password input modal actions
     fn handle_password_input_action(&mut self, action: Action) -> bool {
         match action {
@@ -630,14 +1821,52 @@ impl App {
             Action::DeletePasswordChar => {
                 self.state.delete_password_char();
             }
+            Action::EditPassword(edit) => {
+                self.state.edit_password(edit);
+            }
+            Action::PastePassword(text) => {
+                self.state.paste_password(&text);
+            }
+            Action::PasteClipboardPassword => {
+                match self.clipboard.as_mut().map(ClipboardManager::paste) {
+                    Some(Ok(text)) => self.state.paste_password(&text),
+                    Some(Err(e)) => self.state.set_unlock_error(format!("Failed to paste: {}", e)),
+                    None => self.state.set_unlock_error("Clipboard is not available".to_string()),
+                }
+            }
             Action::ClearPassword => {
                 self.state.clear_password();
             }
             Action::SubmitPassword => {
+                // Another Enter while the password is checked would start a second unlock
+                if self.state.unlock_in_progress() {
+                    return true;
+                }
+                // The dialog counts down until another attempt is allowed
+                if self.state.ui.unlock_wait(Instant::now()).is_some() {
+                    return true;
+                }
                 let password = self.state.get_password();
-                self.unlock_with_password(password);
+                if self.state.reprompt_active() {
+                    self.verify_reprompt(password);
+                } else {
+                    self.unlock_with_password(password);
+                }
             }
             Action::CancelPasswordInput => {
+                // Cancelling a re-prompt only drops the pending action
+                if self.state.reprompt_active() {
+                    self.state.exit_reprompt();
+                    self.state.set_status("Master password re-prompt cancelled", MessageLevel::Info);
+                    return true;
+                }
+                // Cancelling after a lock keeps the app open, commands fail until unlocked
+                if self.state.ui.take_relocked() {
+                    self.state.exit_password_mode();
+                    self.resume_after_unlock = None;
+                    self.state.set_status("✗ Vault is locked, press Ctrl+R to unlock", MessageLevel::Warning);
+                    return true;
+                }
                 // If user cancels unlock, exit the app
                 return false;
             }
@@ -662,26 +1891,728 @@ impl App {
         true
     }
 
+    /// Clear the clipboard once the configured time after a copy has passed
+    fn clear_clipboard_if_due(&mut self) {
+        let Some(clipboard) = self.clipboard.as_mut() else {
+            return;
+        };
+        match clipboard.clear_if_due(Instant::now()) {
+            Ok(true) => self.state.set_status("Clipboard cleared", MessageLevel::Info),
+            Ok(false) => {}
+            Err(e) => self.state.set_status(format!("✗ Failed to clear clipboard: {}", e), MessageLevel::Error),
+        }
+    }
+
+    /// Remember the split between the list and the details panel across restarts
+    fn save_list_percent(&mut self) {
+        let list_percent = self.state.ui.layout.list_percent;
+        if self.config.layout.list_percent == list_percent {
+            return;
+        }
+        self.config.layout.list_percent = list_percent;
+        if let Err(e) = crate::config::save_list_percent(list_percent) {
+            crate::logger::Logger::warn(&format!("Failed to save layout: {}", e));
+        }
+    }
+
     /// Check if clipboard warning should be shown
     pub fn should_show_clipboard_warning(&self) -> bool {
         self.clipboard.is_none()
     }
 
-    /// Update app state and render UI
-    pub fn update(&mut self, ui: &mut crate::ui::UI) -> crate::error::Result<()> {
-        // Clear old status messages
-        self.state.expire_old_status();
+    /// Render the UI if anything changed since the last frame
+    pub fn render(&mut self, ui: &mut crate::ui::UI) -> crate::error::Result<()> {
+        // The status bar shows a countdown until the clipboard is cleared
+        self.state.ui.clipboard_clears_at = self.clipboard.as_ref().and_then(ClipboardManager::clears_at);
+        if self.state.take_dirty() {
+            ui.render(&mut self.state)?;
+        }
+        Ok(())
+    }
+}
+
+/// Remove a stored session token that no longer unlocks the vault
+fn clear_stale_session_token(store: Result<crate::session::SessionManager>) {
+    let result = store.and_then(|session_manager| session_manager.clear_token());
+    match result {
+        Ok(()) => crate::logger::Logger::info("Cleared expired session token"),
+        Err(e) => crate::logger::Logger::warn(&format!("Failed to clear expired session token: {}", e)),
+    }
+}
+
+/// Replace the stored session token after a re-prompt issued a new session
+/// Only done when a token was previously saved, so declining to save is respected
+fn resave_session_token(store: Result<crate::session::SessionManager>, token: &str) {
+    let session_manager = match store {
+        Ok(manager) => manager,
+        Err(e) => {
+            crate::logger::Logger::warn(&format!("Failed to initialize session manager: {}", e));
+            return;
+        }
+    };
 
-        // Advance sync animation
-        self.state.advance_sync_animation();
+    if let Ok(Some(_)) = session_manager.load_token() {
+        if let Err(e) = session_manager.save_token(token) {
+            crate::logger::Logger::warn(&format!("Failed to update saved session token: {}", e));
+        }
+    }
+}
 
-        // Process any incoming messages from background tasks
-        self.process_background_messages();
+/// Fetch the organizations, folders and collections items may refer to
+/// A failure only loses their names, so it doesn't fail the sync
+async fn send_item_metadata(cli: &Backend, message_tx: &mpsc::UnboundedSender<AppMessage>) {
+    match cli.list_organizations().await {
+        Ok(organizations) => {
+            if let Err(e) = message_tx.send(SyncResult::Organizations(organizations).into()) {
+                crate::logger::Logger::error(&format!("Failed to send organizations: {}", e));
+            }
+        }
+        Err(e) => {
+            crate::logger::Logger::warn(&format!("Failed to load organizations: {}", e));
+        }
+    }
 
-        // Render UI
-        ui.render(&mut self.state)?;
+    match cli.list_folders().await {
+        Ok(folders) => {
+            if let Err(e) = message_tx.send(SyncResult::Folders(folders).into()) {
+                crate::logger::Logger::error(&format!("Failed to send folders: {}", e));
+            }
+        }
+        Err(e) => {
+            crate::logger::Logger::warn(&format!("Failed to load folders: {}", e));
+        }
+    }
 
-        Ok(())
+    match cli.list_collections().await {
+        Ok(collections) => {
+            if let Err(e) = message_tx.send(SyncResult::Collections(collections).into()) {
+                crate::logger::Logger::error(&format!("Failed to send collections: {}", e));
+            }
+        }
+        Err(e) => {
+            crate::logger::Logger::warn(&format!("Failed to load collections: {}", e));
+        }
     }
+
+    match cli.equivalent_domains().await {
+        Ok(domains) => {
+            if let Err(e) = message_tx.send(SyncResult::EquivalentDomains(domains).into()) {
+                crate::logger::Logger::error(&format!("Failed to send equivalent domains: {}", e));
+            }
+        }
+        Err(e) => {
+            crate::logger::Logger::warn(&format!("Failed to load equivalent domains: {}", e));
+        }
+    }
+}
+
+/// Build a callback that forwards batches of listed items to the app
+fn item_batch_sender(message_tx: mpsc::UnboundedSender<AppMessage>) -> impl Fn(Vec<VaultItem>) + Send + Sync {
+    move |items| {
+        if let Err(e) = message_tx.send(SyncResult::Batch(items).into()) {
+            crate::logger::Logger::error(&format!("Failed to send vault items: {}", e));
+        }
+    }
+}
+
+/// Build a retry listener that reports retries as sync progress
+fn sync_retry_listener(message_tx: mpsc::UnboundedSender<AppMessage>) -> RetryListener {
+    std::sync::Arc::new(move |attempt, max_attempts| {
+        if let Err(e) = message_tx.send(SyncResult::Retrying(attempt, max_attempts).into()) {
+            crate::logger::Logger::error(&format!("Failed to send retry progress: {}", e));
+        }
+    })
+}
+
+/// Fetch an item's TOTP code from the backend in the background
+fn spawn_totp_fetch(backend: Backend, item_id: String, revision_date: DateTime<Utc>, message_tx: mpsc::UnboundedSender<AppMessage>) {
+    tokio::spawn(async move {
+        let result = match backend.get_totp(&item_id).await {
+            Ok(code) => {
+                // Calculate expiration time (the CLI doesn't report the period, assume the default)
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                let period = totp_util::DEFAULT_PERIOD;
+                let expires_at = ((now / period) + 1) * period; // Next period boundary
+                TotpResult::Fetched(item_id, revision_date, FetchedTotp { code, expires_at, period })
+            }
+            Err(BwError::VaultLocked) => TotpResult::Locked,
+            Err(e) => {
+                let error_msg = e.to_string();
+                crate::logger::Logger::error(&format!("Failed to fetch TOTP for item {}: {}", item_id, error_msg));
+                TotpResult::Error(error_msg)
+            }
+        };
+        if let Err(e) = message_tx.send(result.into()) {
+            crate::logger::Logger::error(&format!("Failed to send TOTP result: {}", e));
+        }
+    });
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake_backend::{self, FakeBackend};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// An App that doesn't touch the config file, cache, keyring or clipboard
+    fn test_app() -> App {
+        let mut app = App::with_config(SessionOptions::new(None, None, true), Config::default());
+        app.use_cache = false;
+        app.use_local_notes = false;
+        app.clipboard = None;
+        app
+    }
+
+    fn start(app: &mut App, backend: &FakeBackend) {
+        let backend: Backend = Arc::new(backend.clone());
+        app.initialize_vault(async move { Ok(backend) });
+    }
+
+    /// Handle messages from background tasks until they stop coming
+    async fn settle(app: &mut App) {
+        while let Ok(Some(message)) = tokio::time::timeout(Duration::from_millis(100), app.next_message()).await {
+            app.handle_message(message);
+        }
+    }
+
+    fn status_text(app: &App) -> &str {
+        app.state.status_message.as_ref().map(|status| status.text.as_str()).unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn test_quit_asks_first_during_a_sync() {
+        let backend = FakeBackend::new(VaultStatus::Unlocked, vec![fake_backend::login("1", "Bank", None)]);
+        let mut app = test_app();
+        let session_manager = crate::session::SessionManager::new().unwrap();
+        start(&mut app, &backend);
+        assert!(app.state.syncing());
+
+        assert!(app.handle_action(Action::Quit, &session_manager).await);
+        assert!(app.state.confirm_quit());
+        assert!(app.handle_action(Action::CancelQuit, &session_manager).await);
+        assert!(!app.state.confirm_quit());
+
+        // Confirming stops the load
+        assert!(app.handle_action(Action::Quit, &session_manager).await);
+        assert!(!app.handle_action(Action::ConfirmQuit, &session_manager).await);
+        assert!(app.sync_task.is_none());
+
+        // Nothing in progress, nothing to confirm
+        let mut app = test_app();
+        start(&mut app, &backend);
+        settle(&mut app).await;
+        assert!(!app.handle_action(Action::Quit, &session_manager).await);
+    }
+
+    #[tokio::test]
+    async fn test_unlocked_vault_loads_items() {
+        let backend = FakeBackend::new(VaultStatus::Unlocked, vec![
+            fake_backend::login("1", "Mail", None),
+            fake_backend::login("2", "Bank", None),
+        ]);
+        let mut app = test_app();
+        start(&mut app, &backend);
+        settle(&mut app).await;
+
+        assert_eq!(app.state.vault.filtered_len(), 2);
+        assert!(app.state.secrets_available());
+        assert!(!app.state.syncing());
+        assert!(status_text(&app).contains("synced"));
+    }
+
+    #[tokio::test]
+    async fn test_locked_vault_unlocks_with_password() {
+        let backend = FakeBackend::new(VaultStatus::Locked, vec![fake_backend::login("1", "Mail", None)]);
+        let mut app = test_app();
+        start(&mut app, &backend);
+        settle(&mut app).await;
+        assert!(app.state.password_input_mode());
+        assert_eq!(app.state.vault.filtered_len(), 0);
+
+        // A wrong password keeps the dialog open with the error
+        app.unlock_with_password("wrong".to_string());
+        assert!(app.state.unlock_in_progress());
+        settle(&mut app).await;
+        assert!(!app.state.unlock_in_progress());
+        assert!(!app.state.syncing());
+        assert!(app.state.password_input_mode());
+        assert!(app.state.ui.unlock_error.as_deref().unwrap_or_default().contains("Invalid master password"));
+
+        // Without the keyring the token isn't offered for saving, the items load right away
+        app.unlock_with_password(fake_backend::PASSWORD.to_string());
+        settle(&mut app).await;
+        assert!(!app.state.password_input_mode());
+        assert!(!app.state.offer_save_token());
+        assert_eq!(app.state.vault.filtered_len(), 1);
+        assert_eq!(backend.unlock_attempts(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_expired_stored_token_is_cleared() {
+        let dir = std::env::temp_dir().join(format!("bwtui-expired-token-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let session_file = dir.join("session.enc");
+        std::fs::write(&session_file, b"stale").unwrap();
+
+        let backend = FakeBackend::new(VaultStatus::Locked, vec![fake_backend::login("1", "Mail", None)]).with_session_token();
+        let mut app = test_app();
+        app.session = SessionOptions::new(None, None, false);
+        app.session_file = Some(session_file.clone());
+        start(&mut app, &backend);
+        settle(&mut app).await;
+
+        assert!(app.state.ui.session_expired);
+        assert!(app.state.password_input_mode());
+        assert!(!session_file.exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_double_submit_unlocks_once() {
+        let backend = FakeBackend::new(VaultStatus::Locked, vec![fake_backend::login("1", "Mail", None)]);
+        let mut app = test_app();
+        start(&mut app, &backend);
+        settle(&mut app).await;
+        let session_manager = crate::session::SessionManager::new().unwrap();
+
+        app.state.paste_password(fake_backend::PASSWORD);
+        app.handle_action(Action::SubmitPassword, &session_manager).await;
+        app.handle_action(Action::SubmitPassword, &session_manager).await;
+        settle(&mut app).await;
+        assert!(!app.state.password_input_mode());
+        assert_eq!(backend.unlock_attempts(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_wrong_passwords_are_counted_and_can_exit() {
+        let backend = FakeBackend::new(VaultStatus::Locked, vec![fake_backend::login("1", "Mail", None)]);
+        let mut app = test_app();
+        app.state.ui.max_unlock_attempts = 3;
+        start(&mut app, &backend);
+        settle(&mut app).await;
+
+        // An empty password isn't an attempt
+        app.unlock_with_password(String::new());
+        assert_eq!(app.state.ui.unlock_failures, 0);
+
+        for _ in 0..2 {
+            app.unlock_with_password("wrong".to_string());
+            settle(&mut app).await;
+        }
+        assert_eq!(app.state.ui.unlock_failures, 2);
+        assert!(!app.unlock_attempts_exhausted());
+
+        app.unlock_with_password("wrong".to_string());
+        settle(&mut app).await;
+        assert!(app.unlock_attempts_exhausted());
+        // Another attempt has to wait
+        assert!(app.state.ui.unlock_wait(Instant::now()).is_some());
+        assert_eq!(backend.unlock_attempts(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_folder_screen_changes_folders() {
+        let mut mail = fake_backend::login("1", "Mail", None);
+        mail.folder_id = Some("f-1".to_string());
+        let backend = FakeBackend::new(VaultStatus::Unlocked, vec![mail, fake_backend::login("2", "Bank", None)])
+            .with_folders(vec![Folder { id: "f-1".to_string(), name: "Personal".to_string() }]);
+        let mut app = test_app();
+        start(&mut app, &backend);
+        settle(&mut app).await;
+        let session_manager = crate::session::SessionManager::new().unwrap();
+        app.handle_action(Action::ToggleFolders, &session_manager).await;
+        assert_eq!(app.state.selected_folder().map(|(folder, count)| (folder.name.as_str(), count)), Some(("Personal", 1)));
+
+        // Creating selects the new folder
+        app.handle_action(Action::NewFolder, &session_manager).await;
+        app.handle_action(Action::PasteFolderName(" Archive ".to_string()), &session_manager).await;
+        app.handle_action(Action::SaveFolder, &session_manager).await;
+        settle(&mut app).await;
+        assert_eq!(status_text(&app), "✓ Created folder 'Archive'");
+        assert_eq!(app.state.selected_folder().map(|(folder, _)| folder.name.as_str()), Some("Archive"));
+
+        app.handle_action(Action::RenameFolder, &session_manager).await;
+        app.handle_action(Action::AppendFolderChar('s'), &session_manager).await;
+        app.handle_action(Action::SaveFolder, &session_manager).await;
+        settle(&mut app).await;
+        assert_eq!(backend.folders()[1].name, "Archives");
+
+        // Deleting a folder leaves its items without a folder
+        app.handle_action(Action::SelectNextFolder, &session_manager).await;
+        app.handle_action(Action::RequestDeleteFolder, &session_manager).await;
+        assert!(app.state.folders.confirm_delete);
+        app.handle_action(Action::ConfirmDeleteFolder, &session_manager).await;
+        settle(&mut app).await;
+        assert_eq!(status_text(&app), "✓ Deleted folder 'Personal'");
+        assert_eq!(backend.folders().len(), 1);
+        assert!(app.state.vault.vault_items.iter().all(|item| item.folder_id.is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_move_item_to_folder() {
+        let backend = FakeBackend::new(VaultStatus::Unlocked, vec![fake_backend::login("1", "Mail", None)])
+            .with_folders(vec![Folder { id: "f-1".to_string(), name: "Personal".to_string() }]);
+        let mut app = test_app();
+        start(&mut app, &backend);
+        settle(&mut app).await;
+        let session_manager = crate::session::SessionManager::new().unwrap();
+
+        // "No folder" is listed first, where the item starts
+        app.handle_action(Action::OpenMoveToFolder, &session_manager).await;
+        assert_eq!(app.state.move_target(), Some(("1".to_string(), None)));
+        app.handle_action(Action::SelectNextFolder, &session_manager).await;
+        app.handle_action(Action::MoveToSelectedFolder, &session_manager).await;
+        assert!(!app.state.show_folders());
+        settle(&mut app).await;
+        assert_eq!(status_text(&app), "✓ Moved 'Mail' to folder 'Personal'");
+        assert_eq!(app.state.selected_item().unwrap().folder_id.as_deref(), Some("f-1"));
+
+        // The picker opens on the item's folder; a sync keeps what the backend saved
+        app.handle_action(Action::OpenMoveToFolder, &session_manager).await;
+        assert_eq!(app.state.folders.selected, 1);
+        app.handle_action(Action::SelectPreviousFolder, &session_manager).await;
+        app.handle_action(Action::MoveToSelectedFolder, &session_manager).await;
+        settle(&mut app).await;
+        assert_eq!(status_text(&app), "✓ Moved 'Mail' out of its folder");
+        app.refresh_vault();
+        settle(&mut app).await;
+        assert_eq!(app.state.selected_item().unwrap().folder_id, None);
+    }
+
+    #[tokio::test]
+    async fn test_collection_access_of_organization_items() {
+        let mut shared = fake_backend::login("1", "Deploy key", None);
+        shared.organization_id = Some("o-1".to_string());
+        shared.collection_ids = Some(vec!["c-1".to_string(), "c-2".to_string()]);
+        let collection = |id: &str, name: &str| Collection { id: id.to_string(), organization_id: "o-1".to_string(), name: name.to_string() };
+        let member = crate::types::OrgMember { id: "m-1".to_string(), name: Some("Bob".to_string()), email: "bob@example.com".to_string() };
+        let backend = FakeBackend::new(VaultStatus::Unlocked, vec![shared])
+            .with_collections(vec![collection("c-1", "Ops"), collection("c-2", "Dev")])
+            .with_collection_access("c-1", CollectionAccess { members: vec![member.clone()], groups: 0 });
+        let mut app = test_app();
+        start(&mut app, &backend);
+        settle(&mut app).await;
+        let item = app.state.selected_item().unwrap();
+        let names: Vec<&str> = app.state.vault.item_collections(item).iter().map(|collection| collection.name.as_str()).collect();
+        assert_eq!(names, vec!["Dev", "Ops"]);
+
+        // Collections the user doesn't manage can't be looked up
+        let session_manager = crate::session::SessionManager::new().unwrap();
+        app.handle_action(Action::ShowCollectionAccess, &session_manager).await;
+        assert_eq!(app.state.collection_access.get("c-1"), Some(&AccessLookup::Loading));
+        settle(&mut app).await;
+        assert_eq!(app.state.collection_access.get("c-1"), Some(&AccessLookup::Loaded(CollectionAccess { members: vec![member], groups: 0 })));
+        assert!(matches!(app.state.collection_access.get("c-2"), Some(AccessLookup::Failed(_))));
+        assert!(app.state.details_panel_visible());
+    }
+
+    #[tokio::test]
+    async fn test_share_item_with_collections() {
+        let collection = |id: &str, name: &str| Collection { id: id.to_string(), organization_id: "o-1".to_string(), name: name.to_string() };
+        let backend = FakeBackend::new(VaultStatus::Unlocked, vec![fake_backend::login("1", "Deploy key", None)])
+            .with_collections(vec![collection("c-1", "Ops"), collection("c-2", "Dev")]);
+        let mut app = test_app();
+        start(&mut app, &backend);
+        settle(&mut app).await;
+
+        let session_manager = crate::session::SessionManager::new().unwrap();
+        app.handle_action(Action::OpenShare, &session_manager).await;
+        assert!(app.state.share_open());
+        // Nothing is shared before a collection is chosen and sharing confirmed
+        app.handle_action(Action::RequestShare, &session_manager).await;
+        assert!(!app.state.share.confirm);
+        app.handle_action(Action::ToggleShareCollection, &session_manager).await;
+        app.handle_action(Action::RequestShare, &session_manager).await;
+        assert!(app.state.share.confirm);
+        app.handle_action(Action::ConfirmShare, &session_manager).await;
+        settle(&mut app).await;
+
+        assert!(!app.state.share_open());
+        assert!(status_text(&app).starts_with("✓ Shared 'Deploy key'"));
+        let item = app.state.selected_item().unwrap();
+        assert_eq!(item.organization_id.as_deref(), Some("o-1"));
+        let names: Vec<&str> = app.state.vault.item_collections(item).iter().map(|collection| collection.name.as_str()).collect();
+        assert_eq!(names, vec!["Dev"]);
+    }
+
+    #[tokio::test]
+    async fn test_search_matches_equivalent_domains_after_sync() {
+        let mut shop = fake_backend::login("1", "Shop", None);
+        shop.login.as_mut().unwrap().uris = Some(vec![crate::types::Uri { uri: "https://amzn.com".to_string(), match_type: None }]);
+        let domains = EquivalentDomains::new(vec![vec!["amazon.com".to_string(), "amzn.com".to_string()]]);
+        let backend = FakeBackend::new(VaultStatus::Unlocked, vec![shop]).with_equivalent_domains(domains);
+        let mut app = test_app();
+        start(&mut app, &backend);
+        settle(&mut app).await;
+
+        app.state.vault.set_filter_query("amazon".to_string(), None);
+        assert_eq!(app.state.vault.filtered_len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_toggle_reprompt() {
+        let backend = FakeBackend::new(VaultStatus::Unlocked, vec![fake_backend::login("1", "Bank", None)]);
+        let mut app = test_app();
+        start(&mut app, &backend);
+        settle(&mut app).await;
+
+        let session_manager = crate::session::SessionManager::new().unwrap();
+        app.handle_action(Action::ToggleReprompt, &session_manager).await;
+        settle(&mut app).await;
+        assert!(app.state.selected_item().unwrap().requires_reprompt());
+        assert_eq!(status_text(&app), "✓ Master password re-prompt turned on for 'Bank'");
+
+        // Turning it off asks for the master password first
+        app.handle_action(Action::ToggleReprompt, &session_manager).await;
+        assert!(app.state.reprompt_active());
+        app.state.exit_reprompt();
+        app.state.grant_reprompt("1".to_string());
+        app.handle_action(Action::ToggleReprompt, &session_manager).await;
+        settle(&mut app).await;
+        assert!(!app.state.selected_item().unwrap().requires_reprompt());
+    }
+
+    #[tokio::test]
+    async fn test_copy_password_asks_for_reprompt() {
+        let mut item = fake_backend::login("1", "Bank", None);
+        item.reprompt = Some(1);
+        let backend = FakeBackend::new(VaultStatus::Unlocked, vec![item]);
+        let mut app = test_app();
+        start(&mut app, &backend);
+        settle(&mut app).await;
+        let session_manager = crate::session::SessionManager::new().unwrap();
+
+        app.handle_action(Action::CopyPassword, &session_manager).await;
+        assert!(app.state.reprompt_active());
+
+        // The copy goes ahead once the master password is verified
+        app.verify_reprompt(fake_backend::PASSWORD.to_string());
+        settle(&mut app).await;
+        assert!(!app.state.reprompt_active());
+        assert_eq!(status_text(&app), "✗ Clipboard not available");
+    }
+
+    #[tokio::test]
+    async fn test_quick_copy_asks_for_reprompt() {
+        let mut protected = fake_backend::login("2", "Bank", None);
+        protected.reprompt = Some(1);
+        let backend = FakeBackend::new(VaultStatus::Unlocked, vec![fake_backend::login("1", "Alpha", None), protected]);
+        let mut app = test_app();
+        start(&mut app, &backend);
+        settle(&mut app).await;
+        let session_manager = crate::session::SessionManager::new().unwrap();
+        assert_eq!(app.state.selected_item().unwrap().name, "Alpha");
+
+        app.handle_action(Action::QuickCopyPassword(1), &session_manager).await;
+        assert!(app.state.reprompt_active());
+
+        // The grant is for the numbered result, not the selected item
+        app.verify_reprompt(fake_backend::PASSWORD.to_string());
+        settle(&mut app).await;
+        assert!(!app.state.reprompt_active());
+        assert_eq!(status_text(&app), "✗ Clipboard not available");
+    }
+
+    #[tokio::test]
+    async fn test_refresh_syncs_and_replaces_items() {
+        let backend = FakeBackend::new(VaultStatus::Unlocked, vec![fake_backend::login("1", "Mail", None)]);
+        let mut app = test_app();
+        start(&mut app, &backend);
+        settle(&mut app).await;
+
+        backend.set_items(vec![
+            fake_backend::login("1", "Mail", None),
+            fake_backend::login("2", "Bank", None),
+            fake_backend::login("3", "Shop", None),
+        ]);
+        app.refresh_vault();
+        settle(&mut app).await;
+
+        assert_eq!(backend.syncs(), 1);
+        assert_eq!(app.state.vault.filtered_len(), 3);
+        assert!(!app.state.syncing());
+        // The sync status popup tells what the sync changed
+        assert!(matches!(
+            &app.state.sync.last_success().unwrap().outcome,
+            SyncOutcome::Synced { items: 3, changes: Some(changes) } if changes.added == 2
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_lock_during_refresh_resumes_after_unlock() {
+        let backend = FakeBackend::new(VaultStatus::Unlocked, vec![fake_backend::login("1", "Mail", None)]);
+        let mut app = test_app();
+        start(&mut app, &backend);
+        settle(&mut app).await;
+
+        backend.lock();
+        app.refresh_vault();
+        settle(&mut app).await;
+        assert!(app.state.password_input_mode());
+        assert_eq!(backend.syncs(), 0);
+        // The loaded items stay visible behind the dialog
+        assert_eq!(app.state.vault.filtered_len(), 1);
+
+        app.unlock_with_password(fake_backend::PASSWORD.to_string());
+        settle(&mut app).await;
+        assert!(!app.state.password_input_mode());
+        assert_eq!(backend.syncs(), 1);
+        assert!(status_text(&app).contains("synced"));
+    }
+
+    #[tokio::test]
+    async fn test_totp_prefetched_once_the_selection_settles() {
+        let backend = FakeBackend::new(VaultStatus::Unlocked, vec![
+            fake_backend::login("1", "Bank", Some("0189!")),
+            fake_backend::login("2", "Mail", None),
+        ])
+        .with_totp("1", "123456");
+        let mut app = test_app();
+        start(&mut app, &backend);
+        settle(&mut app).await;
+        app.state.select_index(0);
+        let session_manager = crate::session::SessionManager::new().unwrap();
+
+        app.handle_action(Action::Tick, &session_manager).await;
+        assert!(!app.state.totp_loading());
+
+        // The code is fetched without opening the details
+        tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+        app.handle_action(Action::Tick, &session_manager).await;
+        assert!(app.state.totp_loading());
+        settle(&mut app).await;
+        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
+        let item = app.state.selected_item().unwrap();
+        assert_eq!(app.state.item_details.totp(item, now).map(|totp| totp.code.as_str()), Some("123456"));
+    }
+
+    #[tokio::test]
+    async fn test_totp_generated_locally_or_fetched() {
+        let secret = "JBSWY3DPEHPK3PXP";
+        let backend = FakeBackend::new(VaultStatus::Unlocked, vec![
+            fake_backend::login("1", "Bank", Some("0189!")),
+            fake_backend::login("2", "Mail", Some(secret)),
+        ])
+        .with_totp("1", "123456");
+        let mut app = test_app();
+        start(&mut app, &backend);
+        settle(&mut app).await;
+
+        // A secret bwtui can't use is left to the backend
+        app.state.select_index(0);
+        app.fetch_totp_code();
+        settle(&mut app).await;
+        assert_eq!(app.state.current_totp_code().map(String::as_str), Some("123456"));
+
+        // A valid secret is used directly
+        app.state.select_index(1);
+        app.fetch_totp_code();
+        let expected = totp_util::generate_totp(secret).unwrap().code;
+        assert_eq!(app.state.current_totp_code(), Some(&expected));
+    }
+
+    #[tokio::test]
+    async fn test_watched_totp_stays_while_browsing() {
+        let secret = "JBSWY3DPEHPK3PXP";
+        let backend = FakeBackend::new(VaultStatus::Unlocked, vec![
+            fake_backend::login("1", "Bank", Some("0189!")),
+            fake_backend::login("2", "Mail", Some(secret)),
+            fake_backend::login("3", "Shop", None),
+        ])
+        .with_totp("1", "123456");
+        let mut app = test_app();
+        start(&mut app, &backend);
+        settle(&mut app).await;
+        let session_manager = crate::session::SessionManager::new().unwrap();
+
+        // A code only the backend can generate is fetched
+        app.state.select_index(0);
+        app.handle_action(Action::ToggleTotpWatch, &session_manager).await;
+        app.state.select_index(2);
+        app.handle_action(Action::Tick, &session_manager).await;
+        settle(&mut app).await;
+        let watched = |app: &App| app.state.ui.totp_watch.as_ref().and_then(|watch| watch.code.as_ref()).map(|totp| totp.code.clone());
+        assert_eq!(watched(&app).as_deref(), Some("123456"));
+
+        // Watching another item replaces it, its code is generated locally
+        app.state.select_index(1);
+        app.handle_action(Action::ToggleTotpWatch, &session_manager).await;
+        app.state.select_index(2);
+        app.handle_action(Action::Tick, &session_manager).await;
+        assert_eq!(watched(&app), Some(totp_util::generate_totp(secret).unwrap().code));
+
+        // An item without a TOTP stops the watch
+        app.handle_action(Action::ToggleTotpWatch, &session_manager).await;
+        assert!(app.state.ui.totp_watch.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_single_result_opens_details() {
+        let backend = FakeBackend::new(VaultStatus::Unlocked, vec![
+            fake_backend::login("1", "Alpha", None),
+            fake_backend::login("2", "Beta", None),
+            fake_backend::login("3", "Gamma", None),
+        ]);
+        let mut app = test_app();
+        app.config.search.single_result = SingleResultAction::OpenDetails;
+        start(&mut app, &backend);
+        settle(&mut app).await;
+        let session_manager = crate::session::SessionManager::new().unwrap();
+
+        app.handle_action(Action::AppendFilter('h'), &session_manager).await;
+        assert_eq!(app.state.selected_item().unwrap().name, "Alpha");
+        assert!(app.state.details_panel_visible());
+
+        // Only narrowing down to the result acts on it, not typing on
+        app.state.toggle_details_panel();
+        app.handle_action(Action::AppendFilter('a'), &session_manager).await;
+        assert!(!app.state.details_panel_visible());
+    }
+
+    #[tokio::test]
+    async fn test_single_result_copy_asks_for_reprompt() {
+        let mut protected = fake_backend::login("2", "Bank", None);
+        protected.reprompt = Some(1);
+        let backend = FakeBackend::new(VaultStatus::Unlocked, vec![fake_backend::login("1", "Alpha", None), protected]);
+        let mut app = test_app();
+        app.config.search.single_result = SingleResultAction::CopyPassword;
+        start(&mut app, &backend);
+        settle(&mut app).await;
+        let session_manager = crate::session::SessionManager::new().unwrap();
+
+        app.handle_action(Action::AppendFilter('k'), &session_manager).await;
+        assert_eq!(app.state.selected_item().unwrap().name, "Bank");
+        assert!(app.state.reprompt_active());
+    }
+
+    #[tokio::test]
+    async fn test_picker_stays_open_when_copy_fails() {
+        let backend = FakeBackend::new(VaultStatus::Unlocked, vec![fake_backend::login("1", "Alpha", None)]);
+        let mut app = test_app();
+        app.state.ui.pick = Some(crate::args::PickField::Password);
+        start(&mut app, &backend);
+        settle(&mut app).await;
+        let session_manager = crate::session::SessionManager::new().unwrap();
+
+        // Without a clipboard there is nothing to exit with
+        assert!(app.handle_action(Action::Pick, &session_manager).await);
+        assert!(!app.picked());
+        assert_eq!(status_text(&app), "✗ Clipboard not available");
+    }
+
+    #[tokio::test]
+    async fn test_not_logged_in() {
+        let backend = FakeBackend::new(VaultStatus::Unauthenticated, Vec::new());
+        let mut app = test_app();
+        start(&mut app, &backend);
+        settle(&mut app).await;
+
+        assert!(app.state.show_not_logged_in_error());
+        assert!(!app.state.syncing());
+    }
+}
@@ -0,0 +1,234 @@
+use crate::types::ItemType;
+use clap::{Parser, Subcommand, ValueEnum};
+
+/// Terminal UI for the Bitwarden CLI
+#[derive(Debug, Default, Parser)]
+#[command(version, about)]
+pub struct Args {
+    /// Use this session token instead of BW_SESSION or the stored one
+    #[arg(long, value_name = "TOKEN")]
+    pub session: Option<String>,
+
+    /// Never read or write the stored session token
+    #[arg(long)]
+    pub no_keyring: bool,
+
+    /// Start with this search text
+    #[arg(long, value_name = "TEXT")]
+    pub query: Option<String>,
+
+    /// Start on this tab
+    #[arg(long = "type", value_enum, value_name = "TYPE")]
+    pub item_type: Option<TabArg>,
+
+    /// Only list the items of this folder
+    #[arg(long, value_name = "NAME")]
+    pub folder: Option<String>,
+
+    /// Don't read or write the vault cache
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// Disable actions that change the vault
+    #[arg(long)]
+    pub readonly: bool,
+
+    /// Keep everything in memory: no vault cache, saved session or log file
+    #[arg(long, conflicts_with = "rotate_keys")]
+    pub ephemeral: bool,
+
+    /// Path to the bw executable
+    #[arg(long, value_name = "PATH")]
+    pub bw_path: Option<String>,
+
+    /// Data directory for bw, passed as BITWARDENCLI_APPDATA_DIR
+    #[arg(long, value_name = "DIR")]
+    pub bw_appdata_dir: Option<String>,
+
+    /// Use the configuration, cache and saved session of this profile
+    #[arg(long, value_name = "NAME", value_parser = crate::profile::parse_name)]
+    pub profile: Option<String>,
+
+    /// Borderless layout without the tab bar, for small popups such as tmux display-popup
+    #[arg(long)]
+    pub compact: bool,
+
+    /// Re-encrypt the saved session with a fresh key, rewrite the (unencrypted) vault cache, wipe the old files and exit
+    #[arg(long)]
+    pub rotate_keys: bool,
+
+    /// Check the bw CLI, keyring, clipboard, cache, session and terminal, print a report and exit
+    #[arg(long)]
+    pub doctor: bool,
+
+    /// Show a synthetic vault of this many items instead of the real one
+    #[cfg(feature = "demo")]
+    #[arg(long, value_name = "COUNT")]
+    pub demo: Option<usize>,
+
+    /// Time filtering and rendering the --demo vault without a terminal, print the results and exit
+    #[cfg(feature = "demo")]
+    #[arg(long, requires = "demo")]
+    pub bench: bool,
+
+    /// Write a performance trace of CLI calls, cache access, filtering and rendering to this file
+    #[arg(long, value_name = "FILE")]
+    pub trace: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Modes other than the full interface
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Subcommand)]
+pub enum Command {
+    /// Minimal picker that copies a field of the chosen item and exits, for a hotkey
+    Pick {
+        /// Field to copy
+        #[arg(long, value_enum, default_value_t = PickField::Password)]
+        field: PickField,
+    },
+}
+
+/// Fields that `pick` can copy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PickField {
+    Username,
+    Password,
+    Totp,
+}
+
+/// Tabs that can be selected with --type, or by a saved view in the config
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TabArg {
+    All,
+    Login,
+    Note,
+    Card,
+    Identity,
+}
+
+impl Args {
+    /// Number of synthetic items when running with --demo
+    #[cfg(feature = "demo")]
+    pub fn demo_items(&self) -> Option<usize> {
+        self.demo
+    }
+
+    /// Number of synthetic items when running with --demo
+    #[cfg(not(feature = "demo"))]
+    pub fn demo_items(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl Args {
+    /// The field to copy when running as a picker
+    pub fn pick_field(&self) -> Option<PickField> {
+        self.command.map(|Command::Pick { field }| field)
+    }
+}
+
+impl PickField {
+    /// The copy action for the field
+    pub fn copy_action(self) -> crate::events::Action {
+        match self {
+            PickField::Username => crate::events::Action::CopyUsername,
+            PickField::Password => crate::events::Action::CopyPassword,
+            PickField::Totp => crate::events::Action::CopyTotp,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PickField::Username => "username",
+            PickField::Password => "password",
+            PickField::Totp => "TOTP code",
+        }
+    }
+}
+
+impl TabArg {
+    /// The item type filter of the tab
+    pub fn item_type(self) -> Option<ItemType> {
+        match self {
+            TabArg::All => None,
+            TabArg::Login => Some(ItemType::Login),
+            TabArg::Note => Some(ItemType::SecureNote),
+            TabArg::Card => Some(ItemType::Card),
+            TabArg::Identity => Some(ItemType::Identity),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, clap::Error> {
+        Args::try_parse_from(std::iter::once("bwtui").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn test_parse_session_flags() {
+        let args = parse(&["--session", "abc", "--no-keyring"]).unwrap();
+        assert_eq!(args.session.as_deref(), Some("abc"));
+        assert!(args.no_keyring);
+
+        let args = parse(&["--session=xyz"]).unwrap();
+        assert_eq!(args.session.as_deref(), Some("xyz"));
+    }
+
+    #[test]
+    fn test_parse_startup_flags() {
+        let args = parse(&["--query", "git", "--type", "card", "--folder", "Work", "--no-cache", "--readonly", "--profile", "work"]).unwrap();
+        assert_eq!(args.query.as_deref(), Some("git"));
+        assert_eq!(args.item_type.and_then(TabArg::item_type), Some(ItemType::Card));
+        assert_eq!(args.folder.as_deref(), Some("Work"));
+        assert!(args.no_cache);
+        assert!(args.readonly);
+        assert_eq!(args.profile.as_deref(), Some("work"));
+
+        let args = parse(&[]).unwrap();
+        assert!(args.item_type.is_none() && !args.no_cache && !args.readonly && !args.rotate_keys);
+        assert!(parse(&["--rotate-keys", "--profile", "work"]).unwrap().rotate_keys);
+        assert!(parse(&["--doctor"]).unwrap().doctor);
+        assert!(parse(&["--ephemeral"]).unwrap().ephemeral);
+        assert!(parse(&["--ephemeral", "--rotate-keys"]).is_err());
+        assert!(parse(&["--compact"]).unwrap().compact);
+        assert_eq!(parse(&["--trace", "trace.json"]).unwrap().trace.as_deref(), Some("trace.json"));
+
+        let args = parse(&["--bw-path", "/snap/bin/bw", "--bw-appdata-dir", "/tmp/bw"]).unwrap();
+        assert_eq!(args.bw_path.as_deref(), Some("/snap/bin/bw"));
+        assert_eq!(args.bw_appdata_dir.as_deref(), Some("/tmp/bw"));
+    }
+
+    #[cfg(feature = "demo")]
+    #[test]
+    fn test_parse_demo_flags() {
+        let args = parse(&["--demo", "5000", "--bench"]).unwrap();
+        assert_eq!(args.demo_items(), Some(5000));
+        assert!(args.bench);
+        assert!(parse(&["--bench"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_pick() {
+        assert_eq!(parse(&[]).unwrap().pick_field(), None);
+        assert_eq!(parse(&["pick"]).unwrap().pick_field(), Some(PickField::Password));
+
+        let args = parse(&["--profile", "work", "pick", "--field", "totp"]).unwrap();
+        assert_eq!(args.pick_field(), Some(PickField::Totp));
+        assert_eq!(args.profile.as_deref(), Some("work"));
+        assert!(parse(&["pick", "--field", "notes"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse(&["--session"]).is_err());
+        assert!(parse(&["--unknown"]).is_err());
+        assert!(parse(&["--type", "ssh"]).is_err());
+        assert!(parse(&["--profile", "../other"]).is_err());
+    }
+}
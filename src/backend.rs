@@ -0,0 +1,117 @@
+#[cfg(feature = "api-backend")]
+use crate::api::ApiBackend;
+use crate::cli::BitwardenCli;
+use crate::config::{BackendKind, Config};
+use crate::error::Result;
+#[cfg(not(feature = "api-backend"))]
+use crate::error::BwError;
+use crate::retry::RetryListener;
+use crate::session::SessionOptions;
+use crate::types::{Collection, CollectionAccess, EquivalentDomains, Folder, Organization, VaultItem};
+use futures_util::future::BoxFuture;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VaultStatus {
+    Locked,
+    Unlocked,
+    Unauthenticated,
+}
+
+/// Callback receiving vault items as they are parsed
+pub type ItemBatchFn = dyn Fn(Vec<VaultItem>) + Send + Sync;
+
+/// Shared handle to the backend in use
+pub type Backend = Arc<dyn VaultBackend>;
+
+/// Result of unlocking the vault
+pub struct Unlocked {
+    /// Token to save for later runs, if the backend has one
+    pub session_token: Option<String>,
+    /// The backend to use from now on
+    pub backend: Backend,
+}
+
+/// Where vault data comes from: the bw CLI or the server API
+pub trait VaultBackend: Send + Sync {
+    /// Check whether the vault is unlocked
+    fn check_status(&self) -> BoxFuture<'_, Result<VaultStatus>>;
+
+    /// Unlock the vault with the master password
+    fn unlock<'a>(&'a self, password: &'a str) -> BoxFuture<'a, Result<Unlocked>>;
+
+    /// Fetch the latest vault data from the server
+    fn sync(&self) -> BoxFuture<'_, Result<()>>;
+
+    /// List all vault items, passing them to `on_batch` as they are parsed, and return the count
+    fn list_items<'a>(&'a self, on_batch: &'a ItemBatchFn) -> BoxFuture<'a, Result<usize>>;
+
+    /// List the organizations the user is a member of
+    fn list_organizations(&self) -> BoxFuture<'_, Result<Vec<Organization>>>;
+
+    /// List the folders items can be filed in
+    fn list_folders(&self) -> BoxFuture<'_, Result<Vec<Folder>>>;
+
+    /// List the collections of the organizations the user is a member of
+    fn list_collections(&self) -> BoxFuture<'_, Result<Vec<Collection>>>;
+
+    /// Who can access an organization collection, which only its managers may see
+    fn collection_access<'a>(&'a self, organization_id: &'a str, collection_id: &'a str) -> BoxFuture<'a, Result<CollectionAccess>>;
+
+    /// Groups of domains the account treats as the same site, the user's own and the global ones
+    fn equivalent_domains(&self) -> BoxFuture<'_, Result<EquivalentDomains>>;
+
+    /// Get the current TOTP code of an item
+    fn get_totp<'a>(&'a self, item_id: &'a str) -> BoxFuture<'a, Result<String>>;
+
+    /// Move an item to the trash
+    fn delete_item<'a>(&'a self, item_id: &'a str) -> BoxFuture<'a, Result<()>>;
+
+    /// Move a personal item into an organization, in the given collections of it
+    fn share_item<'a>(&'a self, item_id: &'a str, organization_id: &'a str, collection_ids: &'a [String]) -> BoxFuture<'a, Result<()>>;
+
+    /// Turn the master password re-prompt of an item on or off
+    fn set_reprompt<'a>(&'a self, item_id: &'a str, enabled: bool) -> BoxFuture<'a, Result<()>>;
+
+    /// Move an item into a folder, or out of any folder with None
+    fn move_item<'a>(&'a self, item_id: &'a str, folder_id: Option<&'a str>) -> BoxFuture<'a, Result<()>>;
+
+    /// Create a folder, returning it with its new id
+    fn create_folder<'a>(&'a self, name: &'a str) -> BoxFuture<'a, Result<Folder>>;
+
+    /// Rename a folder
+    fn rename_folder<'a>(&'a self, folder_id: &'a str, name: &'a str) -> BoxFuture<'a, Result<()>>;
+
+    /// Delete a folder, moving its items out of it
+    fn delete_folder<'a>(&'a self, folder_id: &'a str) -> BoxFuture<'a, Result<()>>;
+
+    /// A handle to the same backend that reports retries to the given listener
+    fn with_retry_listener(&self, listener: RetryListener) -> Backend;
+
+    /// Whether the backend started with a session token, so a locked vault means it expired
+    fn has_session_token(&self) -> bool {
+        false
+    }
+
+    /// A warning about the version of the backend's tooling, if bwtui may not work well with it
+    fn version_warning(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Why `type = "api"` can't be used by this build
+#[cfg(not(feature = "api-backend"))]
+pub const API_BACKEND_MISSING: &str = "The api backend is not part of this build of bwtui, rebuild it with --features api-backend";
+
+/// Create the backend selected in the configuration
+pub async fn connect(config: &Config, session: &SessionOptions) -> Result<Backend> {
+    match config.backend.kind {
+        BackendKind::Cli => Ok(Arc::new(BitwardenCli::new(&config.cli, session).await?)),
+        #[cfg(feature = "api-backend")]
+        BackendKind::Api => Ok(Arc::new(ApiBackend::new(&config.backend, config.cli.timeout())?)),
+        #[cfg(not(feature = "api-backend"))]
+        BackendKind::Api => Err(BwError::Unsupported(API_BACKEND_MISSING.to_string())),
+        #[cfg(feature = "demo")]
+        BackendKind::Demo => Ok(Arc::new(crate::mock_data::DemoBackend::new(config.backend.demo_items))),
+    }
+}
@@ -1,8 +1,9 @@
 use crate::error::{BwError, Result};
-use crate::types::VaultItem;
+use crate::types::{Folder, Organization, VaultItem};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
 /// Cache data structure - stores only non-sensitive metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,6 +12,10 @@ pub struct CachedVaultData {
     pub cached_at: chrono::DateTime<chrono::Utc>,
     /// Cached items (without passwords, TOTP secrets, and notes)
     pub items: Vec<CachedVaultItem>,
+    /// Organizations the items may belong to (names only, not sensitive)
+    pub organizations: Vec<Organization>,
+    /// Folders the items are filed in (names only, not sensitive)
+    pub folders: Vec<Folder>,
 }
 
 /// Cached vault item without sensitive data
@@ -29,12 +34,15 @@ pub struct CachedVaultItem {
     pub card: Option<CachedCardData>,
     /// Identity data (not sensitive, all can be cached)
     pub identity: Option<CachedIdentityData>,
+    /// Whether the master password must be re-entered to use the item's secrets
+    pub reprompt: Option<u8>,
 }
 
-/// Simplified URI for caching (without match_type which contains serde_json::Value)
+/// URI for caching
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CachedUri {
     pub uri: String,
+    pub match_type: Option<crate::types::UriMatchType>,
 }
 
 /// Login data without sensitive fields
@@ -46,6 +54,7 @@ pub struct CachedLoginData {
     pub has_password: bool,
     /// Indicates that a TOTP secret exists (but don't store the secret itself)
     pub has_totp: bool,
+    pub password_revision_date: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 /// Card data without sensitive fields
@@ -91,7 +100,7 @@ impl CachedVaultData {
             .map(|item| CachedVaultItem {
                 id: item.id.clone(),
                 name: item.name.clone(),
-                item_type: item.item_type.clone(),
+                item_type: item.item_type,
                 favorite: item.favorite,
                 folder_id: item.folder_id.clone(),
                 organization_id: item.organization_id.clone(),
@@ -101,10 +110,12 @@ impl CachedVaultData {
                     uris: login.uris.as_ref().map(|uris| {
                         uris.iter().map(|uri| CachedUri {
                             uri: uri.uri.clone(),
+                            match_type: uri.match_type,
                         }).collect()
                     }),
                     has_password: login.password.is_some(),
                     has_totp: login.totp.is_some(),
+                    password_revision_date: login.password_revision_date,
                 }),
                 card: item.card.as_ref().map(|card| CachedCardData {
                     brand: card.brand.clone(),
@@ -133,15 +144,30 @@ impl CachedVaultData {
                     passport_number: identity.passport_number.clone(),
                     username: identity.username.clone(),
                 }),
+                reprompt: item.reprompt,
             })
             .collect();
 
         Self {
             cached_at: chrono::Utc::now(),
             items: cached_items,
+            organizations: Vec::new(),
+            folders: Vec::new(),
         }
     }
 
+    /// Also cache the organizations the items belong to
+    pub fn with_organizations(mut self, organizations: &[Organization]) -> Self {
+        self.organizations = organizations.to_vec();
+        self
+    }
+
+    /// Also cache the folders the items are filed in
+    pub fn with_folders(mut self, folders: &[Folder]) -> Self {
+        self.folders = folders.to_vec();
+        self
+    }
+
     /// Convert cached items to VaultItems (with placeholders for secrets)
     pub fn to_vault_items(&self) -> Vec<VaultItem> {
         self.items
@@ -149,7 +175,7 @@ impl CachedVaultData {
             .map(|cached| VaultItem {
                 id: cached.id.clone(),
                 name: cached.name.clone(),
-                item_type: cached.item_type.clone(),
+                item_type: cached.item_type,
                 favorite: cached.favorite,
                 folder_id: cached.folder_id.clone(),
                 organization_id: cached.organization_id.clone(),
@@ -161,10 +187,10 @@ impl CachedVaultData {
                     uris: login.uris.as_ref().map(|uris| {
                         uris.iter().map(|cached_uri| crate::types::Uri {
                             uri: cached_uri.uri.clone(),
-                            match_type: None, // Don't store match_type in cache
+                            match_type: cached_uri.match_type,
                         }).collect()
                     }),
-                    password_revision_date: None,
+                    password_revision_date: login.password_revision_date,
                 }),
                 card: cached.card.as_ref().map(|card| crate::types::CardData {
                     brand: card.brand.clone(),
@@ -201,18 +227,31 @@ impl CachedVaultData {
                 password_history: None,
                 attachments: None,
                 collection_ids: None,
-                reprompt: None,
+                reprompt: cached.reprompt,
+                raw_fields: Default::default(),
             })
             .collect()
     }
 }
 
+/// How long ago the cache was written, such as "3 days ago"
+pub fn describe_age(cached_at: chrono::DateTime<chrono::Utc>, now: chrono::DateTime<chrono::Utc>) -> String {
+    let age = now - cached_at;
+    let (count, unit) = if age.num_days() > 0 {
+        (age.num_days(), "day")
+    } else if age.num_hours() > 0 {
+        (age.num_hours(), "hour")
+    } else if age.num_minutes() > 0 {
+        (age.num_minutes(), "minute")
+    } else {
+        return "just now".to_string();
+    };
+    format!("{} {}{} ago", count, unit, if count == 1 { "" } else { "s" })
+}
+
 /// Get the cache file path
 fn get_cache_path() -> Result<PathBuf> {
-    let home_dir = dirs::home_dir()
-        .ok_or_else(|| BwError::CommandFailed("Could not determine home directory".to_string()))?;
-
-    let cache_dir = home_dir.join(".bwtui");
+    let cache_dir = crate::profile::data_dir()?;
     
     // Create directory if it doesn't exist
     if !cache_dir.exists() {
@@ -224,75 +263,339 @@ fn get_cache_path() -> Result<PathBuf> {
     Ok(cache_dir.join("vault_cache.bin"))
 }
 
+/// Marks cache files that start with a format version
+const CACHE_MAGIC: &[u8; 4] = b"BWTC";
+
+/// Format version written by this build, bump it and add a migration when the layout changes
+/// Version 1 is the unversioned format written before the header existed
+const CACHE_VERSION: u32 = 3;
+
+/// Prefix the serialized cache with the magic bytes and format version
+fn encode(data: &CachedVaultData) -> std::result::Result<Vec<u8>, bincode::Error> {
+    let mut encoded = CACHE_MAGIC.to_vec();
+    encoded.extend_from_slice(&CACHE_VERSION.to_le_bytes());
+    encoded.extend(bincode::serialize(data)?);
+    Ok(encoded)
+}
+
+/// Decode a cache file of any known version, returning the data and the version it was written in
+fn decode(bytes: &[u8]) -> std::result::Result<(CachedVaultData, u32), String> {
+    let (version, payload) = match bytes.strip_prefix(CACHE_MAGIC.as_slice()) {
+        Some(rest) if rest.len() >= 4 => {
+            let (version, payload) = rest.split_at(4);
+            (u32::from_le_bytes(version.try_into().unwrap_or_default()), payload)
+        }
+        Some(_) => return Err("truncated header".to_string()),
+        None => (1, bytes),
+    };
+    migrate(version, payload).map(|data| (data, version))
+}
+
+/// Read a payload written in an older format version into the current layout
+fn migrate(version: u32, payload: &[u8]) -> std::result::Result<CachedVaultData, String> {
+    match version {
+        1 => bincode::deserialize::<v1::CachedVaultData>(payload)
+            .map(|old| CachedVaultData::from(v2::CachedVaultData::from(old)))
+            .map_err(|e| e.to_string()),
+        2 => bincode::deserialize::<v2::CachedVaultData>(payload).map(CachedVaultData::from).map_err(|e| e.to_string()),
+        CACHE_VERSION => bincode::deserialize(payload).map_err(|e| e.to_string()),
+        version => Err(format!("unsupported cache version {}", version)),
+    }
+}
+
+/// The payload layout of version 1, frozen as it was before the header existed
+/// Bincode reads fields by position, so fields added since can't be defaulted and need this copy
+mod v1 {
+    use super::{CachedCardData, CachedIdentityData};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub struct CachedVaultData {
+        pub cached_at: chrono::DateTime<chrono::Utc>,
+        pub items: Vec<CachedVaultItem>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct CachedVaultItem {
+        pub id: String,
+        pub name: String,
+        pub item_type: crate::types::ItemType,
+        pub favorite: bool,
+        pub folder_id: Option<String>,
+        pub organization_id: Option<String>,
+        pub revision_date: chrono::DateTime<chrono::Utc>,
+        pub login: Option<CachedLoginData>,
+        // Card and identity data are unchanged since version 1, freeze a copy here before changing them
+        pub card: Option<CachedCardData>,
+        pub identity: Option<CachedIdentityData>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct CachedUri {
+        pub uri: String,
+    }
+
+    #[derive(Deserialize)]
+    pub struct CachedLoginData {
+        pub username: Option<String>,
+        pub uris: Option<Vec<CachedUri>>,
+        pub has_password: bool,
+        pub has_totp: bool,
+    }
+}
+
+/// Organizations, folders, URI match types and password dates weren't cached yet, they are filled by the next sync
+impl From<v1::CachedVaultData> for v2::CachedVaultData {
+    fn from(old: v1::CachedVaultData) -> Self {
+        let items = old.items
+            .into_iter()
+            .map(|item| v2::CachedVaultItem {
+                id: item.id,
+                name: item.name,
+                item_type: item.item_type,
+                favorite: item.favorite,
+                folder_id: item.folder_id,
+                organization_id: item.organization_id,
+                revision_date: item.revision_date,
+                login: item.login.map(|login| CachedLoginData {
+                    username: login.username,
+                    uris: login.uris.map(|uris| {
+                        uris.into_iter().map(|uri| CachedUri { uri: uri.uri, match_type: None }).collect()
+                    }),
+                    has_password: login.has_password,
+                    has_totp: login.has_totp,
+                    password_revision_date: None,
+                }),
+                card: item.card,
+                identity: item.identity,
+            })
+            .collect();
+
+        Self {
+            cached_at: old.cached_at,
+            items,
+            organizations: Vec::new(),
+            folders: Vec::new(),
+        }
+    }
+}
+
+/// The payload layout of version 2, before the re-prompt setting was cached
+mod v2 {
+    use super::{CachedCardData, CachedIdentityData, CachedLoginData};
+    use crate::types::{Folder, Organization};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub struct CachedVaultData {
+        pub cached_at: chrono::DateTime<chrono::Utc>,
+        pub items: Vec<CachedVaultItem>,
+        pub organizations: Vec<Organization>,
+        pub folders: Vec<Folder>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct CachedVaultItem {
+        pub id: String,
+        pub name: String,
+        pub item_type: crate::types::ItemType,
+        pub favorite: bool,
+        pub folder_id: Option<String>,
+        pub organization_id: Option<String>,
+        pub revision_date: chrono::DateTime<chrono::Utc>,
+        // Login, card and identity data are unchanged since version 2, freeze a copy here before changing them
+        pub login: Option<CachedLoginData>,
+        pub card: Option<CachedCardData>,
+        pub identity: Option<CachedIdentityData>,
+    }
+}
+
+/// The re-prompt setting wasn't cached yet, it is filled by the next sync
+impl From<v2::CachedVaultData> for CachedVaultData {
+    fn from(old: v2::CachedVaultData) -> Self {
+        let items = old.items
+            .into_iter()
+            .map(|item| CachedVaultItem {
+                id: item.id,
+                name: item.name,
+                item_type: item.item_type,
+                favorite: item.favorite,
+                folder_id: item.folder_id,
+                organization_id: item.organization_id,
+                revision_date: item.revision_date,
+                login: item.login,
+                card: item.card,
+                identity: item.identity,
+                reprompt: None,
+            })
+            .collect();
+
+        Self {
+            cached_at: old.cached_at,
+            items,
+            organizations: old.organizations,
+            folders: old.folders,
+        }
+    }
+}
+
+/// The previous generation of a cache file, kept as a fallback
+fn backup_path(cache_path: &Path) -> PathBuf {
+    cache_path.with_extension("bin.bak")
+}
+
+/// The file a new cache is written to before it replaces the current one
+fn temp_path(cache_path: &Path) -> PathBuf {
+    cache_path.with_extension("bin.tmp")
+}
+
 /// Load cache from disk
+#[tracing::instrument]
 pub fn load_cache() -> Result<Option<CachedVaultData>> {
-    let cache_path = get_cache_path()?;
-
-    if !cache_path.exists() {
-        crate::logger::Logger::info("No cache file found");
+    if crate::profile::ephemeral() {
         return Ok(None);
     }
+    Ok(load_from(&get_cache_path()?))
+}
 
-    let data = fs::read(&cache_path).map_err(|e| {
-        let error_msg = format!("Failed to read cache file: {}", e);
-        crate::logger::Logger::error(&error_msg);
-        BwError::CommandFailed(error_msg)
-    })?;
+/// Read a cache file, None if it is missing or can't be decoded
+fn read_cache_file(path: &Path) -> Option<CachedVaultData> {
+    let data = match fs::read(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+        Err(e) => {
+            crate::logger::Logger::error(&format!("Failed to read cache file {}: {}", path.display(), e));
+            return None;
+        }
+    };
 
-    match bincode::deserialize::<CachedVaultData>(&data) {
-        Ok(cached_data) => {
-            crate::logger::Logger::info(&format!("Successfully loaded cache with {} items", cached_data.items.len()));
-            Ok(Some(cached_data))
+    match decode(&data) {
+        Ok((cached_data, version)) => {
+            if version < CACHE_VERSION {
+                crate::logger::Logger::info(&format!("Upgrading cache from format version {} to {}", version, CACHE_VERSION));
+                if let Err(e) = save_to(path, &cached_data) {
+                    crate::logger::Logger::warn(&format!("Failed to upgrade cache file: {}", e));
+                }
+            }
+            Some(cached_data)
         }
         Err(e) => {
-            // If deserialization fails, delete the corrupted cache and return None
-            // This handles format changes or corrupted files gracefully
-            let error_msg = format!("Cache file corrupted or incompatible format: {}", e);
-            crate::logger::Logger::warn(&error_msg);
-            if let Err(remove_err) = fs::remove_file(&cache_path) {
-                crate::logger::Logger::error(&format!("Failed to remove corrupted cache file: {}", remove_err));
-            } else {
-                crate::logger::Logger::info("Corrupted cache file removed");
+            // Format changes and corrupted files are handled by falling back or starting over
+            crate::logger::Logger::warn(&format!("Cache file {} corrupted or incompatible format: {}", path.display(), e));
+            None
+        }
+    }
+}
+
+/// Load the cache, falling back to the backup if the current file is missing or corrupted
+fn load_from(cache_path: &Path) -> Option<CachedVaultData> {
+    if let Some(cached_data) = read_cache_file(cache_path) {
+        crate::logger::Logger::info(&format!("Successfully loaded cache with {} items", cached_data.items.len()));
+        return Some(cached_data);
+    }
+
+    let backup = backup_path(cache_path);
+    if let Some(cached_data) = read_cache_file(&backup) {
+        // Put the backup back in place so the next start doesn't need it
+        crate::logger::Logger::warn(&format!("Recovered cache with {} items from backup", cached_data.items.len()));
+        if let Err(e) = fs::copy(&backup, cache_path) {
+            crate::logger::Logger::error(&format!("Failed to restore cache from backup: {}", e));
+        }
+        return Some(cached_data);
+    }
+
+    // Neither file is usable, remove whatever is left
+    for path in [cache_path, backup.as_path()] {
+        if path.exists() {
+            match fs::remove_file(path) {
+                Ok(()) => crate::logger::Logger::info(&format!("Unusable cache file {} removed", path.display())),
+                Err(e) => crate::logger::Logger::error(&format!("Failed to remove unusable cache file: {}", e)),
             }
-            Ok(None)
         }
     }
+    if !cache_path.exists() {
+        crate::logger::Logger::info("No cache file found");
+    }
+    None
 }
 
 /// Save cache to disk
+#[tracing::instrument(skip_all, fields(items = data.items.len()))]
 pub fn save_cache(data: &CachedVaultData) -> Result<()> {
-    let cache_path = get_cache_path()?;
+    if crate::profile::ephemeral() {
+        return Ok(());
+    }
+    save_to(&get_cache_path()?, data)
+}
 
-    let encoded = bincode::serialize(data).map_err(|e| {
+/// Write the cache to a temporary file and move it into place, keeping the previous
+/// cache as a backup, so a crash mid-write never leaves a partial cache behind
+fn save_to(cache_path: &Path, data: &CachedVaultData) -> Result<()> {
+    let encoded = encode(data).map_err(|e| {
         let error_msg = format!("Failed to serialize cache: {}", e);
         crate::logger::Logger::error(&error_msg);
         BwError::CommandFailed(error_msg)
     })?;
 
-    fs::write(&cache_path, encoded).map_err(|e| {
+    let write_error = |e: std::io::Error| {
         let error_msg = format!("Failed to write cache file: {}", e);
         crate::logger::Logger::error(&error_msg);
         BwError::CommandFailed(error_msg)
-    })?;
+    };
+
+    let temp = temp_path(cache_path);
+    let mut file = fs::File::create(&temp).map_err(write_error)?;
+    file.write_all(&encoded).and_then(|_| file.sync_all()).map_err(write_error)?;
+    drop(file);
+
+    if cache_path.exists() {
+        fs::rename(cache_path, backup_path(cache_path)).map_err(write_error)?;
+    }
+    fs::rename(&temp, cache_path).map_err(write_error)?;
 
     Ok(())
 }
 
-/// Clear the cache file
+/// Write the cache again and wipe the old file and its backup, returning false when there is no cache
+/// The cache is not encrypted, it holds no secrets, so the new file has the same contents
+pub fn rewrite_cache() -> Result<bool> {
+    let Some(data) = load_cache()? else {
+        return Ok(false);
+    };
+
+    let cache_path = get_cache_path()?;
+    for path in [cache_path.clone(), backup_path(&cache_path)] {
+        if path.exists() {
+            crate::maintenance::wipe_file(&path).map_err(|e| crate::maintenance::wipe_error("cache file", e))?;
+        }
+    }
+    save_cache(&data)?;
+    crate::logger::Logger::info("Cache file rewritten");
+    Ok(true)
+}
+
+/// Clear the cache file, its backup and any unfinished write
 pub fn clear_cache() -> Result<()> {
+    if crate::profile::ephemeral() {
+        return Ok(());
+    }
     let cache_path = get_cache_path()?;
-    
-    if cache_path.exists() {
-        fs::remove_file(&cache_path).map_err(|e| {
+    let paths = [backup_path(&cache_path), temp_path(&cache_path), cache_path];
+
+    if !paths.iter().any(|path| path.exists()) {
+        crate::logger::Logger::info("No cache file to clear");
+        return Ok(());
+    }
+
+    for path in paths.iter().filter(|path| path.exists()) {
+        fs::remove_file(path).map_err(|e| {
             let error_msg = format!("Failed to remove cache file: {}", e);
             crate::logger::Logger::error(&error_msg);
             BwError::CommandFailed(error_msg)
         })?;
-        crate::logger::Logger::info("Cache file cleared");
-    } else {
-        crate::logger::Logger::info("No cache file to clear");
     }
-    
+    crate::logger::Logger::info("Cache file cleared");
+
     Ok(())
 }
 
@@ -331,9 +634,184 @@ mod tests {
             attachments: None,
             collection_ids: None,
             reprompt: None,
+            raw_fields: Default::default(),
         }
     }
 
+    fn temp_cache_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("bwtui-cache-test-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("vault_cache.bin")
+    }
+
+    #[test]
+    fn test_save_keeps_previous_generation_as_backup() {
+        let path = temp_cache_path("backup");
+        let first = CachedVaultData::from_vault_items(&[create_test_item_with_secrets("1", "First", "a", "p")]);
+        let second = CachedVaultData::from_vault_items(&[create_test_item_with_secrets("2", "Second", "b", "p")]);
+
+        save_to(&path, &first).unwrap();
+        save_to(&path, &second).unwrap();
+        assert!(!temp_path(&path).exists());
+        assert_eq!(load_from(&path).unwrap().items[0].name, "Second");
+        assert_eq!(read_cache_file(&backup_path(&path)).unwrap().items[0].name, "First");
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_load_falls_back_to_backup_when_corrupted() {
+        let path = temp_cache_path("corrupt");
+        let data = CachedVaultData::from_vault_items(&[create_test_item_with_secrets("1", "Kept", "a", "p")]);
+        save_to(&path, &data).unwrap();
+        save_to(&path, &data).unwrap();
+
+        fs::write(&path, b"garbage").unwrap();
+        assert_eq!(load_from(&path).unwrap().items[0].name, "Kept");
+        // The backup was restored in place of the corrupted file
+        assert!(read_cache_file(&path).is_some());
+
+        // Nothing usable left: both files are removed
+        fs::write(&path, b"garbage").unwrap();
+        fs::write(backup_path(&path), b"garbage").unwrap();
+        assert!(load_from(&path).is_none());
+        assert!(!path.exists() && !backup_path(&path).exists());
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    /// Append a string as bincode writes it: a u64 length, then the bytes
+    fn push_str(bytes: &mut Vec<u8>, value: &str) {
+        bytes.extend_from_slice(&(value.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(value.as_bytes());
+    }
+
+    /// A cache file as written before the header existed, with a single login item
+    fn version_1_cache(name: &str) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        push_str(&mut bytes, "2024-01-01T00:00:00Z"); // cached_at
+        bytes.extend_from_slice(&1u64.to_le_bytes()); // items
+        push_str(&mut bytes, "1"); // id
+        push_str(&mut bytes, name);
+        bytes.push(1); // item_type: Login
+        bytes.push(1); // favorite
+        bytes.push(1); // folder_id: Some
+        push_str(&mut bytes, "f");
+        bytes.push(0); // organization_id: None
+        push_str(&mut bytes, "2023-06-01T12:00:00Z"); // revision_date
+        bytes.push(1); // login: Some
+        bytes.push(1); // username: Some
+        push_str(&mut bytes, "user@example.com");
+        bytes.push(1); // uris: Some, each only a uri
+        bytes.extend_from_slice(&1u64.to_le_bytes());
+        push_str(&mut bytes, "https://example.com");
+        bytes.push(1); // has_password
+        bytes.push(0); // has_totp
+        bytes.push(0); // card: None
+        bytes.push(0); // identity: None
+        bytes
+    }
+
+    /// A version 2 cache file, before the re-prompt setting was cached, with a single login item
+    fn version_2_cache(name: &str) -> Vec<u8> {
+        let mut bytes = CACHE_MAGIC.to_vec();
+        bytes.extend_from_slice(&2u32.to_le_bytes());
+        push_str(&mut bytes, "2024-01-01T00:00:00Z"); // cached_at
+        bytes.extend_from_slice(&1u64.to_le_bytes()); // items
+        push_str(&mut bytes, "1"); // id
+        push_str(&mut bytes, name);
+        bytes.push(1); // item_type: Login
+        bytes.push(0); // favorite
+        bytes.push(0); // folder_id: None
+        bytes.push(0); // organization_id: None
+        push_str(&mut bytes, "2023-06-01T12:00:00Z"); // revision_date
+        bytes.push(1); // login: Some
+        bytes.push(0); // username: None
+        bytes.push(1); // uris: Some
+        bytes.extend_from_slice(&1u64.to_le_bytes());
+        push_str(&mut bytes, "https://example.com");
+        bytes.push(0); // match_type: None
+        bytes.push(1); // has_password
+        bytes.push(1); // has_totp
+        bytes.push(1); // password_revision_date: Some
+        push_str(&mut bytes, "2023-05-01T00:00:00Z");
+        bytes.push(0); // card: None
+        bytes.push(0); // identity: None
+        bytes.extend_from_slice(&1u64.to_le_bytes()); // organizations
+        push_str(&mut bytes, "o-1");
+        push_str(&mut bytes, "Acme");
+        bytes.extend_from_slice(&0u64.to_le_bytes()); // folders
+        bytes
+    }
+
+    #[test]
+    fn test_decode_versions() {
+        let data = CachedVaultData::from_vault_items(&[create_test_item_with_secrets("1", "Item", "a", "p")]);
+
+        let (decoded, version) = decode(&encode(&data).unwrap()).unwrap();
+        assert_eq!(version, CACHE_VERSION);
+        assert_eq!(decoded.items[0].name, "Item");
+
+        // Files written before the header existed are version 1
+        let (decoded, version) = decode(&version_1_cache("Item")).unwrap();
+        assert_eq!(version, 1);
+        assert_eq!(decoded.cached_at.to_rfc3339(), "2024-01-01T00:00:00+00:00");
+        assert!(decoded.organizations.is_empty() && decoded.folders.is_empty());
+        let item = &decoded.items[0];
+        assert_eq!(item.name, "Item");
+        assert_eq!(item.item_type, crate::types::ItemType::Login);
+        assert!(item.favorite);
+        assert_eq!(item.folder_id.as_deref(), Some("f"));
+        assert_eq!(item.revision_date.to_rfc3339(), "2023-06-01T12:00:00+00:00");
+        let login = item.login.as_ref().unwrap();
+        assert_eq!(login.username.as_deref(), Some("user@example.com"));
+        let uris = login.uris.as_ref().unwrap();
+        assert_eq!((uris[0].uri.as_str(), uris[0].match_type), ("https://example.com", None));
+        assert!(login.has_password && !login.has_totp);
+        assert_eq!(login.password_revision_date, None);
+        assert!(item.card.is_none() && item.identity.is_none());
+        assert_eq!(item.reprompt, None);
+
+        // Version 2 didn't cache the re-prompt setting yet
+        let (decoded, version) = decode(&version_2_cache("Item")).unwrap();
+        assert_eq!(version, 2);
+        assert_eq!(decoded.organizations, vec![Organization { id: "o-1".to_string(), name: "Acme".to_string() }]);
+        let item = &decoded.items[0];
+        assert_eq!(item.name, "Item");
+        let login = item.login.as_ref().unwrap();
+        assert_eq!(login.uris.as_ref().unwrap()[0].uri, "https://example.com");
+        assert!(login.has_password && login.has_totp);
+        assert_eq!(login.password_revision_date.map(|date| date.to_rfc3339()).as_deref(), Some("2023-05-01T00:00:00+00:00"));
+        assert_eq!(item.reprompt, None);
+
+        let mut future = CACHE_MAGIC.to_vec();
+        future.extend_from_slice(&(CACHE_VERSION + 1).to_le_bytes());
+        assert!(decode(&future).is_err());
+        assert!(decode(b"BWTC").is_err());
+    }
+
+    #[test]
+    fn test_load_upgrades_old_cache_in_place() {
+        let path = temp_cache_path("upgrade");
+        fs::write(&path, version_1_cache("Old")).unwrap();
+
+        assert_eq!(load_from(&path).unwrap().items[0].name, "Old");
+        assert!(fs::read(&path).unwrap().starts_with(CACHE_MAGIC));
+        // The upgraded file is read back in the current version
+        assert_eq!(decode(&fs::read(&path).unwrap()).unwrap().1, CACHE_VERSION);
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_describe_age() {
+        let now = chrono::Utc::now();
+        assert_eq!(describe_age(now, now), "just now");
+        assert_eq!(describe_age(now - chrono::Duration::minutes(1), now), "1 minute ago");
+        assert_eq!(describe_age(now - chrono::Duration::hours(5), now), "5 hours ago");
+        assert_eq!(describe_age(now - chrono::Duration::days(3), now), "3 days ago");
+    }
+
     #[test]
     fn test_cache_data_creation() {
         let items = vec![];
@@ -380,6 +858,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_cache_preserves_metadata() {
         let items = vec![
             VaultItem {
@@ -401,11 +880,13 @@ mod tests {
                 password_history: None,
                 attachments: None,
                 collection_ids: None,
-                reprompt: None,
+                reprompt: Some(1),
+                raw_fields: Default::default(),
             },
         ];
         
         let cache = CachedVaultData::from_vault_items(&items);
+        let (cache, _) = decode(&encode(&cache).unwrap()).unwrap();
         let restored_items = cache.to_vault_items();
         
         let restored_item = &restored_items[0];
@@ -415,9 +896,25 @@ mod tests {
         assert_eq!(restored_item.folder_id, Some("folder-123".to_string()));
         assert_eq!(restored_item.organization_id, Some("org-456".to_string()));
         assert_eq!(restored_item.revision_date.to_rfc3339(), "2023-01-01T00:00:00+00:00");
+        assert!(restored_item.requires_reprompt());
+    }
+
+    #[test]
+    fn test_cache_round_trip_keeps_organizations_and_folders() {
+        let organizations = vec![Organization { id: "org-456".to_string(), name: "Acme".to_string() }];
+        let folders = vec![Folder { id: "folder-123".to_string(), name: "Work/Infra".to_string() }];
+        let cache = CachedVaultData::from_vault_items(&[])
+            .with_organizations(&organizations)
+            .with_folders(&folders);
+
+        let encoded = bincode::serialize(&cache).unwrap();
+        let decoded: CachedVaultData = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(decoded.organizations, organizations);
+        assert_eq!(decoded.folders, folders);
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_cache_with_multiple_item_types() {
         let items = vec![
             VaultItem {
@@ -446,6 +943,7 @@ mod tests {
                 attachments: None,
                 collection_ids: None,
                 reprompt: None,
+                raw_fields: Default::default(),
             },
             VaultItem {
                 id: "2".to_string(),
@@ -467,6 +965,7 @@ mod tests {
                 attachments: None,
                 collection_ids: None,
                 reprompt: None,
+                raw_fields: Default::default(),
             },
         ];
         
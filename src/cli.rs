@@ -1,72 +1,197 @@
+use crate::backend::{Backend, ItemBatchFn, Unlocked, VaultBackend, VaultStatus};
+use crate::cli_version::Compatibility;
+use crate::config::CliConfig;
 use crate::error::{BwError, Result};
-use crate::session::SessionManager;
-use crate::types::VaultItem;
+use crate::json_stream::JsonArrayStream;
+use crate::retry::{self, RetryListener, RetryPolicy};
+use crate::session::SessionOptions;
+use crate::types::{Collection, CollectionAccess, EquivalentDomains, Folder, Organization, VaultItem};
+use futures_util::future::BoxFuture;
 use serde::Deserialize;
-use std::process::Stdio;
+use std::path::PathBuf;
+use std::process::{Output, Stdio};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::process::Command;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum VaultStatus {
-    Locked,
-    Unlocked,
-    Unauthenticated,
-}
-
 #[derive(Debug, Deserialize)]
 struct StatusResponse {
     status: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct FolderResponse {
+    id: Option<String>, // The "No Folder" pseudo-folder has no id
+    name: String,
+}
+
 /// Bitwarden CLI wrapper
 #[derive(Clone)]
 pub struct BitwardenCli {
     session_token: Option<String>,
+    retry_listener: Option<RetryListener>, // Notified when a command is retried
+    timeout: Duration, // Maximum time a single bw command may run
+    program: PathBuf, // The bw executable
+    appdata_dir: Option<PathBuf>, // Passed to bw as BITWARDENCLI_APPDATA_DIR
+    compatibility: Compatibility, // How well the installed bw version is supported
 }
 
+/// Number of parsed items sent to the UI at once while listing the vault
+const ITEM_BATCH_SIZE: usize = 200;
+
+/// Environment variable `bw unlock --passwordenv` reads the master password from
+const PASSWORD_ENV: &str = "BW_PASSWORD";
+
+/// Default maximum time a single bw command may run
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
 impl BitwardenCli {
-    /// Create a new Bitwarden CLI instance
-    pub async fn new() -> Result<Self> {
+    /// Create a new Bitwarden CLI instance running the configured executable
+    pub async fn new(config: &CliConfig, session: &SessionOptions) -> Result<Self> {
+        let mut cli = Self::unverified(config);
+
         // Check if bw CLI is available
-        let output = Command::new("bw")
-            .arg("--version")
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .output()
-            .await
-            .map_err(|_| {
-                crate::logger::Logger::error("Bitwarden CLI not found. Please install: npm install -g @bitwarden/cli");
-                BwError::CliNotFound
-            })?;
+        let version = cli.version().await?;
+        crate::logger::Logger::info(&format!("Bitwarden CLI {} found and verified", version));
+        cli.compatibility = Compatibility::of(&version);
+        if let Some(warning) = cli.compatibility.warning() {
+            crate::logger::Logger::warn(&warning);
+        }
+
+        cli.session_token = session.load_token()?;
+        Ok(cli)
+    }
+
+    /// An instance for the configured executable, without checking that it runs
+    pub fn unverified(config: &CliConfig) -> Self {
+        Self {
+            session_token: None,
+            retry_listener: None,
+            timeout: config.timeout(),
+            program: config.bw_program(),
+            appdata_dir: config.appdata_dir(),
+            compatibility: Compatibility::Supported,
+        }
+    }
+
+    /// The executable that is run
+    pub fn program(&self) -> &std::path::Path {
+        &self.program
+    }
+
+    /// The version printed by `bw --version`, CliNotFound if bw can't be run
+    pub async fn version(&self) -> Result<String> {
+        let output = self.run(self.command().arg("--version"), "bw --version").await.map_err(|_| {
+            crate::logger::Logger::error(&format!(
+                "Bitwarden CLI not found at '{}'. Please install: npm install -g @bitwarden/cli",
+                self.program.display()
+            ));
+            BwError::CliNotFound
+        })?;
 
         if !output.status.success() {
-            crate::logger::Logger::error("Bitwarden CLI not found or not executable");
+            crate::logger::Logger::error(&format!("Bitwarden CLI at '{}' not found or not executable", self.program.display()));
             return Err(BwError::CliNotFound);
         }
 
-        crate::logger::Logger::info("Bitwarden CLI found and verified");
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
 
-        // Load session token from encrypted storage
-        let session_manager = SessionManager::new().map_err(|e| {
-            crate::logger::Logger::error(&format!("Failed to initialize session manager: {}", e));
-            e
-        })?;
-        let session_token = session_manager.load_token().map_err(|e| {
-            crate::logger::Logger::warn(&format!("Failed to load session token: {}", e));
-            e
-        })?;
+    /// Start a bw command, with the configured appdata directory if any
+    fn command(&self) -> Command {
+        let mut cmd = Command::new(&self.program);
+        if let Some(appdata_dir) = &self.appdata_dir {
+            cmd.env("BITWARDENCLI_APPDATA_DIR", appdata_dir);
+        }
+        cmd
+    }
 
-        if session_token.is_some() {
-            crate::logger::Logger::info("Session token loaded from storage");
-        } else {
-            crate::logger::Logger::info("No session token found in storage");
+    /// Return a copy of this instance that reports retries to the given listener
+    pub fn with_retry_listener(&self, listener: RetryListener) -> Self {
+        Self {
+            retry_listener: Some(listener),
+            ..self.clone()
         }
+    }
 
-        Ok(Self { session_token })
+    /// Run a bw command, killing it if it exceeds the timeout or the caller is cancelled
+    #[tracing::instrument(skip(self, cmd))]
+    async fn run(&self, cmd: &mut Command, name: &str) -> Result<Output> {
+        // Dropping the output future (timeout or task abort) kills the child process
+        cmd.kill_on_drop(true);
+        self.wait(cmd.output(), name).await
     }
 
-    /// Check the current vault status
+    /// Run a bw command fed `input` on stdin, for payloads that must not show in the process list
+    #[tracing::instrument(skip(self, cmd, input))]
+    async fn run_with_input(&self, cmd: &mut Command, input: &[u8], name: &str) -> Result<Output> {
+        cmd.kill_on_drop(true).stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
+        let output = async {
+            let mut child = cmd.spawn()?;
+            if let Some(mut stdin) = child.stdin.take() {
+                // Dropping stdin closes it, so bw stops reading
+                stdin.write_all(input).await?;
+            }
+            child.wait_with_output().await
+        };
+        self.wait(output, name).await
+    }
+
+    /// Wait for a command's output, up to the timeout
+    async fn wait(&self, output: impl std::future::Future<Output = std::io::Result<Output>>, name: &str) -> Result<Output> {
+        match tokio::time::timeout(self.timeout, output).await {
+            Ok(Ok(output)) => Ok(output),
+            Ok(Err(e)) => {
+                let error_msg = format!("Failed to execute {}: {}", name, e);
+                crate::logger::Logger::error(&error_msg);
+                Err(BwError::CommandFailed(error_msg))
+            }
+            Err(_) => {
+                let error_msg = format!("{} timed out after {}s", name, self.timeout.as_secs());
+                crate::logger::Logger::error(&error_msg);
+                Err(BwError::CommandTimedOut(error_msg))
+            }
+        }
+    }
+
+    /// Check the current vault status, retrying transient failures
     pub async fn check_status(&self) -> Result<VaultStatus> {
-        let mut cmd = Command::new("bw");
+        retry::retry(RetryPolicy::default(), "bw status", self.retry_listener.as_ref(), || self.check_status_once()).await
+    }
+
+    /// List all vault items, retrying transient failures
+    /// Items are passed to `on_batch` as they are parsed, and the total count is returned
+    pub async fn list_items<F>(&self, on_batch: F) -> Result<usize>
+    where
+        F: Fn(Vec<VaultItem>),
+    {
+        retry::retry(RetryPolicy::default(), "bw list items", self.retry_listener.as_ref(), || self.list_items_once(&on_batch)).await
+    }
+
+    /// List the organizations the user is a member of, retrying transient failures
+    pub async fn list_organizations(&self) -> Result<Vec<Organization>> {
+        retry::retry(RetryPolicy::default(), "bw list organizations", self.retry_listener.as_ref(), || self.list_organizations_once()).await
+    }
+
+    /// List the folders items can be filed in, retrying transient failures
+    pub async fn list_folders(&self) -> Result<Vec<Folder>> {
+        retry::retry(RetryPolicy::default(), "bw list folders", self.retry_listener.as_ref(), || self.list_folders_once()).await
+    }
+
+    /// List the collections of the user's organizations, retrying transient failures
+    pub async fn list_collections(&self) -> Result<Vec<Collection>> {
+        retry::retry(RetryPolicy::default(), "bw list collections", self.retry_listener.as_ref(), || self.list_collections_once()).await
+    }
+
+    /// Sync vault with server, retrying transient failures
+    pub async fn sync(&self) -> Result<()> {
+        retry::retry(RetryPolicy::default(), "bw sync", self.retry_listener.as_ref(), || self.sync_once()).await
+    }
+
+    /// Check the current vault status
+    async fn check_status_once(&self) -> Result<VaultStatus> {
+        let mut cmd = self.command();
         cmd.arg("status");
 
         if let Some(_token) = &self.session_token {
@@ -74,14 +199,7 @@ impl BitwardenCli {
             cmd.env("BW_SESSION", _token);
         }
 
-        let output = cmd
-            .output()
-            .await
-            .map_err(|e| {
-                let error_msg = format!("Failed to execute bw status: {}", e);
-                crate::logger::Logger::error(&error_msg);
-                BwError::CommandFailed(error_msg)
-            })?;
+        let output = self.run(&mut cmd, "bw status").await?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -110,70 +228,110 @@ impl BitwardenCli {
     }
 
     /// List all vault items
-    pub async fn list_items(&self) -> Result<Vec<VaultItem>> {
-        let mut cmd = Command::new("bw");
+    async fn list_items_once<F>(&self, on_batch: &F) -> Result<usize>
+    where
+        F: Fn(Vec<VaultItem>),
+    {
+        let mut cmd = self.command();
         cmd.arg("list").arg("items");
 
         if let Some(_token) = &self.session_token {
             cmd.env("BW_SESSION", _token);
         }
 
-        let output = cmd
-            .output()
-            .await
-            .map_err(|e| {
-                let error_msg = format!("Failed to execute bw list: {}", e);
+        // Dropping the future (timeout or task abort) kills the child process
+        cmd.stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        match tokio::time::timeout(self.timeout, stream_items(cmd, on_batch)).await {
+            Ok(result) => result,
+            Err(_) => {
+                let error_msg = format!("bw list timed out after {}s", self.timeout.as_secs());
                 crate::logger::Logger::error(&error_msg);
-                BwError::CommandFailed(error_msg)
-            })?;
+                Err(BwError::CommandTimedOut(error_msg))
+            }
+        }
+    }
+
+    /// List the organizations the user is a member of
+    async fn list_organizations_once(&self) -> Result<Vec<Organization>> {
+        let mut cmd = self.command();
+        cmd.arg("list").arg("organizations");
+
+        if let Some(_token) = &self.session_token {
+            cmd.env("BW_SESSION", _token);
+        }
+
+        let output = self.run(&mut cmd, "bw list organizations").await?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
             let sanitized_stderr = crate::logger::Logger::sanitize_message(&stderr);
-            
-            // Check for common error messages
-            if stderr.contains("not logged in") {
-                crate::logger::Logger::error("Vault is not logged in");
-                return Err(BwError::NotLoggedIn);
-            } else if stderr.contains("locked") {
-                crate::logger::Logger::error("Vault is locked");
-                return Err(BwError::VaultLocked);
-            }
-            
-            let error_msg = format!("bw list items failed: {}", sanitized_stderr);
+            let error_msg = format!("bw list organizations failed: {}", sanitized_stderr);
             crate::logger::Logger::error(&error_msg);
             return Err(BwError::CommandFailed(format!(
-                "bw list items failed: {}",
+                "bw list organizations failed: {}",
                 stderr
             )));
         }
 
-        let items: Vec<VaultItem> = serde_json::from_slice(&output.stdout).map_err(|e| {
-            let error_msg = format!("Failed to parse vault items: {}", e);
+        serde_json::from_slice(&output.stdout).map_err(|e| {
+            let error_msg = format!("Failed to parse organizations: {}", e);
             crate::logger::Logger::error(&error_msg);
             BwError::ParseError(error_msg)
-        })?;
+        })
+    }
+
+    /// List the folders items can be filed in
+    async fn list_folders_once(&self) -> Result<Vec<Folder>> {
+        let mut cmd = self.command();
+        cmd.arg("list").arg("folders");
+
+        if let Some(_token) = &self.session_token {
+            cmd.env("BW_SESSION", _token);
+        }
+
+        let output = self.run(&mut cmd, "bw list folders").await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let sanitized_stderr = crate::logger::Logger::sanitize_message(&stderr);
+            let error_msg = format!("bw list folders failed: {}", sanitized_stderr);
+            crate::logger::Logger::error(&error_msg);
+            return Err(BwError::CommandFailed(format!(
+                "bw list folders failed: {}",
+                stderr
+            )));
+        }
 
-        Ok(items)
+        parse_folders(&output.stdout)
     }
+
     /// Sync vault with server
-    pub async fn sync(&self) -> Result<()> {
-        let mut cmd = Command::new("bw");
+    async fn sync_once(&self) -> Result<()> {
+        let mut cmd = self.command();
         cmd.arg("sync");
 
         if let Some(_token) = &self.session_token {
             cmd.env("BW_SESSION", _token);
         }
 
-        let output = cmd.output().await.map_err(|e| {
-            let error_msg = format!("Failed to execute bw sync: {}", e);
-            crate::logger::Logger::error(&error_msg);
-            BwError::CommandFailed(error_msg)
-        })?;
+        let output = self.run(&mut cmd, "bw sync").await?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
             let sanitized_stderr = crate::logger::Logger::sanitize_message(&stderr);
+
+            if stderr.contains("not logged in") {
+                crate::logger::Logger::error("Vault is not logged in");
+                return Err(BwError::NotLoggedIn);
+            } else if stderr.contains("locked") {
+                crate::logger::Logger::error("Vault is locked");
+                return Err(BwError::VaultLocked);
+            }
+
             let error_msg = format!("bw sync failed: {}", sanitized_stderr);
             crate::logger::Logger::error(&error_msg);
             return Err(BwError::CommandFailed(format!(
@@ -193,23 +351,19 @@ impl BitwardenCli {
     }
 
     /// Unlock vault with password and return session token
+    /// The password goes through the environment of bw, arguments are visible to every user in `ps`
     pub async fn unlock(&self, password: &str) -> Result<String> {
-        let mut cmd = Command::new("bw");
+        let mut cmd = self.command();
         cmd.arg("unlock")
             .arg("--raw")
-            .arg(password)
+            .arg("--passwordenv")
+            .arg(PASSWORD_ENV)
+            .env(PASSWORD_ENV, password)
             .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
 
-        let output = cmd
-            .output()
-            .await
-            .map_err(|e| {
-                let error_msg = format!("Failed to execute bw unlock: {}", e);
-                crate::logger::Logger::error(&error_msg);
-                BwError::CommandFailed(error_msg)
-            })?;
+        let output = self.run(&mut cmd, "bw unlock").await?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -246,7 +400,7 @@ impl BitwardenCli {
 
     /// Get TOTP code for a specific item ID
     pub async fn get_totp(&self, item_id: &str) -> Result<String> {
-        let mut cmd = Command::new("bw");
+        let mut cmd = self.command();
         cmd.arg("get")
             .arg("totp")
             .arg(item_id);
@@ -255,14 +409,7 @@ impl BitwardenCli {
             cmd.env("BW_SESSION", _token);
         }
 
-        let output = cmd
-            .output()
-            .await
-            .map_err(|e| {
-                let error_msg = format!("Failed to execute bw get totp for item {}: {}", item_id, e);
-                crate::logger::Logger::error(&error_msg);
-                BwError::CommandFailed(format!("Failed to execute bw get totp: {}", e))
-            })?;
+        let output = self.run(&mut cmd, "bw get totp").await?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -296,11 +443,572 @@ impl BitwardenCli {
         Ok(totp_code)
     }
 
-    /// Create a new instance with a specific session token
-    pub fn with_session_token(token: String) -> Self {
+    /// Move an item to the trash
+    pub async fn delete_item(&self, item_id: &str) -> Result<()> {
+        let mut cmd = self.command();
+        cmd.arg("delete")
+            .arg("item")
+            .arg(item_id);
+
+        if let Some(_token) = &self.session_token {
+            cmd.env("BW_SESSION", _token);
+        }
+
+        let output = self.run(&mut cmd, "bw delete item").await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let sanitized_stderr = crate::logger::Logger::sanitize_message(&stderr);
+
+            if stderr.contains("not logged in") {
+                crate::logger::Logger::error("Vault is not logged in");
+                return Err(BwError::NotLoggedIn);
+            } else if stderr.contains("locked") {
+                crate::logger::Logger::error("Vault is locked");
+                return Err(BwError::VaultLocked);
+            }
+
+            let error_msg = format!("bw delete item failed for item {}: {}", item_id, sanitized_stderr);
+            crate::logger::Logger::error(&error_msg);
+            return Err(BwError::CommandFailed(format!(
+                "bw delete item failed: {}",
+                stderr.trim()
+            )));
+        }
+
+        crate::logger::Logger::info(&format!("Item {} moved to trash", item_id));
+        Ok(())
+    }
+
+    async fn list_collections_once(&self) -> Result<Vec<Collection>> {
+        let stdout = self.run_command(&["list", "collections"], "bw list collections").await?;
+        parse_json(&stdout, "collections")
+    }
+
+    /// Who can access a collection, which only the organization's managers may see
+    pub async fn collection_access(&self, organization_id: &str, collection_id: &str) -> Result<CollectionAccess> {
+        let members = self
+            .run_command(&["list", "org-members", "--organizationid", organization_id], "bw list org-members")
+            .await?;
+        let details = self
+            .run_command(&["get", "org-collection", collection_id, "--organizationid", organization_id], "bw get org-collection")
+            .await?;
+        Ok(CollectionAccess::new(parse_json(&details, "collection")?, parse_json(&members, "organization members")?))
+    }
+
+    /// Move a personal item into an organization, in the given collections of it
+    pub async fn share_item(&self, item_id: &str, organization_id: &str, collection_ids: &[String]) -> Result<()> {
+        let collections = encode_json(&serde_json::json!(collection_ids));
+        self.run_command(&["share", item_id, organization_id, &collections], "bw share").await?;
+        crate::logger::Logger::info(&format!("Item {} shared with organization {}", item_id, organization_id));
+        Ok(())
+    }
+
+    /// Turn the master password re-prompt of an item on or off, keeping the rest of it as bw has it
+    pub async fn set_reprompt(&self, item_id: &str, enabled: bool) -> Result<()> {
+        self.edit_item(item_id, |item| item["reprompt"] = serde_json::json!(u8::from(enabled))).await?;
+        crate::logger::Logger::info(&format!("Master password re-prompt of item {} turned {}", item_id, if enabled { "on" } else { "off" }));
+        Ok(())
+    }
+
+    /// Move an item into a folder, or out of any folder with None
+    pub async fn move_item(&self, item_id: &str, folder_id: Option<&str>) -> Result<()> {
+        self.edit_item(item_id, |item| item["folderId"] = serde_json::json!(folder_id)).await?;
+        crate::logger::Logger::info(&format!("Item {} moved to folder {}", item_id, folder_id.unwrap_or("none")));
+        Ok(())
+    }
+
+    /// Change an item as `bw get item` gives it and save it with `bw edit item`
+    /// The item goes to `bw edit` on stdin, as it holds the item's secrets
+    async fn edit_item(&self, item_id: &str, change: impl FnOnce(&mut serde_json::Value)) -> Result<()> {
+        let stdout = self.run_command(&["get", "item", item_id], "bw get item").await?;
+        let mut item: serde_json::Value = parse_json(&stdout, "item")?;
+        change(&mut item);
+        let encoded = encode_json(&item);
+        self.run_command_with_input(&["edit", "item", item_id], Some(encoded.as_bytes()), "bw edit item").await?;
+        Ok(())
+    }
+
+    /// Create a folder, returning it with the id the server gave it
+    pub async fn create_folder(&self, name: &str) -> Result<Folder> {
+        let stdout = self.run_command(&["create", "folder", &encode_folder(name)], "bw create folder").await?;
+        let folder: FolderResponse = serde_json::from_slice(&stdout)
+            .map_err(|e| BwError::ParseError(format!("Failed to parse the created folder: {}", e)))?;
+        let id = folder.id.ok_or_else(|| BwError::ParseError("The created folder has no id".to_string()))?;
+        crate::logger::Logger::info(&format!("Folder {} created", id));
+        Ok(Folder { id, name: folder.name })
+    }
+
+    /// Rename a folder
+    pub async fn rename_folder(&self, folder_id: &str, name: &str) -> Result<()> {
+        self.run_command(&["edit", "folder", folder_id, &encode_folder(name)], "bw edit folder").await?;
+        crate::logger::Logger::info(&format!("Folder {} renamed", folder_id));
+        Ok(())
+    }
+
+    /// Delete a folder, its items are moved out of it
+    pub async fn delete_folder(&self, folder_id: &str) -> Result<()> {
+        self.run_command(&["delete", "folder", folder_id], "bw delete folder").await?;
+        crate::logger::Logger::info(&format!("Folder {} deleted", folder_id));
+        Ok(())
+    }
+
+    /// Run a bw command, returning its output
+    async fn run_command(&self, args: &[&str], name: &str) -> Result<Vec<u8>> {
+        self.run_command_with_input(args, None, name).await
+    }
+
+    async fn run_command_with_input(&self, args: &[&str], input: Option<&[u8]>, name: &str) -> Result<Vec<u8>> {
+        let mut cmd = self.command();
+        cmd.args(args);
+
+        if let Some(_token) = &self.session_token {
+            cmd.env("BW_SESSION", _token);
+        }
+
+        let output = match input {
+            Some(input) => self.run_with_input(&mut cmd, input, name).await?,
+            None => self.run(&mut cmd, name).await?,
+        };
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let sanitized_stderr = crate::logger::Logger::sanitize_message(&stderr);
+
+            if stderr.contains("not logged in") {
+                crate::logger::Logger::error("Vault is not logged in");
+                return Err(BwError::NotLoggedIn);
+            } else if stderr.contains("locked") {
+                crate::logger::Logger::error("Vault is locked");
+                return Err(BwError::VaultLocked);
+            }
+
+            crate::logger::Logger::error(&format!("{} failed: {}", name, sanitized_stderr));
+            return Err(BwError::CommandFailed(format!("{} failed: {}", name, stderr.trim())));
+        }
+
+        Ok(output.stdout)
+    }
+
+    /// Create a new instance with a specific session token and the same settings
+    pub fn with_session_token(&self, token: String) -> Self {
         Self {
             session_token: Some(token),
+            retry_listener: None,
+            ..self.clone()
         }
     }
 }
 
+impl VaultBackend for BitwardenCli {
+    fn check_status(&self) -> BoxFuture<'_, Result<VaultStatus>> {
+        Box::pin(BitwardenCli::check_status(self))
+    }
+
+    fn unlock<'a>(&'a self, password: &'a str) -> BoxFuture<'a, Result<Unlocked>> {
+        Box::pin(async move {
+            let token = BitwardenCli::unlock(self, password).await?;
+            Ok(Unlocked {
+                backend: Arc::new(self.with_session_token(token.clone())),
+                session_token: Some(token),
+            })
+        })
+    }
+
+    fn sync(&self) -> BoxFuture<'_, Result<()>> {
+        Box::pin(BitwardenCli::sync(self))
+    }
+
+    fn list_items<'a>(&'a self, on_batch: &'a ItemBatchFn) -> BoxFuture<'a, Result<usize>> {
+        Box::pin(BitwardenCli::list_items(self, on_batch))
+    }
+
+    fn list_organizations(&self) -> BoxFuture<'_, Result<Vec<Organization>>> {
+        Box::pin(BitwardenCli::list_organizations(self))
+    }
+
+    fn list_folders(&self) -> BoxFuture<'_, Result<Vec<Folder>>> {
+        Box::pin(BitwardenCli::list_folders(self))
+    }
+
+    fn list_collections(&self) -> BoxFuture<'_, Result<Vec<Collection>>> {
+        Box::pin(BitwardenCli::list_collections(self))
+    }
+
+    fn collection_access<'a>(&'a self, organization_id: &'a str, collection_id: &'a str) -> BoxFuture<'a, Result<CollectionAccess>> {
+        Box::pin(BitwardenCli::collection_access(self, organization_id, collection_id))
+    }
+
+    fn equivalent_domains(&self) -> BoxFuture<'_, Result<EquivalentDomains>> {
+        // bw syncs the domain settings but has no command to list them, so the account's own groups and
+        // exclusions are unknown here: search gets Bitwarden's global groups
+        Box::pin(async { Ok(EquivalentDomains::global()) })
+    }
+
+    fn get_totp<'a>(&'a self, item_id: &'a str) -> BoxFuture<'a, Result<String>> {
+        Box::pin(BitwardenCli::get_totp(self, item_id))
+    }
+
+    fn delete_item<'a>(&'a self, item_id: &'a str) -> BoxFuture<'a, Result<()>> {
+        Box::pin(BitwardenCli::delete_item(self, item_id))
+    }
+
+    fn share_item<'a>(&'a self, item_id: &'a str, organization_id: &'a str, collection_ids: &'a [String]) -> BoxFuture<'a, Result<()>> {
+        Box::pin(BitwardenCli::share_item(self, item_id, organization_id, collection_ids))
+    }
+
+    fn set_reprompt<'a>(&'a self, item_id: &'a str, enabled: bool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(BitwardenCli::set_reprompt(self, item_id, enabled))
+    }
+
+    fn move_item<'a>(&'a self, item_id: &'a str, folder_id: Option<&'a str>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(BitwardenCli::move_item(self, item_id, folder_id))
+    }
+
+    fn create_folder<'a>(&'a self, name: &'a str) -> BoxFuture<'a, Result<Folder>> {
+        Box::pin(BitwardenCli::create_folder(self, name))
+    }
+
+    fn rename_folder<'a>(&'a self, folder_id: &'a str, name: &'a str) -> BoxFuture<'a, Result<()>> {
+        Box::pin(BitwardenCli::rename_folder(self, folder_id, name))
+    }
+
+    fn delete_folder<'a>(&'a self, folder_id: &'a str) -> BoxFuture<'a, Result<()>> {
+        Box::pin(BitwardenCli::delete_folder(self, folder_id))
+    }
+
+    fn with_retry_listener(&self, listener: RetryListener) -> Backend {
+        Arc::new(BitwardenCli::with_retry_listener(self, listener))
+    }
+
+    fn has_session_token(&self) -> bool {
+        self.session_token.is_some()
+    }
+
+    fn version_warning(&self) -> Option<String> {
+        self.compatibility.warning()
+    }
+}
+
+/// Parse the output of `bw list folders`, skipping the "No Folder" pseudo-folder
+fn parse_folders(stdout: &[u8]) -> Result<Vec<Folder>> {
+    let folders: Vec<FolderResponse> = serde_json::from_slice(stdout).map_err(|e| {
+        let error_msg = format!("Failed to parse folders: {}", e);
+        crate::logger::Logger::error(&error_msg);
+        BwError::ParseError(error_msg)
+    })?;
+
+    Ok(folders
+        .into_iter()
+        .filter_map(|folder| Some(Folder { id: folder.id?, name: folder.name }))
+        .collect())
+}
+
+/// Parse the JSON output of a bw command
+fn parse_json<T: serde::de::DeserializeOwned>(stdout: &[u8], what: &str) -> Result<T> {
+    serde_json::from_slice(stdout).map_err(|e| {
+        let error_msg = format!("Failed to parse {}: {}", what, e);
+        crate::logger::Logger::error(&error_msg);
+        BwError::ParseError(error_msg)
+    })
+}
+
+/// The base64 JSON `bw create/edit folder` expect
+fn encode_folder(name: &str) -> String {
+    encode_json(&serde_json::json!({ "name": name }))
+}
+
+/// JSON encoded in base64 for bw's arguments, like `bw encode` gives
+fn encode_json(value: &serde_json::Value) -> String {
+    use base64::Engine;
+    base64::engine::general_purpose::STANDARD.encode(value.to_string())
+}
+
+/// Run `bw list items`, parsing the JSON array from stdout as it is read
+/// Only one item is buffered at a time, instead of the whole output
+#[tracing::instrument(skip_all)]
+async fn stream_items<F>(mut cmd: Command, on_batch: &F) -> Result<usize>
+where
+    F: Fn(Vec<VaultItem>),
+{
+    let mut child = cmd.spawn().map_err(|e| {
+        let error_msg = format!("Failed to execute bw list: {}", e);
+        crate::logger::Logger::error(&error_msg);
+        BwError::CommandFailed(error_msg)
+    })?;
+
+    // Read stderr concurrently so a chatty CLI can't block on a full pipe
+    let mut stderr = child.stderr.take();
+    let stderr_task = tokio::spawn(async move {
+        let mut buffer = Vec::new();
+        if let Some(stderr) = stderr.as_mut() {
+            let _ = stderr.read_to_end(&mut buffer).await;
+        }
+        buffer
+    });
+
+    let mut stdout = child.stdout.take()
+        .ok_or_else(|| BwError::CommandFailed("Failed to capture bw list output".to_string()))?;
+    let mut stream = JsonArrayStream::new();
+    let mut batch = Vec::with_capacity(ITEM_BATCH_SIZE);
+    let mut total = 0;
+    let mut parse_result = Ok(());
+    let mut chunk = vec![0u8; 64 * 1024];
+
+    loop {
+        let read = stdout.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        // After a parse error keep draining stdout so the process can exit
+        if parse_result.is_err() {
+            continue;
+        }
+
+        let parsed = stream.feed(&chunk[..read]).and_then(|elements| {
+            for element in elements {
+                let item: VaultItem = serde_json::from_slice(&element)
+                    .map_err(|e| BwError::ParseError(format!("Failed to parse vault items: {}", e)))?;
+                batch.push(item);
+                if batch.len() == ITEM_BATCH_SIZE {
+                    total += batch.len();
+                    on_batch(std::mem::replace(&mut batch, Vec::with_capacity(ITEM_BATCH_SIZE)));
+                }
+            }
+            Ok(())
+        });
+        parse_result = parsed;
+    }
+
+    let status = child.wait().await?;
+    let stderr = String::from_utf8_lossy(&stderr_task.await.unwrap_or_default()).to_string();
+
+    if !status.success() {
+        let sanitized_stderr = crate::logger::Logger::sanitize_message(&stderr);
+
+        // Check for common error messages
+        if stderr.contains("not logged in") {
+            crate::logger::Logger::error("Vault is not logged in");
+            return Err(BwError::NotLoggedIn);
+        } else if stderr.contains("locked") {
+            crate::logger::Logger::error("Vault is locked");
+            return Err(BwError::VaultLocked);
+        }
+
+        let error_msg = format!("bw list items failed: {}", sanitized_stderr);
+        crate::logger::Logger::error(&error_msg);
+        return Err(BwError::CommandFailed(format!(
+            "bw list items failed: {}",
+            stderr
+        )));
+    }
+
+    if let Err(e) = parse_result.and_then(|_| stream.finish()) {
+        crate::logger::Logger::error(&e.to_string());
+        return Err(e);
+    }
+
+    if !batch.is_empty() {
+        total += batch.len();
+        on_batch(batch);
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    fn item_json(id: usize) -> String {
+        format!(
+            r#"{{"id": "{}", "name": "Item {}", "type": 1, "favorite": false, "revisionDate": "2024-01-01T00:00:00Z"}}"#,
+            id, id
+        )
+    }
+
+    #[cfg(unix)]
+    fn shell(script: &str) -> Command {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(script).stdout(Stdio::piped()).stderr(Stdio::piped());
+        cmd
+    }
+
+    /// Write an executable `bw` running the given shell script, alone in a new temporary directory
+    #[cfg(unix)]
+    fn fake_bw(script: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let dir = std::env::temp_dir().join(format!("bwtui-fake-bw-{}-{}", std::process::id(), COUNT.fetch_add(1, Ordering::Relaxed)));
+        std::fs::create_dir_all(&dir).unwrap();
+        let program = dir.join("bw");
+        std::fs::write(&program, format!("#!/bin/sh\n{}", script)).unwrap();
+        std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o755)).unwrap();
+        program
+    }
+
+    #[cfg(unix)]
+    fn remove_fake_bw(program: &std::path::Path) {
+        std::fs::remove_dir_all(program.parent().unwrap()).unwrap();
+    }
+
+    #[cfg(unix)]
+    fn fake_bw_config(program: &std::path::Path) -> CliConfig {
+        CliConfig { bw_path: Some(program.to_string_lossy().into_owned()), ..CliConfig::default() }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stream_items_in_batches() {
+        let items: Vec<String> = (0..ITEM_BATCH_SIZE + 5).map(item_json).collect();
+        let script = format!("printf '%s' '[{}]'", items.join(","));
+
+        let batches = std::sync::Mutex::new(Vec::new());
+        let total = stream_items(shell(&script), &|batch: Vec<VaultItem>| batches.lock().unwrap().push(batch.len()))
+            .await
+            .unwrap();
+
+        assert_eq!(total, ITEM_BATCH_SIZE + 5);
+        assert_eq!(*batches.lock().unwrap(), vec![ITEM_BATCH_SIZE, 5]);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stream_items_reports_cli_errors() {
+        let result = stream_items(shell("echo 'You are not logged in.' >&2; exit 1"), &|_: Vec<VaultItem>| {}).await;
+        assert!(matches!(result, Err(BwError::NotLoggedIn)));
+
+        let result = stream_items(shell("printf '[{\"id\": '"), &|_: Vec<VaultItem>| {}).await;
+        assert!(matches!(result, Err(BwError::ParseError(_))));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_custom_program_and_appdata_dir() {
+        // A fake bw that only reports unlocked when given the appdata directory
+        let program = fake_bw(r#"
+            [ "$1" = status ] && [ "$BITWARDENCLI_APPDATA_DIR" = /tmp/bw-data ] && echo '{"status":"unlocked"}'
+            exit 0
+        "#);
+
+        let config = CliConfig { appdata_dir: Some("/tmp/bw-data".to_string()), ..fake_bw_config(&program) };
+        let session = SessionOptions::new(Some("token".to_string()), None, true);
+        let cli = BitwardenCli::new(&config, &session).await.unwrap();
+        assert_eq!(cli.check_status_once().await.unwrap(), VaultStatus::Unlocked);
+
+        let config = fake_bw_config(&program.with_file_name("missing"));
+        assert!(matches!(BitwardenCli::new(&config, &session).await, Err(BwError::CliNotFound)));
+
+        remove_fake_bw(&program);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unlock_passes_password_in_environment() {
+        // A fake bw that only unlocks when the password is in the environment and not in its arguments
+        let program = fake_bw(r#"
+            case "$*" in *hunter2*) exit 1;; esac
+            [ "$*" = 'unlock --raw --passwordenv BW_PASSWORD' ] && [ "$BW_PASSWORD" = hunter2 ] && echo token
+            exit 0
+        "#);
+
+        let cli = BitwardenCli::new(&fake_bw_config(&program), &SessionOptions::new(None, None, true)).await.unwrap();
+        assert_eq!(cli.unlock("hunter2").await.unwrap(), "token");
+
+        remove_fake_bw(&program);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_create_folder_sends_encoded_json() {
+        // A fake bw that echoes the created folder when given {"name":"Work"} in base64
+        let program = fake_bw(r#"
+            [ "$*" = '--version' ] && echo 2024.6.0 && exit 0
+            [ "$*" = 'create folder eyJuYW1lIjoiV29yayJ9' ] && echo '{"object":"folder","id":"f-1","name":"Work"}' && exit 0
+            echo 'Not found.' >&2
+            exit 1
+        "#);
+
+        let cli = BitwardenCli::new(&fake_bw_config(&program), &SessionOptions::new(None, None, true)).await.unwrap();
+        assert_eq!(cli.create_folder("Work").await.unwrap(), Folder { id: "f-1".to_string(), name: "Work".to_string() });
+        assert!(matches!(cli.delete_folder("f-1").await, Err(BwError::CommandFailed(_))));
+
+        remove_fake_bw(&program);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_set_reprompt_edits_the_item_through_stdin() {
+        // A fake bw that saves what `bw edit item` reads from stdin next to itself
+        let program = fake_bw(r#"
+            [ "$*" = '--version' ] && echo 2024.6.0 && exit 0
+            [ "$*" = 'get item i-1' ] && echo '{"id":"i-1","name":"Bank","reprompt":0,"login":{"password":"hunter2"}}' && exit 0
+            [ "$*" = 'edit item i-1' ] && cat > "$(dirname "$0")/edited" && exit 0
+            echo 'Not found.' >&2
+            exit 1
+        "#);
+
+        let cli = BitwardenCli::new(&fake_bw_config(&program), &SessionOptions::new(None, None, true)).await.unwrap();
+        cli.set_reprompt("i-1", true).await.unwrap();
+
+        use base64::Engine;
+        let encoded = std::fs::read_to_string(program.with_file_name("edited")).unwrap();
+        let item: serde_json::Value = serde_json::from_slice(&base64::engine::general_purpose::STANDARD.decode(encoded).unwrap()).unwrap();
+        assert_eq!(item["reprompt"], 1);
+        assert_eq!(item["login"]["password"], "hunter2");
+
+        remove_fake_bw(&program);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_move_item_clears_the_folder() {
+        let program = fake_bw(r#"
+            [ "$*" = '--version' ] && echo 2024.6.0 && exit 0
+            [ "$*" = 'get item i-1' ] && echo '{"id":"i-1","name":"Bank","folderId":"f-1"}' && exit 0
+            [ "$*" = 'edit item i-1' ] && cat > "$(dirname "$0")/edited" && exit 0
+            echo 'Not found.' >&2
+            exit 1
+        "#);
+
+        let cli = BitwardenCli::new(&fake_bw_config(&program), &SessionOptions::new(None, None, true)).await.unwrap();
+        cli.move_item("i-1", None).await.unwrap();
+
+        use base64::Engine;
+        let encoded = std::fs::read_to_string(program.with_file_name("edited")).unwrap();
+        let item: serde_json::Value = serde_json::from_slice(&base64::engine::general_purpose::STANDARD.decode(encoded).unwrap()).unwrap();
+        assert_eq!(item["folderId"], serde_json::Value::Null);
+        assert_eq!(item["name"], "Bank");
+
+        remove_fake_bw(&program);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_collection_access_names_members() {
+        // A fake bw for an organization admin
+        let program = fake_bw(r#"
+            [ "$*" = '--version' ] && echo 2024.6.0 && exit 0
+            [ "$*" = 'list org-members --organizationid o-1' ] && echo '[{"id":"m-1","name":"Alice","email":"alice@example.com"},{"id":"m-2","name":"Bob","email":"bob@example.com"}]' && exit 0
+            [ "$*" = 'get org-collection c-1 --organizationid o-1' ] && echo '{"id":"c-1","users":[{"id":"m-2"}],"groups":[]}' && exit 0
+            echo 'You do not have permission.' >&2
+            exit 1
+        "#);
+
+        let cli = BitwardenCli::new(&fake_bw_config(&program), &SessionOptions::new(None, None, true)).await.unwrap();
+        let access = cli.collection_access("o-1", "c-1").await.unwrap();
+        assert_eq!(access.members.iter().map(|member| member.label()).collect::<Vec<_>>(), vec!["Bob <bob@example.com>"]);
+        assert!(matches!(cli.collection_access("o-2", "c-2").await, Err(BwError::CommandFailed(_))));
+
+        remove_fake_bw(&program);
+    }
+
+    #[test]
+    fn test_parse_folders_skips_no_folder() {
+        let stdout = br#"[{"object":"folder","id":"f-1","name":"Work/Infra"},{"object":"folder","id":null,"name":"No Folder"}]"#;
+        let folders = parse_folders(stdout).unwrap();
+        assert_eq!(folders, vec![Folder { id: "f-1".to_string(), name: "Work/Infra".to_string() }]);
+    }
+}
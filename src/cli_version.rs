@@ -0,0 +1,101 @@
+use std::fmt;
+
+/// A bw release number, such as 2024.6.0 (or 1.22.1 for releases before 2022)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CliVersion {
+    major: u32,
+    minor: u32,
+    patch: u32,
+}
+
+impl CliVersion {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self { major, minor, patch }
+    }
+
+    /// Parse the output of `bw --version`, ignoring a leading "v" and anything after the number
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let text = text.strip_prefix('v').unwrap_or(text);
+        let number = text.split(|c: char| !c.is_ascii_digit() && c != '.').next()?;
+        let mut parts = number.split('.').map(|part| part.parse::<u32>().ok());
+        let major = parts.next()??;
+        let minor = parts.next().flatten().unwrap_or(0);
+        let patch = parts.next().flatten().unwrap_or(0);
+        Some(Self::new(major, minor, patch))
+    }
+}
+
+impl fmt::Display for CliVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Oldest bw whose JSON has everything bwtui reads (re-prompt flags, password revision dates)
+pub const MIN_CLI_VERSION: CliVersion = CliVersion::new(2023, 1, 0);
+
+/// Newest bw bwtui was tested with, later releases may have changed their JSON
+pub const NEWEST_TESTED_CLI_VERSION: CliVersion = CliVersion::new(2026, 9, 0);
+
+/// How well bwtui is expected to work with a bw version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compatibility {
+    Supported,
+    TooOld(CliVersion),
+    Untested(CliVersion),
+    /// The version couldn't be parsed
+    Unknown,
+}
+
+impl Compatibility {
+    pub fn of(version: &str) -> Self {
+        match CliVersion::parse(version) {
+            Some(version) if version < MIN_CLI_VERSION => Compatibility::TooOld(version),
+            Some(version) if version > NEWEST_TESTED_CLI_VERSION => Compatibility::Untested(version),
+            Some(_) => Compatibility::Supported,
+            None => Compatibility::Unknown,
+        }
+    }
+
+    /// What to tell the user, None when the version is supported
+    pub fn warning(self) -> Option<String> {
+        match self {
+            Compatibility::Supported => None,
+            Compatibility::TooOld(version) => Some(format!(
+                "bw {} is older than {}, some fields may be missing, update with npm install -g @bitwarden/cli",
+                version, MIN_CLI_VERSION
+            )),
+            Compatibility::Untested(version) => Some(format!(
+                "bw {} is newer than the tested {}, report any problems reading the vault",
+                version, NEWEST_TESTED_CLI_VERSION
+            )),
+            Compatibility::Unknown => Some("Couldn't read the bw version".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_versions() {
+        assert_eq!(CliVersion::parse("2024.6.0\n"), Some(CliVersion::new(2024, 6, 0)));
+        assert_eq!(CliVersion::parse("v1.22.1"), Some(CliVersion::new(1, 22, 1)));
+        assert_eq!(CliVersion::parse("2025.1.3-beta"), Some(CliVersion::new(2025, 1, 3)));
+        assert_eq!(CliVersion::parse("2024.2"), Some(CliVersion::new(2024, 2, 0)));
+        assert_eq!(CliVersion::parse("unknown"), None);
+        assert!(CliVersion::new(2024, 10, 0) > CliVersion::new(2024, 9, 1));
+    }
+
+    #[test]
+    fn test_compatibility() {
+        assert_eq!(Compatibility::of("2024.6.0"), Compatibility::Supported);
+        assert_eq!(Compatibility::of("1.22.1"), Compatibility::TooOld(CliVersion::new(1, 22, 1)));
+        assert_eq!(Compatibility::of("2099.1.0"), Compatibility::Untested(CliVersion::new(2099, 1, 0)));
+        assert_eq!(Compatibility::of(""), Compatibility::Unknown);
+        assert!(Compatibility::of("2024.6.0").warning().is_none());
+        assert!(Compatibility::of("1.0.0").warning().unwrap().contains("older than"));
+    }
+}
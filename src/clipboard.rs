@@ -1,33 +1,162 @@
 use arboard::Clipboard;
 use crate::error::{BwError, Result};
+use sha2::{Digest, Sha256};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Digest of the last text copied, so a crash can wipe it without clobbering something copied elsewhere later
+static LAST_COPIED: Mutex<Option<[u8; 32]>> = Mutex::new(None);
+
+/// A selection that copied text can be written to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardTarget {
+    /// The regular clipboard (Ctrl+V)
+    Clipboard,
+    /// The primary selection (middle-click paste), only available on Linux
+    Primary,
+}
 
 pub struct ClipboardManager {
     clipboard: Clipboard,
+    targets: Vec<ClipboardTarget>,
+    clear_after: Option<Duration>, // How long copied text stays in the clipboard
+    copied_at: Option<Instant>,
 }
 
 impl ClipboardManager {
     pub fn new() -> Result<Self> {
+        Self::with_targets(vec![ClipboardTarget::Clipboard])
+    }
+
+    /// Create a clipboard manager that copies to the given targets
+    pub fn with_targets(targets: Vec<ClipboardTarget>) -> Result<Self> {
         let clipboard = Clipboard::new()
             .map_err(|e| {
                 let error_msg = format!("Failed to initialize clipboard: {}", e);
                 crate::logger::Logger::error(&error_msg);
                 BwError::ClipboardError(e.to_string())
             })?;
-        
-        crate::logger::Logger::info("Clipboard initialized successfully");
-        Ok(Self { clipboard })
+
+        crate::logger::Logger::info(&format!("Clipboard initialized successfully (targets: {:?})", targets));
+        Ok(Self { clipboard, targets, clear_after: None, copied_at: None })
+    }
+
+    /// Create a clipboard manager from the user configuration
+    pub fn from_config(config: &crate::config::ClipboardConfig) -> Result<Self> {
+        let mut targets = vec![ClipboardTarget::Clipboard];
+        if config.primary_selection {
+            targets.push(ClipboardTarget::Primary);
+        }
+        let mut manager = Self::with_targets(targets)?;
+        manager.clear_after = Some(Duration::from_secs(config.clear_after_secs)).filter(|after| !after.is_zero());
+        Ok(manager)
     }
 
+    /// Copy text to every configured target
+    /// Only the first target is required to succeed, the others are best effort
     pub fn copy(&mut self, text: &str) -> Result<()> {
-        self.clipboard
-            .set_text(text)
-            .map_err(|e| {
-                let error_msg = format!("Failed to copy to clipboard: {}", e);
-                crate::logger::Logger::error(&error_msg);
-                BwError::ClipboardError(e.to_string())
-            })?;
-        
-        Ok(())
+        remember_copied(text);
+        self.copied_at = Some(Instant::now());
+        let targets = self.targets.clone();
+        let mut result = Ok(());
+        for (index, target) in targets.into_iter().enumerate() {
+            match self.copy_to(text, target) {
+                Ok(()) => {}
+                Err(e) if index == 0 => result = Err(e),
+                Err(e) => {
+                    crate::logger::Logger::warn(&format!("Failed to copy to {:?}: {}", target, e));
+                }
+            }
+        }
+        result
+    }
+
+    /// Copy text to a single target
+    pub fn copy_to(&mut self, text: &str, target: ClipboardTarget) -> Result<()> {
+        let result = match target {
+            ClipboardTarget::Clipboard => self.clipboard.set_text(text),
+            ClipboardTarget::Primary => self.set_primary(text),
+        };
+
+        result.map_err(|e| {
+            let error_msg = format!("Failed to copy to {:?}: {}", target, e);
+            crate::logger::Logger::error(&error_msg);
+            BwError::ClipboardError(e.to_string())
+        })
+    }
+
+    /// Read the text in the clipboard
+    pub fn paste(&mut self) -> Result<String> {
+        self.clipboard.get_text().map_err(|e| {
+            crate::logger::Logger::error(&format!("Failed to read clipboard: {}", e));
+            BwError::ClipboardError(e.to_string())
+        })
+    }
+
+    /// When the last copied text will be cleared, if auto-clear is on
+    pub fn clears_at(&self) -> Option<Instant> {
+        Some(self.copied_at? + self.clear_after?)
+    }
+
+    /// Clear the clipboard once the auto-clear time has passed
+    /// Returns whether it was cleared, text copied by another application since is left alone
+    pub fn clear_if_due(&mut self, now: Instant) -> Result<bool> {
+        if self.clears_at().is_none_or(|clears_at| clears_at > now) {
+            return Ok(false);
+        }
+        self.copied_at = None;
+
+        if !self.clipboard.get_text().is_ok_and(|text| is_last_copied(&text)) {
+            return Ok(false);
+        }
+        self.clipboard.clear().map_err(|e| {
+            crate::logger::Logger::error(&format!("Failed to clear clipboard: {}", e));
+            BwError::ClipboardError(e.to_string())
+        })?;
+        if self.targets.contains(&ClipboardTarget::Primary) {
+            if let Err(e) = self.copy_to("", ClipboardTarget::Primary) {
+                crate::logger::Logger::warn(&format!("Failed to clear primary selection: {}", e));
+            }
+        }
+        crate::logger::Logger::info("Clipboard cleared after timeout");
+        Ok(true)
+    }
+
+    #[cfg(all(unix, not(any(target_os = "macos", target_os = "android", target_os = "emscripten"))))]
+    fn set_primary(&mut self, text: &str) -> std::result::Result<(), arboard::Error> {
+        use arboard::{LinuxClipboardKind, SetExtLinux};
+        self.clipboard.set().clipboard(LinuxClipboardKind::Primary).text(text)
+    }
+
+    #[cfg(not(all(unix, not(any(target_os = "macos", target_os = "android", target_os = "emscripten")))))]
+    fn set_primary(&mut self, _text: &str) -> std::result::Result<(), arboard::Error> {
+        Err(arboard::Error::ClipboardNotSupported)
+    }
+}
+
+fn digest(text: &str) -> [u8; 32] {
+    Sha256::digest(text.as_bytes()).into()
+}
+
+fn remember_copied(text: &str) {
+    *LAST_COPIED.lock().unwrap_or_else(|e| e.into_inner()) = Some(digest(text));
+}
+
+/// Whether the text is what bwtui copied last
+fn is_last_copied(text: &str) -> bool {
+    *LAST_COPIED.lock().unwrap_or_else(|e| e.into_inner()) == Some(digest(text))
+}
+
+/// Clear the clipboard if it still holds the last text copied by bwtui
+/// Used from the panic hook, so it opens its own clipboard connection
+pub fn clear_last_copied() -> Result<bool> {
+    let mut clipboard = Clipboard::new().map_err(|e| BwError::ClipboardError(e.to_string()))?;
+    match clipboard.get_text() {
+        Ok(text) if is_last_copied(&text) => {
+            clipboard.clear().map_err(|e| BwError::ClipboardError(e.to_string()))?;
+            Ok(true)
+        }
+        _ => Ok(false),
     }
 }
 
@@ -37,4 +166,18 @@ impl Default for ClipboardManager {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_the_last_copied_text_is_recognized() {
+        remember_copied("hunter2");
+        assert!(is_last_copied("hunter2"));
+        assert!(!is_last_copied("something else"));
 
+        remember_copied("123456");
+        assert!(!is_last_copied("hunter2"));
+        assert!(is_last_copied("123456"));
+    }
+}
@@ -0,0 +1,691 @@
+use crate::error::{BwError, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// User configuration loaded from ~/.bwtui/config.toml (or the selected profile's directory)
+/// Missing keys fall back to their defaults, so an empty file is valid
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub clipboard: ClipboardConfig,
+    pub cli: CliConfig,
+    pub backend: BackendConfig,
+    pub mouse: MouseConfig,
+    pub keys: KeysConfig,
+    pub search: SearchConfig,
+    pub layout: LayoutConfig,
+    pub list: ListConfig,
+    pub cache: CacheConfig,
+    pub accessibility: AccessibilityConfig,
+    pub unlock: UnlockConfig,
+    pub logging: LoggingConfig,
+    /// Saved views, `[[views]]` tables switched between with Alt+E
+    pub views: Vec<ViewConfig>,
+}
+
+/// Clipboard behaviour
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClipboardConfig {
+    /// Also copy to the primary selection (Linux only), so middle-click paste works
+    pub primary_selection: bool,
+    /// Clear the clipboard on a crash if it still holds a value copied by bwtui
+    pub clear_on_panic: bool,
+    /// Seconds after a copy until the clipboard is cleared (0 = never)
+    pub clear_after_secs: u64,
+}
+
+impl Default for ClipboardConfig {
+    fn default() -> Self {
+        Self {
+            primary_selection: false,
+            clear_on_panic: true,
+            clear_after_secs: 0,
+        }
+    }
+}
+
+/// Bitwarden CLI behaviour
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CliConfig {
+    /// Maximum number of seconds a single bw command may run before it is killed
+    pub timeout_secs: u64,
+    /// Seconds between session checks while the vault is idle (0 = off)
+    pub keep_alive_secs: u64,
+    /// Path to the bw executable, found on the PATH if unset
+    pub bw_path: Option<String>,
+    /// Data directory bw uses instead of its default (BITWARDENCLI_APPDATA_DIR)
+    pub appdata_dir: Option<String>,
+}
+
+impl Default for CliConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: crate::cli::DEFAULT_TIMEOUT.as_secs(),
+            keep_alive_secs: 300,
+            bw_path: None,
+            appdata_dir: None,
+        }
+    }
+}
+
+impl CliConfig {
+    /// The command timeout, ignoring a zero value which would fail every command
+    pub fn timeout(&self) -> std::time::Duration {
+        if self.timeout_secs == 0 {
+            crate::cli::DEFAULT_TIMEOUT
+        } else {
+            std::time::Duration::from_secs(self.timeout_secs)
+        }
+    }
+
+    /// The bw executable to run
+    pub fn bw_program(&self) -> PathBuf {
+        self.bw_path.as_deref().map_or_else(|| PathBuf::from("bw"), expand_home)
+    }
+
+    /// The data directory to pass to bw, if configured
+    /// An unset one leaves BITWARDENCLI_APPDATA_DIR from the environment in effect
+    pub fn appdata_dir(&self) -> Option<PathBuf> {
+        self.appdata_dir.as_deref().map(expand_home)
+    }
+
+    /// The interval between session checks, if enabled
+    pub fn keep_alive_interval(&self) -> Option<std::time::Duration> {
+        (self.keep_alive_secs > 0).then(|| std::time::Duration::from_secs(self.keep_alive_secs))
+    }
+}
+
+/// Where vault data comes from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackendConfig {
+    /// Run the bw CLI, or talk to the server directly
+    #[serde(rename = "type")]
+    pub kind: BackendKind,
+    /// Server the api backend logs in to
+    pub server_url: String,
+    /// Account email for the api backend
+    pub email: String,
+    /// Personal API key, used to log in instead of the master password
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    /// Number of synthetic items served by the demo backend
+    #[cfg(feature = "demo")]
+    #[serde(skip)]
+    pub demo_items: usize,
+}
+
+impl Default for BackendConfig {
+    fn default() -> Self {
+        Self {
+            kind: BackendKind::Cli,
+            server_url: "https://vault.bitwarden.com".to_string(),
+            email: String::new(),
+            client_id: None,
+            client_secret: None,
+            #[cfg(feature = "demo")]
+            demo_items: 0,
+        }
+    }
+}
+
+/// Source of vault data
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackendKind {
+    /// The bw CLI, which must be installed and logged in
+    #[default]
+    Cli,
+    /// The Bitwarden (or Vaultwarden) server API, decrypting the vault locally
+    Api,
+    /// A synthetic vault, selected with --demo
+    #[cfg(feature = "demo")]
+    #[serde(skip)]
+    Demo,
+}
+
+/// Mouse behaviour
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MouseConfig {
+    /// What the mouse wheel does over the entry list
+    pub list_wheel: ListWheelMode,
+}
+
+/// Mouse wheel behaviour over the entry list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ListWheelMode {
+    /// Move the selection up and down
+    #[default]
+    Select,
+    /// Scroll the list without changing the selection
+    Scroll,
+}
+
+/// Keyboard behaviour
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeysConfig {
+    /// Key bindings to use
+    pub scheme: KeyScheme,
+}
+
+/// Key binding schemes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyScheme {
+    /// Typing always searches, commands use Ctrl and Alt
+    #[default]
+    Default,
+    /// Vim-like modes: starts in normal mode (j/k, gg/G, yu/yp/yt), `/` or `i` to search
+    Vim,
+}
+
+/// Search behaviour
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchConfig {
+    /// What happens when typing narrows the results to a single item
+    pub single_result: SingleResultAction,
+}
+
+/// Action run on the only search result, which is always selected
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SingleResultAction {
+    /// Only select it
+    #[default]
+    Select,
+    /// Copy its password
+    CopyPassword,
+    /// Open the details panel
+    OpenDetails,
+}
+
+/// Smallest share of the width the entry list can be resized to
+pub const MIN_LIST_PERCENT: u16 = 20;
+
+/// Largest share of the width the entry list can be resized to
+pub const MAX_LIST_PERCENT: u16 = 80;
+
+/// Panel layout
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LayoutConfig {
+    /// Share of the space given to the entry list when the details panel is open
+    pub list_percent: u16,
+    /// How the entry list and details panel are arranged
+    pub mode: LayoutMode,
+    /// Terminal width below which the auto mode stacks the panels
+    pub stack_below_width: u16,
+    /// Borderless panels, a one-line search box and status bar and no tab bar, for small popups
+    pub compact: bool,
+    /// Most redraws per second, when many events arrive at once like a held key
+    pub max_fps: u32,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            list_percent: 50,
+            mode: LayoutMode::Auto,
+            stack_below_width: 100,
+            compact: false,
+            max_fps: 60,
+        }
+    }
+}
+
+/// Arrangement of the entry list and details panel
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LayoutMode {
+    /// Side by side, stacked when the terminal is narrower than `stack_below_width`
+    #[default]
+    Auto,
+    /// Always side by side
+    SideBySide,
+    /// Always stacked, list above details
+    Stacked,
+}
+
+/// Entry list contents
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ListConfig {
+    /// Show each item's folder as a muted suffix in its row
+    pub show_folder: bool,
+    /// Highlight logins whose password is older than this many days (0 = off)
+    pub stale_password_days: u32,
+    /// Glyphs used for item type and folder icons
+    pub icons: IconSet,
+}
+
+/// Icon glyphs, for terminals and fonts that can't show emoji
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IconSet {
+    #[default]
+    Emoji,
+    /// Icons from a patched Nerd Font
+    NerdFont,
+    Ascii,
+}
+
+/// Vault cache behaviour
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Days after which the cache counts as expired (0 = never)
+    pub max_age_days: u32,
+    /// Don't show an expired cache at all instead of warning about it
+    pub ignore_expired: bool,
+}
+
+impl CacheConfig {
+    /// Whether a cache written at `cached_at` is older than the maximum age
+    pub fn is_expired(&self, cached_at: chrono::DateTime<chrono::Utc>, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.max_age_days > 0 && now - cached_at > chrono::Duration::days(self.max_age_days as i64)
+    }
+}
+
+/// Accessibility settings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccessibilityConfig {
+    /// Use ASCII labels instead of symbols, higher contrast colors and spell out what colors convey
+    pub enabled: bool,
+}
+
+/// A named combination of filters, e.g. a "Work" view of the work organization without cards
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ViewConfig {
+    pub name: String,
+    /// Tab to select, all items if unset
+    #[serde(default, rename = "type")]
+    pub item_type: Option<crate::args::TabArg>,
+    /// Item types left out of every tab
+    #[serde(default)]
+    pub hide_types: Vec<crate::args::TabArg>,
+    /// Organization name, or "personal" for the items outside organizations
+    #[serde(default)]
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub folder: Option<String>,
+    /// Search text, e.g. "tag:work"
+    #[serde(default)]
+    pub query: Option<String>,
+}
+
+/// Master password prompt behaviour
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UnlockConfig {
+    /// Exit after this many wrong master passwords in a row (0 = never)
+    pub max_attempts: u32,
+}
+
+/// Log file settings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// Most verbose level written to the log file
+    pub level: LogLevel,
+    /// How each record is written
+    pub format: LogFormat,
+}
+
+/// Log levels, from least to most verbose
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    #[default]
+    Info,
+    /// Also includes the debug output of libraries, such as HTTP requests of the API backend
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub fn filter(self) -> log::LevelFilter {
+        match self {
+            LogLevel::Off => log::LevelFilter::Off,
+            LogLevel::Error => log::LevelFilter::Error,
+            LogLevel::Warn => log::LevelFilter::Warn,
+            LogLevel::Info => log::LevelFilter::Info,
+            LogLevel::Debug => log::LevelFilter::Debug,
+            LogLevel::Trace => log::LevelFilter::Trace,
+        }
+    }
+}
+
+/// Log record formats
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    /// One JSON object per line, with time, level, target, module, file, line and message fields
+    Json,
+}
+
+impl LayoutConfig {
+    /// The list share, clamped to a usable range
+    pub fn list_percent(&self) -> u16 {
+        self.list_percent.clamp(MIN_LIST_PERCENT, MAX_LIST_PERCENT)
+    }
+}
+
+impl Config {
+    /// Override settings given on the command line
+    pub fn apply_args(&mut self, args: &crate::args::Args) {
+        if let Some(bw_path) = &args.bw_path {
+            self.cli.bw_path = Some(bw_path.clone());
+        }
+        if let Some(appdata_dir) = &args.bw_appdata_dir {
+            self.cli.appdata_dir = Some(appdata_dir.clone());
+        }
+        if args.compact {
+            self.layout.compact = true;
+        }
+        #[cfg(feature = "demo")]
+        if let Some(count) = args.demo {
+            self.backend.kind = BackendKind::Demo;
+            self.backend.demo_items = count;
+        }
+    }
+
+    /// Load the configuration, falling back to defaults if it is missing or invalid
+    pub fn load() -> Self {
+        let path = match get_config_path() {
+            Ok(path) => path,
+            Err(e) => {
+                crate::logger::Logger::warn(&format!("Failed to locate config file: {}", e));
+                return Self::default();
+            }
+        };
+
+        match Self::load_from(&path) {
+            Ok(Some(config)) => {
+                crate::logger::Logger::info("Configuration loaded");
+                config
+            }
+            Ok(None) => Self::default(),
+            Err(e) => {
+                crate::logger::Logger::warn(&format!("Failed to load config, using defaults: {}", e));
+                Self::default()
+            }
+        }
+    }
+
+    /// Load the configuration from a file, returning None if it doesn't exist
+    fn load_from(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let contents = fs::read_to_string(path)?;
+        Self::parse(&contents).map(Some)
+    }
+
+    /// Parse the configuration from TOML
+    fn parse(contents: &str) -> Result<Self> {
+        toml::from_str(contents)
+            .map_err(|e| BwError::ParseError(format!("Invalid config file: {}", e)))
+    }
+}
+
+/// Store the entry list share in the config file, keeping the rest of the file intact
+/// In ephemeral mode the share only lasts until exit
+pub fn save_list_percent(list_percent: u16) -> Result<()> {
+    if crate::profile::ephemeral() {
+        return Ok(());
+    }
+    let path = get_config_path()?;
+    let contents = if path.exists() {
+        fs::read_to_string(&path)?
+    } else {
+        String::new()
+    };
+
+    let updated = set_list_percent(&contents, list_percent)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, updated)?;
+    Ok(())
+}
+
+/// Update the layout section of a config file, preserving comments and other settings
+fn set_list_percent(contents: &str, list_percent: u16) -> Result<String> {
+    let mut document: toml_edit::DocumentMut = contents
+        .parse()
+        .map_err(|e| BwError::ParseError(format!("Invalid config file: {}", e)))?;
+
+    if !document.contains_table("layout") {
+        document["layout"] = toml_edit::table();
+    }
+    document["layout"]["list_percent"] = toml_edit::value(i64::from(list_percent));
+    Ok(document.to_string())
+}
+
+/// Expand a leading `~/` to the home directory
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home_dir)) => home_dir.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Get the config file path
+fn get_config_path() -> Result<PathBuf> {
+    Ok(crate::profile::data_dir()?.join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_config_uses_defaults() {
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+        assert!(!Config::default().clipboard.primary_selection);
+    }
+
+    #[test]
+    fn test_parse_clipboard_config() {
+        let config = Config::parse("[clipboard]\nprimary_selection = true\n").unwrap();
+        assert!(config.clipboard.primary_selection);
+        assert!(config.clipboard.clear_on_panic);
+
+        let config = Config::parse("[clipboard]\nclear_on_panic = false\nclear_after_secs = 30\n").unwrap();
+        assert!(!config.clipboard.clear_on_panic);
+        assert_eq!(config.clipboard.clear_after_secs, 30);
+    }
+
+    #[test]
+    fn test_parse_cli_timeout() {
+        assert_eq!(Config::default().cli.timeout(), crate::cli::DEFAULT_TIMEOUT);
+
+        let config = Config::parse("[cli]\ntimeout_secs = 15\n").unwrap();
+        assert_eq!(config.cli.timeout(), std::time::Duration::from_secs(15));
+
+        let config = Config::parse("[cli]\ntimeout_secs = 0\n").unwrap();
+        assert_eq!(config.cli.timeout(), crate::cli::DEFAULT_TIMEOUT);
+    }
+
+    #[test]
+    fn test_parse_backend() {
+        assert_eq!(Config::default().backend.kind, BackendKind::Cli);
+
+        let config = Config::parse("[backend]\ntype = \"api\"\nserver_url = \"https://vw.example.com\"\nemail = \"me@example.com\"\n").unwrap();
+        assert_eq!(config.backend.kind, BackendKind::Api);
+        assert_eq!(config.backend.server_url, "https://vw.example.com");
+        assert_eq!(config.backend.email, "me@example.com");
+        assert_eq!(config.backend.client_id, None);
+    }
+
+    #[test]
+    fn test_parse_cli_paths() {
+        let config = Config::default();
+        assert_eq!(config.cli.bw_program(), PathBuf::from("bw"));
+        assert_eq!(config.cli.appdata_dir(), None);
+
+        let config = Config::parse("[cli]\nbw_path = \"/snap/bin/bw\"\nappdata_dir = \"~/bw-work\"\n").unwrap();
+        assert_eq!(config.cli.bw_program(), PathBuf::from("/snap/bin/bw"));
+        assert_eq!(config.cli.appdata_dir(), dirs::home_dir().map(|home| home.join("bw-work")));
+    }
+
+    #[test]
+    fn test_parse_cli_keep_alive() {
+        assert_eq!(Config::default().cli.keep_alive_interval(), Some(std::time::Duration::from_secs(300)));
+
+        let config = Config::parse("[cli]\nkeep_alive_secs = 0\n").unwrap();
+        assert_eq!(config.cli.keep_alive_interval(), None);
+    }
+
+    #[test]
+    fn test_parse_mouse_config() {
+        assert_eq!(Config::default().mouse.list_wheel, ListWheelMode::Select);
+
+        let config = Config::parse("[mouse]\nlist_wheel = \"scroll\"\n").unwrap();
+        assert_eq!(config.mouse.list_wheel, ListWheelMode::Scroll);
+    }
+
+    #[test]
+    fn test_parse_key_scheme() {
+        assert_eq!(Config::default().keys.scheme, KeyScheme::Default);
+
+        let config = Config::parse("[keys]\nscheme = \"vim\"\n").unwrap();
+        assert_eq!(config.keys.scheme, KeyScheme::Vim);
+    }
+
+    #[test]
+    fn test_parse_single_result_action() {
+        assert_eq!(Config::default().search.single_result, SingleResultAction::Select);
+
+        let config = Config::parse("[search]\nsingle_result = \"copy_password\"\n").unwrap();
+        assert_eq!(config.search.single_result, SingleResultAction::CopyPassword);
+    }
+
+    #[test]
+    fn test_layout_list_percent_is_clamped() {
+        assert_eq!(Config::default().layout.list_percent(), 50);
+
+        let config = Config::parse("[layout]\nlist_percent = 95\n").unwrap();
+        assert_eq!(config.layout.list_percent(), MAX_LIST_PERCENT);
+    }
+
+    #[test]
+    fn test_parse_layout_mode() {
+        assert_eq!(Config::default().layout.mode, LayoutMode::Auto);
+
+        let config = Config::parse("[layout]\nmode = \"side-by-side\"\nstack_below_width = 80\n").unwrap();
+        assert_eq!(config.layout.mode, LayoutMode::SideBySide);
+        assert_eq!(config.layout.stack_below_width, 80);
+        assert!(!config.layout.compact);
+        assert!(Config::parse("[layout]\ncompact = true\n").unwrap().layout.compact);
+        assert_eq!(Config::default().layout.max_fps, 60);
+        assert_eq!(Config::parse("[layout]\nmax_fps = 30\n").unwrap().layout.max_fps, 30);
+    }
+
+    #[test]
+    fn test_parse_logging_config() {
+        assert_eq!(Config::default().logging.level.filter(), log::LevelFilter::Info);
+        assert_eq!(Config::default().logging.format, LogFormat::Text);
+
+        let config = Config::parse("[logging]\nlevel = \"debug\"\nformat = \"json\"\n").unwrap();
+        assert_eq!(config.logging.level, LogLevel::Debug);
+        assert_eq!(config.logging.format, LogFormat::Json);
+        assert!(Config::parse("[logging]\nlevel = \"loud\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_cache_config() {
+        let now = chrono::Utc::now();
+        let old = now - chrono::Duration::days(10);
+        assert!(!Config::default().cache.is_expired(old, now));
+
+        let config = Config::parse("[cache]\nmax_age_days = 7\nignore_expired = true\n").unwrap();
+        assert!(config.cache.ignore_expired);
+        assert!(config.cache.is_expired(old, now));
+        assert!(!config.cache.is_expired(now - chrono::Duration::days(3), now));
+    }
+
+    #[test]
+    fn test_parse_list_config() {
+        assert!(!Config::default().list.show_folder);
+
+        assert_eq!(Config::default().list.stale_password_days, 0);
+
+        let config = Config::parse("[list]\nshow_folder = true\nstale_password_days = 365\n").unwrap();
+        assert!(config.list.show_folder);
+        assert_eq!(config.list.stale_password_days, 365);
+        assert_eq!(config.list.icons, IconSet::Emoji);
+
+        let config = Config::parse("[list]\nicons = \"nerd-font\"\n").unwrap();
+        assert_eq!(config.list.icons, IconSet::NerdFont);
+        assert!(Config::parse("[list]\nicons = \"wingdings\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_accessibility_config() {
+        assert!(!Config::default().accessibility.enabled);
+
+        let config = Config::parse("[accessibility]\nenabled = true\n").unwrap();
+        assert!(config.accessibility.enabled);
+    }
+
+    #[test]
+    fn test_parse_views() {
+        assert!(Config::default().views.is_empty());
+
+        let config = Config::parse(concat!(
+            "[[views]]\nname = \"Work\"\nowner = \"Acme\"\ntype = \"login\"\nhide_types = [\"card\"]\nquery = \"tag:work\"\n",
+            "[[views]]\nname = \"Personal\"\nowner = \"personal\"\n",
+        )).unwrap();
+        assert_eq!(config.views.len(), 2);
+        assert_eq!(config.views[0].item_type, Some(crate::args::TabArg::Login));
+        assert_eq!(config.views[0].hide_types, [crate::args::TabArg::Card]);
+        assert_eq!(config.views[0].query.as_deref(), Some("tag:work"));
+        assert_eq!(config.views[1].folder, None);
+
+        // A view needs a name
+        assert!(Config::parse("[[views]]\nowner = \"Acme\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_unlock_config() {
+        assert_eq!(Config::default().unlock.max_attempts, 0);
+
+        let config = Config::parse("[unlock]\nmax_attempts = 5\n").unwrap();
+        assert_eq!(config.unlock.max_attempts, 5);
+    }
+
+    #[test]
+    fn test_set_list_percent_preserves_other_settings() {
+        let contents = "# My settings\n[clipboard]\nprimary_selection = true\n";
+        let updated = set_list_percent(contents, 60).unwrap();
+        assert!(updated.starts_with("# My settings\n"));
+
+        let config = Config::parse(&updated).unwrap();
+        assert!(config.clipboard.primary_selection);
+        assert_eq!(config.layout.list_percent, 60);
+
+        // Updating an existing value replaces it
+        let config = Config::parse(&set_list_percent(&updated, 35).unwrap()).unwrap();
+        assert_eq!(config.layout.list_percent, 35);
+    }
+
+    #[test]
+    fn test_invalid_config_is_an_error() {
+        assert!(Config::parse("[clipboard]\nprimary_selection = \"yes\"\n").is_err());
+    }
+}
@@ -0,0 +1,249 @@
+use crate::backend::VaultStatus;
+use crate::cli::BitwardenCli;
+use crate::cli_version::{Compatibility, MIN_CLI_VERSION, NEWEST_TESTED_CLI_VERSION};
+use crate::clipboard::ClipboardManager;
+use crate::config::{BackendKind, Config};
+use crate::session::{SessionManager, SessionOptions};
+use std::io::IsTerminal;
+
+/// Result of a single check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// A checked part of the environment, with how to fix it when it doesn't pass
+#[derive(Debug)]
+struct Check {
+    name: &'static str,
+    outcome: Outcome,
+    detail: String,
+    hint: Option<&'static str>,
+}
+
+impl Check {
+    fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self { name, outcome: Outcome::Pass, detail: detail.into(), hint: None }
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>, hint: &'static str) -> Self {
+        Self { name, outcome: Outcome::Warn, detail: detail.into(), hint: Some(hint) }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, hint: &'static str) -> Self {
+        Self { name, outcome: Outcome::Fail, detail: detail.into(), hint: Some(hint) }
+    }
+
+    /// The report lines of the check
+    fn lines(&self) -> Vec<String> {
+        let label = match self.outcome {
+            Outcome::Pass => "PASS",
+            Outcome::Warn => "WARN",
+            Outcome::Fail => "FAIL",
+        };
+        let mut lines = vec![format!("[{}] {}: {}", label, self.name, self.detail)];
+        if let Some(hint) = self.hint {
+            lines.push(format!("       {}", hint));
+        }
+        lines
+    }
+}
+
+/// Check everything bwtui depends on and print a report, returning false if a check failed
+pub async fn run(config: &Config, session: &SessionOptions) -> bool {
+    let mut checks = Vec::new();
+
+    let cli_available = if config.backend.kind == BackendKind::Cli {
+        let check = check_cli(config).await;
+        let available = check.outcome != Outcome::Fail;
+        checks.push(check);
+        available
+    } else {
+        checks.push(Check::pass("bw CLI", "Not used by the api backend"));
+        false
+    };
+    checks.push(check_keyring(session));
+    checks.push(check_clipboard(config));
+    checks.push(check_cache(config));
+    checks.push(check_session(config, session, cli_available).await);
+    checks.push(check_terminal());
+
+    for check in &checks {
+        for line in check.lines() {
+            println!("{}", line);
+        }
+    }
+
+    let failed = checks.iter().filter(|check| check.outcome == Outcome::Fail).count();
+    let warned = checks.iter().filter(|check| check.outcome == Outcome::Warn).count();
+    println!();
+    println!("{} checks, {} failed, {} warnings", checks.len(), failed, warned);
+    crate::logger::Logger::info(&format!("Doctor: {} failed, {} warnings", failed, warned));
+    failed == 0
+}
+
+async fn check_cli(config: &Config) -> Check {
+    let cli = BitwardenCli::unverified(&config.cli);
+    match cli.version().await {
+        Ok(version) => {
+            let detail = format!("Version {} at {}", version, cli.program().display());
+            match Compatibility::of(&version) {
+                Compatibility::Supported => Check::pass("bw CLI", detail),
+                Compatibility::TooOld(_) => Check::warn(
+                    "bw CLI",
+                    format!("{}, older than {}", detail, MIN_CLI_VERSION),
+                    "Update it with `npm install -g @bitwarden/cli`",
+                ),
+                Compatibility::Untested(_) => Check::warn(
+                    "bw CLI",
+                    format!("{}, newer than the tested {}", detail, NEWEST_TESTED_CLI_VERSION),
+                    "If items are missing or fail to load, please report it with this version",
+                ),
+                Compatibility::Unknown => Check::warn("bw CLI", detail, "The version couldn't be read, is this the Bitwarden CLI?"),
+            }
+        }
+        Err(_) => Check::fail(
+            "bw CLI",
+            format!("Can't run {}", cli.program().display()),
+            "Install it with `npm install -g @bitwarden/cli`, or set bw_path under [cli]",
+        ),
+    }
+}
+
+fn check_keyring(session: &SessionOptions) -> Check {
+    if !session.use_keyring() {
+        return Check::pass("Keyring", "Not used with --no-keyring");
+    }
+    match SessionManager::check_keyring() {
+        Ok(()) => Check::pass("Keyring", "Available"),
+        Err(e) => Check::warn(
+            "Keyring",
+            e,
+            "Start a Secret Service provider (such as gnome-keyring), or use --no-keyring with BW_SESSION",
+        ),
+    }
+}
+
+fn check_clipboard(config: &Config) -> Check {
+    match ClipboardManager::from_config(&config.clipboard) {
+        Ok(_) => Check::pass("Clipboard", "Available"),
+        Err(e) => Check::fail(
+            "Clipboard",
+            e.to_string(),
+            "On Linux an X11 or Wayland session is needed, over SSH use X forwarding",
+        ),
+    }
+}
+
+fn check_cache(config: &Config) -> Check {
+    match crate::cache::load_cache() {
+        Ok(Some(data)) => {
+            let now = chrono::Utc::now();
+            let detail = format!("{} items, written {}", data.items.len(), crate::cache::describe_age(data.cached_at, now));
+            if config.cache.is_expired(data.cached_at, now) {
+                Check::warn("Cache", detail, "Older than max_age_days under [cache], it is replaced by the next sync")
+            } else {
+                Check::pass("Cache", detail)
+            }
+        }
+        Ok(None) => Check::pass("Cache", "None yet, it is written after the vault is loaded"),
+        Err(e) => Check::fail("Cache", e.to_string(), "Check the permissions of the ~/.bwtui directory"),
+    }
+}
+
+async fn check_session(config: &Config, session: &SessionOptions, cli_available: bool) -> Check {
+    #[cfg(not(feature = "api-backend"))]
+    if config.backend.kind == BackendKind::Api {
+        return Check::fail("Session", crate::backend::API_BACKEND_MISSING, "Rebuild bwtui with --features api-backend, or set type = \"cli\"");
+    }
+    if config.backend.kind == BackendKind::Api {
+        return Check::pass("Session", "The api backend asks for the master password at every start");
+    }
+    if !cli_available {
+        return Check::fail("Session", "Not checked without the bw CLI", "Fix the bw CLI first");
+    }
+
+    let has_token = matches!(session.load_token(), Ok(Some(_)));
+    let status = match BitwardenCli::new(&config.cli, session).await {
+        Ok(cli) => cli.check_status().await,
+        Err(e) => Err(e),
+    };
+    match status {
+        Ok(VaultStatus::Unlocked) => Check::pass("Session", "The vault is unlocked"),
+        Ok(VaultStatus::Locked) if has_token => Check::warn(
+            "Session",
+            "The session token no longer unlocks the vault",
+            "bwtui asks for the master password and replaces the saved token",
+        ),
+        Ok(VaultStatus::Locked) => Check::pass("Session", "Locked, bwtui asks for the master password"),
+        Ok(VaultStatus::Unauthenticated) => Check::fail("Session", "Not logged in", "Run `bw login` first"),
+        Err(e) => Check::fail("Session", e.to_string(), "Run `bw status` to see what bw reports"),
+    }
+}
+
+fn check_terminal() -> Check {
+    if !std::io::stdout().is_terminal() {
+        return Check::fail("Terminal", "Output is not a terminal", "Run bwtui in an interactive terminal");
+    }
+    let size = crossterm::terminal::size().ok();
+    let term = std::env::var("TERM").unwrap_or_default();
+    let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|name| std::env::var(name).ok().filter(|value| !value.is_empty()))
+        .unwrap_or_default();
+    terminal_check(size, &term, &colorterm, &locale)
+}
+
+/// Judge the terminal from its size, TERM, COLORTERM and the locale
+fn terminal_check(size: Option<(u16, u16)>, term: &str, colorterm: &str, locale: &str) -> Check {
+    let Some((width, height)) = size else {
+        return Check::fail("Terminal", "Can't read the terminal size", "Run bwtui in an interactive terminal");
+    };
+    let colors = if colorterm == "truecolor" || colorterm == "24bit" {
+        "true color"
+    } else if term.contains("256color") {
+        "256 colors"
+    } else {
+        "basic colors"
+    };
+    let detail = format!("{}x{}, {} ({})", width, height, colors, if term.is_empty() { "TERM unset" } else { term });
+
+    let locale = locale.to_ascii_lowercase();
+    if !locale.contains("utf-8") && !locale.contains("utf8") && !cfg!(windows) {
+        Check::warn("Terminal", detail, "The locale isn't UTF-8, set icons = \"ascii\" under [list] or enable [accessibility]")
+    } else if width < 60 || height < 15 {
+        Check::warn("Terminal", detail, "The window is small, some panels and dialogs may be cut off")
+    } else if term == "dumb" {
+        Check::warn("Terminal", detail, "TERM=dumb, colors and the cursor may not work")
+    } else {
+        Check::pass("Terminal", detail)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_lines() {
+        let check = Check::fail("Clipboard", "No display", "Use X forwarding");
+        assert_eq!(check.lines(), vec!["[FAIL] Clipboard: No display", "       Use X forwarding"]);
+        assert_eq!(Check::pass("Cache", "3 items").lines(), vec!["[PASS] Cache: 3 items"]);
+    }
+
+    #[test]
+    fn test_terminal_check() {
+        let check = terminal_check(Some((120, 40)), "xterm-256color", "truecolor", "en_US.UTF-8");
+        assert_eq!(check.outcome, Outcome::Pass);
+        assert_eq!(check.detail, "120x40, true color (xterm-256color)");
+
+        assert_eq!(terminal_check(Some((40, 10)), "xterm", "", "C.UTF-8").outcome, Outcome::Warn);
+        assert_eq!(terminal_check(None, "xterm", "", "C.UTF-8").outcome, Outcome::Fail);
+        if !cfg!(windows) {
+            assert_eq!(terminal_check(Some((120, 40)), "xterm", "", "C").outcome, Outcome::Warn);
+        }
+    }
+}
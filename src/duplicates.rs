@@ -0,0 +1,163 @@
+use crate::types::VaultItem;
+use std::collections::HashMap;
+
+/// Why a group of items was reported as duplicates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateKind {
+    /// Logins with the same username and URI
+    SameLogin,
+    /// Items with the same name
+    SameName,
+}
+
+impl DuplicateKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DuplicateKind::SameLogin => "Same login",
+            DuplicateKind::SameName => "Same name",
+        }
+    }
+}
+
+/// Items that look like duplicates of each other
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateGroup {
+    pub kind: DuplicateKind,
+    /// What the items have in common, for display
+    pub key: String,
+    /// IDs of the items, in vault order
+    pub item_ids: Vec<String>,
+}
+
+/// Group items with the same username and URI, or the same name
+/// A name group with the same items as a login group is not reported twice
+pub fn find_duplicates(items: &[VaultItem]) -> Vec<DuplicateGroup> {
+    let login_groups = group_by(items, DuplicateKind::SameLogin, |item| {
+        let username = item.username()?.trim();
+        let uri = item.primary_uri()?.uri.trim();
+        if username.is_empty() || uri.is_empty() {
+            return None;
+        }
+        Some(format!("{} @ {}", username.to_lowercase(), normalize_uri(uri)))
+    });
+
+    let name_groups = group_by(items, DuplicateKind::SameName, |item| {
+        let name = item.name.trim();
+        (!name.is_empty()).then(|| name.to_lowercase())
+    });
+
+    let mut groups = login_groups.clone();
+    groups.extend(name_groups.into_iter().filter(|group| {
+        !login_groups.iter().any(|login_group| login_group.item_ids == group.item_ids)
+    }));
+    groups
+}
+
+/// Group items sharing a key, keeping only groups with more than one item, sorted by key
+fn group_by(items: &[VaultItem], kind: DuplicateKind, key: impl Fn(&VaultItem) -> Option<String>) -> Vec<DuplicateGroup> {
+    let mut by_key: HashMap<String, Vec<String>> = HashMap::new();
+    for item in items {
+        if let Some(key) = key(item) {
+            by_key.entry(key).or_default().push(item.id.clone());
+        }
+    }
+
+    let mut groups: Vec<DuplicateGroup> = by_key
+        .into_iter()
+        .filter(|(_, item_ids)| item_ids.len() > 1)
+        .map(|(key, item_ids)| DuplicateGroup { kind, key, item_ids })
+        .collect();
+    groups.sort_by(|a, b| a.key.cmp(&b.key));
+    groups
+}
+
+/// Compare URIs regardless of scheme, case and trailing slash
+fn normalize_uri(uri: &str) -> String {
+    uri.trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_end_matches('/')
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ItemType, LoginData, Uri};
+
+    fn login(id: &str, name: &str, username: &str, uri: &str) -> VaultItem {
+        VaultItem {
+            id: id.to_string(),
+            name: name.to_string(),
+            item_type: ItemType::Login,
+            login: Some(LoginData {
+                username: Some(username.to_string()),
+                password: None,
+                totp: None,
+                uris: Some(vec![Uri { uri: uri.to_string(), match_type: None }]),
+                password_revision_date: None,
+            }),
+            card: None,
+            identity: None,
+            notes: None,
+            fields: None,
+            favorite: false,
+            folder_id: None,
+            organization_id: None,
+            revision_date: chrono::Utc::now(),
+            object: None,
+            creation_date: None,
+            deleted_date: None,
+            password_history: None,
+            attachments: None,
+            collection_ids: None,
+            reprompt: None,
+            raw_fields: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_groups_same_login() {
+        let items = vec![
+            login("1", "GitHub", "octocat", "https://github.com/"),
+            login("2", "GitHub (old)", "Octocat", "http://GitHub.com"),
+            login("3", "GitHub", "someone", "https://github.com"),
+            login("4", "Gmail", "octocat", "https://mail.google.com"),
+        ];
+
+        let groups = find_duplicates(&items);
+        assert_eq!(groups, vec![
+            DuplicateGroup {
+                kind: DuplicateKind::SameLogin,
+                key: "octocat @ github.com".to_string(),
+                item_ids: vec!["1".to_string(), "2".to_string()],
+            },
+            DuplicateGroup {
+                kind: DuplicateKind::SameName,
+                key: "github".to_string(),
+                item_ids: vec!["1".to_string(), "3".to_string()],
+            },
+        ]);
+    }
+
+    #[test]
+    fn test_same_items_are_reported_once() {
+        let items = vec![
+            login("1", "GitHub", "octocat", "https://github.com"),
+            login("2", "github ", "octocat", "https://github.com"),
+        ];
+
+        let groups = find_duplicates(&items);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].kind, DuplicateKind::SameLogin);
+    }
+
+    #[test]
+    fn test_no_duplicates() {
+        let items = vec![
+            login("1", "GitHub", "octocat", "https://github.com"),
+            login("2", "Gmail", "", "https://mail.google.com"),
+            login("3", "Bank", "", "https://mail.google.com"),
+        ];
+        assert!(find_duplicates(&items).is_empty());
+    }
+}
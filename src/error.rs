@@ -18,9 +18,17 @@ pub enum BwError {
     #[error("Failed to execute bw command: {0}")]
     CommandFailed(String),
 
+    #[error("{0}")]
+    CommandTimedOut(String),
+
     #[error("Failed to parse CLI output: {0}")]
     ParseError(String),
 
+    /// A feature left out of this build
+    #[cfg(not(feature = "api-backend"))]
+    #[error("{0}")]
+    Unsupported(String),
+
     #[error("Clipboard error: {0}")]
     ClipboardError(String),
     
@@ -30,3 +38,31 @@ pub enum BwError {
 
 pub type Result<T> = std::result::Result<T, BwError>;
 
+/// Fragments of `bw` error output that indicate a temporary network problem
+const TRANSIENT_ERROR_PATTERNS: &[&str] = &[
+    "etimedout",
+    "econnreset",
+    "econnrefused",
+    "enotfound",
+    "eai_again",
+    "socket hang up",
+    "fetch failed",
+    "network",
+    "timed out",
+    "502 bad gateway",
+    "503 service unavailable",
+    "504 gateway timeout",
+];
+
+impl BwError {
+    /// Whether the error is likely temporary and the command worth retrying
+    pub fn is_transient(&self) -> bool {
+        match self {
+            BwError::CommandFailed(message) => {
+                let message = message.to_lowercase();
+                TRANSIENT_ERROR_PATTERNS.iter().any(|pattern| message.contains(pattern))
+            }
+            _ => false,
+        }
+    }
+}
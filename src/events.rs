@@ -1,13 +1,17 @@
-use crossterm::event::{self, Event as CrosstermEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent, MouseEventKind};
-use std::time::Duration;
-use crate::state::AppState;
-use crate::ui::widgets::{details::DetailsClickHandler, entry_list::EntryListClickHandler, clickable::Clickable};
+use crossterm::event::{Event as CrosstermEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent, MouseEventKind};
+use crate::config::{Config, KeyScheme, ListWheelMode};
+use crate::state::{AppState, Focus, TextEdit};
+use crate::ui::widgets::clickable::is_click_in_area;
 
 #[derive(Debug, Clone)]
 pub enum Action {
     Quit,
     LockAndQuit, // Clear session token and quit
+    ConfirmQuit, // Quit even though an operation is in progress
+    CancelQuit,
+    Suspend, // Hand the terminal back to the shell until resumed with `fg`
     Tick, // Periodic update for TOTP countdown and other time-based updates
+    Resize, // The terminal was resized, scroll positions need re-clamping
 
     // Navigation
     MoveUp,
@@ -23,7 +27,14 @@ pub enum Action {
     // Filter
     AppendFilter(char),
     DeleteFilterChar,
+    EditFilter(TextEdit),
+    PasteFilter(String), // Text pasted into the terminal, handled as a whole
     ClearFilter,
+    ToggleFuzzyMatching,
+    ToggleCaseSensitive,
+    CycleOwnerFilter,
+    CycleView, // Switch to the next saved view of the config, then back to all items
+    ToggleShowHidden, // List the hidden items too
 
     // Actions
     CopyUsername,
@@ -31,8 +42,17 @@ pub enum Action {
     CopyTotp,
     CopyCardNumber,
     CopyCardCvv,
+    CopyCardExpiry,
+    CopyCardAll,
+    CopyIdentityName,
+    CopyIdentityAddress,
+    CopyIdentityPhone,
+    CopyIdentityEmail,
+    QuickCopyPassword(usize), // Copy the password of a top search result (position in the filtered list)
+    Pick, // Copy the `pick` field of the selected item and exit
     FetchTotp,
     Refresh,
+    CancelSync,
     ToggleDetailsPanel,
     OpenDetailsPanel,
 
@@ -40,11 +60,18 @@ pub enum Action {
     ScrollDetailsUp,
     ScrollDetailsDown,
 
+    // Entry list scrolling without changing the selection
+    ScrollListUp,
+    ScrollListDown,
+
     // Password input actions
     SubmitPassword,
     CancelPasswordInput,
     AppendPasswordChar(char),
     DeletePasswordChar,
+    EditPassword(TextEdit),
+    PastePassword(String),
+    PasteClipboardPassword, // Ctrl+V, for terminals without bracketed paste
     ClearPassword,
 
     // Save token actions
@@ -53,6 +80,112 @@ pub enum Action {
 
     // Details panel actions
     CloseDetailsPanel,
+    ToggleDetailsFullscreen,
+    ToggleRawNotes, // Show notes as written instead of rendering their markdown
+    ShrinkList,
+    GrowList,
+
+    // Status history popup
+    ToggleStatusHistory,
+    ToggleSyncDetails,
+    ScrollStatusHistoryUp,
+    ScrollStatusHistoryDown,
+
+    // Duplicate items report
+    ToggleDuplicates,
+    SelectNextDuplicate,
+    SelectPreviousDuplicate,
+    JumpToDuplicate,
+    RequestDeleteDuplicate,
+    ConfirmDeleteDuplicate,
+    CancelDeleteDuplicate,
+
+    // Who can access the selected organization item
+    ShowCollectionAccess,
+
+    // Master password re-prompt of the selected item
+    ToggleReprompt,
+
+    // Sharing an item with an organization
+    OpenShare,
+    CloseShare,
+    SelectNextShareCollection,
+    SelectPreviousShareCollection,
+    ToggleShareCollection,
+    RequestShare,
+    ConfirmShare,
+    CancelShare,
+
+    // Folder management screen
+    ToggleFolders,
+    SelectNextFolder,
+    SelectPreviousFolder,
+    FilterBySelectedFolder,
+    NewFolder,
+    RenameFolder,
+    SaveFolder,
+    CancelFolderEdit,
+    AppendFolderChar(char),
+    DeleteFolderChar,
+    EditFolderName(TextEdit),
+    PasteFolderName(String),
+    RequestDeleteFolder,
+    ConfirmDeleteFolder,
+    CancelDeleteFolder,
+    OpenMoveToFolder,
+    MoveToSelectedFolder,
+
+    // Local note dialog, also used for tags
+    OpenLocalNote,
+    OpenLocalTags,
+    SaveLocalNote,
+    CloseLocalNote,
+    AppendLocalNoteChar(char),
+    DeleteLocalNoteChar,
+    EditLocalNote(TextEdit),
+    PasteLocalNote(String),
+
+    // Hidden items
+    ToggleItemHidden,
+
+    // Tag list
+    ToggleTagList,
+    SelectNextTag,
+    SelectPreviousTag,
+    FilterBySelectedTag,
+
+    // TOTP dashboard
+    ToggleTotpDashboard,
+    SelectNextTotpRow,
+    SelectPreviousTotpRow,
+    CopyDashboardTotp, // Copy the code of the selected row
+
+    // Keyboard focus
+    FocusSearch,
+    FocusList,
+    KeyPrefix(Option<char>), // First key of a two-key sequence (vim scheme), None cancels it
+
+    // Details panel field focus
+    FocusDetails,
+    UnfocusDetails,
+    FocusNextField,
+    FocusPreviousField,
+    CopyFocusedField,
+    CopyDetailField(crate::state::DetailField), // Copy a field clicked in the details panel
+    ToggleRevealField, // Show or mask the focused hidden field
+
+    // QR code popup
+    ShowTotpQrCode,
+    ToggleTotpWatch, // Keep the selected item's TOTP code in the status bar while browsing
+    ShowFieldQrCode, // QR code of the focused details panel field
+    CloseQrCode,
+
+    // "Copy as" format picker
+    OpenCopyAs,
+    CloseCopyAs,
+    SelectNextCopyFormat,
+    SelectPreviousCopyFormat,
+    CopyItemAs(crate::item_format::CopyFormat),
 
     // Tab switching
     SelectItemTypeTab(Option<crate::types::ItemType>),
@@ -60,46 +193,58 @@ pub enum Action {
     CyclePreviousTab,
 }
 
-pub struct EventHandler;
+pub struct EventHandler {
+    list_wheel: ListWheelMode,
+    keys: KeyScheme,
+}
 
 impl EventHandler {
     pub fn new() -> Self {
-        Self
+        Self::from_config(&Config::default())
     }
 
-    /// Poll for next event with timeout
-    pub fn poll_event(&self, timeout: Duration, state: &AppState) -> std::io::Result<Option<Action>> {
-        if event::poll(timeout)? {
-            match event::read()? {
-                CrosstermEvent::Key(key) => {
-                    // Only process key press events, ignore key release and repeat events
-                    if key.kind == KeyEventKind::Press {
-                        if let Some(action) = self.handle_key(key, state) {
-                            return Ok(Some(action));
-                        }
-                        // If no action for this key, fall through to Tick
-                    }
-                }
-                CrosstermEvent::Mouse(mouse) => {
-                    if let Some(action) = self.handle_mouse(mouse, state) {
-                        return Ok(Some(action));
-                    }
-                    // If no action for this mouse event, fall through to Tick
-                }
-                _ => {}
-            }
+    /// Create an event handler from the user's mouse and keyboard configuration
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            list_wheel: config.mouse.list_wheel,
+            keys: config.keys.scheme,
+        }
+    }
+
+    /// Convert a terminal event to an action
+    pub fn handle_event(&self, event: CrosstermEvent, state: &AppState) -> Option<Action> {
+        match event {
+            // Only process key press events, ignore key release and repeat events
+            CrosstermEvent::Key(key) if key.kind == KeyEventKind::Press => self.handle_key(key, state),
+            CrosstermEvent::Mouse(mouse) => self.handle_mouse(mouse, state),
+            CrosstermEvent::Paste(text) => handle_paste(text, state),
+            CrosstermEvent::Resize(..) => Some(Action::Resize),
+            _ => None,
         }
-        // Return Tick action to ensure UI refreshes periodically
-        // This is important for updating TOTP countdown and other time-based displays
-        Ok(Some(Action::Tick))
     }
 
     /// Convert key event to action (unified mode)
     fn handle_key(&self, key: KeyEvent, state: &AppState) -> Option<Action> {
+        // Raw mode stops the terminal from suspending on Ctrl+Z, so it works in every mode
+        if key.code == KeyCode::Char('z') && key.modifiers == KeyModifiers::CONTROL {
+            return Some(Action::Suspend);
+        }
+
+        // Asked whether to quit in the middle of an operation, on top of any other dialog
+        if state.confirm_quit() {
+            return match (key.code, key.modifiers) {
+                (KeyCode::Char('y'), KeyModifiers::NONE) | (KeyCode::Char('Y'), _) | (KeyCode::Enter, _) => Some(Action::ConfirmQuit),
+                // Asking to quit again means it
+                (KeyCode::Char('q'), KeyModifiers::CONTROL) => Some(Action::ConfirmQuit),
+                (KeyCode::Char('n'), KeyModifiers::NONE) | (KeyCode::Char('N'), _) | (KeyCode::Esc, _) => Some(Action::CancelQuit),
+                _ => None,
+            };
+        }
+
         // Handle password input mode
         if state.password_input_mode() {
-            // If we're currently syncing (unlocking), only allow quit action
-            if state.syncing() {
+            // If we're currently unlocking or verifying a re-prompt, only allow quit action
+            if state.unlock_in_progress() {
                 return match (key.code, key.modifiers) {
                     // Quit application (Ctrl+C always works)
                     (KeyCode::Char('q'), KeyModifiers::CONTROL) => Some(Action::Quit),
@@ -113,16 +258,23 @@ impl EventHandler {
                 // Cancel
                 (KeyCode::Esc, _) => Some(Action::CancelPasswordInput),
                 // Delete character
+                (KeyCode::Backspace, KeyModifiers::ALT) => Some(Action::EditPassword(TextEdit::DeleteWord)),
                 (KeyCode::Backspace, _) => Some(Action::DeletePasswordChar),
+                (KeyCode::Char('v'), KeyModifiers::CONTROL) => Some(Action::PasteClipboardPassword),
+                // Shell-style cursor keys, nothing is copied from this dialog
+                (KeyCode::Char('a'), KeyModifiers::CONTROL) => Some(Action::EditPassword(TextEdit::Home)),
+                (KeyCode::Char('e'), KeyModifiers::CONTROL) => Some(Action::EditPassword(TextEdit::End)),
+                (KeyCode::Char('w'), KeyModifiers::CONTROL) => Some(Action::EditPassword(TextEdit::DeleteWord)),
                 // Clear password
                 (KeyCode::Char('x'), KeyModifiers::CONTROL) => Some(Action::ClearPassword),
                 // Quit application (Ctrl+C always works)
                 (KeyCode::Char('q'), KeyModifiers::CONTROL) => Some(Action::Quit),
-                // Any other printable character
+                // Any other printable character, AltGr arrives as Ctrl+Alt on Windows
                 (KeyCode::Char(c), KeyModifiers::NONE) | (KeyCode::Char(c), KeyModifiers::SHIFT) => {
                     Some(Action::AppendPasswordChar(c))
                 }
-                _ => None,
+                (KeyCode::Char(c), modifiers) if is_alt_gr(modifiers) => Some(Action::AppendPasswordChar(c)),
+                _ => text_edit(key).map(Action::EditPassword),
             };
         }
 
@@ -149,6 +301,273 @@ impl EventHandler {
             };
         }
 
+        // Handle status history popup
+        if state.show_status_history() {
+            return match (key.code, key.modifiers) {
+                (KeyCode::Esc, _) | (KeyCode::Char('g'), KeyModifiers::CONTROL) => Some(Action::ToggleStatusHistory),
+                (KeyCode::Up, _) | (KeyCode::Char('k'), KeyModifiers::CONTROL) => Some(Action::ScrollStatusHistoryUp),
+                (KeyCode::Down, _) | (KeyCode::Char('j'), KeyModifiers::CONTROL) => Some(Action::ScrollStatusHistoryDown),
+                (KeyCode::Char('q'), KeyModifiers::CONTROL) => Some(Action::Quit),
+                _ => None,
+            };
+        }
+
+        // Handle sync status popup
+        if state.show_sync_details() {
+            return match (key.code, key.modifiers) {
+                (KeyCode::Esc, _) | (KeyCode::Char('y'), KeyModifiers::ALT) => Some(Action::ToggleSyncDetails),
+                (KeyCode::Char('r'), KeyModifiers::NONE) | (KeyCode::Char('r'), KeyModifiers::CONTROL) => Some(Action::Refresh),
+                (KeyCode::Char('q'), KeyModifiers::CONTROL) => Some(Action::Quit),
+                _ => None,
+            };
+        }
+
+        // Handle QR code popup
+        if state.qr_popup().is_some() {
+            return match (key.code, key.modifiers) {
+                (KeyCode::Esc, _) | (KeyCode::Enter, _) | (KeyCode::Char('q'), KeyModifiers::ALT) => Some(Action::CloseQrCode),
+                (KeyCode::Char('q'), KeyModifiers::CONTROL) => Some(Action::Quit),
+                _ => None,
+            };
+        }
+
+        // Handle "copy as" format picker
+        if state.copy_as_open() {
+            return match (key.code, key.modifiers) {
+                (KeyCode::Esc, _) | (KeyCode::Char('s'), KeyModifiers::ALT) => Some(Action::CloseCopyAs),
+                (KeyCode::Up, _) | (KeyCode::Char('k'), KeyModifiers::CONTROL) => Some(Action::SelectPreviousCopyFormat),
+                (KeyCode::Down, _) | (KeyCode::Char('j'), KeyModifiers::CONTROL) => Some(Action::SelectNextCopyFormat),
+                (KeyCode::Enter, _) => state.selected_copy_format().map(Action::CopyItemAs),
+                // Number keys pick a format directly
+                (KeyCode::Char(c @ '1'..='9'), KeyModifiers::NONE) => {
+                    let index = c as usize - '1' as usize;
+                    state.copy_formats().get(index).copied().map(Action::CopyItemAs)
+                }
+                (KeyCode::Char('q'), KeyModifiers::CONTROL) => Some(Action::Quit),
+                _ => None,
+            };
+        }
+
+        // Handle duplicate items report
+        if state.show_duplicates() {
+            if state.duplicates.confirm_delete {
+                return match (key.code, key.modifiers) {
+                    (KeyCode::Char('y'), _) | (KeyCode::Char('Y'), _) => Some(Action::ConfirmDeleteDuplicate),
+                    (KeyCode::Char('q'), KeyModifiers::CONTROL) => Some(Action::Quit),
+                    _ => Some(Action::CancelDeleteDuplicate), // Anything else keeps the item
+                };
+            }
+            return match (key.code, key.modifiers) {
+                (KeyCode::Esc, _) | (KeyCode::Char('d'), KeyModifiers::ALT) => Some(Action::ToggleDuplicates),
+                (KeyCode::Up, _) | (KeyCode::Char('k'), KeyModifiers::CONTROL) => Some(Action::SelectPreviousDuplicate),
+                (KeyCode::Down, _) | (KeyCode::Char('j'), KeyModifiers::CONTROL) => Some(Action::SelectNextDuplicate),
+                (KeyCode::Enter, _) => Some(Action::JumpToDuplicate),
+                (KeyCode::Delete, _) => Some(Action::RequestDeleteDuplicate),
+                (KeyCode::Char('q'), KeyModifiers::CONTROL) => Some(Action::Quit),
+                _ => None,
+            };
+        }
+
+        // Handle the folder management screen
+        if state.show_folders() {
+            if state.folders.confirm_delete {
+                return match (key.code, key.modifiers) {
+                    (KeyCode::Char('y'), _) | (KeyCode::Char('Y'), _) => Some(Action::ConfirmDeleteFolder),
+                    (KeyCode::Char('q'), KeyModifiers::CONTROL) => Some(Action::Quit),
+                    _ => Some(Action::CancelDeleteFolder), // Anything else keeps the folder
+                };
+            }
+            // Typing a folder name, a one-line text input
+            if state.folders.edit.is_some() {
+                return match (key.code, key.modifiers) {
+                    (KeyCode::Enter, _) => Some(Action::SaveFolder),
+                    (KeyCode::Esc, _) => Some(Action::CancelFolderEdit),
+                    (KeyCode::Backspace, KeyModifiers::ALT) => Some(Action::EditFolderName(TextEdit::DeleteWord)),
+                    (KeyCode::Backspace, _) => Some(Action::DeleteFolderChar),
+                    (KeyCode::Char('a'), KeyModifiers::CONTROL) => Some(Action::EditFolderName(TextEdit::Home)),
+                    (KeyCode::Char('e'), KeyModifiers::CONTROL) => Some(Action::EditFolderName(TextEdit::End)),
+                    (KeyCode::Char('w'), KeyModifiers::CONTROL) => Some(Action::EditFolderName(TextEdit::DeleteWord)),
+                    (KeyCode::Char('q'), KeyModifiers::CONTROL) => Some(Action::Quit),
+                    (KeyCode::Char(c), KeyModifiers::NONE) | (KeyCode::Char(c), KeyModifiers::SHIFT) => {
+                        Some(Action::AppendFolderChar(c))
+                    }
+                    (KeyCode::Char(c), modifiers) if is_alt_gr(modifiers) => Some(Action::AppendFolderChar(c)),
+                    _ => text_edit(key).map(Action::EditFolderName),
+                };
+            }
+            // Picking the folder to move an item to
+            if state.folders.moving.is_some() {
+                return match (key.code, key.modifiers) {
+                    (KeyCode::Esc, _) | (KeyCode::Char('j'), KeyModifiers::ALT) => Some(Action::ToggleFolders),
+                    (KeyCode::Up, _) | (KeyCode::Char('k'), KeyModifiers::CONTROL) => Some(Action::SelectPreviousFolder),
+                    (KeyCode::Down, _) | (KeyCode::Char('j'), KeyModifiers::CONTROL) => Some(Action::SelectNextFolder),
+                    (KeyCode::Enter, _) => Some(Action::MoveToSelectedFolder),
+                    (KeyCode::Char('q'), KeyModifiers::CONTROL) => Some(Action::Quit),
+                    _ => None,
+                };
+            }
+            return match (key.code, key.modifiers) {
+                (KeyCode::Esc, _) | (KeyCode::Char('k'), KeyModifiers::ALT) => Some(Action::ToggleFolders),
+                (KeyCode::Up, _) | (KeyCode::Char('k'), KeyModifiers::CONTROL) => Some(Action::SelectPreviousFolder),
+                (KeyCode::Down, _) | (KeyCode::Char('j'), KeyModifiers::CONTROL) => Some(Action::SelectNextFolder),
+                (KeyCode::Enter, _) => Some(Action::FilterBySelectedFolder),
+                (KeyCode::Char('n'), KeyModifiers::NONE) => Some(Action::NewFolder),
+                (KeyCode::Char('r'), KeyModifiers::NONE) => Some(Action::RenameFolder),
+                (KeyCode::Delete, _) => Some(Action::RequestDeleteFolder),
+                (KeyCode::Char('q'), KeyModifiers::CONTROL) => Some(Action::Quit),
+                _ => None,
+            };
+        }
+
+        // Handle the dialog sharing an item with an organization
+        if state.share_open() {
+            if state.share.confirm {
+                return match (key.code, key.modifiers) {
+                    (KeyCode::Char('y'), _) | (KeyCode::Char('Y'), _) => Some(Action::ConfirmShare),
+                    (KeyCode::Char('q'), KeyModifiers::CONTROL) => Some(Action::Quit),
+                    _ => Some(Action::CancelShare), // Anything else keeps the item personal
+                };
+            }
+            return match (key.code, key.modifiers) {
+                (KeyCode::Esc, _) | (KeyCode::Char('u'), KeyModifiers::ALT) => Some(Action::CloseShare),
+                (KeyCode::Up, _) | (KeyCode::Char('k'), KeyModifiers::CONTROL) => Some(Action::SelectPreviousShareCollection),
+                (KeyCode::Down, _) | (KeyCode::Char('j'), KeyModifiers::CONTROL) => Some(Action::SelectNextShareCollection),
+                (KeyCode::Char(' '), _) => Some(Action::ToggleShareCollection),
+                (KeyCode::Enter, _) => Some(Action::RequestShare),
+                (KeyCode::Char('q'), KeyModifiers::CONTROL) => Some(Action::Quit),
+                _ => None,
+            };
+        }
+
+        // Handle the tag list
+        if state.tag_list_open() {
+            return match (key.code, key.modifiers) {
+                (KeyCode::Esc, _) | (KeyCode::Char('l'), KeyModifiers::ALT) => Some(Action::ToggleTagList),
+                (KeyCode::Up, _) | (KeyCode::Char('k'), KeyModifiers::CONTROL) => Some(Action::SelectPreviousTag),
+                (KeyCode::Down, _) | (KeyCode::Char('j'), KeyModifiers::CONTROL) => Some(Action::SelectNextTag),
+                (KeyCode::Enter, _) => Some(Action::FilterBySelectedTag),
+                (KeyCode::Char('q'), KeyModifiers::CONTROL) => Some(Action::Quit),
+                _ => None,
+            };
+        }
+
+        // Handle the local note dialog, a one-line text input
+        if state.local_note_open() {
+            return match (key.code, key.modifiers) {
+                (KeyCode::Enter, _) => Some(Action::SaveLocalNote),
+                (KeyCode::Esc, _) => Some(Action::CloseLocalNote),
+                (KeyCode::Backspace, KeyModifiers::ALT) => Some(Action::EditLocalNote(TextEdit::DeleteWord)),
+                (KeyCode::Backspace, _) => Some(Action::DeleteLocalNoteChar),
+                (KeyCode::Char('a'), KeyModifiers::CONTROL) => Some(Action::EditLocalNote(TextEdit::Home)),
+                (KeyCode::Char('e'), KeyModifiers::CONTROL) => Some(Action::EditLocalNote(TextEdit::End)),
+                (KeyCode::Char('w'), KeyModifiers::CONTROL) => Some(Action::EditLocalNote(TextEdit::DeleteWord)),
+                (KeyCode::Char('q'), KeyModifiers::CONTROL) => Some(Action::Quit),
+                (KeyCode::Char(c), KeyModifiers::NONE) | (KeyCode::Char(c), KeyModifiers::SHIFT) => {
+                    Some(Action::AppendLocalNoteChar(c))
+                }
+                (KeyCode::Char(c), modifiers) if is_alt_gr(modifiers) => Some(Action::AppendLocalNoteChar(c)),
+                _ => text_edit(key).map(Action::EditLocalNote),
+            };
+        }
+
+        // Handle the TOTP dashboard
+        if state.show_totp_dashboard() {
+            return match (key.code, key.modifiers) {
+                (KeyCode::Esc, _) | (KeyCode::Char('t'), KeyModifiers::ALT) => Some(Action::ToggleTotpDashboard),
+                (KeyCode::Up, _) | (KeyCode::Char('k'), KeyModifiers::CONTROL) => Some(Action::SelectPreviousTotpRow),
+                (KeyCode::Down, _) | (KeyCode::Char('j'), KeyModifiers::CONTROL) => Some(Action::SelectNextTotpRow),
+                (KeyCode::Enter, _) => Some(Action::CopyDashboardTotp),
+                (KeyCode::Char('q'), KeyModifiers::CONTROL) => Some(Action::Quit),
+                _ => None,
+            };
+        }
+
+        // The picker only searches, moves and copies, whatever the key scheme
+        if state.ui.pick.is_some() {
+            return match (key.code, key.modifiers) {
+                (KeyCode::Enter, _) => Some(Action::Pick),
+                (KeyCode::Esc, _) | (KeyCode::Char('q'), KeyModifiers::CONTROL) => Some(Action::Quit),
+                (KeyCode::Up, _) | (KeyCode::Char('k'), KeyModifiers::CONTROL) => Some(Action::MoveUp),
+                (KeyCode::Down, _) | (KeyCode::Char('j'), KeyModifiers::CONTROL) => Some(Action::MoveDown),
+                (KeyCode::PageUp, _) => Some(Action::PageUp),
+                (KeyCode::PageDown, _) => Some(Action::PageDown),
+                (KeyCode::Backspace, KeyModifiers::ALT) => Some(Action::EditFilter(TextEdit::DeleteWord)),
+                (KeyCode::Backspace, _) => Some(Action::DeleteFilterChar),
+                (KeyCode::Char('a'), KeyModifiers::CONTROL) => Some(Action::EditFilter(TextEdit::Home)),
+                (KeyCode::Char('e'), KeyModifiers::CONTROL) => Some(Action::EditFilter(TextEdit::End)),
+                (KeyCode::Char('w'), KeyModifiers::CONTROL) => Some(Action::EditFilter(TextEdit::DeleteWord)),
+                (KeyCode::Char('x'), KeyModifiers::CONTROL) => Some(Action::ClearFilter),
+                (KeyCode::Char('f'), KeyModifiers::ALT) => Some(Action::ToggleFuzzyMatching),
+                (KeyCode::Char('c'), KeyModifiers::ALT) => Some(Action::ToggleCaseSensitive),
+                (KeyCode::Char('c'), KeyModifiers::CONTROL) if state.syncing() => Some(Action::CancelSync),
+                (KeyCode::Char(c), KeyModifiers::NONE) | (KeyCode::Char(c), KeyModifiers::SHIFT) => {
+                    Some(Action::AppendFilter(c))
+                }
+                (KeyCode::Char(c), modifiers) if is_alt_gr(modifiers) => Some(Action::AppendFilter(c)),
+                _ => text_edit(key).map(Action::EditFilter),
+            };
+        }
+
+        // The vim scheme only adds bindings, other keys behave as in the default scheme
+        if self.keys == KeyScheme::Vim {
+            if let Some(action) = vim_key(key, state) {
+                return Some(action);
+            }
+        }
+
+        // Tab moves between the search box, the list and the details panel, / jumps to the search box
+        match (key.code, key.modifiers) {
+            (KeyCode::BackTab, _) | (KeyCode::Tab, KeyModifiers::SHIFT) => return Some(focus_previous(state)),
+            (KeyCode::Tab, _) => return Some(focus_next(state)),
+            (KeyCode::Char('/'), _) if state.focus() != Focus::Search => return Some(Action::FocusSearch),
+            _ => {}
+        }
+
+        // Letters are commands while the list has focus, instead of typing into the search box
+        // Keys not handled here fall through to the normal mode bindings
+        if state.focus() == Focus::List {
+            match (key.code, key.modifiers) {
+                (KeyCode::Char('k'), KeyModifiers::NONE) => return Some(Action::MoveUp),
+                (KeyCode::Char('j'), KeyModifiers::NONE) => return Some(Action::MoveDown),
+                (KeyCode::Char('g'), KeyModifiers::NONE) => return Some(Action::Home),
+                (KeyCode::Char('G'), _) => return Some(Action::End),
+                (KeyCode::Char(_), KeyModifiers::NONE | KeyModifiers::SHIFT) | (KeyCode::Backspace, _) => return None,
+                (KeyCode::Char(_), modifiers) if is_alt_gr(modifiers) => return None,
+                _ => {}
+            }
+        }
+
+        // Field navigation while the details panel has focus
+        // Keys not handled here fall through to the normal mode bindings
+        if state.details_focused() {
+            match (key.code, key.modifiers) {
+                (KeyCode::Esc, _) => return Some(Action::UnfocusDetails),
+                (KeyCode::Enter, _) => return Some(Action::CopyFocusedField),
+                (KeyCode::Char(' '), KeyModifiers::NONE) => return Some(Action::ToggleRevealField),
+                (KeyCode::Char('q'), KeyModifiers::ALT) => return Some(Action::ShowFieldQrCode),
+                (KeyCode::Up, KeyModifiers::NONE) | (KeyCode::Char('k'), KeyModifiers::CONTROL) => {
+                    return Some(Action::FocusPreviousField)
+                }
+                (KeyCode::Down, KeyModifiers::NONE) | (KeyCode::Char('j'), KeyModifiers::CONTROL) => {
+                    return Some(Action::FocusNextField)
+                }
+                _ => {}
+            }
+        }
+
+        // Editing keys move in the search text while there is some, otherwise they act on the list and tabs
+        if state.focus() == Focus::Search {
+            match (key.code, key.modifiers) {
+                (KeyCode::Char('a'), KeyModifiers::CONTROL) => return Some(Action::EditFilter(TextEdit::Home)),
+                (KeyCode::Char('e'), KeyModifiers::CONTROL) => return Some(Action::EditFilter(TextEdit::End)),
+                (KeyCode::Char('w'), KeyModifiers::CONTROL) => return Some(Action::EditFilter(TextEdit::DeleteWord)),
+                _ => {}
+            }
+            if let Some(edit) = text_edit(key).filter(|_| !state.vault.filter_query.is_empty()) {
+                return Some(Action::EditFilter(edit));
+            }
+        }
+
         // Normal mode
         match (key.code, key.modifiers) {
             // Escape key - close details panel if open, otherwise quit
@@ -194,8 +613,50 @@ impl EventHandler {
             (KeyCode::Backspace, _) => Some(Action::DeleteFilterChar),
             (KeyCode::Char('x'), KeyModifiers::CONTROL) => Some(Action::ClearFilter),
 
-            // Open details panel (doesn't close if already open)
-            (KeyCode::Enter, _) => Some(Action::OpenDetailsPanel),
+            // Matching mode (Alt, like the search toggles of most editors)
+            (KeyCode::Char('f'), KeyModifiers::ALT) => Some(Action::ToggleFuzzyMatching),
+            (KeyCode::Char('c'), KeyModifiers::ALT) => Some(Action::ToggleCaseSensitive),
+            (KeyCode::Char('o'), KeyModifiers::ALT) => Some(Action::CycleOwnerFilter),
+            (KeyCode::Char('e'), KeyModifiers::ALT) => Some(Action::CycleView),
+            (KeyCode::Char('v'), KeyModifiers::ALT) => Some(Action::ToggleShowHidden),
+            // Leave noisy items out of the list
+            (KeyCode::Char('h'), KeyModifiers::ALT) => Some(Action::ToggleItemHidden),
+
+            // Vault analysis
+            (KeyCode::Char('d'), KeyModifiers::ALT) => Some(Action::ToggleDuplicates),
+            // Codes of every item with a TOTP secret
+            (KeyCode::Char('t'), KeyModifiers::ALT) => Some(Action::ToggleTotpDashboard),
+            // Note and tags kept on this computer only
+            (KeyCode::Char('n'), KeyModifiers::ALT) => Some(Action::OpenLocalNote),
+            (KeyCode::Char('g'), KeyModifiers::ALT) => Some(Action::OpenLocalTags),
+            (KeyCode::Char('l'), KeyModifiers::ALT) => Some(Action::ToggleTagList),
+            (KeyCode::Char('k'), KeyModifiers::ALT) => Some(Action::ToggleFolders),
+            (KeyCode::Char('j'), KeyModifiers::ALT) => Some(Action::OpenMoveToFolder),
+            // Members of the selected organization item's collections
+            (KeyCode::Char('a'), KeyModifiers::ALT) => Some(Action::ShowCollectionAccess),
+            (KeyCode::Char('u'), KeyModifiers::ALT) => Some(Action::OpenShare),
+            (KeyCode::Char('r'), KeyModifiers::ALT) => Some(Action::ToggleReprompt),
+            (KeyCode::Char('y'), KeyModifiers::ALT) => Some(Action::ToggleSyncDetails),
+
+            // Copy the password of one of the numbered top search results
+            (KeyCode::Char(c @ '1'..='9'), KeyModifiers::ALT) if state.quick_copy_available() => {
+                Some(Action::QuickCopyPassword(c as usize - '1' as usize))
+            }
+
+            // Show the TOTP secret as a QR code for an authenticator app
+            (KeyCode::Char('q'), KeyModifiers::ALT) => Some(Action::ShowTotpQrCode),
+            (KeyCode::Char('w'), KeyModifiers::ALT) => Some(Action::ToggleTotpWatch),
+            // Copy the item as JSON, environment variables or a .netrc entry
+            (KeyCode::Char('s'), KeyModifiers::ALT) => Some(Action::OpenCopyAs),
+
+            // Open details panel, or move focus into it if already open
+            (KeyCode::Enter, _) => {
+                if state.details_panel_visible() {
+                    Some(Action::FocusDetails)
+                } else {
+                    Some(Action::OpenDetailsPanel)
+                }
+            }
 
             // Actions with Ctrl modifier
             (KeyCode::Char('u'), KeyModifiers::CONTROL) => Some(Action::CopyUsername),
@@ -203,8 +664,16 @@ impl EventHandler {
             (KeyCode::Char('t'), KeyModifiers::CONTROL) => Some(Action::CopyTotp),
             (KeyCode::Char('n'), KeyModifiers::CONTROL) => Some(Action::CopyCardNumber),
             (KeyCode::Char('m'), KeyModifiers::CONTROL) => Some(Action::CopyCardCvv),
+            (KeyCode::Char('y'), KeyModifiers::CONTROL) => Some(Action::CopyCardExpiry),
+            (KeyCode::Char('b'), KeyModifiers::CONTROL) => Some(Action::CopyCardAll),
+            (KeyCode::Char('f'), KeyModifiers::CONTROL) => Some(Action::CopyIdentityName),
+            (KeyCode::Char('p'), KeyModifiers::ALT) => Some(Action::CopyIdentityAddress),
+            (KeyCode::Char('o'), KeyModifiers::CONTROL) => Some(Action::CopyIdentityPhone),
+            (KeyCode::Char('i'), KeyModifiers::ALT) => Some(Action::CopyIdentityEmail),
             (KeyCode::Char('r'), KeyModifiers::CONTROL) => Some(Action::Refresh),
+            (KeyCode::Char('c'), KeyModifiers::CONTROL) if state.syncing() => Some(Action::CancelSync),
             (KeyCode::Char('d'), KeyModifiers::CONTROL) => Some(Action::ToggleDetailsPanel),
+            (KeyCode::Char('g'), KeyModifiers::CONTROL) => Some(Action::ToggleStatusHistory),
 
             // Tab switching with number keys (Ctrl+number for old behavior, number alone for new)
             (KeyCode::Char('1'), KeyModifiers::CONTROL) => Some(Action::SelectItemTypeTab(None)), // All types
@@ -213,9 +682,11 @@ impl EventHandler {
             (KeyCode::Char('4'), KeyModifiers::CONTROL) => Some(Action::SelectItemTypeTab(Some(crate::types::ItemType::Card))),
             (KeyCode::Char('5'), KeyModifiers::CONTROL) => Some(Action::SelectItemTypeTab(Some(crate::types::ItemType::Identity))),
 
-            // Tab cycling with Tab key
-            (KeyCode::Tab, KeyModifiers::SHIFT) => Some(Action::CyclePreviousTab),
-            (KeyCode::Tab, _) => Some(Action::CycleNextTab),
+            // Resize the split between the list and the details panel
+            (KeyCode::Left, KeyModifiers::CONTROL) => Some(Action::ShrinkList),
+            (KeyCode::Right, KeyModifiers::CONTROL) => Some(Action::GrowList),
+            (KeyCode::Char('z'), KeyModifiers::ALT) => Some(Action::ToggleDetailsFullscreen),
+            (KeyCode::Char('m'), KeyModifiers::ALT) => Some(Action::ToggleRawNotes),
 
             // Tab cycling with Left/Right arrow keys
             (KeyCode::Left, _) => Some(Action::CyclePreviousTab),
@@ -228,6 +699,7 @@ impl EventHandler {
             (KeyCode::Char(c), KeyModifiers::NONE) | (KeyCode::Char(c), KeyModifiers::SHIFT) => {
                 Some(Action::AppendFilter(c))
             }
+            (KeyCode::Char(c), modifiers) if is_alt_gr(modifiers) => Some(Action::AppendFilter(c)),
 
             _ => None,
         }
@@ -237,29 +709,24 @@ impl EventHandler {
     fn handle_mouse(&self, mouse: MouseEvent, state: &AppState) -> Option<Action> {
         match mouse.kind {
             MouseEventKind::Down(crossterm::event::MouseButton::Left) => {
-                // Try details panel first (if visible)
-                if state.details_panel_visible() {
-                    let details_handler = DetailsClickHandler;
-                    if let Some(action) = details_handler.handle_click(mouse, state, state.ui.details_panel_area) {
-                        return Some(action);
-                    }
-                }
+                // Look up the clickable area rendered under the cursor
+                state.ui.click_regions.action_at(mouse.column, mouse.row)
+            }
+            MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+                let up = mouse.kind == MouseEventKind::ScrollUp;
 
-                // Try entry list
-                let list_handler = EntryListClickHandler;
-                if let Some(action) = list_handler.handle_click(mouse, state, state.ui.list_area) {
-                    return Some(action);
+                // Over the details panel the wheel scrolls its content
+                if state.details_panel_visible() && is_click_in_area(mouse, state.ui.details_panel_area) {
+                    return Some(if up { Action::ScrollDetailsUp } else { Action::ScrollDetailsDown });
                 }
 
-                None
-            }
-            MouseEventKind::ScrollUp => {
-                // Scroll up moves selection up
-                Some(Action::MoveUp)
-            }
-            MouseEventKind::ScrollDown => {
-                // Scroll down moves selection down
-                Some(Action::MoveDown)
+                // Anywhere else it acts on the entry list
+                Some(match (self.list_wheel, up) {
+                    (ListWheelMode::Select, true) => Action::MoveUp,
+                    (ListWheelMode::Select, false) => Action::MoveDown,
+                    (ListWheelMode::Scroll, true) => Action::ScrollListUp,
+                    (ListWheelMode::Scroll, false) => Action::ScrollListDown,
+                })
             }
             _ => None,
         }
@@ -273,3 +740,356 @@ impl Default for EventHandler {
     }
 }
 
+
+/// Keys of the vim scheme, where the search box is insert mode and the list normal mode
+fn vim_key(key: KeyEvent, state: &AppState) -> Option<Action> {
+    match state.focus() {
+        // Leaving insert mode keeps the query
+        Focus::Search => match key.code {
+            KeyCode::Esc | KeyCode::Enter => Some(Action::FocusList),
+            _ => None,
+        },
+        Focus::List => {
+            // Second key of a sequence, Ctrl shortcuts (e.g. Ctrl+Q) still work in between
+            if let Some(prefix) = state.ui.pending_key.filter(|_| !key.modifiers.contains(KeyModifiers::CONTROL)) {
+                return Some(match (prefix, key.code) {
+                    ('g', KeyCode::Char('g')) => Action::Home,
+                    ('y', KeyCode::Char('u')) => Action::CopyUsername,
+                    ('y', KeyCode::Char('p')) => Action::CopyPassword,
+                    ('y', KeyCode::Char('t')) => Action::CopyTotp,
+                    _ => Action::KeyPrefix(None), // Anything else cancels the sequence
+                });
+            }
+            match (key.code, key.modifiers) {
+                (KeyCode::Char(c @ ('g' | 'y')), KeyModifiers::NONE) => Some(Action::KeyPrefix(Some(c))),
+                (KeyCode::Char('i'), KeyModifiers::NONE) => Some(Action::FocusSearch),
+                (KeyCode::Char('d'), KeyModifiers::CONTROL) => Some(Action::PageDown),
+                (KeyCode::Char('u'), KeyModifiers::CONTROL) => Some(Action::PageUp),
+                _ => None,
+            }
+        }
+        Focus::Details => match (key.code, key.modifiers) {
+            (KeyCode::Char('j'), KeyModifiers::NONE) => Some(Action::FocusNextField),
+            (KeyCode::Char('k'), KeyModifiers::NONE) => Some(Action::FocusPreviousField),
+            _ => None,
+        },
+    }
+}
+
+/// Move focus forward: search box, list, then the details panel when it has fields
+fn focus_next(state: &AppState) -> Action {
+    match state.focus() {
+        Focus::Search => Action::FocusList,
+        Focus::List if state.can_focus_details() => Action::FocusDetails,
+        Focus::List | Focus::Details => Action::FocusSearch,
+    }
+}
+
+/// Move focus backward, the reverse of `focus_next`
+fn focus_previous(state: &AppState) -> Action {
+    match state.focus() {
+        Focus::Search if state.can_focus_details() => Action::FocusDetails,
+        Focus::Search | Focus::Details => Action::FocusList,
+        Focus::List => Action::FocusSearch,
+    }
+}
+
+/// Pasted text goes to the text input that has the keyboard, if any
+fn handle_paste(text: String, state: &AppState) -> Option<Action> {
+    if state.password_input_mode() {
+        return (!state.unlock_in_progress()).then_some(Action::PastePassword(text));
+    }
+    if state.local_note_open() {
+        return Some(Action::PasteLocalNote(text));
+    }
+    if state.folders.edit.is_some() {
+        return Some(Action::PasteFolderName(text));
+    }
+    if state.dialog_open() {
+        return None;
+    }
+    (state.ui.pick.is_some() || state.focus() == Focus::Search).then_some(Action::PasteFilter(text))
+}
+
+/// Cursor movement and deletion keys shared by every text input
+fn text_edit(key: KeyEvent) -> Option<TextEdit> {
+    match (key.code, key.modifiers) {
+        (KeyCode::Left, KeyModifiers::NONE) => Some(TextEdit::Left),
+        (KeyCode::Right, KeyModifiers::NONE) => Some(TextEdit::Right),
+        (KeyCode::Home, KeyModifiers::NONE) => Some(TextEdit::Home),
+        (KeyCode::End, KeyModifiers::NONE) => Some(TextEdit::End),
+        (KeyCode::Delete, KeyModifiers::NONE) => Some(TextEdit::DeleteForward),
+        (KeyCode::Backspace, KeyModifiers::ALT) => Some(TextEdit::DeleteWord),
+        _ => None,
+    }
+}
+
+/// Characters typed with AltGr (e.g. '@' or 'é' on many layouts) are reported as Ctrl+Alt on Windows
+fn is_alt_gr(modifiers: KeyModifiers) -> bool {
+    modifiers - KeyModifiers::SHIFT == KeyModifiers::CONTROL | KeyModifiers::ALT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(handler: &EventHandler, state: &AppState, code: KeyCode, modifiers: KeyModifiers) -> Option<Action> {
+        handler.handle_event(CrosstermEvent::Key(KeyEvent::new(code, modifiers)), state)
+    }
+
+    #[test]
+    fn test_letters_filter_in_search_and_navigate_in_list() {
+        let handler = EventHandler::new();
+        let mut state = AppState::new();
+
+        assert!(matches!(press(&handler, &state, KeyCode::Char('j'), KeyModifiers::NONE), Some(Action::AppendFilter('j'))));
+        assert!(matches!(press(&handler, &state, KeyCode::Char('/'), KeyModifiers::NONE), Some(Action::AppendFilter('/'))));
+        assert!(matches!(press(&handler, &state, KeyCode::Tab, KeyModifiers::NONE), Some(Action::FocusList)));
+
+        state.focus_list();
+        assert!(matches!(press(&handler, &state, KeyCode::Char('j'), KeyModifiers::NONE), Some(Action::MoveDown)));
+        assert!(matches!(press(&handler, &state, KeyCode::Char('G'), KeyModifiers::SHIFT), Some(Action::End)));
+        // Unbound letters don't reach the filter
+        assert!(press(&handler, &state, KeyCode::Char('x'), KeyModifiers::NONE).is_none());
+        assert!(press(&handler, &state, KeyCode::Backspace, KeyModifiers::NONE).is_none());
+        // Shortcuts with Ctrl keep working
+        assert!(matches!(press(&handler, &state, KeyCode::Char('p'), KeyModifiers::CONTROL), Some(Action::CopyPassword)));
+        assert!(matches!(press(&handler, &state, KeyCode::Char('/'), KeyModifiers::NONE), Some(Action::FocusSearch)));
+    }
+
+    #[test]
+    fn test_vim_scheme() {
+        let config = Config { keys: crate::config::KeysConfig { scheme: KeyScheme::Vim }, ..Config::default() };
+        let handler = EventHandler::from_config(&config);
+        let mut state = AppState::new();
+        state.focus_list();
+
+        assert!(matches!(press(&handler, &state, KeyCode::Char('g'), KeyModifiers::NONE), Some(Action::KeyPrefix(Some('g')))));
+        assert!(matches!(press(&handler, &state, KeyCode::Char('d'), KeyModifiers::CONTROL), Some(Action::PageDown)));
+        assert!(matches!(press(&handler, &state, KeyCode::Char('i'), KeyModifiers::NONE), Some(Action::FocusSearch)));
+
+        state.ui.pending_key = Some('g');
+        assert!(matches!(press(&handler, &state, KeyCode::Char('g'), KeyModifiers::NONE), Some(Action::Home)));
+        state.ui.pending_key = Some('y');
+        assert!(matches!(press(&handler, &state, KeyCode::Char('p'), KeyModifiers::NONE), Some(Action::CopyPassword)));
+        assert!(matches!(press(&handler, &state, KeyCode::Char('x'), KeyModifiers::NONE), Some(Action::KeyPrefix(None))));
+        assert!(matches!(press(&handler, &state, KeyCode::Char('q'), KeyModifiers::CONTROL), Some(Action::Quit)));
+
+        // Insert mode types into the search box until Esc
+        state.ui.pending_key = None;
+        state.focus_search();
+        assert!(matches!(press(&handler, &state, KeyCode::Char('g'), KeyModifiers::NONE), Some(Action::AppendFilter('g'))));
+        assert!(matches!(press(&handler, &state, KeyCode::Esc, KeyModifiers::NONE), Some(Action::FocusList)));
+
+        // The default scheme has no sequences
+        let handler = EventHandler::new();
+        state.focus_list();
+        assert!(matches!(press(&handler, &state, KeyCode::Char('g'), KeyModifiers::NONE), Some(Action::Home)));
+        assert!(matches!(press(&handler, &state, KeyCode::Char('d'), KeyModifiers::CONTROL), Some(Action::ToggleDetailsPanel)));
+    }
+
+    #[test]
+    fn test_quick_copy_only_while_filtering() {
+        let handler = EventHandler::new();
+        let mut state = AppState::new();
+        assert!(press(&handler, &state, KeyCode::Char('1'), KeyModifiers::ALT).is_none());
+
+        state.set_filter_query("git".to_string());
+        assert!(matches!(press(&handler, &state, KeyCode::Char('1'), KeyModifiers::ALT), Some(Action::QuickCopyPassword(0))));
+        assert!(matches!(press(&handler, &state, KeyCode::Char('9'), KeyModifiers::ALT), Some(Action::QuickCopyPassword(8))));
+    }
+
+    #[test]
+    fn test_picker_keys() {
+        let handler = EventHandler::new();
+        let mut state = AppState::new();
+        state.ui.pick = Some(crate::args::PickField::Password);
+        state.set_filter_query("git".to_string());
+
+        assert!(matches!(press(&handler, &state, KeyCode::Enter, KeyModifiers::NONE), Some(Action::Pick)));
+        assert!(matches!(press(&handler, &state, KeyCode::Esc, KeyModifiers::NONE), Some(Action::Quit)));
+        assert!(matches!(press(&handler, &state, KeyCode::Char('j'), KeyModifiers::NONE), Some(Action::AppendFilter('j'))));
+        // Panels and other copies aren't part of the picker
+        assert!(press(&handler, &state, KeyCode::Char('d'), KeyModifiers::CONTROL).is_none());
+        assert!(press(&handler, &state, KeyCode::Char('1'), KeyModifiers::ALT).is_none());
+        assert!(press(&handler, &state, KeyCode::Tab, KeyModifiers::NONE).is_none());
+    }
+
+    #[test]
+    fn test_totp_dashboard_keys() {
+        let handler = EventHandler::new();
+        let mut state = AppState::new();
+        assert!(matches!(press(&handler, &state, KeyCode::Char('t'), KeyModifiers::ALT), Some(Action::ToggleTotpDashboard)));
+
+        state.totp_dashboard.visible = true;
+        assert!(matches!(press(&handler, &state, KeyCode::Enter, KeyModifiers::NONE), Some(Action::CopyDashboardTotp)));
+        assert!(matches!(press(&handler, &state, KeyCode::Down, KeyModifiers::NONE), Some(Action::SelectNextTotpRow)));
+        assert!(matches!(press(&handler, &state, KeyCode::Esc, KeyModifiers::NONE), Some(Action::ToggleTotpDashboard)));
+        // Typing doesn't reach the search box behind it
+        assert!(press(&handler, &state, KeyCode::Char('j'), KeyModifiers::NONE).is_none());
+    }
+
+    #[test]
+    fn test_local_note_keys() {
+        let handler = EventHandler::new();
+        let mut state = AppState::new();
+        assert!(matches!(press(&handler, &state, KeyCode::Char('n'), KeyModifiers::ALT), Some(Action::OpenLocalNote)));
+
+        state.ui.local_note_edit = Some(crate::state::LocalNoteEdit {
+            item_id: "1".to_string(),
+            item_name: "Mail".to_string(),
+            annotation: crate::state::Annotation::Note,
+            input: crate::state::TextInput::new(),
+        });
+        assert!(matches!(press(&handler, &state, KeyCode::Char('j'), KeyModifiers::NONE), Some(Action::AppendLocalNoteChar('j'))));
+        assert!(matches!(press(&handler, &state, KeyCode::Left, KeyModifiers::NONE), Some(Action::EditLocalNote(TextEdit::Left))));
+        assert!(matches!(press(&handler, &state, KeyCode::Enter, KeyModifiers::NONE), Some(Action::SaveLocalNote)));
+        assert!(matches!(handle_paste("VPN".to_string(), &state), Some(Action::PasteLocalNote(_))));
+    }
+
+    #[test]
+    fn test_tag_list_keys() {
+        let handler = EventHandler::new();
+        let mut state = AppState::new();
+        assert!(matches!(press(&handler, &state, KeyCode::Char('g'), KeyModifiers::ALT), Some(Action::OpenLocalTags)));
+        assert!(matches!(press(&handler, &state, KeyCode::Char('l'), KeyModifiers::ALT), Some(Action::ToggleTagList)));
+        assert!(matches!(press(&handler, &state, KeyCode::Char('h'), KeyModifiers::ALT), Some(Action::ToggleItemHidden)));
+        assert!(matches!(press(&handler, &state, KeyCode::Char('v'), KeyModifiers::ALT), Some(Action::ToggleShowHidden)));
+        assert!(matches!(press(&handler, &state, KeyCode::Char('a'), KeyModifiers::ALT), Some(Action::ShowCollectionAccess)));
+
+        state.ui.tag_list_selected = Some(0);
+        assert!(matches!(press(&handler, &state, KeyCode::Down, KeyModifiers::NONE), Some(Action::SelectNextTag)));
+        assert!(matches!(press(&handler, &state, KeyCode::Enter, KeyModifiers::NONE), Some(Action::FilterBySelectedTag)));
+        assert!(matches!(press(&handler, &state, KeyCode::Esc, KeyModifiers::NONE), Some(Action::ToggleTagList)));
+    }
+
+    #[test]
+    fn test_folder_screen_keys() {
+        let handler = EventHandler::new();
+        let mut state = AppState::new();
+        assert!(matches!(press(&handler, &state, KeyCode::Char('k'), KeyModifiers::ALT), Some(Action::ToggleFolders)));
+
+        state.toggle_folders();
+        assert!(matches!(press(&handler, &state, KeyCode::Char('n'), KeyModifiers::NONE), Some(Action::NewFolder)));
+        assert!(matches!(press(&handler, &state, KeyCode::Delete, KeyModifiers::NONE), Some(Action::RequestDeleteFolder)));
+        assert!(matches!(press(&handler, &state, KeyCode::Enter, KeyModifiers::NONE), Some(Action::FilterBySelectedFolder)));
+
+        // While typing a name, letters go to the name
+        state.start_new_folder();
+        assert!(matches!(press(&handler, &state, KeyCode::Char('n'), KeyModifiers::NONE), Some(Action::AppendFolderChar('n'))));
+        assert!(matches!(press(&handler, &state, KeyCode::Enter, KeyModifiers::NONE), Some(Action::SaveFolder)));
+        assert!(matches!(press(&handler, &state, KeyCode::Esc, KeyModifiers::NONE), Some(Action::CancelFolderEdit)));
+
+        state.folders.cancel_edit();
+        state.folders.confirm_delete = true;
+        assert!(matches!(press(&handler, &state, KeyCode::Char('y'), KeyModifiers::NONE), Some(Action::ConfirmDeleteFolder)));
+        assert!(matches!(press(&handler, &state, KeyCode::Esc, KeyModifiers::NONE), Some(Action::CancelDeleteFolder)));
+
+        // The move picker only selects and moves
+        state.folders.confirm_delete = false;
+        state.folders.moving = Some("i-1".to_string());
+        assert!(matches!(press(&handler, &state, KeyCode::Enter, KeyModifiers::NONE), Some(Action::MoveToSelectedFolder)));
+        assert!(press(&handler, &state, KeyCode::Char('n'), KeyModifiers::NONE).is_none());
+    }
+
+    #[test]
+    fn test_share_dialog_keys() {
+        let handler = EventHandler::new();
+        let mut state = AppState::new();
+        assert!(matches!(press(&handler, &state, KeyCode::Char('u'), KeyModifiers::ALT), Some(Action::OpenShare)));
+
+        state.share.open("1".to_string());
+        assert!(matches!(press(&handler, &state, KeyCode::Char(' '), KeyModifiers::NONE), Some(Action::ToggleShareCollection)));
+        assert!(matches!(press(&handler, &state, KeyCode::Enter, KeyModifiers::NONE), Some(Action::RequestShare)));
+        assert!(matches!(press(&handler, &state, KeyCode::Esc, KeyModifiers::NONE), Some(Action::CloseShare)));
+
+        state.share.confirm = true;
+        assert!(matches!(press(&handler, &state, KeyCode::Char('y'), KeyModifiers::NONE), Some(Action::ConfirmShare)));
+        assert!(matches!(press(&handler, &state, KeyCode::Char('n'), KeyModifiers::NONE), Some(Action::CancelShare)));
+    }
+
+    #[test]
+    fn test_confirm_quit_keys() {
+        let handler = EventHandler::new();
+        let mut state = AppState::new();
+        state.ui.confirm_quit = true;
+        state.ui.password_input_mode = true;
+        assert!(matches!(press(&handler, &state, KeyCode::Char('y'), KeyModifiers::NONE), Some(Action::ConfirmQuit)));
+        assert!(matches!(press(&handler, &state, KeyCode::Char('q'), KeyModifiers::CONTROL), Some(Action::ConfirmQuit)));
+        assert!(matches!(press(&handler, &state, KeyCode::Esc, KeyModifiers::NONE), Some(Action::CancelQuit)));
+        assert!(press(&handler, &state, KeyCode::Char('a'), KeyModifiers::NONE).is_none());
+    }
+
+    #[test]
+    fn test_search_editing_keys() {
+        let handler = EventHandler::new();
+        let mut state = AppState::new();
+        state.focus_search();
+
+        // Without text the keys keep their list and tab meanings
+        assert!(matches!(press(&handler, &state, KeyCode::Left, KeyModifiers::NONE), Some(Action::CyclePreviousTab)));
+        assert!(matches!(press(&handler, &state, KeyCode::Home, KeyModifiers::NONE), Some(Action::Home)));
+
+        state.set_filter_query("git".to_string());
+        assert!(matches!(press(&handler, &state, KeyCode::Left, KeyModifiers::NONE), Some(Action::EditFilter(TextEdit::Left))));
+        assert!(matches!(press(&handler, &state, KeyCode::Home, KeyModifiers::NONE), Some(Action::EditFilter(TextEdit::Home))));
+        assert!(matches!(press(&handler, &state, KeyCode::Delete, KeyModifiers::NONE), Some(Action::EditFilter(TextEdit::DeleteForward))));
+        assert!(matches!(press(&handler, &state, KeyCode::Backspace, KeyModifiers::ALT), Some(Action::EditFilter(TextEdit::DeleteWord))));
+        // The shell keys edit the search text, the copy shortcuts they would clash with are elsewhere
+        assert!(matches!(press(&handler, &state, KeyCode::Char('w'), KeyModifiers::CONTROL), Some(Action::EditFilter(TextEdit::DeleteWord))));
+        assert!(matches!(press(&handler, &state, KeyCode::Char('a'), KeyModifiers::CONTROL), Some(Action::EditFilter(TextEdit::Home))));
+        assert!(matches!(press(&handler, &state, KeyCode::Char('e'), KeyModifiers::CONTROL), Some(Action::EditFilter(TextEdit::End))));
+        assert!(matches!(press(&handler, &state, KeyCode::Char('b'), KeyModifiers::CONTROL), Some(Action::CopyCardAll)));
+        assert!(matches!(press(&handler, &state, KeyCode::Char('i'), KeyModifiers::ALT), Some(Action::CopyIdentityEmail)));
+        assert!(matches!(press(&handler, &state, KeyCode::Char('p'), KeyModifiers::ALT), Some(Action::CopyIdentityAddress)));
+        // The list keeps its keys while it has focus
+        state.focus_list();
+        assert!(matches!(press(&handler, &state, KeyCode::Home, KeyModifiers::NONE), Some(Action::Home)));
+
+        // The picker and the password dialog get them too
+        state.ui.pick = Some(crate::args::PickField::Password);
+        assert!(matches!(press(&handler, &state, KeyCode::Char('w'), KeyModifiers::CONTROL), Some(Action::EditFilter(TextEdit::DeleteWord))));
+        state.ui.enter_password_mode();
+        assert!(matches!(press(&handler, &state, KeyCode::Char('a'), KeyModifiers::CONTROL), Some(Action::EditPassword(TextEdit::Home))));
+        assert!(matches!(press(&handler, &state, KeyCode::Right, KeyModifiers::NONE), Some(Action::EditPassword(TextEdit::Right))));
+        assert!(matches!(press(&handler, &state, KeyCode::Backspace, KeyModifiers::NONE), Some(Action::DeletePasswordChar)));
+    }
+
+    #[test]
+    fn test_paste_goes_to_focused_input() {
+        let handler = EventHandler::new();
+        let mut state = AppState::new();
+        let paste = |state: &AppState| handler.handle_event(CrosstermEvent::Paste("hunter2\n".to_string()), state);
+
+        state.focus_search();
+        assert!(matches!(paste(&state), Some(Action::PasteFilter(text)) if text == "hunter2\n"));
+        state.focus_list();
+        assert!(paste(&state).is_none());
+
+        state.ui.enter_password_mode();
+        assert!(matches!(paste(&state), Some(Action::PastePassword(_))));
+        assert!(matches!(press(&handler, &state, KeyCode::Char('v'), KeyModifiers::CONTROL), Some(Action::PasteClipboardPassword)));
+        // Nothing is typed while the password is being checked
+        state.ui.unlock_in_progress = true;
+        assert!(paste(&state).is_none());
+        assert!(matches!(press(&handler, &state, KeyCode::Char('q'), KeyModifiers::CONTROL), Some(Action::Quit)));
+        assert!(press(&handler, &state, KeyCode::Enter, KeyModifiers::NONE).is_none());
+        // A vault sync running behind the dialog doesn't block it
+        state.ui.unlock_in_progress = false;
+        state.sync.syncing = true;
+        assert!(matches!(press(&handler, &state, KeyCode::Char('a'), KeyModifiers::NONE), Some(Action::AppendPasswordChar('a'))));
+    }
+
+    #[test]
+    fn test_tab_cycles_focus() {
+        let handler = EventHandler::new();
+        let mut state = AppState::new();
+
+        // Without fields to focus in the details panel, Tab only switches between search box and list
+        state.focus_list();
+        assert!(matches!(press(&handler, &state, KeyCode::Tab, KeyModifiers::NONE), Some(Action::FocusSearch)));
+        assert!(matches!(press(&handler, &state, KeyCode::BackTab, KeyModifiers::SHIFT), Some(Action::FocusSearch)));
+        state.focus_search();
+        assert!(matches!(press(&handler, &state, KeyCode::BackTab, KeyModifiers::SHIFT), Some(Action::FocusList)));
+    }
+}
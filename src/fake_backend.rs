@@ -0,0 +1,323 @@
+use crate::backend::{Backend, ItemBatchFn, Unlocked, VaultBackend, VaultStatus};
+use crate::error::{BwError, Result};
+use crate::retry::RetryListener;
+use crate::types::{Collection, CollectionAccess, EquivalentDomains, Folder, ItemType, LoginData, Organization, VaultItem};
+use futures_util::future::BoxFuture;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Master password the fake vault unlocks with
+pub const PASSWORD: &str = "correct horse";
+
+/// Session token returned by a successful unlock
+pub const SESSION_TOKEN: &str = "fake-session";
+
+/// Contents and call counts of a fake vault, shared by every handle to it
+struct FakeVault {
+    status: VaultStatus,
+    items: Vec<VaultItem>,
+    folders: Vec<Folder>,
+    collections: Vec<Collection>,
+    collection_access: HashMap<String, CollectionAccess>, // By collection id, the others can't be seen
+    equivalent_domains: EquivalentDomains,
+    totp_codes: HashMap<String, String>,
+    session_token: bool,
+    unlock_attempts: usize,
+    syncs: usize,
+}
+
+/// In-memory backend for tests of the App's unlock, sync and TOTP flows
+#[derive(Clone)]
+pub struct FakeBackend {
+    vault: Arc<Mutex<FakeVault>>,
+}
+
+impl FakeBackend {
+    pub fn new(status: VaultStatus, items: Vec<VaultItem>) -> Self {
+        Self {
+            vault: Arc::new(Mutex::new(FakeVault {
+                status,
+                items,
+                folders: Vec::new(),
+                collections: Vec::new(),
+                equivalent_domains: EquivalentDomains::default(),
+                collection_access: HashMap::new(),
+                totp_codes: HashMap::new(),
+                session_token: false,
+                unlock_attempts: 0,
+                syncs: 0,
+            })),
+        }
+    }
+
+    /// Code `get_totp` returns for an item
+    pub fn with_totp(self, item_id: &str, code: &str) -> Self {
+        self.vault().totp_codes.insert(item_id.to_string(), code.to_string());
+        self
+    }
+
+    /// Started with a session token, so a locked vault means it expired
+    pub fn with_session_token(self) -> Self {
+        self.vault().session_token = true;
+        self
+    }
+
+    /// Folders `list_folders` returns
+    pub fn with_folders(self, folders: Vec<Folder>) -> Self {
+        self.vault().folders = folders;
+        self
+    }
+
+    /// Collections `list_collections` returns
+    pub fn with_collections(self, collections: Vec<Collection>) -> Self {
+        self.vault().collections = collections;
+        self
+    }
+
+    /// Equivalent domains `equivalent_domains` returns
+    pub fn with_equivalent_domains(self, domains: EquivalentDomains) -> Self {
+        self.vault().equivalent_domains = domains;
+        self
+    }
+
+    /// Who `collection_access` says can access a collection, as if the user managed it
+    pub fn with_collection_access(self, collection_id: &str, access: CollectionAccess) -> Self {
+        self.vault().collection_access.insert(collection_id.to_string(), access);
+        self
+    }
+
+    pub fn folders(&self) -> Vec<Folder> {
+        self.vault().folders.clone()
+    }
+
+    /// Lock the vault, so commands fail until it is unlocked again
+    pub fn lock(&self) {
+        self.vault().status = VaultStatus::Locked;
+    }
+
+    /// Replace the items, as if they were changed on another device
+    pub fn set_items(&self, items: Vec<VaultItem>) {
+        self.vault().items = items;
+    }
+
+    pub fn unlock_attempts(&self) -> usize {
+        self.vault().unlock_attempts
+    }
+
+    pub fn syncs(&self) -> usize {
+        self.vault().syncs
+    }
+
+    fn vault(&self) -> std::sync::MutexGuard<'_, FakeVault> {
+        self.vault.lock().unwrap()
+    }
+
+    fn ensure_unlocked(&self) -> Result<()> {
+        match self.vault().status {
+            VaultStatus::Unlocked => Ok(()),
+            VaultStatus::Locked => Err(BwError::VaultLocked),
+            VaultStatus::Unauthenticated => Err(BwError::NotLoggedIn),
+        }
+    }
+}
+
+impl VaultBackend for FakeBackend {
+    fn check_status(&self) -> BoxFuture<'_, Result<VaultStatus>> {
+        Box::pin(async move { Ok(self.vault().status) })
+    }
+
+    fn unlock<'a>(&'a self, password: &'a str) -> BoxFuture<'a, Result<Unlocked>> {
+        Box::pin(async move {
+            let mut vault = self.vault();
+            vault.unlock_attempts += 1;
+            if password != PASSWORD {
+                return Err(BwError::CommandFailed("Invalid master password".to_string()));
+            }
+            vault.status = VaultStatus::Unlocked;
+            Ok(Unlocked { session_token: Some(SESSION_TOKEN.to_string()), backend: Arc::new(self.clone()) })
+        })
+    }
+
+    fn sync(&self) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async move {
+            self.ensure_unlocked()?;
+            self.vault().syncs += 1;
+            Ok(())
+        })
+    }
+
+    fn list_items<'a>(&'a self, on_batch: &'a ItemBatchFn) -> BoxFuture<'a, Result<usize>> {
+        Box::pin(async move {
+            self.ensure_unlocked()?;
+            let items = self.vault().items.clone();
+            let count = items.len();
+            on_batch(items);
+            Ok(count)
+        })
+    }
+
+    fn list_organizations(&self) -> BoxFuture<'_, Result<Vec<Organization>>> {
+        Box::pin(async { Ok(Vec::new()) })
+    }
+
+    fn list_folders(&self) -> BoxFuture<'_, Result<Vec<Folder>>> {
+        Box::pin(async move { Ok(self.vault().folders.clone()) })
+    }
+
+    fn list_collections(&self) -> BoxFuture<'_, Result<Vec<Collection>>> {
+        Box::pin(async move { Ok(self.vault().collections.clone()) })
+    }
+
+    fn equivalent_domains(&self) -> BoxFuture<'_, Result<EquivalentDomains>> {
+        Box::pin(async move { Ok(self.vault().equivalent_domains.clone()) })
+    }
+
+    fn collection_access<'a>(&'a self, _organization_id: &'a str, collection_id: &'a str) -> BoxFuture<'a, Result<CollectionAccess>> {
+        Box::pin(async move {
+            self.ensure_unlocked()?;
+            self.vault()
+                .collection_access
+                .get(collection_id)
+                .cloned()
+                .ok_or_else(|| BwError::CommandFailed("You do not have permission to view this collection".to_string()))
+        })
+    }
+
+    fn get_totp<'a>(&'a self, item_id: &'a str) -> BoxFuture<'a, Result<String>> {
+        Box::pin(async move {
+            self.ensure_unlocked()?;
+            self.vault()
+                .totp_codes
+                .get(item_id)
+                .cloned()
+                .ok_or_else(|| BwError::CommandFailed("No TOTP configured for this item".to_string()))
+        })
+    }
+
+    fn delete_item<'a>(&'a self, item_id: &'a str) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            self.ensure_unlocked()?;
+            self.vault().items.retain(|item| item.id != item_id);
+            Ok(())
+        })
+    }
+
+    fn share_item<'a>(&'a self, item_id: &'a str, organization_id: &'a str, collection_ids: &'a [String]) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            self.ensure_unlocked()?;
+            let mut vault = self.vault();
+            let item = vault
+                .items
+                .iter_mut()
+                .find(|item| item.id == item_id)
+                .ok_or_else(|| BwError::CommandFailed("Item not found".to_string()))?;
+            item.organization_id = Some(organization_id.to_string());
+            item.collection_ids = Some(collection_ids.to_vec());
+            Ok(())
+        })
+    }
+
+    fn set_reprompt<'a>(&'a self, item_id: &'a str, enabled: bool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            self.ensure_unlocked()?;
+            let mut vault = self.vault();
+            let item = vault
+                .items
+                .iter_mut()
+                .find(|item| item.id == item_id)
+                .ok_or_else(|| BwError::CommandFailed("Item not found".to_string()))?;
+            item.reprompt = Some(u8::from(enabled));
+            Ok(())
+        })
+    }
+
+    fn move_item<'a>(&'a self, item_id: &'a str, folder_id: Option<&'a str>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            self.ensure_unlocked()?;
+            let mut vault = self.vault();
+            let item = vault
+                .items
+                .iter_mut()
+                .find(|item| item.id == item_id)
+                .ok_or_else(|| BwError::CommandFailed("Item not found".to_string()))?;
+            item.folder_id = folder_id.map(str::to_string);
+            Ok(())
+        })
+    }
+
+    fn create_folder<'a>(&'a self, name: &'a str) -> BoxFuture<'a, Result<Folder>> {
+        Box::pin(async move {
+            self.ensure_unlocked()?;
+            let mut vault = self.vault();
+            let folder = Folder { id: format!("folder-{}", vault.folders.len() + 1), name: name.to_string() };
+            vault.folders.push(folder.clone());
+            Ok(folder)
+        })
+    }
+
+    fn rename_folder<'a>(&'a self, folder_id: &'a str, name: &'a str) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            self.ensure_unlocked()?;
+            let mut vault = self.vault();
+            let folder = vault
+                .folders
+                .iter_mut()
+                .find(|folder| folder.id == folder_id)
+                .ok_or_else(|| BwError::CommandFailed("Folder not found".to_string()))?;
+            folder.name = name.to_string();
+            Ok(())
+        })
+    }
+
+    fn delete_folder<'a>(&'a self, folder_id: &'a str) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            self.ensure_unlocked()?;
+            let mut vault = self.vault();
+            vault.folders.retain(|folder| folder.id != folder_id);
+            for item in vault.items.iter_mut().filter(|item| item.folder_id.as_deref() == Some(folder_id)) {
+                item.folder_id = None;
+            }
+            Ok(())
+        })
+    }
+
+    fn with_retry_listener(&self, _listener: RetryListener) -> Backend {
+        Arc::new(self.clone())
+    }
+
+    fn has_session_token(&self) -> bool {
+        self.vault().session_token
+    }
+}
+
+/// A login item, with a TOTP secret if given
+pub fn login(id: &str, name: &str, totp: Option<&str>) -> VaultItem {
+    VaultItem {
+        id: id.to_string(),
+        name: name.to_string(),
+        item_type: ItemType::Login,
+        login: Some(LoginData {
+            username: Some(format!("{}@example.com", id)),
+            password: Some(format!("{}-password", id)),
+            totp: totp.map(str::to_string),
+            uris: None,
+            password_revision_date: None,
+        }),
+        card: None,
+        identity: None,
+        notes: None,
+        fields: None,
+        favorite: false,
+        folder_id: None,
+        organization_id: None,
+        revision_date: chrono::Utc::now(),
+        object: None,
+        creation_date: None,
+        deleted_date: None,
+        password_history: None,
+        attachments: None,
+        collection_ids: None,
+        reprompt: None,
+        raw_fields: Default::default(),
+    }
+}
@@ -0,0 +1,96 @@
+/// Groups of domains that belong to the same site, from Bitwarden's global equivalent domains
+/// The server hands these to the api backend, bw has no command to list them so the CLI backend uses this copy
+pub const GROUPS: &[&[&str]] = &[
+    &["ameritrade.com", "tdameritrade.com"],
+    &["bankofamerica.com", "bofa.com", "mbna.com", "usecfo.com"],
+    &["sprint.com", "sprintpcs.com", "nextel.com"],
+    &["youtube.com", "google.com", "gmail.com"],
+    &["apple.com", "icloud.com"],
+    &["wellsfargo.com", "wf.com", "wellsfargoadvisors.com"],
+    &["mymerrill.com", "ml.com", "merrilledge.com"],
+    &["accountonline.com", "citi.com", "citibank.com", "citicards.com", "citibankonline.com"],
+    &["cnet.com", "cnettv.com", "com.com", "download.com", "news.com", "search.com", "upload.com"],
+    &["bananarepublic.com", "gap.com", "oldnavy.com", "piperlime.com"],
+    &[
+        "bing.com", "hotmail.com", "live.com", "microsoft.com", "msn.com", "passport.net", "windows.com",
+        "microsoftonline.com", "office.com", "office365.com", "microsoftstore.com", "xbox.com", "azure.com",
+        "windowsazure.com",
+    ],
+    &["ua2go.com", "ual.com", "united.com", "unitedwifi.com"],
+    &["overture.com", "yahoo.com"],
+    &["zonealarm.com", "zonelabs.com"],
+    &["paypal.com", "paypal-search.com"],
+    &["avon.com", "youravon.com"],
+    &["1800contacts.com", "800contacts.com"],
+    &[
+        "amazon.com", "amzn.com", "amazon.ae", "amazon.ca", "amazon.co.jp", "amazon.co.uk", "amazon.com.au",
+        "amazon.com.br", "amazon.com.mx", "amazon.com.tr", "amazon.de", "amazon.es", "amazon.fr", "amazon.in",
+        "amazon.it", "amazon.nl", "amazon.pl", "amazon.sa", "amazon.se", "amazon.sg",
+    ],
+    &["cox.com", "cox.net", "coxbusiness.com"],
+    &["mynortonaccount.com", "norton.com"],
+    &["verizon.com", "verizon.net"],
+    &["rakuten.com", "buy.com"],
+    &["siriusxm.com", "sirius.com"],
+    &["ea.com", "origin.com", "play4free.com", "tiberiumalliance.com"],
+    &["37signals.com", "basecamp.com", "basecamphq.com", "highrisehq.com"],
+    &["steampowered.com", "steamcommunity.com", "steamgames.com"],
+    &["chart.io", "chartio.com"],
+    &["gotomeeting.com", "citrixonline.com"],
+    &["gogoair.com", "gogoinflight.com"],
+    &["mysql.com", "oracle.com"],
+    &["discover.com", "discovercard.com"],
+    &["dcu.org", "dcu-online.org"],
+    &["healthcare.gov", "cuidadodesalud.gov", "cms.gov"],
+    &["pepco.com", "pepcoholdings.com"],
+    &["century21.com", "21online.com"],
+    &["comcast.com", "comcast.net", "xfinity.com"],
+    &["cricketwireless.com", "aiowireless.com"],
+    &["mandtbank.com", "mtb.com"],
+    &["dropbox.com", "getdropbox.com"],
+    &["snapfish.com", "snapfish.ca"],
+    &["alibaba.com", "aliexpress.com", "aliyun.com", "net.cn"],
+    &["playstation.com", "sonyentertainmentnetwork.com"],
+    &["zendesk.com", "zopim.com"],
+    &["autodesk.com", "tinkercad.com"],
+    &["facebook.com", "messenger.com"],
+    &["skysports.com", "skybet.com", "skyvegas.com"],
+    &["disneymoviesanywhere.com", "go.com", "disney.com", "dadt.com", "disneyplus.com"],
+    &["pokemon-gl.com", "pokemon.com"],
+    &["myuv.com", "uvvu.com"],
+    &["mdsol.com", "imedidata.com"],
+    &["sears.com", "shld.net"],
+    &["belkin.com", "seedonk.com"],
+    &["turbotax.com", "intuit.com"],
+    &["shopify.com", "myshopify.com"],
+    &[
+        "ebay.com", "ebay.at", "ebay.be", "ebay.ca", "ebay.ch", "ebay.cn", "ebay.co.jp", "ebay.co.th", "ebay.co.uk",
+        "ebay.com.au", "ebay.com.hk", "ebay.com.my", "ebay.com.sg", "ebay.com.tw", "ebay.de", "ebay.es", "ebay.fr",
+        "ebay.ie", "ebay.in", "ebay.it", "ebay.nl", "ebay.ph", "ebay.pl",
+    ],
+    &["techdata.com", "techdata.ch"],
+    &["schwab.com", "schwabplan.com"],
+    &["tesla.com", "teslamotors.com"],
+    &["morganstanley.com", "morganstanleyclientserv.com", "stockplanconnect.com", "ms.com"],
+    &["taxact.com", "taxactonline.com"],
+    &[
+        "mediawiki.org", "wikibooks.org", "wikidata.org", "wikimedia.org", "wikinews.org", "wikipedia.org",
+        "wikiquote.org", "wikisource.org", "wikiversity.org", "wikivoyage.org", "wiktionary.org",
+    ],
+    &[
+        "stackexchange.com", "superuser.com", "stackoverflow.com", "serverfault.com", "mathoverflow.net",
+        "askubuntu.com", "stackapps.com",
+    ],
+    &["docusign.com", "docusign.net"],
+    &[
+        "envato.com", "themeforest.net", "codecanyon.net", "videohive.net", "audiojungle.net", "graphicriver.net",
+        "photodune.net", "3docean.net",
+    ],
+    &["discord.com", "discordapp.com", "discord.gg"],
+    &["netcup.de", "netcup.eu", "customercontrolpanel.de"],
+    &["proton.me", "protonmail.com", "protonvpn.com"],
+    &["ubisoft.com", "ubi.com"],
+    &["transferwise.com", "wise.com"],
+    &["atlassian.com", "bitbucket.org", "trello.com", "statuspage.io", "atlassian.net", "jira.com"],
+    &["twitter.com", "x.com"],
+];
@@ -0,0 +1,56 @@
+use crate::error::{BwError, Result};
+use std::fs::{File, OpenOptions, TryLockError};
+use std::path::Path;
+
+/// Advisory lock held by the instance that owns the profile's cache and saved session
+/// Released when dropped, or by the OS if the process dies
+#[derive(Debug)]
+pub struct InstanceLock {
+    _file: File,
+}
+
+impl InstanceLock {
+    /// Lock the current profile, None if another instance already holds it
+    pub fn acquire() -> Result<Option<Self>> {
+        let path = crate::profile::data_dir()?.join("bwtui.lock");
+        Self::acquire_at(&path)
+    }
+
+    fn acquire_at(path: &Path) -> Result<Option<Self>> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| {
+                BwError::CommandFailed(format!("Failed to create data directory: {}", e))
+            })?;
+        }
+
+        let file = OpenOptions::new().create(true).truncate(false).write(true).open(path).map_err(|e| {
+            BwError::CommandFailed(format!("Failed to open lock file: {}", e))
+        })?;
+
+        match file.try_lock() {
+            Ok(()) => Ok(Some(Self { _file: file })),
+            Err(TryLockError::WouldBlock) => Ok(None),
+            Err(TryLockError::Error(e)) => Err(BwError::CommandFailed(format!("Failed to lock {}: {}", path.display(), e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_second_lock_is_refused_until_released() {
+        let dir = std::env::temp_dir().join(format!("bwtui-lock-test-{}", std::process::id()));
+        let path = dir.join("bwtui.lock");
+
+        let first = InstanceLock::acquire_at(&path).unwrap();
+        assert!(first.is_some());
+        assert!(InstanceLock::acquire_at(&path).unwrap().is_none());
+
+        drop(first);
+        assert!(InstanceLock::acquire_at(&path).unwrap().is_some());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,179 @@
+use crate::types::VaultItem;
+
+/// Formats an item can be copied as, to paste into other tools
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyFormat {
+    /// The item as the CLI returns it
+    Json,
+    /// `export NAME='value'` lines for the custom fields
+    Env,
+    /// A `.netrc` entry for the login's host
+    Netrc,
+}
+
+impl CopyFormat {
+    pub const ALL: [CopyFormat; 3] = [CopyFormat::Json, CopyFormat::Env, CopyFormat::Netrc];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CopyFormat::Json => "JSON",
+            CopyFormat::Env => "environment variables",
+            CopyFormat::Netrc => ".netrc entry",
+        }
+    }
+
+    /// The formats that have something to show for the item
+    pub fn available_for(item: &VaultItem) -> Vec<CopyFormat> {
+        Self::ALL.into_iter().filter(|format| format.format(item).is_some()).collect()
+    }
+
+    /// Serialize the item, if the format applies to it
+    pub fn format(self, item: &VaultItem) -> Option<String> {
+        match self {
+            CopyFormat::Json => serde_json::to_string_pretty(item).ok(),
+            CopyFormat::Env => env_exports(item),
+            CopyFormat::Netrc => netrc_entry(item),
+        }
+    }
+}
+
+fn env_exports(item: &VaultItem) -> Option<String> {
+    let lines: Vec<String> = item.fields.as_ref()?
+        .iter()
+        .filter_map(|field| {
+            let name = env_name(field.name.as_deref()?)?;
+            let value = item.custom_field_value(field)?;
+            Some(format!("export {}={}", name, shell_quote(&value.text())))
+        })
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// A shell variable name for a field name: "api-key" becomes API_KEY
+fn env_name(name: &str) -> Option<String> {
+    let mut env_name: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect();
+    if env_name.is_empty() || env_name.chars().all(|c| c == '_') {
+        return None;
+    }
+    if env_name.starts_with(|c: char| c.is_ascii_digit()) {
+        env_name.insert(0, '_');
+    }
+    Some(env_name)
+}
+
+/// Single-quote a value for POSIX shells
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+fn netrc_entry(item: &VaultItem) -> Option<String> {
+    let login = item.login.as_ref()?;
+    let domain = item.domain()?;
+    // The machine is a host name, without credentials or a port
+    let host = domain.rsplit('@').next()?.split(':').next()?;
+    if host.is_empty() {
+        return None;
+    }
+
+    let mut entry = format!("machine {}", host);
+    if let Some(username) = &login.username {
+        entry.push_str(&format!(" login {}", netrc_quote(username)));
+    }
+    if let Some(password) = &login.password {
+        entry.push_str(&format!(" password {}", netrc_quote(password)));
+    }
+    Some(entry)
+}
+
+/// Quote a .netrc token containing whitespace or quotes, as curl reads them
+fn netrc_quote(value: &str) -> String {
+    if value.is_empty() || value.contains(|c: char| c.is_whitespace() || c == '"') {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CustomField, CustomFieldType, ItemType, LoginData, Uri};
+
+    fn create_item(login: Option<LoginData>, fields: Option<Vec<CustomField>>) -> VaultItem {
+        VaultItem {
+            id: "1".to_string(),
+            name: "Deploy".to_string(),
+            item_type: ItemType::Login,
+            login,
+            card: None,
+            identity: None,
+            notes: None,
+            fields,
+            favorite: false,
+            folder_id: None,
+            organization_id: None,
+            revision_date: chrono::Utc::now(),
+            object: None,
+            creation_date: None,
+            deleted_date: None,
+            password_history: None,
+            attachments: None,
+            collection_ids: None,
+            reprompt: None,
+            raw_fields: Default::default(),
+        }
+    }
+
+    fn login(uri: &str, username: &str, password: &str) -> LoginData {
+        LoginData {
+            username: Some(username.to_string()),
+            password: Some(password.to_string()),
+            totp: None,
+            uris: Some(vec![Uri { uri: uri.to_string(), match_type: None }]),
+            password_revision_date: None,
+        }
+    }
+
+    fn field(name: &str, value: &str) -> CustomField {
+        CustomField {
+            name: Some(name.to_string()),
+            value: Some(value.to_string()),
+            field_type: Some(CustomFieldType::Hidden),
+            linked_id: None,
+        }
+    }
+
+    #[test]
+    fn test_env_exports() {
+        let item = create_item(None, Some(vec![field("api-key", "it's secret"), field("2fa backup", "abc"), field("--", "skipped")]));
+        assert_eq!(
+            CopyFormat::Env.format(&item).unwrap(),
+            "export API_KEY='it'\\''s secret'\nexport _2FA_BACKUP='abc'"
+        );
+        assert_eq!(CopyFormat::Env.format(&create_item(None, None)), None);
+    }
+
+    #[test]
+    fn test_netrc_entry() {
+        let item = create_item(Some(login("https://git.example.com:8443/repo", "alice", "hunter2")), None);
+        assert_eq!(CopyFormat::Netrc.format(&item).unwrap(), "machine git.example.com login alice password hunter2");
+
+        let item = create_item(Some(login("example.com", "alice", "two words")), None);
+        assert_eq!(CopyFormat::Netrc.format(&item).unwrap(), "machine example.com login alice password \"two words\"");
+    }
+
+    #[test]
+    fn test_available_formats() {
+        let item = create_item(Some(login("https://example.com", "alice", "hunter2")), None);
+        assert_eq!(CopyFormat::available_for(&item), vec![CopyFormat::Json, CopyFormat::Netrc]);
+
+        let item = create_item(None, Some(vec![field("token", "abc")]));
+        assert_eq!(CopyFormat::available_for(&item), vec![CopyFormat::Json, CopyFormat::Env]);
+        let json: serde_json::Value = serde_json::from_str(&CopyFormat::Json.format(&item).unwrap()).unwrap();
+        assert_eq!(json["name"], "Deploy");
+    }
+}
@@ -0,0 +1,147 @@
+use crate::error::{BwError, Result};
+
+/// Incrementally splits a top-level JSON array into the raw bytes of its elements,
+/// so large outputs can be parsed as they arrive instead of being buffered whole
+#[derive(Debug, Default)]
+pub struct JsonArrayStream {
+    started: bool,
+    finished: bool,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    element: Vec<u8>,
+}
+
+impl JsonArrayStream {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next chunk of input, returning every element it completed
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<Vec<Vec<u8>>> {
+        let mut elements = Vec::new();
+
+        for &byte in chunk {
+            if !self.started {
+                match byte {
+                    b'[' => self.started = true,
+                    _ if byte.is_ascii_whitespace() => {}
+                    _ => return Err(parse_error("expected a JSON array")),
+                }
+                continue;
+            }
+
+            if self.finished {
+                if !byte.is_ascii_whitespace() {
+                    return Err(parse_error("unexpected data after the JSON array"));
+                }
+                continue;
+            }
+
+            if self.in_string {
+                self.element.push(byte);
+                if self.escaped {
+                    self.escaped = false;
+                } else if byte == b'\\' {
+                    self.escaped = true;
+                } else if byte == b'"' {
+                    self.in_string = false;
+                }
+                continue;
+            }
+
+            match byte {
+                b'"' => {
+                    self.in_string = true;
+                    self.element.push(byte);
+                }
+                b'{' | b'[' => {
+                    self.depth += 1;
+                    self.element.push(byte);
+                }
+                b'}' => {
+                    self.depth = self.depth.checked_sub(1).ok_or_else(|| parse_error("unbalanced '}'"))?;
+                    self.element.push(byte);
+                }
+                // The closing bracket of the array itself
+                b']' if self.depth == 0 => {
+                    self.take_element(&mut elements);
+                    self.finished = true;
+                }
+                b']' => {
+                    self.depth -= 1;
+                    self.element.push(byte);
+                }
+                b',' if self.depth == 0 => self.take_element(&mut elements),
+                _ if self.depth == 0 && byte.is_ascii_whitespace() => {}
+                _ => self.element.push(byte),
+            }
+        }
+
+        Ok(elements)
+    }
+
+    /// Check that the array was complete once the input ended
+    pub fn finish(&self) -> Result<()> {
+        if self.finished {
+            Ok(())
+        } else {
+            Err(parse_error("unexpected end of the JSON array"))
+        }
+    }
+
+    fn take_element(&mut self, elements: &mut Vec<Vec<u8>>) {
+        if !self.element.is_empty() {
+            elements.push(std::mem::take(&mut self.element));
+        }
+    }
+}
+
+fn parse_error(message: &str) -> BwError {
+    BwError::ParseError(format!("Invalid JSON array: {}", message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(chunks: &[&str]) -> Result<Vec<String>> {
+        let mut stream = JsonArrayStream::new();
+        let mut elements = Vec::new();
+        for chunk in chunks {
+            for element in stream.feed(chunk.as_bytes())? {
+                elements.push(String::from_utf8(element).unwrap());
+            }
+        }
+        stream.finish()?;
+        Ok(elements)
+    }
+
+    #[test]
+    fn test_splits_elements_across_chunks() {
+        let elements = split(&[" [{\"a\": [1, ", "2]}, {\"b\"", ": \"x\"}", ", 3 ]\n"]).unwrap();
+        assert_eq!(elements, vec!["{\"a\": [1, 2]}", "{\"b\": \"x\"}", "3"]);
+    }
+
+    #[test]
+    fn test_ignores_brackets_and_commas_in_strings() {
+        let elements = split(&["[{\"name\": \"a, b ] } [\"}, {\"q\": \"say \\\"hi\\\", ok\"}]"]).unwrap();
+        assert_eq!(elements, vec!["{\"name\": \"a, b ] } [\"}", "{\"q\": \"say \\\"hi\\\", ok\"}"]);
+        // Whitespace inside strings is preserved
+        assert_eq!(split(&["[\"a b\"]"]).unwrap(), vec!["\"a b\""]);
+    }
+
+    #[test]
+    fn test_empty_array() {
+        assert!(split(&["[]"]).unwrap().is_empty());
+        assert!(split(&["  [ \n ]  "]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_rejects_invalid_input() {
+        assert!(split(&["{\"a\": 1}"]).is_err());
+        assert!(split(&["[{\"a\": 1}"]).is_err());
+        assert!(split(&["[1] x"]).is_err());
+        assert!(split(&["[}]"]).is_err());
+    }
+}
@@ -0,0 +1,150 @@
+use crate::error::{BwError, Result};
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Notes attached to items on this computer only, by item id
+/// They are never sent to the server, so they don't change the vault
+pub type LocalNotes = BTreeMap<String, String>;
+
+/// Tags attached to items on this computer only, by item id, in lowercase
+pub type LocalTags = BTreeMap<String, BTreeSet<String>>;
+
+/// Ids of the items left out of the list and search results unless hidden items are shown
+pub type HiddenItems = BTreeSet<String>;
+
+const NOTES_FILE: &str = "local_notes.json";
+const TAGS_FILE: &str = "local_tags.json";
+const HIDDEN_FILE: &str = "hidden_items.json";
+
+/// Read the local notes, empty if there are none or they can't be read
+pub fn load() -> LocalNotes {
+    load_file(NOTES_FILE)
+}
+
+/// Write the local notes, skipped in ephemeral mode
+pub fn save(notes: &LocalNotes) -> Result<()> {
+    save_file(NOTES_FILE, notes)
+}
+
+/// Read the local tags, empty if there are none or they can't be read
+pub fn load_tags() -> LocalTags {
+    load_file(TAGS_FILE)
+}
+
+/// Write the local tags, skipped in ephemeral mode
+pub fn save_tags(tags: &LocalTags) -> Result<()> {
+    save_file(TAGS_FILE, tags)
+}
+
+/// Read the hidden items, none if they can't be read
+pub fn load_hidden() -> HiddenItems {
+    load_file(HIDDEN_FILE)
+}
+
+/// Write the hidden items, skipped in ephemeral mode
+pub fn save_hidden(hidden: &HiddenItems) -> Result<()> {
+    save_file(HIDDEN_FILE, hidden)
+}
+
+/// Split typed tags on commas and spaces, e.g. "Work, #vpn" gives "vpn" and "work"
+pub fn parse_tags(text: &str) -> BTreeSet<String> {
+    text.split(|c: char| c == ',' || c.is_whitespace())
+        .map(|tag| tag.trim_start_matches('#').to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+fn file_path(name: &str) -> Result<PathBuf> {
+    Ok(crate::profile::data_dir()?.join(name))
+}
+
+fn load_file<T: DeserializeOwned + Default>(name: &str) -> T {
+    if crate::profile::ephemeral() {
+        return T::default();
+    }
+    match file_path(name) {
+        Ok(path) => load_from(&path),
+        Err(e) => {
+            crate::logger::Logger::warn(&format!("Failed to locate {}: {}", name, e));
+            T::default()
+        }
+    }
+}
+
+fn load_from<T: DeserializeOwned + Default>(path: &Path) -> T {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return T::default(),
+        Err(e) => {
+            crate::logger::Logger::error(&format!("Failed to read {}: {}", path.display(), e));
+            return T::default();
+        }
+    };
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        crate::logger::Logger::warn(&format!("Local annotations file {} is invalid: {}", path.display(), e));
+        T::default()
+    })
+}
+
+fn save_file<T: Serialize>(name: &str, value: &T) -> Result<()> {
+    if crate::profile::ephemeral() {
+        return Ok(());
+    }
+    let path = file_path(name)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    save_to(&path, value)
+}
+
+/// Write to a temporary file first, so a crash mid-write never loses the annotations
+fn save_to<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| BwError::ParseError(format!("Failed to serialize {}: {}", path.display(), e)))?;
+    let temp = path.with_extension("json.tmp");
+    fs::write(&temp, json)?;
+    fs::rename(&temp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_notes_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("bwtui-notes-test-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join(NOTES_FILE)
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = temp_notes_path("roundtrip");
+        assert!(load_from::<LocalNotes>(&path).is_empty());
+
+        let notes = LocalNotes::from([("1".to_string(), "Use the VPN first".to_string())]);
+        save_to(&path, &notes).unwrap();
+        assert_eq!(load_from::<LocalNotes>(&path), notes);
+        assert!(!path.with_extension("json.tmp").exists());
+
+        let tags = LocalTags::from([("1".to_string(), BTreeSet::from(["work".to_string()]))]);
+        save_to(&path, &tags).unwrap();
+        assert_eq!(load_from::<LocalTags>(&path), tags);
+    }
+
+    #[test]
+    fn test_parse_tags() {
+        let tags: Vec<String> = parse_tags(" Work, #vpn  work,,").into_iter().collect();
+        assert_eq!(tags, ["vpn", "work"]);
+        assert!(parse_tags(" , # ").is_empty());
+    }
+
+    #[test]
+    fn test_invalid_file_is_ignored() {
+        let path = temp_notes_path("invalid");
+        fs::write(&path, "not json").unwrap();
+        assert!(load_from::<LocalNotes>(&path).is_empty());
+    }
+}
@@ -1,7 +1,10 @@
+use crate::config::{LogFormat, LoggingConfig};
 use crate::error::{BwError, Result};
-use log::LevelFilter;
+use log::{Level, LevelFilter};
 use simplelog::{ConfigBuilder, WriteLogger};
-use std::fs::{self, OpenOptions};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::panic::Location;
 use std::path::{Path, PathBuf};
 use std::sync::{Mutex, OnceLock};
 
@@ -12,9 +15,13 @@ static LOG_PATH: OnceLock<Mutex<Option<PathBuf>>> = OnceLock::new();
 pub struct Logger;
 
 impl Logger {
-    /// Initialize the logger
-    /// Creates a timestamped log file and cleans up old logs
-    pub fn init() -> Result<()> {
+    /// Initialize the logger with the configured level and format
+    /// Creates a timestamped log file and cleans up old logs, nothing is logged in ephemeral mode
+    pub fn init(logging: &LoggingConfig) -> Result<()> {
+        if crate::profile::ephemeral() {
+            return Ok(());
+        }
+
         let log_dir = Self::get_log_directory()?;
         
         // Clean up old log files
@@ -43,19 +50,21 @@ impl Logger {
                 .map_err(|e| BwError::CommandFailed(format!("Failed to set log file permissions: {}", e)))?;
         }
         
-        // Create custom config
-        let mut config_builder = ConfigBuilder::default();
-        config_builder.set_time_format_rfc3339();
-        let _ = config_builder.set_time_offset_to_local(); // Ignore error, use default if it fails
-        let config = config_builder.build();
-        
-        // Initialize simplelog
-        WriteLogger::init(
-            LevelFilter::Info, // Log ERROR, WARN, and INFO
-            config,
-            file,
-        )
-        .map_err(|e| BwError::CommandFailed(format!("Failed to initialize logger: {}", e)))?;
+        let level = logging.level.filter();
+        let installed = match logging.format {
+            LogFormat::Text => {
+                // Create custom config
+                let mut config_builder = ConfigBuilder::default();
+                config_builder.set_time_format_rfc3339();
+                let _ = config_builder.set_time_offset_to_local(); // Ignore error, use default if it fails
+                WriteLogger::init(level, config_builder.build(), file)
+                    .map_err(|e| e.to_string())
+            }
+            LogFormat::Json => log::set_boxed_logger(Box::new(JsonLogger { level, file: Mutex::new(file) }))
+                .map(|()| log::set_max_level(level))
+                .map_err(|e| e.to_string()),
+        };
+        installed.map_err(|e| BwError::CommandFailed(format!("Failed to initialize logger: {}", e)))?;
         
         // Store log path
         let log_path_mutex = LOG_PATH.get_or_init(|| Mutex::new(None));
@@ -108,7 +117,7 @@ impl Logger {
         }
         
         // Sort by modification time (newest first)
-        log_files.sort_by(|a, b| b.1.cmp(&a.1));
+        log_files.sort_by_key(|b| std::cmp::Reverse(b.1));
         
         // Keep only the 5 most recent, delete the rest
         if log_files.len() > 5 {
@@ -166,21 +175,124 @@ impl Logger {
     }
     
     /// Log an error message (sanitized)
+    #[track_caller]
     pub fn error(message: &str) {
-        let sanitized = Self::sanitize_message(message);
-        log::error!("{}", sanitized);
+        Self::write(Level::Error, message, Location::caller());
     }
     
     /// Log a warning message (sanitized)
+    #[track_caller]
     pub fn warn(message: &str) {
-        let sanitized = Self::sanitize_message(message);
-        log::warn!("{}", sanitized);
+        Self::write(Level::Warn, message, Location::caller());
     }
     
     /// Log an info message (sanitized)
+    #[track_caller]
     pub fn info(message: &str) {
+        Self::write(Level::Info, message, Location::caller());
+    }
+
+    /// Log a sanitized message under the module of the code that called the logger
+    fn write(level: Level, message: &str, location: &Location) {
+        if level > log::max_level() {
+            return;
+        }
         let sanitized = Self::sanitize_message(message);
-        log::info!("{}", sanitized);
+        let module = module_path_of(location.file());
+        log::logger().log(
+            &log::Record::builder()
+                .level(level)
+                .target(&module)
+                .module_path(Some(&module))
+                .file(Some(location.file()))
+                .line(Some(location.line()))
+                .args(format_args!("{}", sanitized))
+                .build(),
+        );
+    }
+}
+
+/// Module path of a source file, such as bwtui::actions::copy for src/actions/copy.rs
+fn module_path_of(file: &str) -> String {
+    let file = file.replace('\\', "/");
+    let path = file.strip_prefix("src/").unwrap_or(&file);
+    let path = path.strip_suffix(".rs").unwrap_or(path);
+    let path = path.strip_suffix("/mod").unwrap_or(path);
+    match path {
+        "main" => "bwtui".to_string(),
+        path => format!("bwtui::{}", path.replace('/', "::")),
     }
 }
 
+/// Writes each record as a JSON object on its own line, for log analysis tools
+struct JsonLogger {
+    level: LevelFilter,
+    file: Mutex<File>,
+}
+
+impl log::Log for JsonLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let line = json_line(record, &chrono::Local::now().to_rfc3339());
+        if let Ok(mut file) = self.file.lock() {
+            let _ = writeln!(file, "{}", line);
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// A record as a single line of JSON
+fn json_line(record: &log::Record, time: &str) -> String {
+    serde_json::json!({
+        "time": time,
+        "level": record.level().as_str(),
+        "target": record.target(),
+        "module": record.module_path(),
+        "file": record.file(),
+        "line": record.line(),
+        "message": record.args().to_string(),
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_path_of() {
+        assert_eq!(module_path_of("src/main.rs"), "bwtui");
+        assert_eq!(module_path_of("src/app.rs"), "bwtui::app");
+        assert_eq!(module_path_of("src/actions/copy.rs"), "bwtui::actions::copy");
+        assert_eq!(module_path_of("src\\ui\\mod.rs"), "bwtui::ui");
+    }
+
+    #[test]
+    fn test_json_line() {
+        let record = log::Record::builder()
+            .level(Level::Warn)
+            .target("bwtui::cache")
+            .module_path(Some("bwtui::cache"))
+            .file(Some("src/cache.rs"))
+            .line(Some(12))
+            .args(format_args!("Cache \"old\""))
+            .build();
+        let value: serde_json::Value = serde_json::from_str(&json_line(&record, "2024-01-01T00:00:00+00:00")).unwrap();
+        assert_eq!(value["level"], "WARN");
+        assert_eq!(value["target"], "bwtui::cache");
+        assert_eq!(value["module"], "bwtui::cache");
+        assert_eq!(value["line"], 12);
+        assert_eq!(value["message"], "Cache \"old\"");
+    }
+}
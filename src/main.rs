@@ -1,36 +1,103 @@
 mod actions;
+#[cfg(feature = "api-backend")]
+mod api;
 mod app;
+mod args;
+mod backend;
 mod cache;
 mod cli;
+mod cli_version;
 mod clipboard;
+mod config;
+mod doctor;
+mod duplicates;
 mod error;
 mod events;
+#[cfg(test)]
+mod fake_backend;
+mod global_domains;
+mod instance;
+mod item_format;
+mod json_stream;
+mod local_notes;
 mod logger;
+mod maintenance;
+#[cfg(feature = "demo")]
+mod mock_data;
+mod profile;
+mod qr;
+mod retry;
 mod session;
+mod signals;
 mod state;
 mod terminal;
+mod text;
+mod totp_util;
+mod trace;
 mod types;
 mod ui;
 
 use app::App;
+use clap::Parser;
+use crossterm::event::EventStream;
 use error::Result;
-use events::EventHandler;
+use events::{Action, EventHandler};
+use futures_util::StreamExt;
 use session::SessionManager;
 use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+/// How often time-based displays (TOTP countdown, status expiry) are refreshed
+const CLOCK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Frame interval of the loading spinner
+const ANIMATION_INTERVAL: Duration = Duration::from_millis(100);
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    // Parse arguments before touching the terminal so errors and help are readable
+    let args = args::Args::parse();
+    if let Some(profile) = &args.profile {
+        profile::set(profile);
+    }
+    if args.ephemeral {
+        profile::set_ephemeral();
+    }
+
     // Initialize logger early (before TUI starts)
     // If logger initialization fails, log to stderr but continue execution
-    if let Err(e) = logger::Logger::init() {
+    // Read the config once up front for the log settings (problems with it can't be logged yet)
+    if let Err(e) = logger::Logger::init(&config::Config::load().logging) {
         eprintln!("Warning: Failed to initialize logger: {}", e);
         eprintln!("Continuing without file logging...");
     } else {
         logger::Logger::info("Application starting");
     }
     
+    // Kept until exit, dropping it finishes writing the trace file
+    let _trace_guard = args.trace.as_deref().map(trace::start).transpose()?;
+
+    #[cfg(feature = "demo")]
+    if args.bench {
+        return mock_data::bench(args.demo.unwrap_or_default());
+    }
+
+    if args.doctor {
+        let session = session::SessionOptions::new(args.session.clone(), std::env::var("BW_SESSION").ok(), args.no_keyring);
+        let mut config = config::Config::load();
+        config.apply_args(&args);
+        if !doctor::run(&config, &session).await {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if args.rotate_keys {
+        return maintenance::rotate_keys();
+    }
+
     // Run the application and handle cleanup
-    let result = run().await;
+    let result = run(args).await;
     
     // Log shutdown
     logger::Logger::info("Application shutting down");
@@ -41,15 +108,19 @@ async fn main() -> Result<()> {
     result
 }
 
-async fn run() -> Result<()> {
+async fn run(args: args::Args) -> Result<()> {
     // Setup terminal
     terminal::setup().map_err(|e| {
         logger::Logger::error(&format!("Failed to setup terminal: {}", e));
         e
     })?;
+    let _terminal_guard = terminal::TerminalGuard;
 
     // Initialize application
-    let mut app = App::new();
+    let session = session::SessionOptions::new(args.session.clone(), std::env::var("BW_SESSION").ok(), args.no_keyring || args.demo_items().is_some());
+    let mut app = App::new(session);
+    app.apply_args(&args);
+    terminal::install_panic_hook(app.config.clipboard.clear_on_panic);
     
     // Show clipboard warning if needed
     if app.should_show_clipboard_warning() {
@@ -57,8 +128,26 @@ async fn run() -> Result<()> {
         app.state.set_status("Warning: Clipboard not available", state::MessageLevel::Warning);
     }
 
+    // Only one instance per profile writes the cache and saved session, ephemeral mode writes neither
+    let _instance_lock = if profile::ephemeral() {
+        None
+    } else {
+        match instance::InstanceLock::acquire() {
+            Ok(Some(lock)) => Some(lock),
+            Ok(None) => {
+                app.use_read_only_storage();
+                None
+            }
+            Err(e) => {
+                logger::Logger::warn(&format!("Failed to lock the profile, continuing without it: {}", e));
+                None
+            }
+        }
+    };
+
     // Load cache and start vault initialization
     app.load_from_cache();
+    app.load_local_notes();
     app.start_vault_initialization();
 
     // Initialize UI, event handler, and session manager
@@ -66,34 +155,95 @@ async fn run() -> Result<()> {
         logger::Logger::error(&format!("Failed to initialize UI: {}", e));
         e
     })?;
-    let event_handler = EventHandler::new();
+    let event_handler = EventHandler::from_config(&app.config);
     let session_manager = SessionManager::new().map_err(|e| {
         logger::Logger::error(&format!("Failed to initialize session manager: {}", e));
         e
     })?;
 
-    // Main event loop
+    // Main event loop: sleep until a terminal event, a background message or a timer fires,
+    // and only redraw when something changed
+    let mut events = EventStream::new();
+    let mut clock = tokio::time::interval(CLOCK_INTERVAL);
+    clock.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let mut animation = tokio::time::interval(ANIMATION_INTERVAL);
+    animation.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let mut frames = ui::frame_limiter::FrameLimiter::new(app.config.layout.max_fps);
+    let mut signals = signals::Signals::new().map_err(|e| {
+        logger::Logger::error(&format!("Failed to listen for signals: {}", e));
+        e
+    })?;
+
     loop {
-        // Update app state and render UI
-        if let Err(e) = app.update(&mut ui) {
-            logger::Logger::error(&format!("Error updating app: {}", e));
-            // Continue execution - don't break on update errors
+        // The picker exits as soon as its field is copied
+        if app.picked() {
+            logger::Logger::info("Picked an item, exiting");
+            break;
+        }
+        if app.unlock_attempts_exhausted() {
+            break;
         }
 
-        // Poll for events with 100ms timeout for smooth animation
-        match event_handler.poll_event(Duration::from_millis(100), &app.state) {
-            Ok(Some(action)) => {
-                // Handle the action (returns false if should quit)
-                if !app.handle_action(action, &session_manager).await {
-                    break;
+        // A change made too soon after the last frame is drawn when the next one is due
+        let now = std::time::Instant::now();
+        if app.state.dirty && frames.due(now) {
+            frames.drawn(now);
+            if let Err(e) = app.render(&mut ui) {
+                logger::Logger::error(&format!("Error rendering UI: {}", e));
+                // Continue execution - don't break on render errors
+            }
+        }
+        let next_frame = frames.next_frame().map_or_else(tokio::time::Instant::now, tokio::time::Instant::from_std);
+
+        let action = tokio::select! {
+            event = events.next() => match event {
+                Some(Ok(event)) => {
+                    // Resizes and ignored keys still need a redraw
+                    app.state.mark_dirty();
+                    event_handler.handle_event(event, &app.state)
+                }
+                Some(Err(e)) => {
+                    logger::Logger::error(&format!("Error reading events: {}", e));
+                    // Continue execution - don't break on read errors
+                    None
                 }
+                None => break,
+            },
+            Some(message) = app.next_message() => {
+                app.handle_message(message);
+                None
             }
-            Ok(None) => {
-                // No event, continue
+            // Draw a change that was held back by the frame rate limit
+            _ = tokio::time::sleep_until(next_frame), if app.state.dirty => None,
+            // Spinner frames, only while something is loading, at their own pace whatever the input
+            _ = animation.tick(), if app.state.animating() => {
+                app.state.advance_sync_animation();
+                None
             }
-            Err(e) => {
-                logger::Logger::error(&format!("Error polling events: {}", e));
-                // Continue execution - don't break on poll errors
+            // TOTP countdown and status message expiry
+            _ = clock.tick() => Some(Action::Tick),
+            signal = signals.recv() => match signal {
+                signals::Signal::Terminate => {
+                    logger::Logger::info("Terminated by signal");
+                    break;
+                }
+                signals::Signal::Suspend => Some(Action::Suspend),
+                signals::Signal::Continue => {
+                    resume(&mut app, &mut ui);
+                    None
+                }
+            },
+        };
+
+        if let Some(Action::Suspend) = action {
+            suspend(&mut app, &mut ui);
+            continue;
+        }
+
+        if let Some(action) = action {
+            // Handle the action (returns false if should quit)
+            if !app.handle_action(action, &session_manager).await {
+                break;
             }
         }
     }
@@ -104,5 +254,33 @@ async fn run() -> Result<()> {
         e
     })?;
 
+    if app.unlock_attempts_exhausted() {
+        eprintln!("Too many wrong master passwords, exiting");
+    }
+
     Ok(())
 }
+
+/// Give the terminal back to the shell until the process is continued
+fn suspend(app: &mut App, ui: &mut ui::UI) {
+    #[cfg(unix)]
+    {
+        if let Err(e) = terminal::suspend() {
+            logger::Logger::error(&format!("Failed to suspend: {}", e));
+        }
+        resume(app, ui);
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = ui;
+        app.state.set_status("Suspending is not supported on this platform", state::MessageLevel::Warning);
+    }
+}
+
+/// Take the terminal back after being stopped and redraw everything
+fn resume(app: &mut App, ui: &mut ui::UI) {
+    if let Err(e) = terminal::resume().and_then(|_| ui.clear()) {
+        logger::Logger::error(&format!("Failed to restore the terminal after resuming: {}", e));
+    }
+    app.state.mark_dirty();
+}
@@ -0,0 +1,62 @@
+use crate::error::{BwError, Result};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+/// Overwrite a file with zeros, flush it to disk and remove it
+pub fn wipe_file(path: &Path) -> std::io::Result<()> {
+    let len = fs::metadata(path)?.len() as usize;
+    let mut file = OpenOptions::new().write(true).open(path)?;
+    file.write_all(&vec![0u8; len])?;
+    file.sync_all()?;
+    drop(file);
+    fs::remove_file(path)
+}
+
+/// Re-encrypt the saved session of the current profile with a fresh key, rewrite its
+/// unencrypted vault cache, wipe the old files, and print what was done
+pub fn rotate_keys() -> Result<()> {
+    let Some(_lock) = crate::instance::InstanceLock::acquire()? else {
+        return Err(BwError::CommandFailed(
+            "Another bwtui instance is using this profile, quit it before rotating keys".to_string(),
+        ));
+    };
+    crate::logger::Logger::info("Rotating cache and session files");
+
+    if crate::cache::rewrite_cache()? {
+        println!("Vault cache rewritten unchanged (it holds no secrets and is not encrypted) and old file wiped");
+    } else {
+        println!("No vault cache to rewrite");
+    }
+
+    let manager = crate::session::SessionManager::new()?;
+    if manager.rotate_token()? {
+        println!("Saved session re-encrypted and old copy wiped");
+    } else {
+        println!("No saved session to re-encrypt");
+    }
+
+    Ok(())
+}
+
+/// Map a wipe failure to the repo error type
+pub(crate) fn wipe_error(what: &str, e: std::io::Error) -> BwError {
+    let error_msg = format!("Failed to wipe {}: {}", what, e);
+    crate::logger::Logger::error(&error_msg);
+    BwError::CommandFailed(error_msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wipe_file_removes_file() {
+        let path = std::env::temp_dir().join(format!("bwtui-wipe-test-{}", std::process::id()));
+        fs::write(&path, b"secret contents").unwrap();
+
+        wipe_file(&path).unwrap();
+        assert!(!path.exists());
+        assert!(wipe_file(&path).is_err());
+    }
+}
@@ -0,0 +1,341 @@
+use crate::backend::{Backend, ItemBatchFn, Unlocked, VaultBackend, VaultStatus};
+use crate::error::{BwError, Result};
+use crate::retry::RetryListener;
+use crate::state::AppState;
+use crate::types::{
+    CardData, Collection, CollectionAccess, CustomField, CustomFieldType, EquivalentDomains, Folder, IdentityData, ItemType, LoginData, OrgMember,
+    Organization, Uri, VaultItem,
+};
+use futures_util::future::BoxFuture;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Items sent to the UI at once, like the CLI's streaming batches
+const BATCH_SIZE: usize = 200;
+
+const SERVICES: &[&str] = &[
+    "GitHub", "GitLab", "Acme Mail", "Northwind Bank", "Contoso VPN", "Fabrikam HR", "Jira", "Slack",
+    "AWS Console", "Azure Portal", "Grafana", "Kibana", "Netflix", "Spotify", "Dropbox", "Wiki",
+];
+
+const FIRST_NAMES: &[&str] = &["alice", "bob", "carol", "dave", "erin", "frank", "grace", "heidi"];
+
+const FOLDERS: &[&str] = &["Work", "Work/Infra", "Personal", "Finance", "Shared"];
+
+/// Small deterministic generator, so the same count always gives the same vault
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        // xorshift64*
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn pick<'a>(&mut self, values: &[&'a str]) -> &'a str {
+        values[self.below(values.len())]
+    }
+
+    fn digits(&mut self, count: usize) -> String {
+        (0..count).map(|_| char::from(b'0' + self.below(10) as u8)).collect()
+    }
+}
+
+/// Folders of the synthetic vault
+pub fn folders() -> Vec<Folder> {
+    FOLDERS.iter()
+        .enumerate()
+        .map(|(index, name)| Folder { id: format!("demo-folder-{}", index), name: name.to_string() })
+        .collect()
+}
+
+/// Organizations of the synthetic vault
+pub fn organizations() -> Vec<Organization> {
+    vec![Organization { id: "demo-org-0".to_string(), name: "Acme Corp".to_string() }]
+}
+
+/// Collections of the synthetic vault, all organization items are in the only one
+pub fn collections() -> Vec<Collection> {
+    vec![Collection { id: "demo-collection-0".to_string(), organization_id: "demo-org-0".to_string(), name: "Engineering".to_string() }]
+}
+
+/// Generate `count` synthetic items of every type, mostly logins like a real vault
+pub fn generate_items(count: usize) -> Vec<VaultItem> {
+    let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+    let now = chrono::Utc::now();
+    (0..count).map(|index| generate_item(index, &mut rng, now)).collect()
+}
+
+fn generate_item(index: usize, rng: &mut Rng, now: chrono::DateTime<chrono::Utc>) -> VaultItem {
+    let service = rng.pick(SERVICES);
+    let user = rng.pick(FIRST_NAMES);
+    let item_type = match rng.below(10) {
+        0 => ItemType::SecureNote,
+        1 => ItemType::Card,
+        2 => ItemType::Identity,
+        _ => ItemType::Login,
+    };
+    let name = match item_type {
+        ItemType::SecureNote => format!("{} notes {}", service, index),
+        ItemType::Card => format!("{} card {}", service, index),
+        ItemType::Identity => format!("{} {}", user, index),
+        _ => format!("{} {}", service, index),
+    };
+    let domain = format!("{}.example.com", service.to_lowercase().replace(' ', "-"));
+    let revision_date = now - chrono::Duration::days(rng.below(1500) as i64);
+
+    let mut item = VaultItem {
+        id: format!("demo-{:06}", index),
+        name,
+        item_type,
+        login: (item_type == ItemType::Login).then(|| LoginData {
+            username: Some(format!("{}@{}", user, domain)),
+            password: Some(format!("demo-{}-{}", rng.digits(6), rng.digits(6))),
+            totp: (rng.below(4) == 0).then(|| "JBSWY3DPEHPK3PXP".to_string()),
+            uris: Some(vec![Uri { uri: format!("https://{}/login", domain), match_type: None }]),
+            password_revision_date: Some(revision_date),
+        }),
+        card: (item_type == ItemType::Card).then(|| CardData {
+            brand: Some("Visa".to_string()),
+            card_holder_name: Some(user.to_string()),
+            number: Some(format!("4{}", rng.digits(15))),
+            exp_month: Some(format!("{}", 1 + rng.below(12))),
+            exp_year: Some(format!("{}", 2027 + rng.below(5))),
+            code: Some(rng.digits(3)),
+        }),
+        identity: (item_type == ItemType::Identity).then(|| IdentityData {
+            title: None,
+            first_name: Some(user.to_string()),
+            middle_name: None,
+            last_name: Some("Example".to_string()),
+            address1: Some(format!("{} Main Street", 1 + rng.below(999))),
+            address2: None,
+            address3: None,
+            city: Some("Springfield".to_string()),
+            state: None,
+            postal_code: Some(rng.digits(5)),
+            country: Some("US".to_string()),
+            phone: Some(format!("555-{}", rng.digits(4))),
+            email: Some(format!("{}@example.com", user)),
+            ssn: None,
+            license_number: None,
+            passport_number: None,
+            username: Some(user.to_string()),
+        }),
+        notes: (item_type == ItemType::SecureNote || rng.below(5) == 0)
+            .then(|| format!("Synthetic item {} for demos and benchmarks", index)),
+        fields: (rng.below(3) == 0).then(|| vec![CustomField {
+            name: Some("api_key".to_string()),
+            value: Some(format!("key-{}", rng.digits(12))),
+            field_type: Some(CustomFieldType::Hidden),
+            linked_id: None,
+        }]),
+        favorite: rng.below(20) == 0,
+        folder_id: (rng.below(3) != 0).then(|| format!("demo-folder-{}", rng.below(FOLDERS.len()))),
+        organization_id: (rng.below(5) == 0).then(|| "demo-org-0".to_string()),
+        revision_date,
+        object: Some("item".to_string()),
+        creation_date: Some(revision_date),
+        deleted_date: None,
+        password_history: None,
+        attachments: None,
+        collection_ids: None,
+        reprompt: Some(0),
+        raw_fields: Default::default(),
+    };
+    if item.organization_id.is_some() {
+        item.collection_ids = Some(vec!["demo-collection-0".to_string()]);
+    }
+    item
+}
+
+/// Backend serving a synthetic vault, for demos and screenshots without a real account
+#[derive(Clone)]
+pub struct DemoBackend {
+    items: Arc<Vec<VaultItem>>,
+}
+
+impl DemoBackend {
+    pub fn new(count: usize) -> Self {
+        Self { items: Arc::new(generate_items(count)) }
+    }
+}
+
+impl VaultBackend for DemoBackend {
+    fn check_status(&self) -> BoxFuture<'_, Result<VaultStatus>> {
+        Box::pin(async { Ok(VaultStatus::Unlocked) })
+    }
+
+    fn unlock<'a>(&'a self, _password: &'a str) -> BoxFuture<'a, Result<Unlocked>> {
+        Box::pin(async move { Ok(Unlocked { session_token: None, backend: Arc::new(self.clone()) }) })
+    }
+
+    fn sync(&self) -> BoxFuture<'_, Result<()>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn list_items<'a>(&'a self, on_batch: &'a ItemBatchFn) -> BoxFuture<'a, Result<usize>> {
+        Box::pin(async move {
+            for batch in self.items.chunks(BATCH_SIZE) {
+                on_batch(batch.to_vec());
+            }
+            Ok(self.items.len())
+        })
+    }
+
+    fn list_organizations(&self) -> BoxFuture<'_, Result<Vec<Organization>>> {
+        Box::pin(async { Ok(organizations()) })
+    }
+
+    fn list_folders(&self) -> BoxFuture<'_, Result<Vec<Folder>>> {
+        Box::pin(async { Ok(folders()) })
+    }
+
+    fn list_collections(&self) -> BoxFuture<'_, Result<Vec<Collection>>> {
+        Box::pin(async { Ok(collections()) })
+    }
+
+    fn equivalent_domains(&self) -> BoxFuture<'_, Result<EquivalentDomains>> {
+        Box::pin(async { Ok(EquivalentDomains::new(vec![vec!["github.example.com".to_string(), "ghe.example.com".to_string()]])) })
+    }
+
+    fn collection_access<'a>(&'a self, _organization_id: &'a str, _collection_id: &'a str) -> BoxFuture<'a, Result<CollectionAccess>> {
+        Box::pin(async {
+            let members = FIRST_NAMES[..3]
+                .iter()
+                .enumerate()
+                .map(|(index, name)| OrgMember {
+                    id: format!("demo-member-{}", index),
+                    name: Some(format!("{}{}", name[..1].to_uppercase(), &name[1..])),
+                    email: format!("{}@acme.example.com", name),
+                })
+                .collect();
+            Ok(CollectionAccess { members, groups: 1 })
+        })
+    }
+
+    fn get_totp<'a>(&'a self, item_id: &'a str) -> BoxFuture<'a, Result<String>> {
+        Box::pin(async move {
+            let secret = self.items
+                .iter()
+                .find(|item| item.id == item_id)
+                .and_then(|item| item.login.as_ref()?.totp.clone())
+                .ok_or_else(|| BwError::CommandFailed("No TOTP configured for this item".to_string()))?;
+            Ok(crate::totp_util::generate_totp(&secret)?.code)
+        })
+    }
+
+    fn delete_item<'a>(&'a self, _item_id: &'a str) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async { Err(BwError::CommandFailed("Items can't be deleted in demo mode".to_string())) })
+    }
+
+    fn share_item<'a>(&'a self, _item_id: &'a str, _organization_id: &'a str, _collection_ids: &'a [String]) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async { Err(BwError::CommandFailed("Items can't be shared in demo mode".to_string())) })
+    }
+
+    fn set_reprompt<'a>(&'a self, _item_id: &'a str, _enabled: bool) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async { Err(BwError::CommandFailed("Items can't be changed in demo mode".to_string())) })
+    }
+
+    fn move_item<'a>(&'a self, _item_id: &'a str, _folder_id: Option<&'a str>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async { Err(BwError::CommandFailed("Items can't be changed in demo mode".to_string())) })
+    }
+
+    fn create_folder<'a>(&'a self, _name: &'a str) -> BoxFuture<'a, Result<Folder>> {
+        Box::pin(async { Err(BwError::CommandFailed("Folders can't be changed in demo mode".to_string())) })
+    }
+
+    fn rename_folder<'a>(&'a self, _folder_id: &'a str, _name: &'a str) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async { Err(BwError::CommandFailed("Folders can't be changed in demo mode".to_string())) })
+    }
+
+    fn delete_folder<'a>(&'a self, _folder_id: &'a str) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async { Err(BwError::CommandFailed("Folders can't be changed in demo mode".to_string())) })
+    }
+
+    fn with_retry_listener(&self, _listener: RetryListener) -> Backend {
+        Arc::new(self.clone())
+    }
+}
+
+/// Average time of `runs` calls of `f`
+fn time(runs: u32, mut f: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..runs {
+        f();
+    }
+    start.elapsed() / runs
+}
+
+/// Time filtering and rendering a synthetic vault without a terminal and print the results
+pub fn bench(count: usize) -> Result<()> {
+    const RUNS: u32 = 20;
+
+    let items = generate_items(count);
+    let mut state = AppState::new();
+    state.set_folders(folders());
+    state.set_organizations(organizations());
+    let load = time(1, || { state.load_items_with_secrets(items.clone()); });
+    println!("{} items, averages of {} runs", count, RUNS);
+    println!("{:<28} {:>10.2?}", "load", load);
+
+    for query in ["git", "acme mail", "alice example", "zzz"] {
+        let elapsed = time(RUNS, || {
+            state.set_filter_query(String::new());
+            state.set_filter_query(query.to_string());
+        });
+        println!("{:<28} {:>10.2?} ({} matches)", format!("filter \"{}\"", query), elapsed, state.vault.filtered_len());
+    }
+    state.set_filter_query(String::new());
+
+    let backend = ratatui::backend::TestBackend::new(160, 48);
+    let mut terminal = ratatui::Terminal::new(backend).map_err(|e| BwError::CommandFailed(e.to_string()))?;
+    let mut render_error = None;
+    let elapsed = time(RUNS, || {
+        if let Err(e) = terminal.draw(|frame| crate::ui::draw(frame, &mut state)) {
+            render_error = Some(e);
+        }
+    });
+    if let Some(e) = render_error {
+        return Err(BwError::CommandFailed(format!("Failed to render: {}", e)));
+    }
+    println!("{:<28} {:>10.2?}", "render 160x48", elapsed);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_items_is_deterministic() {
+        let items = generate_items(100);
+        assert_eq!(items.len(), 100);
+        assert_eq!(items[42].name, generate_items(43)[42].name);
+        assert!(items.iter().any(|item| item.item_type == ItemType::Card));
+        assert!(items.iter().filter(|item| item.item_type == ItemType::Login).all(|item| item.login.is_some()));
+    }
+
+    #[tokio::test]
+    async fn test_demo_backend_lists_in_batches() {
+        let backend = DemoBackend::new(BATCH_SIZE + 1);
+        let batches = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sizes = batches.clone();
+        let on_batch = move |batch: Vec<VaultItem>| sizes.lock().unwrap().push(batch.len());
+        let count = backend.list_items(&on_batch).await.unwrap();
+        assert_eq!(count, BATCH_SIZE + 1);
+        assert_eq!(*batches.lock().unwrap(), vec![BATCH_SIZE, 1]);
+    }
+
+    #[test]
+    fn test_bench_runs() {
+        bench(50).unwrap();
+    }
+}
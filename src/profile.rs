@@ -0,0 +1,80 @@
+use crate::error::{BwError, Result};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+/// Profile selected with --profile, set once at startup
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Whether nothing may be written to the data directory (--ephemeral)
+static EPHEMERAL: AtomicBool = AtomicBool::new(false);
+
+/// Keep the vault, session token and logs in memory only, for shared machines
+/// Modules that store files in the data directory check `ephemeral` and skip them
+pub fn set_ephemeral() {
+    EPHEMERAL.store(true, Ordering::Relaxed);
+}
+
+/// Whether --ephemeral was given
+pub fn ephemeral() -> bool {
+    EPHEMERAL.load(Ordering::Relaxed)
+}
+
+/// Use the configuration, cache and session token of the named profile
+pub fn set(name: &str) {
+    if PROFILE.set(name.to_string()).is_err() {
+        crate::logger::Logger::warn("Profile already set, ignoring");
+    }
+}
+
+/// Name of the selected profile, if any
+pub fn current() -> Option<&'static str> {
+    PROFILE.get().map(String::as_str)
+}
+
+/// Directory holding the configuration, cache and session token of the selected profile
+pub fn data_dir() -> Result<PathBuf> {
+    let home_dir = dirs::home_dir()
+        .ok_or_else(|| BwError::CommandFailed("Could not determine home directory".to_string()))?;
+    Ok(dir_for(&home_dir, current()))
+}
+
+/// ~/.bwtui, or ~/.bwtui/profiles/<name> for a profile
+fn dir_for(home_dir: &Path, profile: Option<&str>) -> PathBuf {
+    let dir = home_dir.join(".bwtui");
+    match profile {
+        Some(name) => dir.join("profiles").join(name),
+        None => dir,
+    }
+}
+
+/// Check a profile name, which is used in paths and keyring entry names
+pub fn parse_name(name: &str) -> std::result::Result<String, String> {
+    let valid = !name.is_empty()
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(name.to_string())
+    } else {
+        Err("profile names may only contain letters, digits, '-' and '_'".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_dir() {
+        let home = Path::new("/home/user");
+        assert_eq!(dir_for(home, None), PathBuf::from("/home/user/.bwtui"));
+        assert_eq!(dir_for(home, Some("work")), PathBuf::from("/home/user/.bwtui/profiles/work"));
+    }
+
+    #[test]
+    fn test_parse_name() {
+        assert_eq!(parse_name("work-2_b"), Ok("work-2_b".to_string()));
+        assert!(parse_name("").is_err());
+        assert!(parse_name("../etc").is_err());
+        assert!(parse_name("a b").is_err());
+    }
+}
@@ -0,0 +1,55 @@
+use qrcode::types::QrError;
+use qrcode::{Color, QrCode};
+
+/// Light modules around the code, scanners need a margin to find it
+const QUIET_ZONE: usize = 2;
+
+/// Render data as a QR code of half blocks, two rows of modules per line
+/// Dark modules are drawn with the foreground color, so show it dark on a light background
+pub fn qr_lines(data: &str) -> Result<Vec<String>, QrError> {
+    let code = QrCode::new(data.as_bytes())?;
+    let width = code.width();
+    let colors = code.to_colors();
+    let size = width + 2 * QUIET_ZONE;
+
+    let dark = |x: usize, y: usize| {
+        (QUIET_ZONE..width + QUIET_ZONE).contains(&x)
+            && (QUIET_ZONE..width + QUIET_ZONE).contains(&y)
+            && colors[(y - QUIET_ZONE) * width + x - QUIET_ZONE] == Color::Dark
+    };
+
+    Ok((0..size)
+        .step_by(2)
+        .map(|y| {
+            (0..size)
+                .map(|x| match (dark(x, y), dark(x, y + 1)) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                })
+                .collect()
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_qr_lines() {
+        // Version 1 codes are 21 modules wide
+        let lines = qr_lines("hello").unwrap();
+        assert_eq!(lines.len(), 13);
+        assert!(lines.iter().all(|line| line.chars().count() == 25));
+        assert!(lines[0].chars().all(|c| c == ' '));
+        // Top edge of the finder pattern in the top left corner
+        assert!(lines[1].chars().skip(2).take(7).all(|c| c == '█' || c == '▀'));
+    }
+
+    #[test]
+    fn test_too_much_data_is_an_error() {
+        assert!(qr_lines(&"x".repeat(5000)).is_err());
+    }
+}
@@ -0,0 +1,157 @@
+use crate::error::Result;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Callback invoked before each retry with (attempt, max_attempts)
+pub type RetryListener = Arc<dyn Fn(u32, u32) + Send + Sync>;
+
+/// Exponential backoff settings for transient CLI failures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for each subsequent one
+    pub base_delay: Duration,
+    /// Upper bound for the delay before jitter
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(8),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before the given retry (1 = first retry), without jitter
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+
+    /// Delay before the given retry with up to 50% random jitter added
+    fn delay_with_jitter(&self, retry: u32) -> Duration {
+        let backoff = self.backoff(retry);
+        // Sub-second clock noise is random enough to spread out concurrent retries
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .subsec_nanos();
+        let jitter = backoff.mul_f64((nanos % 1000) as f64 / 2000.0);
+        backoff + jitter
+    }
+}
+
+/// Run an operation, retrying transient failures with exponential backoff
+pub async fn retry<T, F, Fut>(
+    policy: RetryPolicy,
+    operation: &str,
+    listener: Option<&RetryListener>,
+    mut f: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        match f().await {
+            Err(e) if e.is_transient() && attempt < policy.max_attempts => {
+                let delay = policy.delay_with_jitter(attempt);
+                attempt += 1;
+                crate::logger::Logger::warn(&format!(
+                    "{} failed with a transient error, retrying ({}/{}) in {}ms: {}",
+                    operation,
+                    attempt,
+                    policy.max_attempts,
+                    delay.as_millis(),
+                    e
+                ));
+                if let Some(listener) = listener {
+                    listener(attempt, policy.max_attempts);
+                }
+                tokio::time::sleep(delay).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::BwError;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(4),
+        }
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.backoff(1), Duration::from_millis(500));
+        assert_eq!(policy.backoff(2), Duration::from_millis(1000));
+        assert_eq!(policy.backoff(3), Duration::from_millis(2000));
+        assert_eq!(policy.backoff(10), Duration::from_secs(8));
+    }
+
+    #[tokio::test]
+    async fn test_retries_transient_errors() {
+        let calls = AtomicU32::new(0);
+        let retries = Arc::new(AtomicU32::new(0));
+        let retries_clone = retries.clone();
+        let listener: RetryListener = Arc::new(move |attempt, max| {
+            assert_eq!(max, 3);
+            retries_clone.store(attempt, Ordering::SeqCst);
+        });
+
+        let result = retry(fast_policy(), "test", Some(&listener), || async {
+            if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err(BwError::CommandFailed("fetch failed: ECONNRESET".to_string()))
+            } else {
+                Ok(42)
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert_eq!(retries.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let calls = AtomicU32::new(0);
+        let result: Result<()> = retry(fast_policy(), "test", None, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(BwError::CommandFailed("request timed out".to_string()))
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_permanent_errors() {
+        let calls = AtomicU32::new(0);
+        let result: Result<()> = retry(fast_policy(), "test", None, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(BwError::VaultLocked)
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
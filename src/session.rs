@@ -2,6 +2,64 @@ use crate::error::{BwError, Result};
 use std::fs;
 use std::path::PathBuf;
 
+/// Where the session token comes from, set from the command line and environment
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionOptions {
+    /// Token to use instead of the stored one (`--session` or `BW_SESSION`)
+    pub token: Option<String>,
+    /// Never read or write the stored token (`--no-keyring`)
+    pub no_keyring: bool,
+}
+
+impl SessionOptions {
+    /// `--session` wins over `BW_SESSION`, which wins over the stored token
+    pub fn new(session_arg: Option<String>, env_token: Option<String>, no_keyring: bool) -> Self {
+        let token = session_arg
+            .filter(|token| !token.trim().is_empty())
+            .or_else(|| env_token.filter(|token| !token.trim().is_empty()));
+        Self { token, no_keyring }
+    }
+
+    /// Whether the stored token may be read and written, never in ephemeral mode
+    pub fn use_keyring(&self) -> bool {
+        !self.no_keyring && !crate::profile::ephemeral()
+    }
+
+    /// Whether the token to start with comes from storage, rather than the command line or BW_SESSION
+    pub fn uses_stored_token(&self) -> bool {
+        self.token.is_none() && self.use_keyring()
+    }
+
+    /// The token to start with: the given one, else the stored one unless the keyring is disabled
+    pub fn load_token(&self) -> Result<Option<String>> {
+        if let Some(token) = &self.token {
+            crate::logger::Logger::info("Using session token from the command line or BW_SESSION");
+            return Ok(Some(token.clone()));
+        }
+
+        if self.no_keyring {
+            crate::logger::Logger::info("Stored session token disabled with --no-keyring");
+            return Ok(None);
+        }
+
+        let session_manager = SessionManager::new().map_err(|e| {
+            crate::logger::Logger::error(&format!("Failed to initialize session manager: {}", e));
+            e
+        })?;
+        let session_token = session_manager.load_token().map_err(|e| {
+            crate::logger::Logger::warn(&format!("Failed to load session token: {}", e));
+            e
+        })?;
+
+        if session_token.is_some() {
+            crate::logger::Logger::info("Session token loaded from storage");
+        } else {
+            crate::logger::Logger::info("No session token found in storage");
+        }
+        Ok(session_token)
+    }
+}
+
 /// Session token manager with platform-specific encryption
 pub struct SessionManager {
     /// Path to the encrypted session file
@@ -14,15 +72,17 @@ impl SessionManager {
         Ok(Self { session_file })
     }
 
+    /// A token stored in the given file instead of the profile's
+    pub fn at(session_file: PathBuf) -> Self {
+        Self { session_file }
+    }
+
     /// Get the path to the session file
     fn get_session_file_path() -> Result<PathBuf> {
-        let home_dir = dirs::home_dir()
-            .ok_or_else(|| BwError::CommandFailed("Could not determine home directory".to_string()))?;
-
-        let config_dir = home_dir.join(".bwtui");
+        let config_dir = crate::profile::data_dir()?;
         
-        // Create directory if it doesn't exist
-        if !config_dir.exists() {
+        // Create directory if it doesn't exist, ephemeral mode never stores a token in it
+        if !config_dir.exists() && !crate::profile::ephemeral() {
             fs::create_dir_all(&config_dir).map_err(|e| {
                 BwError::CommandFailed(format!("Failed to create config directory: {}", e))
             })?;
@@ -77,8 +137,63 @@ impl SessionManager {
         Ok(())
     }
 
+    /// Store the saved token again with fresh encryption and wipe the old copy,
+    /// returning false when no token is saved
+    pub fn rotate_token(&self) -> Result<bool> {
+        let Some(token) = self.load_token()? else {
+            return Ok(false);
+        };
+
+        crate::maintenance::wipe_file(&self.session_file)
+            .map_err(|e| crate::maintenance::wipe_error("session file", e))?;
+        Self::delete_secret()?;
+        self.save_token(&token)?;
+        crate::logger::Logger::info("Session token rotated");
+        Ok(true)
+    }
+
+    /// Check that the keyring can be reached, without changing what it stores
+    #[cfg(not(target_os = "windows"))]
+    pub fn check_keyring() -> std::result::Result<(), String> {
+        use keyring::Entry;
+
+        match Entry::new(&keyring_service(), &whoami::username()).and_then(|entry| entry.get_password()) {
+            Ok(_) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// DPAPI is always available on Windows
+    #[cfg(target_os = "windows")]
+    pub fn check_keyring() -> std::result::Result<(), String> {
+        Ok(())
+    }
+
+    /// Remove the keyring entry so the token is stored as a new secret
+    #[cfg(not(target_os = "windows"))]
+    fn delete_secret() -> Result<()> {
+        use keyring::Entry;
+
+        let entry = Entry::new(&keyring_service(), &whoami::username())
+            .and_then(|entry| entry.delete_password())
+            .or_else(|e| match e {
+                keyring::Error::NoEntry => Ok(()),
+                e => Err(e),
+            });
+        entry.map_err(|e| {
+            let error_msg = format!("Failed to delete keyring entry: {}", e);
+            crate::logger::Logger::error(&error_msg);
+            BwError::CommandFailed(error_msg)
+        })
+    }
+
+    /// The DPAPI blob lives in the session file, which is already wiped
+    #[cfg(target_os = "windows")]
+    fn delete_secret() -> Result<()> {
+        Ok(())
+    }
+
     /// Clear the session token
-    #[allow(dead_code)]
     pub fn clear_token(&self) -> Result<()> {
         if self.session_file.exists() {
             fs::remove_file(&self.session_file).map_err(|e| {
@@ -191,7 +306,7 @@ impl SessionManager {
         use keyring::Entry;
         
         let username = whoami::username();
-        let entry = Entry::new("bwtui-bitwarden", &username)
+        let entry = Entry::new(&keyring_service(), &username)
             .map_err(|e| {
                 let error_msg = format!("Failed to create keyring entry: {}", e);
                 crate::logger::Logger::error(&error_msg);
@@ -216,7 +331,7 @@ impl SessionManager {
         
         if encrypted_data == b"KEYRING" {
             let username = whoami::username();
-            let entry = Entry::new("bwtui-bitwarden", &username)
+            let entry = Entry::new(&keyring_service(), &username)
                 .map_err(|e| {
                     let error_msg = format!("Failed to create keyring entry: {}", e);
                     crate::logger::Logger::error(&error_msg);
@@ -237,6 +352,15 @@ impl SessionManager {
     }
 }
 
+/// Keyring service the session token is stored under, separate for each profile
+#[cfg(not(target_os = "windows"))]
+fn keyring_service() -> String {
+    match crate::profile::current() {
+        Some(profile) => format!("bwtui-bitwarden-{}", profile),
+        None => "bwtui-bitwarden".to_string(),
+    }
+}
+
 impl Default for SessionManager {
     fn default() -> Self {
         Self::new().expect("Failed to initialize SessionManager")
@@ -247,6 +371,24 @@ impl Default for SessionManager {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_session_options_precedence() {
+        let options = SessionOptions::new(Some("arg".to_string()), Some("env".to_string()), false);
+        assert_eq!(options.token.as_deref(), Some("arg"));
+
+        let options = SessionOptions::new(None, Some("env".to_string()), false);
+        assert_eq!(options.token.as_deref(), Some("env"));
+        assert!(!options.uses_stored_token());
+        assert!(SessionOptions::new(None, None, false).uses_stored_token());
+
+        // Empty values are ignored
+        let options = SessionOptions::new(Some(" ".to_string()), Some(String::new()), true);
+        assert_eq!(options.token, None);
+        assert!(!options.use_keyring());
+        assert!(!options.uses_stored_token());
+        assert_eq!(options.load_token().unwrap(), None);
+    }
+
     #[test]
     fn test_session_manager_creation() {
         let manager = SessionManager::new();
@@ -0,0 +1,58 @@
+use crate::error::Result;
+
+/// Process signals the main loop reacts to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signal {
+    /// SIGTERM or SIGHUP: restore the terminal and exit
+    Terminate,
+    /// SIGTSTP sent by another process, e.g. `kill -TSTP`
+    Suspend,
+    /// SIGCONT: the process was continued after being stopped
+    Continue,
+}
+
+/// Listens for signals that would otherwise leave the shell in raw mode
+pub struct Signals {
+    #[cfg(unix)]
+    terminate: tokio::signal::unix::Signal,
+    #[cfg(unix)]
+    hangup: tokio::signal::unix::Signal,
+    #[cfg(unix)]
+    suspend: tokio::signal::unix::Signal,
+    #[cfg(unix)]
+    resume: tokio::signal::unix::Signal,
+}
+
+impl Signals {
+    #[cfg(unix)]
+    pub fn new() -> Result<Self> {
+        use tokio::signal::unix::{signal, SignalKind};
+        Ok(Self {
+            terminate: signal(SignalKind::terminate())?,
+            hangup: signal(SignalKind::hangup())?,
+            suspend: signal(SignalKind::from_raw(signal_hook::consts::SIGTSTP))?,
+            resume: signal(SignalKind::from_raw(signal_hook::consts::SIGCONT))?,
+        })
+    }
+
+    #[cfg(not(unix))]
+    pub fn new() -> Result<Self> {
+        Ok(Self {})
+    }
+
+    /// Wait for the next signal
+    #[cfg(unix)]
+    pub async fn recv(&mut self) -> Signal {
+        tokio::select! {
+            _ = self.terminate.recv() => Signal::Terminate,
+            _ = self.hangup.recv() => Signal::Terminate,
+            _ = self.suspend.recv() => Signal::Suspend,
+            _ = self.resume.recv() => Signal::Continue,
+        }
+    }
+
+    #[cfg(not(unix))]
+    pub async fn recv(&mut self) -> Signal {
+        std::future::pending().await
+    }
+}
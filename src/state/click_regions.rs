@@ -0,0 +1,74 @@
+use crate::events::Action;
+use ratatui::layout::{Position, Rect};
+
+/// A screen area that triggers an action when clicked
+#[derive(Debug, Clone)]
+pub struct ClickRegion {
+    pub area: Rect,
+    pub action: Action,
+}
+
+/// Clickable areas recorded while rendering the last frame
+#[derive(Debug, Default)]
+pub struct ClickRegions {
+    regions: Vec<ClickRegion>,
+}
+
+impl ClickRegions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget the regions of the previous frame
+    pub fn clear(&mut self) {
+        self.regions.clear();
+    }
+
+    /// Register a clickable area, ignoring empty ones (e.g. scrolled out of view)
+    pub fn push(&mut self, area: Rect, action: Action) {
+        if !area.is_empty() {
+            self.regions.push(ClickRegion { area, action });
+        }
+    }
+
+    /// Find the action for a click, preferring regions registered last (drawn on top)
+    pub fn action_at(&self, column: u16, row: u16) -> Option<Action> {
+        let position = Position::new(column, row);
+        self.regions
+            .iter()
+            .rev()
+            .find(|region| region.area.contains(position))
+            .map(|region| region.action.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_action_at_finds_containing_region() {
+        let mut regions = ClickRegions::new();
+        regions.push(Rect::new(0, 0, 10, 1), Action::CopyUsername);
+        regions.push(Rect::new(0, 1, 10, 2), Action::CopyPassword);
+
+        assert!(matches!(regions.action_at(5, 0), Some(Action::CopyUsername)));
+        assert!(matches!(regions.action_at(9, 2), Some(Action::CopyPassword)));
+        assert!(regions.action_at(10, 0).is_none());
+        assert!(regions.action_at(0, 3).is_none());
+    }
+
+    #[test]
+    fn test_later_regions_take_precedence() {
+        let mut regions = ClickRegions::new();
+        regions.push(Rect::new(0, 0, 10, 10), Action::MoveUp);
+        regions.push(Rect::new(2, 2, 2, 2), Action::MoveDown);
+        regions.push(Rect::new(5, 5, 0, 3), Action::Quit);
+
+        assert!(matches!(regions.action_at(3, 3), Some(Action::MoveDown)));
+        assert!(matches!(regions.action_at(5, 5), Some(Action::MoveUp)));
+
+        regions.clear();
+        assert!(regions.action_at(3, 3).is_none());
+    }
+}
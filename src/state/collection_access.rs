@@ -0,0 +1,63 @@
+use crate::types::CollectionAccess;
+use std::collections::HashMap;
+
+/// Who can access a collection, once asked for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccessLookup {
+    Loading,
+    Loaded(CollectionAccess),
+    Failed(String), // Usually because only the organization's managers may see it
+}
+
+/// Access of the collections looked up with Alt+A, by collection id, kept in memory only
+#[derive(Debug, Default)]
+pub struct CollectionAccessCache {
+    lookups: HashMap<String, AccessLookup>,
+}
+
+impl CollectionAccessCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, collection_id: &str) -> Option<&AccessLookup> {
+        self.lookups.get(collection_id)
+    }
+
+    /// Mark a collection as being looked up, returning false if it is already known or loading
+    /// A failed lookup is tried again
+    pub fn start(&mut self, collection_id: &str) -> bool {
+        if matches!(self.lookups.get(collection_id), Some(AccessLookup::Loading | AccessLookup::Loaded(_))) {
+            return false;
+        }
+        self.lookups.insert(collection_id.to_string(), AccessLookup::Loading);
+        true
+    }
+
+    pub fn store(&mut self, collection_id: &str, lookup: AccessLookup) {
+        self.lookups.insert(collection_id.to_string(), lookup);
+    }
+
+    pub fn clear(&mut self) {
+        self.lookups.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failed_lookups_are_retried() {
+        let mut cache = CollectionAccessCache::new();
+        assert!(cache.start("c-1"));
+        assert!(!cache.start("c-1"));
+
+        cache.store("c-1", AccessLookup::Failed("no permission".to_string()));
+        assert!(cache.start("c-1"));
+
+        cache.store("c-1", AccessLookup::Loaded(CollectionAccess::default()));
+        assert!(!cache.start("c-1"));
+        assert_eq!(cache.get("c-1"), Some(&AccessLookup::Loaded(CollectionAccess::default())));
+    }
+}
@@ -0,0 +1,221 @@
+use crate::types::VaultItem;
+
+/// A copyable field shown in the details panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DetailField {
+    Username,
+    Password,
+    Totp,
+    Uri(usize),         // Index into login.uris
+    CardNumber,
+    CardExpiry,
+    CardCvv,
+    IdentityName,
+    IdentityAddress,
+    IdentityPhone,
+    IdentityEmail,
+    CustomField(usize), // Index into item.fields
+}
+
+impl DetailField {
+    /// List the focusable fields of an item, in the order they appear in the details panel
+    pub fn fields_for(item: &VaultItem) -> Vec<DetailField> {
+        let mut fields = Vec::new();
+
+        if let Some(login) = &item.login {
+            if login.username.is_some() {
+                fields.push(DetailField::Username);
+            }
+            if login.password.is_some() {
+                fields.push(DetailField::Password);
+            }
+            if login.totp.is_some() {
+                fields.push(DetailField::Totp);
+            }
+            if let Some(uris) = &login.uris {
+                fields.extend((0..uris.len()).map(DetailField::Uri));
+            }
+        }
+
+        if let Some(card) = &item.card {
+            if card.number.is_some() {
+                fields.push(DetailField::CardNumber);
+            }
+            if card.expiry().is_some() {
+                fields.push(DetailField::CardExpiry);
+            }
+            if card.code.is_some() {
+                fields.push(DetailField::CardCvv);
+            }
+        }
+
+        if let Some(identity) = &item.identity {
+            if identity.full_name().is_some() {
+                fields.push(DetailField::IdentityName);
+            }
+            if identity.formatted_address().is_some() {
+                fields.push(DetailField::IdentityAddress);
+            }
+            if identity.phone.is_some() {
+                fields.push(DetailField::IdentityPhone);
+            }
+            if identity.email.is_some() {
+                fields.push(DetailField::IdentityEmail);
+            }
+        }
+
+        if let Some(custom_fields) = &item.fields {
+            for (index, field) in custom_fields.iter().enumerate() {
+                // Match the details panel, which skips fields without a name or value
+                let has_name = field.name.as_ref().is_some_and(|n| !n.is_empty());
+                let has_value = item.custom_field_value(field).is_some_and(|value| !value.text().is_empty());
+                if has_name && has_value {
+                    fields.push(DetailField::CustomField(index));
+                }
+            }
+        }
+
+        fields
+    }
+
+    /// Name of the field as shown in the details panel
+    pub fn label(self, item: &VaultItem) -> String {
+        match self {
+            DetailField::Username => "Username".to_string(),
+            DetailField::Password => "Password".to_string(),
+            DetailField::Totp => "TOTP".to_string(),
+            DetailField::Uri(_) => "URI".to_string(),
+            DetailField::CardNumber => "Card number".to_string(),
+            DetailField::CardExpiry => "Expiry".to_string(),
+            DetailField::CardCvv => "CVV".to_string(),
+            DetailField::IdentityName => "Name".to_string(),
+            DetailField::IdentityAddress => "Address".to_string(),
+            DetailField::IdentityPhone => "Phone".to_string(),
+            DetailField::IdentityEmail => "Email".to_string(),
+            DetailField::CustomField(index) => item.fields.as_ref()
+                .and_then(|fields| fields.get(index))
+                .and_then(|field| field.name.clone())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// The field's value and whether it is a secret
+    /// TOTP codes are generated, so the field has no stored value
+    pub fn value(self, item: &VaultItem) -> Option<(String, bool)> {
+        let login = item.login.as_ref();
+        let card = item.card.as_ref();
+        let identity = item.identity.as_ref();
+        match self {
+            DetailField::Username => Some((login?.username.clone()?, false)),
+            DetailField::Password => Some((login?.password.clone()?, true)),
+            DetailField::Totp => None,
+            DetailField::Uri(index) => Some((login?.uris.as_ref()?.get(index)?.uri.clone(), false)),
+            DetailField::CardNumber => Some((card?.number.clone()?, true)),
+            DetailField::CardExpiry => Some((card?.expiry()?, false)),
+            DetailField::CardCvv => Some((card?.code.clone()?, true)),
+            DetailField::IdentityName => Some((identity?.full_name()?, false)),
+            DetailField::IdentityAddress => Some((identity?.formatted_address()?, false)),
+            DetailField::IdentityPhone => Some((identity?.phone.clone()?, false)),
+            DetailField::IdentityEmail => Some((identity?.email.clone()?, false)),
+            DetailField::CustomField(index) => {
+                let value = item.custom_field_value(item.fields.as_ref()?.get(index)?)?;
+                Some((value.text(), value.is_secret()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CustomField, CustomFieldType, ItemType, LoginData, Uri};
+
+    fn create_login_item(fields: Option<Vec<CustomField>>) -> VaultItem {
+        VaultItem {
+            id: "1".to_string(),
+            name: "Test".to_string(),
+            item_type: ItemType::Login,
+            login: Some(LoginData {
+                username: Some("user".to_string()),
+                password: Some("pass".to_string()),
+                totp: None,
+                uris: Some(vec![
+                    Uri { uri: "https://a.example".to_string(), match_type: None },
+                    Uri { uri: "https://b.example".to_string(), match_type: None },
+                ]),
+                password_revision_date: None,
+            }),
+            card: None,
+            identity: None,
+            notes: None,
+            fields,
+            favorite: false,
+            folder_id: None,
+            organization_id: None,
+            revision_date: chrono::Utc::now(),
+            object: None,
+            creation_date: None,
+            deleted_date: None,
+            password_history: None,
+            attachments: None,
+            collection_ids: None,
+            reprompt: None,
+            raw_fields: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_login_fields_in_display_order() {
+        let item = create_login_item(None);
+        assert_eq!(
+            DetailField::fields_for(&item),
+            vec![
+                DetailField::Username,
+                DetailField::Password,
+                DetailField::Uri(0),
+                DetailField::Uri(1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_custom_fields_without_value_are_skipped() {
+        let item = create_login_item(Some(vec![
+            CustomField { name: Some("empty".to_string()), value: None, field_type: Some(CustomFieldType::Text), linked_id: None },
+            CustomField { name: Some("pin".to_string()), value: Some("1234".to_string()), field_type: Some(CustomFieldType::Hidden), linked_id: None },
+        ]));
+        let fields = DetailField::fields_for(&item);
+        assert_eq!(fields.last(), Some(&DetailField::CustomField(1)));
+        assert!(!fields.contains(&DetailField::CustomField(0)));
+    }
+
+    #[test]
+    fn test_linked_custom_fields_use_the_linked_value() {
+        let linked = |linked_id| CustomField {
+            name: Some("linked".to_string()),
+            value: None,
+            field_type: Some(CustomFieldType::Linked),
+            linked_id: Some(linked_id),
+        };
+        let item = create_login_item(Some(vec![linked(101), linked(305)]));
+        // Linked to the password, a card number doesn't exist on a login
+        let fields = DetailField::fields_for(&item);
+        assert!(fields.contains(&DetailField::CustomField(0)));
+        assert!(!fields.contains(&DetailField::CustomField(1)));
+    }
+
+    #[test]
+    fn test_field_labels_and_values() {
+        let item = create_login_item(Some(vec![CustomField {
+            name: Some("Wi-Fi".to_string()),
+            value: Some("correct horse".to_string()),
+            field_type: Some(CustomFieldType::Hidden),
+            linked_id: None,
+        }]));
+        assert_eq!(DetailField::Password.value(&item), Some(("pass".to_string(), true)));
+        assert_eq!(DetailField::Uri(1).value(&item), Some(("https://b.example".to_string(), false)));
+        assert_eq!(DetailField::CardNumber.value(&item), None);
+        assert_eq!(DetailField::CustomField(0).label(&item), "Wi-Fi");
+        assert_eq!(DetailField::CustomField(0).value(&item), Some(("correct horse".to_string(), true)));
+    }
+}
@@ -0,0 +1,112 @@
+use crate::duplicates::DuplicateGroup;
+
+/// State of the duplicate items report
+#[derive(Debug, Default)]
+pub struct DuplicatesState {
+    pub visible: bool,
+    pub groups: Vec<DuplicateGroup>,
+    pub selected: usize, // Index of the selected item across all groups
+    pub confirm_delete: bool, // Whether deleting the selected item awaits confirmation
+    pub deleting: bool, // Whether the selected item is being deleted
+}
+
+impl DuplicatesState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self, groups: Vec<DuplicateGroup>) {
+        self.visible = true;
+        self.groups = groups;
+        self.selected = 0;
+        self.confirm_delete = false;
+        self.deleting = false;
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+        self.groups.clear();
+        self.confirm_delete = false;
+    }
+
+    /// Replace the groups after the vault changed, keeping the selection in range
+    pub fn refresh(&mut self, groups: Vec<DuplicateGroup>) {
+        self.groups = groups;
+        self.selected = self.selected.min(self.item_count().saturating_sub(1));
+    }
+
+    /// Number of selectable items across all groups
+    pub fn item_count(&self) -> usize {
+        self.groups.iter().map(|group| group.item_ids.len()).sum()
+    }
+
+    /// ID of the selected item
+    pub fn selected_item_id(&self) -> Option<&str> {
+        self.groups
+            .iter()
+            .flat_map(|group| group.item_ids.iter())
+            .nth(self.selected)
+            .map(String::as_str)
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.item_count() {
+            self.selected += 1;
+        }
+        self.confirm_delete = false;
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+        self.confirm_delete = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::duplicates::DuplicateKind;
+
+    fn group(ids: &[&str]) -> DuplicateGroup {
+        DuplicateGroup {
+            kind: DuplicateKind::SameName,
+            key: "name".to_string(),
+            item_ids: ids.iter().map(|id| id.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_selection_spans_groups() {
+        let mut duplicates = DuplicatesState::new();
+        duplicates.open(vec![group(&["1", "2"]), group(&["3", "4", "5"])]);
+        assert_eq!(duplicates.item_count(), 5);
+        assert_eq!(duplicates.selected_item_id(), Some("1"));
+
+        for _ in 0..10 {
+            duplicates.select_next();
+        }
+        assert_eq!(duplicates.selected_item_id(), Some("5"));
+
+        duplicates.select_previous();
+        duplicates.select_previous();
+        assert_eq!(duplicates.selected_item_id(), Some("3"));
+
+        // Moving cancels a pending delete
+        duplicates.confirm_delete = true;
+        duplicates.select_next();
+        assert!(!duplicates.confirm_delete);
+    }
+
+    #[test]
+    fn test_refresh_keeps_selection_in_range() {
+        let mut duplicates = DuplicatesState::new();
+        duplicates.open(vec![group(&["1", "2"]), group(&["3", "4"])]);
+        duplicates.selected = 3;
+
+        duplicates.refresh(vec![group(&["1", "2"])]);
+        assert_eq!(duplicates.selected_item_id(), Some("2"));
+
+        duplicates.refresh(Vec::new());
+        assert_eq!(duplicates.selected_item_id(), None);
+    }
+}
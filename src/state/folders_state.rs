@@ -0,0 +1,111 @@
+use super::TextInput;
+
+/// Folder name being typed in the folder screen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FolderEdit {
+    Create,
+    Rename(String), // folder_id
+}
+
+/// State of the folder management screen
+#[derive(Debug, Default)]
+pub struct FoldersState {
+    pub visible: bool,
+    pub selected: usize, // Index of the selected folder, by name
+    pub edit: Option<FolderEdit>,
+    pub input: TextInput, // Name typed while editing
+    pub confirm_delete: bool, // Whether deleting the selected folder awaits confirmation
+    pub busy: bool, // Whether a change is being sent to the vault
+    pub moving: Option<String>, // Item the screen picks a folder for, listing "No folder" first
+}
+
+impl FoldersState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self) {
+        self.visible = true;
+        self.selected = 0;
+        self.edit = None;
+        self.confirm_delete = false;
+        self.moving = None;
+    }
+
+    /// Open the screen as a picker of the folder to move an item to
+    pub fn open_move(&mut self, item_id: String, selected: usize) {
+        self.open();
+        self.moving = Some(item_id);
+        self.selected = selected;
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+        self.edit = None;
+        self.confirm_delete = false;
+        self.moving = None;
+    }
+
+    /// Start typing a folder name, from the current one when renaming
+    pub fn start_edit(&mut self, edit: FolderEdit, name: &str) {
+        self.input.set(name.to_string());
+        self.edit = Some(edit);
+        self.confirm_delete = false;
+    }
+
+    pub fn cancel_edit(&mut self) {
+        self.edit = None;
+    }
+
+    /// Keep the selection in range after folders were added or removed
+    pub fn clamp_selection(&mut self, count: usize) {
+        self.selected = self.selected.min(count.saturating_sub(1));
+    }
+
+    pub fn select_next(&mut self, count: usize) {
+        if self.selected + 1 < count {
+            self.selected += 1;
+        }
+        self.confirm_delete = false;
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+        self.confirm_delete = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selection_and_edit() {
+        let mut folders = FoldersState::new();
+        folders.open();
+        folders.select_next(2);
+        folders.select_next(2);
+        assert_eq!(folders.selected, 1);
+
+        // Moving cancels a pending deletion
+        folders.confirm_delete = true;
+        folders.select_previous();
+        assert_eq!(folders.selected, 0);
+        assert!(!folders.confirm_delete);
+
+        folders.start_edit(FolderEdit::Rename("f-1".to_string()), "Work");
+        assert_eq!(folders.input.text(), "Work");
+        folders.close();
+        assert_eq!(folders.edit, None);
+
+        folders.selected = 3;
+        folders.clamp_selection(2);
+        assert_eq!(folders.selected, 1);
+
+        // The move picker starts on the item's folder, and forgets the item once closed
+        folders.open_move("i-1".to_string(), 2);
+        assert_eq!((folders.moving.as_deref(), folders.selected), (Some("i-1"), 2));
+        folders.close();
+        assert_eq!(folders.moving, None);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::types::VaultItem;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// A TOTP code fetched from the backend, valid until `expires_at` (unix seconds)
+#[derive(Debug, Clone, PartialEq)]
+pub struct FetchedTotp {
+    pub code: String,
+    pub expires_at: u64,
+    pub period: u64,
+}
+
+/// Values fetched for one revision of an item
+#[derive(Debug, Default)]
+struct ItemDetails {
+    revision_date: DateTime<Utc>,
+    totp: Option<FetchedTotp>,
+    local_totp_failed: bool, // The secret can't be used to generate codes locally
+}
+
+/// Per-item values fetched at runtime, kept in memory only
+/// Entries are keyed by item id and dropped when the item's revision date changes
+#[derive(Debug, Default)]
+pub struct ItemDetailsCache {
+    entries: HashMap<String, ItemDetails>,
+}
+
+impl ItemDetailsCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The fetched TOTP code of an item, if it is still valid at `now`
+    pub fn totp(&self, item: &VaultItem, now: u64) -> Option<&FetchedTotp> {
+        self.get(item)?.totp.as_ref().filter(|totp| now < totp.expires_at)
+    }
+
+    pub fn store_totp(&mut self, item_id: &str, revision_date: DateTime<Utc>, totp: FetchedTotp) {
+        self.entry(item_id, revision_date).totp = Some(totp);
+    }
+
+    /// Whether generating the item's TOTP code locally already failed
+    pub fn local_totp_failed(&self, item: &VaultItem) -> bool {
+        self.get(item).is_some_and(|details| details.local_totp_failed)
+    }
+
+    pub fn mark_local_totp_failed(&mut self, item: &VaultItem) {
+        self.entry(&item.id, item.revision_date).local_totp_failed = true;
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn get(&self, item: &VaultItem) -> Option<&ItemDetails> {
+        self.entries.get(&item.id).filter(|details| details.revision_date == item.revision_date)
+    }
+
+    /// The entry for this revision of the item, replacing one for an older revision
+    fn entry(&mut self, item_id: &str, revision_date: DateTime<Utc>) -> &mut ItemDetails {
+        let details = self.entries.entry(item_id.to_string()).or_default();
+        if details.revision_date != revision_date {
+            *details = ItemDetails { revision_date, ..ItemDetails::default() };
+        }
+        details
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ItemType;
+
+    fn item(id: &str) -> VaultItem {
+        VaultItem {
+            id: id.to_string(),
+            name: id.to_string(),
+            item_type: ItemType::Login,
+            login: None,
+            card: None,
+            identity: None,
+            notes: None,
+            fields: None,
+            favorite: false,
+            folder_id: None,
+            organization_id: None,
+            revision_date: Utc::now(),
+            object: None,
+            creation_date: None,
+            deleted_date: None,
+            password_history: None,
+            attachments: None,
+            collection_ids: None,
+            reprompt: None,
+            raw_fields: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_totp_expires_and_follows_revision() {
+        let mut cache = ItemDetailsCache::new();
+        let mut github = item("github");
+        let totp = FetchedTotp { code: "123456".to_string(), expires_at: 1_000, period: 30 };
+        cache.store_totp(&github.id, github.revision_date, totp.clone());
+
+        assert_eq!(cache.totp(&github, 999), Some(&totp));
+        assert_eq!(cache.totp(&github, 1_000), None);
+        assert_eq!(cache.totp(&item("gitlab"), 999), None);
+
+        // An edited item may have a new secret
+        github.revision_date += chrono::Duration::seconds(1);
+        assert_eq!(cache.totp(&github, 999), None);
+    }
+
+    #[test]
+    fn test_local_totp_failure_is_per_revision() {
+        let mut cache = ItemDetailsCache::new();
+        let mut github = item("github");
+        cache.mark_local_totp_failed(&github);
+        assert!(cache.local_totp_failed(&github));
+
+        github.revision_date += chrono::Duration::seconds(1);
+        assert!(!cache.local_totp_failed(&github));
+
+        cache.mark_local_totp_failed(&github);
+        cache.clear();
+        assert!(!cache.local_totp_failed(&github));
+    }
+}
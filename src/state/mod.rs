@@ -2,12 +2,35 @@ mod vault_state;
 mod ui_state;
 mod sync_state;
 mod status_message;
+mod detail_field;
+mod click_regions;
+mod collection_access;
+mod duplicates_state;
+mod folders_state;
+mod item_details;
+mod prefetch;
+mod scroll_memory;
+mod share_state;
+mod text_input;
+mod totp_dashboard;
 
-pub use status_message::{MessageLevel, StatusMessage};
-pub use vault_state::VaultState;
-pub use ui_state::UIState;
-pub use sync_state::SyncState;
+pub use status_message::{MessageLevel, StatusHistory, StatusMessage, STACKED_STATUS_LIMIT};
+pub use detail_field::DetailField;
+pub use click_regions::ClickRegions;
+pub use collection_access::{AccessLookup, CollectionAccessCache};
+pub use vault_state::{OwnerFilter, SyncChanges, VaultState};
+pub use ui_state::{Annotation, Focus, LocalNoteEdit, QrPopup, TotpWatch, UIState};
+pub use sync_state::{SyncOutcome, SyncState};
+pub use duplicates_state::DuplicatesState;
+pub use folders_state::{FolderEdit, FoldersState};
+pub use item_details::{FetchedTotp, ItemDetailsCache};
+pub use prefetch::Prefetch;
+pub use scroll_memory::ScrollMemory;
+pub use share_state::ShareState;
+pub use text_input::{TextEdit, TextInput};
+pub use totp_dashboard::TotpDashboardState;
 
+use crate::item_format::CopyFormat;
 use crate::types::VaultItem;
 use std::time::Instant;
 
@@ -17,7 +40,19 @@ pub struct AppState {
     pub vault: VaultState,
     pub ui: UIState,
     pub sync: SyncState,
+    pub duplicates: DuplicatesState,
+    pub folders: FoldersState,
+    pub share: ShareState,
+    pub totp_dashboard: TotpDashboardState,
+    pub item_details: ItemDetailsCache,
+    pub prefetch: Prefetch,
+    pub collection_access: CollectionAccessCache,
     pub status_message: Option<StatusMessage>,
+    /// Earlier messages still shown above `status_message`, oldest first
+    pub stacked_status: std::collections::VecDeque<StatusMessage>,
+    pub status_history: StatusHistory,
+    /// Set whenever something visible changed and the UI needs to be redrawn
+    pub dirty: bool,
 }
 
 impl AppState {
@@ -26,25 +61,85 @@ impl AppState {
             vault: VaultState::new(),
             ui: UIState::new(),
             sync: SyncState::new(),
+            duplicates: DuplicatesState::new(),
+            folders: FoldersState::new(),
+            share: ShareState::new(),
+            totp_dashboard: TotpDashboardState::new(),
+            item_details: ItemDetailsCache::new(),
+            prefetch: Prefetch::new(),
+            collection_access: CollectionAccessCache::new(),
             status_message: None,
+            stacked_status: std::collections::VecDeque::new(),
+            status_history: StatusHistory::new(),
+            dirty: true,
         }
     }
 
     // Convenience delegates to vault state
-    pub fn load_cached_items(&mut self, items: Vec<VaultItem>) {
-        self.vault.load_cached_items(items);
+    pub fn load_cached_items(&mut self, items: Vec<VaultItem>, cached_at: chrono::DateTime<chrono::Utc>, expired: bool) {
+        self.vault.load_cached_items(items, cached_at, expired);
+        self.vault.apply_filter(self.ui.get_active_filter()); // Keep the selected tab
         self.reset_details_scroll();
     }
 
-    pub fn load_items_with_secrets(&mut self, items: Vec<VaultItem>) {
+    /// Replace the listed items with those of a sync, keeping the selected item selected
+    /// Returns what the sync changed, and remembers whether it changed the selected item
+    pub fn load_items_with_secrets(&mut self, items: Vec<VaultItem>) -> SyncChanges {
+        let changes = SyncChanges::between(&self.vault.vault_items, &items);
+        let selected = self.selected_item().map(|item| (item.id.clone(), item.revision_date));
         self.vault.load_items_with_secrets(items);
-        self.reset_details_scroll();
+        self.vault.apply_filter(self.ui.get_active_filter()); // Keep the selected tab
+
+        // The watched code may come from an old secret too
+        if let Some(watch) = self.ui.totp_watch.as_mut() {
+            watch.code = None;
+        }
+        if self.totp_dashboard.visible {
+            self.totp_dashboard.reload(&self.vault.vault_items, unix_now());
+        }
+        self.ui.details_refreshed_item = None;
+        match selected {
+            Some((id, revision_date)) if self.vault.select_item(&id) => {
+                if self.selected_item().is_some_and(|item| item.revision_date != revision_date) {
+                    self.ui.details_refreshed_item = Some(id);
+                    self.clear_totp_code(); // The code may come from an old secret
+                }
+            }
+            _ => self.reset_details_scroll(),
+        }
+        changes
+    }
+
+    /// Whether the last sync changed the item shown in the details panel
+    pub fn details_refreshed(&self) -> bool {
+        self.ui.details_refreshed_item.is_some()
+            && self.selected_item().map(|item| &item.id) == self.ui.details_refreshed_item.as_ref()
+    }
+
+    pub fn append_loading_items(&mut self, items: Vec<VaultItem>) {
+        self.vault.append_loading_items(items, self.ui.get_active_filter());
+    }
+
+    pub fn finish_loading_items(&mut self) {
+        self.vault.finish_loading_items();
+    }
+
+    pub fn discard_loading_items(&mut self) {
+        self.vault.discard_loading_items();
     }
 
     pub fn selected_item(&self) -> Option<&VaultItem> {
         self.vault.selected_item()
     }
 
+    pub fn scroll_list_up(&mut self) {
+        self.vault.scroll_list_up();
+    }
+
+    pub fn scroll_list_down(&mut self) {
+        self.vault.scroll_list_down();
+    }
+
     pub fn select_next(&mut self) {
         self.vault.select_next();
         self.reset_details_scroll();
@@ -109,6 +204,16 @@ impl AppState {
         self.reset_details_scroll();
     }
 
+    pub fn paste_filter(&mut self, text: &str) {
+        let type_filter = self.ui.get_active_filter();
+        self.refilter(|vault| vault.paste_filter(text, type_filter));
+    }
+
+    pub fn edit_filter(&mut self, edit: TextEdit) {
+        let type_filter = self.ui.get_active_filter();
+        self.refilter(|vault| vault.edit_filter(edit, type_filter));
+    }
+
     pub fn clear_filter(&mut self) {
         let old_selection = self.vault.selected_item().map(|item| item.id.clone());
         self.vault.clear_filter(self.ui.get_active_filter());
@@ -122,9 +227,143 @@ impl AppState {
         self.reset_details_scroll();
     }
 
+    pub fn toggle_fuzzy_matching(&mut self) {
+        let type_filter = self.ui.get_active_filter();
+        self.refilter(|vault| vault.toggle_fuzzy(type_filter));
+    }
+
+    pub fn toggle_case_sensitive(&mut self) {
+        let type_filter = self.ui.get_active_filter();
+        self.refilter(|vault| vault.toggle_case_sensitive(type_filter));
+    }
+
+    pub fn cycle_owner_filter(&mut self) {
+        let type_filter = self.ui.get_active_filter();
+        self.refilter(|vault| vault.cycle_owner_filter(type_filter));
+    }
+
+    pub fn set_organizations(&mut self, organizations: Vec<crate::types::Organization>) {
+        let type_filter = self.ui.get_active_filter();
+        self.refilter(|vault| vault.set_organizations(organizations, type_filter));
+    }
+
+    pub fn set_folders(&mut self, folders: Vec<crate::types::Folder>) {
+        let type_filter = self.ui.get_active_filter();
+        self.refilter(|vault| vault.set_folders(folders, type_filter));
+    }
+
+    pub fn set_equivalent_domains(&mut self, domains: crate::types::EquivalentDomains) {
+        let type_filter = self.ui.get_active_filter();
+        self.refilter(|vault| vault.set_equivalent_domains(domains, type_filter));
+    }
+
+    /// Replace the known collections, forgetting who could access them
+    pub fn set_collections(&mut self, collections: Vec<crate::types::Collection>) {
+        self.vault.set_collections(collections);
+        self.collection_access.clear();
+    }
+
+    /// Start looking up who can access the selected item's collections, showing them in the details panel
+    /// Returns the (organization_id, collection_id) of the collections to fetch
+    pub fn start_access_lookup(&mut self) -> Vec<(String, String)> {
+        let Some(item) = self.selected_item() else {
+            self.set_status("✗ No item selected", MessageLevel::Warning);
+            return Vec::new();
+        };
+        if item.organization_id.is_none() {
+            self.set_status("Only organization items are shared in collections", MessageLevel::Info);
+            return Vec::new();
+        }
+        let collections: Vec<(String, String)> = self.vault
+            .item_collections(item)
+            .iter()
+            .map(|collection| (collection.organization_id.clone(), collection.id.clone()))
+            .collect();
+        if collections.is_empty() {
+            self.set_status("The item isn't in any collection you can see", MessageLevel::Info);
+            return Vec::new();
+        }
+
+        if !self.details_panel_visible() {
+            self.toggle_details_panel();
+        }
+        collections
+            .into_iter()
+            .filter(|(_, collection_id)| self.collection_access.start(collection_id))
+            .collect()
+    }
+
+    pub fn set_filter_query(&mut self, query: String) {
+        let type_filter = self.ui.get_active_filter();
+        self.refilter(|vault| vault.set_filter_query(query, type_filter));
+    }
+
+    pub fn set_folder_filter(&mut self, name: Option<String>) {
+        let type_filter = self.ui.get_active_filter();
+        self.refilter(|vault| vault.set_folder_filter(name, type_filter));
+    }
+
+    /// Change the filter, clearing the TOTP code if the selected item changed
+    fn refilter(&mut self, update: impl FnOnce(&mut VaultState)) {
+        let old_selection = self.vault.selected_item().map(|item| item.id.clone());
+        update(&mut self.vault);
+        let new_selection = self.vault.selected_item().map(|item| item.id.clone());
+
+        if old_selection != new_selection {
+            self.clear_totp_code();
+        }
+
+        self.reset_details_scroll();
+    }
+
     // Convenience delegates to UI state
     pub fn toggle_details_panel(&mut self) {
-        self.ui.toggle_details_panel();
+        let item_id = self.selected_item().map(|item| item.id.clone());
+        self.ui.toggle_details_panel(item_id.as_deref());
+    }
+
+    pub fn toggle_details_fullscreen(&mut self) {
+        let item_id = self.selected_item().map(|item| item.id.clone());
+        self.ui.toggle_details_fullscreen(item_id.as_deref());
+    }
+
+    pub fn toggle_raw_notes(&mut self) {
+        self.ui.raw_notes = !self.ui.raw_notes;
+    }
+
+    /// Keep the selected item's TOTP code in the status bar, or stop watching the watched item
+    pub fn toggle_totp_watch(&mut self) {
+        let selected = self.selected_item()
+            .filter(|item| item.login.as_ref().is_some_and(|login| login.totp.is_some()))
+            .map(|item| (item.id.clone(), item.name.clone()));
+        let watched = self.ui.totp_watch.as_ref().map(|watch| watch.item_id.clone());
+
+        match selected {
+            Some((item_id, name)) if watched.as_ref() != Some(&item_id) => {
+                self.set_status(format!("Watching the TOTP code of {}", name), MessageLevel::Info);
+                self.ui.totp_watch = Some(TotpWatch { item_id, name, code: None, fetching: false });
+            }
+            _ => match self.ui.totp_watch.take() {
+                Some(watch) => self.set_status(format!("Stopped watching the TOTP code of {}", watch.name), MessageLevel::Info),
+                None => self.set_status("✗ No TOTP configured for this entry", MessageLevel::Warning),
+            },
+        }
+    }
+
+    /// Seconds left on the watched TOTP code, while it is valid
+    pub fn watched_totp_seconds(&self, now: u64) -> Option<u64> {
+        let code = self.ui.totp_watch.as_ref()?.code.as_ref()?;
+        Some(code.expires_at.saturating_sub(now)).filter(|remaining| *remaining > 0)
+    }
+
+    pub fn resize_split(&mut self, delta: i16) -> bool {
+        self.ui.resize_split(delta)
+    }
+
+    /// Re-clamp scroll positions after the terminal was resized
+    pub fn handle_resize(&mut self) {
+        self.vault.follow_selection();
+        self.ui.handle_resize();
     }
 
     pub fn scroll_details_up(&mut self) {
@@ -139,8 +378,73 @@ impl AppState {
         self.ui.set_details_max_scroll(max_scroll);
     }
 
+    /// Show the selected item's details from where they were last scrolled to, or the top
     pub fn reset_details_scroll(&mut self) {
-        self.ui.reset_details_scroll();
+        let item_id = self.selected_item().map(|item| item.id.clone());
+        self.ui.reset_details_scroll(item_id.as_deref());
+    }
+
+    /// Fields of the selected item that can receive focus in the details panel
+    pub fn detail_fields(&self) -> Vec<DetailField> {
+        self.selected_item()
+            .map(DetailField::fields_for)
+            .unwrap_or_default()
+    }
+
+    pub fn focus_details(&mut self) {
+        let count = self.detail_fields().len();
+        self.ui.focus_details(count);
+    }
+
+    pub fn unfocus_details(&mut self) {
+        self.ui.unfocus_details();
+    }
+
+    pub fn focus(&self) -> Focus {
+        self.ui.focus()
+    }
+
+    pub fn focus_search(&mut self) {
+        self.ui.focus_search();
+    }
+
+    pub fn focus_list(&mut self) {
+        self.ui.focus_list();
+    }
+
+    /// Whether the top search results are numbered for copying with Alt+1..9 (the picker copies with Enter)
+    pub fn quick_copy_available(&self) -> bool {
+        !self.vault.filter_query.is_empty() && self.ui.pick.is_none()
+    }
+
+    /// Whether the details panel is open with fields that can receive focus
+    pub fn can_focus_details(&self) -> bool {
+        self.details_panel_visible() && !self.detail_fields().is_empty()
+    }
+
+    pub fn focus_next_field(&mut self) {
+        let count = self.detail_fields().len();
+        self.ui.focus_next_field(count);
+    }
+
+    pub fn focus_previous_field(&mut self) {
+        let count = self.detail_fields().len();
+        self.ui.focus_previous_field(count);
+    }
+
+    /// The field currently focused in the details panel, if any
+    pub fn focused_detail_field(&self) -> Option<DetailField> {
+        let index = self.ui.details_focus?;
+        self.detail_fields().get(index).copied()
+    }
+
+    /// The focused field, if its hidden value is revealed
+    pub fn revealed_detail_field(&self) -> Option<DetailField> {
+        self.focused_detail_field().filter(|_| self.ui.focused_field_revealed)
+    }
+
+    pub fn toggle_focused_field_revealed(&mut self) {
+        self.ui.focused_field_revealed = !self.ui.focused_field_revealed;
     }
 
     pub fn enter_password_mode(&mut self) {
@@ -151,6 +455,10 @@ impl AppState {
         self.ui.exit_password_mode();
     }
 
+    pub fn enter_relock_prompt(&mut self) {
+        self.ui.enter_relock_prompt();
+    }
+
     pub fn append_password_char(&mut self, c: char) {
         self.ui.append_password_char(c);
     }
@@ -159,6 +467,14 @@ impl AppState {
         self.ui.delete_password_char();
     }
 
+    pub fn paste_password(&mut self, text: &str) {
+        self.ui.paste_password(text);
+    }
+
+    pub fn edit_password(&mut self, edit: TextEdit) {
+        self.ui.edit_password(edit);
+    }
+
     pub fn clear_password(&mut self) {
         self.ui.clear_password();
     }
@@ -187,6 +503,427 @@ impl AppState {
         self.ui.show_not_logged_in_popup();
     }
 
+    pub fn toggle_status_history(&mut self) {
+        self.ui.toggle_status_history();
+    }
+
+    pub fn toggle_sync_details(&mut self) {
+        self.sync.details_visible = !self.sync.details_visible;
+    }
+
+    pub fn show_qr_popup(&mut self, title: String, lines: Vec<String>) {
+        self.ui.qr_popup = Some(QrPopup { title, lines });
+    }
+
+    pub fn close_qr_popup(&mut self) {
+        self.ui.qr_popup = None;
+    }
+
+    /// Formats the selected item can be copied as
+    pub fn copy_formats(&self) -> Vec<CopyFormat> {
+        self.selected_item().map(CopyFormat::available_for).unwrap_or_default()
+    }
+
+    pub fn open_copy_as(&mut self) {
+        self.ui.copy_as_selected = Some(0);
+    }
+
+    pub fn close_copy_as(&mut self) {
+        self.ui.copy_as_selected = None;
+    }
+
+    pub fn select_next_copy_format(&mut self) {
+        let count = self.copy_formats().len();
+        if let Some(selected) = self.ui.copy_as_selected.as_mut() {
+            *selected = (*selected + 1).min(count.saturating_sub(1));
+        }
+    }
+
+    pub fn select_previous_copy_format(&mut self) {
+        if let Some(selected) = self.ui.copy_as_selected.as_mut() {
+            *selected = selected.saturating_sub(1);
+        }
+    }
+
+    /// The highlighted format of the open picker
+    pub fn selected_copy_format(&self) -> Option<CopyFormat> {
+        self.copy_formats().get(self.ui.copy_as_selected?).copied()
+    }
+
+    pub fn scroll_status_history_up(&mut self) {
+        self.ui.scroll_status_history_up();
+    }
+
+    pub fn scroll_status_history_down(&mut self) {
+        let max_scroll = self.status_history.len().saturating_sub(1);
+        self.ui.scroll_status_history_down(max_scroll);
+    }
+
+    // Duplicate items report
+    pub fn toggle_duplicates(&mut self) {
+        if self.duplicates.visible {
+            self.duplicates.close();
+        } else {
+            self.duplicates.open(crate::duplicates::find_duplicates(&self.vault.vault_items));
+        }
+    }
+
+    // Folder management screen
+    pub fn toggle_folders(&mut self) {
+        if self.folders.visible {
+            self.folders.close();
+        } else {
+            self.folders.open();
+        }
+    }
+
+    /// The folder selected in the folder screen, with its number of items
+    pub fn selected_folder(&self) -> Option<(&crate::types::Folder, usize)> {
+        self.vault.folder_counts().into_iter().nth(self.folders.selected)
+    }
+
+    pub fn select_next_folder(&mut self) {
+        let count = self.vault.folders().len() + usize::from(self.folders.moving.is_some());
+        self.folders.select_next(count);
+    }
+
+    /// Open the folder screen to pick the folder the selected item goes to
+    pub fn open_move_to_folder(&mut self) {
+        let Some(item) = self.selected_item() else {
+            self.set_status("✗ No item selected", MessageLevel::Warning);
+            return;
+        };
+        let (id, folder_id) = (item.id.clone(), item.folder_id.clone());
+        // "No folder" comes first, then the folders by name
+        let selected = folder_id
+            .and_then(|folder_id| self.vault.folder_counts().iter().position(|(folder, _)| folder.id == folder_id))
+            .map_or(0, |index| index + 1);
+        self.folders.open_move(id, selected);
+    }
+
+    /// The item of the move picker and the folder selected for it, None for no folder
+    pub fn move_target(&self) -> Option<(String, Option<String>)> {
+        let item_id = self.folders.moving.clone()?;
+        let folder_id = match self.folders.selected.checked_sub(1) {
+            None => None,
+            Some(index) => Some(self.vault.folder_counts().get(index)?.0.id.clone()),
+        };
+        Some((item_id, folder_id))
+    }
+
+    pub fn start_new_folder(&mut self) {
+        self.folders.start_edit(FolderEdit::Create, "");
+    }
+
+    pub fn start_rename_folder(&mut self) {
+        if let Some((folder, _)) = self.selected_folder() {
+            let (id, name) = (folder.id.clone(), folder.name.clone());
+            self.folders.start_edit(FolderEdit::Rename(id), &name);
+        }
+    }
+
+    pub fn request_delete_folder(&mut self) {
+        if self.selected_folder().is_some() {
+            self.folders.confirm_delete = true;
+        }
+    }
+
+    /// Close the folder screen and list only the items in the selected folder
+    pub fn filter_by_selected_folder(&mut self) {
+        let Some(name) = self.selected_folder().map(|(folder, _)| folder.name.clone()) else {
+            return;
+        };
+        self.folders.close();
+        self.set_folder_filter(Some(name));
+        self.focus_list();
+    }
+
+    /// Add a folder created in the vault, and select it
+    pub fn add_folder(&mut self, folder: crate::types::Folder) {
+        let id = folder.id.clone();
+        self.vault.add_folder(folder);
+        if let Some(index) = self.vault.folder_counts().iter().position(|(folder, _)| folder.id == id) {
+            self.folders.selected = index;
+        }
+    }
+
+    pub fn rename_folder(&mut self, id: &str, name: &str) {
+        self.vault.rename_folder(id, name);
+    }
+
+    /// Forget a folder deleted from the vault
+    pub fn remove_folder(&mut self, id: &str) {
+        let type_filter = self.ui.get_active_filter();
+        self.refilter(|vault| vault.remove_folder(id, type_filter));
+        self.folders.clamp_selection(self.vault.folders().len());
+    }
+
+    // Sharing an item with an organization
+    /// Open the dialog choosing the collections to share the selected personal item in
+    pub fn open_share(&mut self) {
+        let Some(item) = self.selected_item() else {
+            self.set_status("✗ No item selected", MessageLevel::Warning);
+            return;
+        };
+        if let Some(organization_id) = &item.organization_id {
+            let organization = self.vault.organization_name(organization_id).unwrap_or("an organization");
+            let message = format!("'{}' already belongs to {}", item.name, organization);
+            self.set_status(message, MessageLevel::Info);
+            return;
+        }
+        if self.vault.collections_by_organization().is_empty() {
+            self.set_status("No organization collections to share with", MessageLevel::Info);
+            return;
+        }
+        let id = item.id.clone();
+        self.share.open(id);
+    }
+
+    pub fn select_next_share_collection(&mut self) {
+        let count = self.vault.collections_by_organization().len();
+        self.share.select_next(count);
+    }
+
+    /// Choose the highlighted collection, or leave it out
+    pub fn toggle_share_collection(&mut self) {
+        let collections = self.vault.collections_by_organization();
+        let Some(collection) = collections.get(self.share.selected) else {
+            return;
+        };
+        let others: Vec<&str> = collections
+            .iter()
+            .filter(|other| other.organization_id != collection.organization_id)
+            .map(|other| other.id.as_str())
+            .collect();
+        self.share.toggle(&collection.id, &others);
+    }
+
+    /// Ask to confirm sharing, once a collection is chosen
+    pub fn request_share(&mut self) {
+        if self.share.chosen.is_empty() {
+            self.set_status("Choose at least one collection with Space", MessageLevel::Warning);
+            return;
+        }
+        self.share.confirm = true;
+    }
+
+    /// The item to share, with the organization and collections chosen for it
+    pub fn share_target(&self) -> Option<(String, String, Vec<String>)> {
+        let item_id = self.share.item_id.clone()?;
+        let first = self.share.chosen.first()?;
+        let organization_id = self.vault
+            .collections_by_organization()
+            .into_iter()
+            .find(|collection| &collection.id == first)?
+            .organization_id
+            .clone();
+        Some((item_id, organization_id, self.share.chosen.iter().cloned().collect()))
+    }
+
+    pub fn set_item_reprompt(&mut self, id: &str, enabled: bool) {
+        self.vault.set_item_reprompt(id, enabled);
+    }
+
+    /// Record that an item was moved into a folder, or out of any folder
+    pub fn set_item_folder(&mut self, id: &str, folder_id: Option<String>) {
+        let type_filter = self.ui.get_active_filter();
+        self.refilter(|vault| vault.set_item_folder(id, folder_id, type_filter));
+    }
+
+    /// Record that an item was shared with an organization
+    pub fn mark_item_shared(&mut self, id: &str, organization_id: &str, collection_ids: Vec<String>) {
+        let type_filter = self.ui.get_active_filter();
+        self.refilter(|vault| vault.share_item(id, organization_id, collection_ids, type_filter));
+    }
+
+    // Local notes
+    pub fn set_local_notes(&mut self, notes: crate::local_notes::LocalNotes) {
+        let type_filter = self.ui.get_active_filter();
+        self.refilter(|vault| vault.set_local_notes(notes, type_filter));
+    }
+
+    pub fn set_local_tags(&mut self, tags: crate::local_notes::LocalTags) {
+        let type_filter = self.ui.get_active_filter();
+        self.refilter(|vault| vault.set_local_tags(tags, type_filter));
+    }
+
+    /// Start writing the selected item's local note or tags, from their current text
+    pub fn open_local_note(&mut self, annotation: Annotation) {
+        let Some(item) = self.selected_item() else {
+            self.set_status("✗ No item selected", MessageLevel::Warning);
+            return;
+        };
+        let mut input = TextInput::new();
+        input.set(self.annotation_text(&item.id, annotation));
+        self.ui.local_note_edit = Some(LocalNoteEdit {
+            item_id: item.id.clone(),
+            item_name: item.name.clone(),
+            annotation,
+            input,
+        });
+    }
+
+    /// An item's note, or its tags separated by commas
+    fn annotation_text(&self, id: &str, annotation: Annotation) -> String {
+        match annotation {
+            Annotation::Note => self.vault.local_note(id).unwrap_or_default().to_string(),
+            Annotation::Tags => self.vault.item_tags(id)
+                .map(|tags| tags.iter().map(String::as_str).collect::<Vec<_>>().join(", "))
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn close_local_note(&mut self) {
+        self.ui.local_note_edit = None;
+    }
+
+    /// The text of the open local note, to type into
+    pub fn local_note_input(&mut self) -> Option<&mut TextInput> {
+        self.ui.local_note_edit.as_mut().map(|edit| &mut edit.input)
+    }
+
+    /// Attach the written note or tags to their item, returning what changed
+    pub fn save_local_note(&mut self) -> Option<Annotation> {
+        let edit = self.ui.local_note_edit.take()?;
+        let type_filter = self.ui.get_active_filter();
+        match edit.annotation {
+            Annotation::Note => {
+                if self.vault.local_note(&edit.item_id).unwrap_or_default() == edit.input.text().trim() {
+                    return None;
+                }
+                self.refilter(|vault| vault.set_local_note(&edit.item_id, edit.input.text(), type_filter));
+            }
+            Annotation::Tags => {
+                let tags = crate::local_notes::parse_tags(edit.input.text());
+                if self.vault.item_tags(&edit.item_id).cloned().unwrap_or_default() == tags {
+                    return None;
+                }
+                self.refilter(|vault| vault.set_item_tags(&edit.item_id, edit.input.text(), type_filter));
+            }
+        }
+        Some(edit.annotation)
+    }
+
+    // Hidden items
+    pub fn set_hidden_items(&mut self, hidden: crate::local_notes::HiddenItems) {
+        let type_filter = self.ui.get_active_filter();
+        self.refilter(|vault| vault.set_hidden_items(hidden, type_filter));
+    }
+
+    /// Hide the selected item from the list and search results, or list it again
+    /// Returns false if no item is selected
+    pub fn toggle_item_hidden(&mut self) -> bool {
+        let Some(item) = self.selected_item() else {
+            self.set_status("✗ No item selected", MessageLevel::Warning);
+            return false;
+        };
+        let (id, name) = (item.id.clone(), item.name.clone());
+        let type_filter = self.ui.get_active_filter();
+        let mut hidden = false;
+        self.refilter(|vault| hidden = vault.toggle_hidden(&id, type_filter));
+        if hidden {
+            self.set_status(format!("Hidden {}, Alt+V shows hidden items", name), MessageLevel::Info);
+        } else {
+            self.set_status(format!("{} is listed again", name), MessageLevel::Info);
+        }
+        true
+    }
+
+    pub fn toggle_show_hidden(&mut self) {
+        let type_filter = self.ui.get_active_filter();
+        self.refilter(|vault| vault.toggle_show_hidden(type_filter));
+    }
+
+    // Tag list
+    pub fn open_tag_list(&mut self) {
+        if self.vault.local_tags().is_empty() {
+            self.set_status("No tags yet, add some to the selected item with Alt+G", MessageLevel::Info);
+            return;
+        }
+        self.ui.tag_list_selected = Some(0);
+    }
+
+    pub fn close_tag_list(&mut self) {
+        self.ui.tag_list_selected = None;
+    }
+
+    pub fn select_next_tag(&mut self) {
+        let count = self.vault.tag_counts().len();
+        if let Some(selected) = self.ui.tag_list_selected.as_mut() {
+            *selected = (*selected + 1).min(count.saturating_sub(1));
+        }
+    }
+
+    pub fn select_previous_tag(&mut self) {
+        if let Some(selected) = self.ui.tag_list_selected.as_mut() {
+            *selected = selected.saturating_sub(1);
+        }
+    }
+
+    /// List only the items with the highlighted tag, replacing the search
+    pub fn filter_by_selected_tag(&mut self) {
+        let Some(selected) = self.ui.tag_list_selected.take() else {
+            return;
+        };
+        if let Some((tag, _)) = self.vault.tag_counts().get(selected) {
+            let query = format!("tag:{}", tag);
+            self.set_filter_query(query);
+            self.focus_list();
+        }
+    }
+
+    // TOTP dashboard
+    pub fn toggle_totp_dashboard(&mut self) {
+        if self.totp_dashboard.visible {
+            self.totp_dashboard.close();
+        } else if !self.secrets_available() {
+            self.set_status("⏳ Please wait, loading vault secrets...", MessageLevel::Warning);
+        } else {
+            self.totp_dashboard.open(&self.vault.vault_items, unix_now());
+        }
+    }
+
+    /// Close the report and select its selected item in the list, with the details panel open
+    pub fn jump_to_duplicate(&mut self) {
+        let Some(id) = self.duplicates.selected_item_id().map(str::to_string) else {
+            return;
+        };
+        self.duplicates.close();
+        self.ui.set_item_type_filter(None);
+        if self.vault.reveal_item(&id) {
+            self.reset_details_scroll();
+            self.clear_totp_code();
+            if !self.details_panel_visible() {
+                self.toggle_details_panel();
+            }
+        }
+    }
+
+    /// Forget an item that was deleted from the vault
+    pub fn remove_item(&mut self, id: &str) {
+        let type_filter = self.ui.get_active_filter();
+        self.refilter(|vault| vault.remove_item(id, type_filter));
+        if self.duplicates.visible {
+            self.duplicates.refresh(crate::duplicates::find_duplicates(&self.vault.vault_items));
+        }
+    }
+
+    pub fn enter_reprompt(&mut self, action: crate::events::Action) {
+        self.ui.enter_reprompt(action);
+    }
+
+    pub fn exit_reprompt(&mut self) -> Option<crate::events::Action> {
+        self.ui.exit_reprompt()
+    }
+
+    pub fn grant_reprompt(&mut self, item_id: String) {
+        self.ui.grant_reprompt(item_id);
+    }
+
+    pub fn consume_reprompt_grant(&mut self, item_id: &str) -> bool {
+        self.ui.consume_reprompt_grant(item_id)
+    }
+
     // Convenience delegates to sync state
     pub fn start_sync(&mut self) {
         self.sync.start();
@@ -197,29 +934,75 @@ impl AppState {
     }
 
     pub fn advance_sync_animation(&mut self) {
-        self.sync.advance_animation();
+        if self.animating() {
+            self.sync.advance_animation();
+            self.mark_dirty();
+        }
+    }
+
+    /// Whether a spinner turns, while the vault loads or syncs or a master password is checked
+    pub fn animating(&self) -> bool {
+        self.sync.syncing || self.ui.unlock_in_progress
     }
 
     pub fn sync_spinner(&self) -> &str {
-        self.sync.spinner()
+        if !self.animating() {
+            ""
+        } else if self.ui.accessible {
+            self.sync.ascii_spinner()
+        } else {
+            self.sync.spinner()
+        }
     }
 
     // Status message management
+    /// Show a message, keeping the previous one above it until its display time is over
+    /// Info messages, often progress like "Syncing...", are simply replaced
     pub fn set_status(&mut self, text: impl Into<String>, level: MessageLevel) {
+        let text = text.into();
+        let now = Instant::now();
+        self.status_history.push(text.clone(), level);
+        if let Some(previous) = self.status_message.take() {
+            if previous.level != MessageLevel::Info && previous.text != text && !previous.expired(now) {
+                self.stacked_status.push_back(previous);
+            }
+        }
+        self.stacked_status.retain(|status| !status.expired(now) && status.text != text);
+        while self.stacked_status.len() > STACKED_STATUS_LIMIT {
+            self.stacked_status.pop_front();
+        }
         self.status_message = Some(StatusMessage {
-            text: text.into(),
+            text,
             level,
-            timestamp: Instant::now(),
+            timestamp: now,
         });
+        self.mark_dirty();
     }
 
-    /// Check if status message is older than 3 seconds and clear it
+    /// Clear messages shown for their level's display time
+    /// An earlier message that has time left, like an error, takes the place of an expired newer one
     pub fn expire_old_status(&mut self) {
-        if let Some(status) = &self.status_message {
-            if status.timestamp.elapsed().as_secs() > 3 {
-                self.status_message = None;
-            }
+        let now = Instant::now();
+        let stacked = self.stacked_status.len();
+        self.stacked_status.retain(|status| !status.expired(now));
+        if self.stacked_status.len() != stacked {
+            self.mark_dirty();
         }
+        if self.status_message.as_ref().is_some_and(|status| status.expired(now)) {
+            self.status_message = self.stacked_status.pop_back();
+            self.mark_dirty();
+        }
+    }
+
+    // Redraw tracking
+    #[inline]
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Return whether a redraw is needed and clear the flag
+    pub fn take_dirty(&mut self) -> bool {
+        std::mem::take(&mut self.dirty)
     }
 
     // Convenience accessors for commonly used state
@@ -229,10 +1012,106 @@ impl AppState {
     }
 
     #[inline]
+    /// Whether a dialog covers the panels and takes the keyboard
+    pub fn dialog_open(&self) -> bool {
+        self.confirm_quit()
+            || self.password_input_mode()
+            || self.offer_save_token()
+            || self.show_not_logged_in_error()
+            || self.show_status_history()
+            || self.show_sync_details()
+            || self.show_duplicates()
+            || self.show_folders()
+            || self.share_open()
+            || self.show_totp_dashboard()
+            || self.qr_popup().is_some()
+            || self.copy_as_open()
+            || self.local_note_open()
+            || self.tag_list_open()
+    }
+
     pub fn password_input_mode(&self) -> bool {
         self.ui.password_input_mode
     }
 
+    #[inline]
+    pub fn reprompt_active(&self) -> bool {
+        self.ui.reprompt_action.is_some()
+    }
+
+    /// Whether a submitted master password is being checked, to unlock or for a re-prompt
+    #[inline]
+    pub fn unlock_in_progress(&self) -> bool {
+        self.ui.unlock_in_progress
+    }
+
+    #[inline]
+    pub fn show_status_history(&self) -> bool {
+        self.ui.show_status_history
+    }
+
+    pub fn show_sync_details(&self) -> bool {
+        self.sync.details_visible
+    }
+
+    #[inline]
+    pub fn confirm_quit(&self) -> bool {
+        self.ui.confirm_quit
+    }
+
+    /// What quitting now would cut off: a vault load or sync, a master password check or a TOTP fetch
+    pub fn operation_in_progress(&self) -> Option<&'static str> {
+        if self.syncing() {
+            Some(if self.initial_load_complete() { "A vault sync" } else { "Loading the vault" })
+        } else if self.unlock_in_progress() {
+            Some("Checking the master password")
+        } else if self.totp_loading() {
+            Some("Fetching a TOTP code")
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn qr_popup(&self) -> Option<&QrPopup> {
+        self.ui.qr_popup.as_ref()
+    }
+
+    #[inline]
+    pub fn copy_as_open(&self) -> bool {
+        self.ui.copy_as_selected.is_some()
+    }
+
+    #[inline]
+    pub fn show_duplicates(&self) -> bool {
+        self.duplicates.visible
+    }
+
+    #[inline]
+    pub fn show_folders(&self) -> bool {
+        self.folders.visible
+    }
+
+    #[inline]
+    pub fn share_open(&self) -> bool {
+        self.share.visible()
+    }
+
+    #[inline]
+    pub fn local_note_open(&self) -> bool {
+        self.ui.local_note_edit.is_some()
+    }
+
+    #[inline]
+    pub fn tag_list_open(&self) -> bool {
+        self.ui.tag_list_selected.is_some()
+    }
+
+    #[inline]
+    pub fn show_totp_dashboard(&self) -> bool {
+        self.totp_dashboard.visible
+    }
+
     #[inline]
     pub fn offer_save_token(&self) -> bool {
         self.ui.offer_save_token
@@ -243,6 +1122,16 @@ impl AppState {
         self.ui.details_panel_visible
     }
 
+    #[inline]
+    pub fn details_fullscreen(&self) -> bool {
+        self.ui.details_panel_visible && self.ui.details_fullscreen
+    }
+
+    #[inline]
+    pub fn details_focused(&self) -> bool {
+        self.ui.details_focus.is_some()
+    }
+
     #[inline]
     pub fn show_not_logged_in_error(&self) -> bool {
         self.ui.show_not_logged_in_error
@@ -259,8 +1148,8 @@ impl AppState {
     }
 
     // TOTP management
-    pub fn set_totp_code(&mut self, code: String, expires_at: u64, item_id: String) {
-        self.ui.set_totp_code(code, expires_at, item_id);
+    pub fn set_totp_code(&mut self, code: String, expires_at: u64, period: u64, item_id: String) {
+        self.ui.set_totp_code(code, expires_at, period, item_id);
     }
 
     pub fn clear_totp_code(&mut self) {
@@ -295,15 +1184,70 @@ impl AppState {
         self.ui.totp_remaining_seconds()
     }
 
+    pub fn totp_period(&self) -> u64 {
+        self.ui.totp_period
+    }
+
     pub fn current_totp_code(&self) -> Option<&String> {
         self.ui.current_totp_code.as_ref()
     }
 
+    /// Seconds left on the selected item's TOTP code, while it is valid
+    pub fn displayed_totp_seconds(&self) -> Option<u64> {
+        let item = self.selected_item()?;
+        self.ui.current_totp_code.as_ref()?;
+        if !self.totp_belongs_to_item(&item.id) {
+            return None;
+        }
+        self.totp_remaining_seconds().filter(|remaining| *remaining > 0)
+    }
+
+    /// Whole seconds until the clipboard auto-clears, rounded up
+    pub fn clipboard_clear_seconds(&self, now: Instant) -> Option<u64> {
+        let remaining = self.ui.clipboard_clears_at?.saturating_duration_since(now);
+        Some(remaining.as_millis().div_ceil(1000) as u64)
+    }
+
+    /// Whether the status bar shows a countdown that changes every tick
+    pub fn has_countdown(&self) -> bool {
+        self.ui.clipboard_clears_at.is_some()
+            || self.displayed_totp_seconds().is_some()
+            || self.ui.totp_watch.is_some()
+            || self.show_totp_dashboard()
+            // The unlock dialog may count down until another attempt
+            || (self.password_input_mode() && self.ui.unlock_failures > 0)
+    }
+
     pub fn totp_loading(&self) -> bool {
         self.ui.totp_loading
     }
 
     // Tab filtering
+    /// Apply the filters of a saved view, or show all items again with None
+    pub fn apply_view(&mut self, view: Option<&crate::config::ViewConfig>) {
+        let owner = match view.and_then(|view| view.owner.as_deref()) {
+            None => OwnerFilter::All,
+            Some(name) if name.eq_ignore_ascii_case("personal") => OwnerFilter::Personal,
+            Some(name) => match self.vault.organization_id(name) {
+                Some(id) => OwnerFilter::Organization(id.to_string()),
+                None => {
+                    self.set_status(format!("⚠ No organization named {}, showing every owner", name), MessageLevel::Warning);
+                    OwnerFilter::All
+                }
+            },
+        };
+        let type_filter = view.and_then(|view| view.item_type).and_then(crate::args::TabArg::item_type);
+        let hidden_types = view
+            .map(|view| view.hide_types.iter().filter_map(|tab| tab.item_type()).collect())
+            .unwrap_or_default();
+        let folder = view.and_then(|view| view.folder.clone());
+        let query = view.and_then(|view| view.query.clone()).unwrap_or_default();
+
+        self.ui.set_item_type_filter(type_filter);
+        self.ui.active_view = view.map(|view| view.name.clone());
+        self.refilter(|vault| vault.set_view_filters(owner, folder, hidden_types, query, type_filter));
+    }
+
     pub fn set_item_type_filter(&mut self, filter: Option<crate::types::ItemType>) {
         self.ui.set_item_type_filter(filter);
         // Reapply filter with new type filter
@@ -339,3 +1283,10 @@ impl Default for AppState {
     }
 }
 
+/// Current time in unix seconds, as TOTP codes count it
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
@@ -0,0 +1,63 @@
+use std::time::{Duration, Instant};
+
+/// How long an item must stay selected before its values are prefetched, so scrolling past items fetches nothing
+pub const PREFETCH_DELAY: Duration = Duration::from_millis(300);
+
+/// Debounces prefetching the values of the selected item that need a backend round trip
+#[derive(Debug, Default)]
+pub struct Prefetch {
+    pending: Option<(String, Instant)>, // (item_id, when it was selected)
+    done: Option<String>, // Item prefetched last, not again until another one was selected
+}
+
+impl Prefetch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Note the selected item, restarting the delay when it changed
+    pub fn select(&mut self, item_id: Option<&str>, now: Instant) {
+        match item_id {
+            Some(id) if self.pending.as_ref().is_some_and(|(pending, _)| pending == id) => {}
+            Some(id) => {
+                self.pending = Some((id.to_string(), now));
+                self.done = None;
+            }
+            None => self.pending = None,
+        }
+    }
+
+    /// The item to prefetch, once it stayed selected for the delay and only once per selection
+    pub fn due(&mut self, now: Instant) -> Option<String> {
+        let (id, since) = self.pending.as_ref()?;
+        if now.duration_since(*since) < PREFETCH_DELAY || self.done.as_ref() == Some(id) {
+            return None;
+        }
+        self.done = Some(id.clone());
+        Some(id.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefetch_waits_for_the_selection_to_settle() {
+        let mut prefetch = Prefetch::new();
+        let start = Instant::now();
+        prefetch.select(Some("1"), start);
+        assert_eq!(prefetch.due(start), None);
+
+        // Moving on restarts the delay
+        prefetch.select(Some("2"), start + PREFETCH_DELAY / 2);
+        assert_eq!(prefetch.due(start + PREFETCH_DELAY), None);
+        assert_eq!(prefetch.due(start + PREFETCH_DELAY * 2), Some("2".to_string()));
+        assert_eq!(prefetch.due(start + PREFETCH_DELAY * 3), None);
+
+        // Coming back to an item prefetches it again
+        prefetch.select(Some("1"), start + PREFETCH_DELAY * 3);
+        prefetch.select(Some("2"), start + PREFETCH_DELAY * 3);
+        assert_eq!(prefetch.due(start + PREFETCH_DELAY * 4), Some("2".to_string()));
+    }
+}
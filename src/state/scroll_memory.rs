@@ -0,0 +1,72 @@
+use std::collections::VecDeque;
+
+/// Number of items whose details panel scroll position is remembered
+const CAPACITY: usize = 64;
+
+/// Last details panel scroll position of recently viewed items, least recently used first
+#[derive(Debug, Default)]
+pub struct ScrollMemory {
+    entries: VecDeque<(String, usize)>,
+}
+
+impl ScrollMemory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remember the scroll position of an item, forgetting the oldest one when full
+    pub fn remember(&mut self, item_id: String, offset: usize) {
+        self.entries.retain(|(id, _)| *id != item_id);
+        // The top is the default, so it needs no entry
+        if offset == 0 {
+            return;
+        }
+        if self.entries.len() == CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((item_id, offset));
+    }
+
+    /// The remembered scroll position of an item, marking it as recently used
+    pub fn recall(&mut self, item_id: &str) -> Option<usize> {
+        let index = self.entries.iter().position(|(id, _)| id == item_id)?;
+        let entry = self.entries.remove(index)?;
+        let offset = entry.1;
+        self.entries.push_back(entry);
+        Some(offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remember_and_recall() {
+        let mut memory = ScrollMemory::new();
+        memory.remember("a".to_string(), 5);
+        memory.remember("b".to_string(), 3);
+        assert_eq!(memory.recall("a"), Some(5));
+        assert_eq!(memory.recall("c"), None);
+
+        // Scrolling back to the top forgets the item
+        memory.remember("a".to_string(), 0);
+        assert_eq!(memory.recall("a"), None);
+        assert_eq!(memory.recall("b"), Some(3));
+    }
+
+    #[test]
+    fn test_least_recently_used_is_evicted() {
+        let mut memory = ScrollMemory::new();
+        for index in 0..CAPACITY {
+            memory.remember(index.to_string(), index + 1);
+        }
+        // Using the oldest item keeps it over the second oldest
+        assert_eq!(memory.recall("0"), Some(1));
+        memory.remember("new".to_string(), 7);
+
+        assert_eq!(memory.recall("0"), Some(1));
+        assert_eq!(memory.recall("1"), None);
+        assert_eq!(memory.recall("new"), Some(7));
+    }
+}
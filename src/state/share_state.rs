@@ -0,0 +1,74 @@
+use std::collections::BTreeSet;
+
+/// State of the dialog sharing a personal item with an organization
+#[derive(Debug, Default)]
+pub struct ShareState {
+    pub item_id: Option<String>, // Set while the dialog is open
+    pub selected: usize, // Index of the highlighted collection
+    pub chosen: BTreeSet<String>, // Ids of the collections to share the item in, all of one organization
+    pub confirm: bool, // Whether sharing awaits confirmation
+    pub busy: bool, // Whether the item is being shared
+}
+
+impl ShareState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self, item_id: String) {
+        self.item_id = Some(item_id);
+        self.selected = 0;
+        self.chosen.clear();
+        self.confirm = false;
+    }
+
+    pub fn close(&mut self) {
+        self.item_id = None;
+        self.chosen.clear();
+        self.confirm = false;
+    }
+
+    pub fn visible(&self) -> bool {
+        self.item_id.is_some()
+    }
+
+    /// Choose a collection or leave it out
+    /// An item belongs to a single organization, so `others` (the other organizations' collections) are left out
+    pub fn toggle(&mut self, collection_id: &str, others: &[&str]) {
+        if !self.chosen.remove(collection_id) {
+            self.chosen.retain(|id| !others.contains(&id.as_str()));
+            self.chosen.insert(collection_id.to_string());
+        }
+    }
+
+    pub fn select_next(&mut self, count: usize) {
+        if self.selected + 1 < count {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collections_of_one_organization() {
+        let mut share = ShareState::new();
+        share.open("item".to_string());
+        share.toggle("ops", &["hr"]);
+        share.toggle("dev", &["hr"]);
+        assert_eq!(share.chosen.len(), 2);
+
+        // Choosing another organization's collection starts over
+        share.toggle("hr", &["ops", "dev"]);
+        assert_eq!(share.chosen.iter().collect::<Vec<_>>(), vec!["hr"]);
+
+        share.toggle("hr", &["ops", "dev"]);
+        assert!(share.chosen.is_empty());
+    }
+}
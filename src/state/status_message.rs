@@ -1,4 +1,12 @@
-use std::time::Instant;
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Maximum number of messages kept in the status history
+pub const STATUS_HISTORY_SIZE: usize = 100;
+
+/// Most earlier messages still shown above the newest one in the status bar
+pub const STACKED_STATUS_LIMIT: usize = 2;
 
 #[derive(Debug)]
 pub struct StatusMessage {
@@ -7,7 +15,14 @@ pub struct StatusMessage {
     pub timestamp: Instant,
 }
 
-#[derive(Debug, Clone, Copy)]
+impl StatusMessage {
+    /// Whether the message was shown for its level's display time
+    pub fn expired(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.timestamp) > self.level.display_time()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(dead_code)]
 pub enum MessageLevel {
     Info,
@@ -16,3 +31,68 @@ pub enum MessageLevel {
     Error,
 }
 
+impl MessageLevel {
+    /// Short label for the status history
+    pub fn label(&self) -> &'static str {
+        match self {
+            MessageLevel::Info => "INFO",
+            MessageLevel::Success => "OK",
+            MessageLevel::Warning => "WARN",
+            MessageLevel::Error => "ERROR",
+        }
+    }
+
+    /// How long a message stays in the status bar, longer for problems so they can be read
+    pub fn display_time(&self) -> Duration {
+        match self {
+            MessageLevel::Info | MessageLevel::Success => Duration::from_secs(3),
+            MessageLevel::Warning => Duration::from_secs(5),
+            MessageLevel::Error => Duration::from_secs(8),
+        }
+    }
+}
+
+/// A status message kept in the history after it disappeared from the status bar
+#[derive(Debug, Clone)]
+pub struct StatusHistoryEntry {
+    pub text: String,
+    pub level: MessageLevel,
+    pub time: DateTime<Local>,
+}
+
+/// Ring buffer of the most recent status messages
+#[derive(Debug, Default)]
+pub struct StatusHistory {
+    entries: VecDeque<StatusHistoryEntry>,
+}
+
+impl StatusHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a message, dropping the oldest one when the history is full
+    pub fn push(&mut self, text: String, level: MessageLevel) {
+        if self.entries.len() == STATUS_HISTORY_SIZE {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(StatusHistoryEntry {
+            text,
+            level,
+            time: Local::now(),
+        });
+    }
+
+    /// Iterate over the messages, newest first
+    pub fn newest_first(&self) -> impl Iterator<Item = &StatusHistoryEntry> {
+        self.entries.iter().rev()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
@@ -1,7 +1,32 @@
+use super::SyncChanges;
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+
+/// Number of finished syncs remembered for the sync status popup
+pub const SYNC_HISTORY_SIZE: usize = 10;
+
+/// How a sync ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncOutcome {
+    /// The vault was listed, with the changes since the items shown before (None on the first load)
+    Synced { items: usize, changes: Option<SyncChanges> },
+    Failed(String),
+}
+
+/// A finished sync
+#[derive(Debug, Clone)]
+pub struct SyncRecord {
+    pub time: DateTime<Local>,
+    pub outcome: SyncOutcome,
+}
+
 /// State related to vault synchronization
 #[derive(Debug)]
 pub struct SyncState {
     pub syncing: bool,
+    pub retry: Option<(u32, u32)>, // (attempt, max_attempts) while a command is being retried
+    pub details_visible: bool, // Whether the sync status popup is shown
+    history: VecDeque<SyncRecord>,
     sync_animation_frame: u8,
 }
 
@@ -9,29 +34,66 @@ impl SyncState {
     pub fn new() -> Self {
         Self {
             syncing: false,
+            retry: None,
+            details_visible: false,
+            history: VecDeque::new(),
             sync_animation_frame: 0,
         }
     }
 
     pub fn start(&mut self) {
         self.syncing = true;
+        self.retry = None;
         self.sync_animation_frame = 0;
     }
 
     pub fn stop(&mut self) {
         self.syncing = false;
+        self.retry = None;
     }
 
-    pub fn advance_animation(&mut self) {
-        if self.syncing {
-            self.sync_animation_frame = (self.sync_animation_frame + 1) % 8;
+    /// Remember how a sync ended, forgetting the oldest ones past the history size
+    pub fn record(&mut self, outcome: SyncOutcome) {
+        if self.history.len() == SYNC_HISTORY_SIZE {
+            self.history.pop_front();
         }
+        self.history.push_back(SyncRecord { time: Local::now(), outcome });
     }
 
-    pub fn spinner(&self) -> &str {
-        if !self.syncing {
-            return "";
+    pub fn newest_first(&self) -> impl Iterator<Item = &SyncRecord> {
+        self.history.iter().rev()
+    }
+
+    pub fn last_success(&self) -> Option<&SyncRecord> {
+        self.newest_first().find(|record| matches!(record.outcome, SyncOutcome::Synced { .. }))
+    }
+
+    pub fn last_failure(&self) -> Option<&SyncRecord> {
+        self.newest_first().find(|record| matches!(record.outcome, SyncOutcome::Failed(_)))
+    }
+
+    pub fn set_retry(&mut self, attempt: u32, max_attempts: u32) {
+        self.retry = Some((attempt, max_attempts));
+    }
+
+    /// Describe the current activity, mentioning retries if any
+    pub fn activity(&self, label: &str) -> String {
+        match self.retry {
+            Some((attempt, max_attempts)) => format!("{} - retrying ({}/{})...", label, attempt, max_attempts),
+            None => format!("{}...", label),
         }
+    }
+
+    pub fn advance_animation(&mut self) {
+        self.sync_animation_frame = (self.sync_animation_frame + 1) % 8;
+    }
+
+    /// Current frame of the loading animation (0-7)
+    pub fn animation_frame(&self) -> u8 {
+        self.sync_animation_frame
+    }
+
+    pub fn spinner(&self) -> &str {
         match self.sync_animation_frame {
             0 => "⠋",
             1 => "⠙",
@@ -44,6 +106,11 @@ impl SyncState {
             _ => "⠋",
         }
     }
+
+    /// ASCII spinner for the accessibility mode
+    pub fn ascii_spinner(&self) -> &str {
+        ["|", "/", "-", "\\"][self.sync_animation_frame as usize % 4]
+    }
 }
 
 impl Default for SyncState {
@@ -52,3 +119,25 @@ impl Default for SyncState {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_keeps_the_latest_syncs() {
+        let mut sync = SyncState::new();
+        assert!(sync.last_success().is_none());
+
+        sync.record(SyncOutcome::Synced { items: 3, changes: None });
+        sync.record(SyncOutcome::Failed("timed out".to_string()));
+        assert!(matches!(sync.last_success().unwrap().outcome, SyncOutcome::Synced { items: 3, .. }));
+        assert_eq!(sync.last_failure().unwrap().outcome, SyncOutcome::Failed("timed out".to_string()));
+
+        for items in 0..SYNC_HISTORY_SIZE {
+            sync.record(SyncOutcome::Synced { items, changes: Some(SyncChanges::default()) });
+        }
+        assert_eq!(sync.newest_first().count(), SYNC_HISTORY_SIZE);
+        assert!(sync.last_failure().is_none());
+    }
+}
+
@@ -0,0 +1,179 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Cursor movement and deletion in a text input, besides typing and Backspace
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEdit {
+    Left,
+    Right,
+    Home,
+    End,
+    DeleteForward, // Delete the character after the cursor
+    DeleteWord, // Delete the word before the cursor, like Ctrl+W in a shell
+}
+
+/// Single-line text being typed, with a cursor between user-perceived characters
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextInput {
+    text: String,
+    cursor: usize, // Byte offset into text, always on a grapheme boundary
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Byte offset of the cursor in the text
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Replace the text, with the cursor at its end
+    pub fn set(&mut self, text: String) {
+        self.cursor = text.len();
+        self.text = text;
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.cursor = 0;
+    }
+
+    /// Type a character at the cursor
+    pub fn insert(&mut self, c: char) {
+        self.text.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    /// Insert pasted text at the cursor as a whole, returns whether the text changed
+    /// Line breaks and other control characters are dropped, e.g. the newline copied after a password
+    pub fn insert_str(&mut self, text: &str) -> bool {
+        let text: String = text.chars().filter(|c| !c.is_control()).collect();
+        self.text.insert_str(self.cursor, &text);
+        self.cursor += text.len();
+        !text.is_empty()
+    }
+
+    /// Delete the character before the cursor, returns whether the text changed
+    pub fn delete_back(&mut self) -> bool {
+        let start = self.previous_boundary();
+        self.delete(start, self.cursor)
+    }
+
+    /// Apply a cursor movement or deletion, returns whether the text changed
+    pub fn apply(&mut self, edit: TextEdit) -> bool {
+        match edit {
+            TextEdit::Left => self.cursor = self.previous_boundary(),
+            TextEdit::Right => self.cursor = self.next_boundary(),
+            TextEdit::Home => self.cursor = 0,
+            TextEdit::End => self.cursor = self.text.len(),
+            TextEdit::DeleteForward => return self.delete(self.cursor, self.next_boundary()),
+            TextEdit::DeleteWord => {
+                // Spaces before the cursor go along with the word before them
+                let before = &self.text[..self.cursor];
+                let word_end = before.trim_end().len();
+                let start = before[..word_end]
+                    .char_indices()
+                    .rev()
+                    .find(|(_, c)| c.is_whitespace())
+                    .map_or(0, |(index, c)| index + c.len_utf8());
+                return self.delete(start, self.cursor);
+            }
+        }
+        false
+    }
+
+    fn delete(&mut self, start: usize, end: usize) -> bool {
+        if start == end {
+            return false;
+        }
+        self.text.replace_range(start..end, "");
+        self.cursor = start;
+        true
+    }
+
+    fn previous_boundary(&self) -> usize {
+        self.text[..self.cursor].grapheme_indices(true).next_back().map_or(0, |(index, _)| index)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.text[self.cursor..].graphemes(true).next().map_or(self.cursor, |grapheme| self.cursor + grapheme.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(text: &str, cursor: usize) -> TextInput {
+        TextInput { text: text.to_string(), cursor }
+    }
+
+    #[test]
+    fn test_insert_and_delete_at_cursor() {
+        let mut text = TextInput::new();
+        for c in "gthub".chars() {
+            text.insert(c);
+        }
+        text.apply(TextEdit::Home);
+        text.apply(TextEdit::Right);
+        text.insert('i');
+        assert_eq!(text.text(), "github");
+        assert_eq!(text.cursor(), 2);
+
+        assert!(text.delete_back());
+        assert!(text.apply(TextEdit::DeleteForward));
+        assert_eq!(text.text(), "ghub");
+        text.apply(TextEdit::Home);
+        assert!(!text.delete_back());
+        text.apply(TextEdit::End);
+        assert!(!text.apply(TextEdit::DeleteForward));
+    }
+
+    #[test]
+    fn test_paste_at_cursor() {
+        let mut text = input("mail", 0);
+        assert!(text.insert_str("g\r\n"));
+        assert_eq!(text.text(), "gmail");
+        assert_eq!(text.cursor(), 1);
+        assert!(!text.insert_str("\n"));
+    }
+
+    #[test]
+    fn test_cursor_moves_over_whole_characters() {
+        // 'e' followed by a combining acute accent is one character on screen
+        let mut text = input("cafe\u{301}!", 0);
+        text.apply(TextEdit::End);
+        text.apply(TextEdit::Left);
+        text.apply(TextEdit::Left);
+        assert_eq!(text.cursor(), 3);
+        text.apply(TextEdit::Right);
+        assert_eq!(text.cursor(), "cafe\u{301}".len());
+        assert!(text.delete_back());
+        assert_eq!(text.text(), "caf!");
+    }
+
+    #[test]
+    fn test_delete_word() {
+        let mut text = input("mail work  ", 11);
+        assert!(text.apply(TextEdit::DeleteWord));
+        assert_eq!(text.text(), "mail ");
+        assert!(text.apply(TextEdit::DeleteWord));
+        assert_eq!(text.text(), "");
+        assert!(!text.apply(TextEdit::DeleteWord));
+
+        // Only what is before the cursor
+        let mut text = input("bank login", 4);
+        text.apply(TextEdit::DeleteWord);
+        assert_eq!(text.text(), " login");
+        assert_eq!(text.cursor(), 0);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::totp_util::{self, TotpCode};
+use crate::types::VaultItem;
+
+/// An item listed in the TOTP dashboard
+#[derive(Debug, Clone)]
+pub struct TotpRow {
+    pub name: String,
+    pub username: Option<String>,
+    pub code: Option<TotpCode>, // None when bwtui can't generate the code from the secret
+    secret: Option<String>, // Dropped once it fails, so it isn't parsed again on every tick
+}
+
+/// State of the dashboard listing the TOTP codes of all items, like an authenticator app
+#[derive(Debug, Default)]
+pub struct TotpDashboardState {
+    pub visible: bool,
+    pub rows: Vec<TotpRow>, // Holds secrets, cleared when the dashboard closes
+    pub selected: usize,
+}
+
+impl TotpDashboardState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// List the items with a TOTP secret by name, with their codes at `now` (unix seconds)
+    pub fn open(&mut self, items: &[VaultItem], now: u64) {
+        self.visible = true;
+        self.selected = 0;
+        self.load(items, now);
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+        self.rows.clear();
+    }
+
+    /// Replace the rows after a sync, keeping the selection in range
+    pub fn reload(&mut self, items: &[VaultItem], now: u64) {
+        self.load(items, now);
+        self.selected = self.selected.min(self.rows.len().saturating_sub(1));
+    }
+
+    fn load(&mut self, items: &[VaultItem], now: u64) {
+        self.rows = items.iter()
+            .filter_map(|item| {
+                let secret = item.login.as_ref()?.totp.clone()?;
+                Some(TotpRow {
+                    name: item.name.clone(),
+                    username: item.username().map(str::to_string),
+                    code: None,
+                    secret: Some(secret),
+                })
+            })
+            .collect();
+        self.rows.sort_by_key(|row| row.name.to_lowercase());
+        self.refresh(now);
+    }
+
+    /// Generate the codes that expired by `now`
+    /// Codes with the same period expire together, so they are regenerated in one batch
+    pub fn refresh(&mut self, now: u64) {
+        let expired = |row: &&mut TotpRow| row.code.as_ref().is_none_or(|code| code.expires_at <= now);
+        for row in self.rows.iter_mut().filter(expired) {
+            row.code = row.secret.as_deref().and_then(|secret| totp_util::generate_totp_at(secret, now).ok());
+            if row.code.is_none() {
+                row.secret = None;
+            }
+        }
+    }
+
+    pub fn selected_row(&self) -> Option<&TotpRow> {
+        self.rows.get(self.selected)
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.rows.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fake_backend::login;
+
+    #[test]
+    fn test_lists_items_with_totp_by_name() {
+        let mut dashboard = TotpDashboardState::new();
+        dashboard.open(&[
+            login("1", "mail", Some("JBSWY3DPEHPK3PXP")),
+            login("2", "Bank", None),
+            login("3", "Aws", Some("not base32!")),
+        ], 59);
+
+        let names: Vec<&str> = dashboard.rows.iter().map(|row| row.name.as_str()).collect();
+        assert_eq!(names, ["Aws", "mail"]);
+        assert!(dashboard.rows[0].code.is_none());
+        assert!(dashboard.rows[0].secret.is_none());
+        assert_eq!(dashboard.rows[1].code.as_ref().unwrap().expires_at, 60);
+
+        dashboard.select_next();
+        dashboard.select_next();
+        assert_eq!(dashboard.selected_row().unwrap().name, "mail");
+    }
+
+    #[test]
+    fn test_refresh_regenerates_expired_codes() {
+        let secret = "JBSWY3DPEHPK3PXP";
+        let mut dashboard = TotpDashboardState::new();
+        dashboard.open(&[login("1", "Mail", Some(secret))], 59);
+
+        dashboard.refresh(59);
+        assert_eq!(dashboard.rows[0].code, totp_util::generate_totp_at(secret, 59).ok());
+        dashboard.refresh(60);
+        assert_eq!(dashboard.rows[0].code, totp_util::generate_totp_at(secret, 60).ok());
+        assert_eq!(dashboard.rows[0].code.as_ref().unwrap().expires_at, 90);
+    }
+}
@@ -1,7 +1,51 @@
 use ratatui::layout::Rect;
+use crate::args::PickField;
+use crate::config::{LayoutConfig, ListConfig};
+use crate::events::Action;
+use crate::state::{ClickRegions, FetchedTotp, ScrollMemory, TextEdit, TextInput};
 use crate::types::ItemType;
 
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// A QR code shown in a popup, rendered when it was opened
+#[derive(Debug, Clone, PartialEq)]
+pub struct QrPopup {
+    pub title: String,
+    pub lines: Vec<String>,
+}
+
+/// An item whose TOTP code stays on screen while other items are selected
+#[derive(Debug, Clone, PartialEq)]
+pub struct TotpWatch {
+    pub item_id: String,
+    pub name: String,
+    pub code: Option<FetchedTotp>, // None until the first code is generated or fetched
+    pub fetching: bool, // Whether the code is being fetched from the backend
+}
+
+/// What the local note dialog edits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Annotation {
+    Note,
+    Tags,
+}
+
+/// A local note or tags being written for an item
+#[derive(Debug, Clone)]
+pub struct LocalNoteEdit {
+    pub item_id: String,
+    pub item_name: String,
+    pub annotation: Annotation,
+    pub input: TextInput,
+}
+
+/// Part of the screen that receives typed keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Focus {
+    Search, // Typing filters the list, as when bwtui starts
+    List, // Letters are commands, e.g. j/k to move
+    Details, // Keys move between the fields of the details panel
+}
 
 /// State related to UI modes, dialogs, and layout
 #[derive(Debug)]
@@ -9,21 +53,56 @@ pub struct UIState {
     pub details_panel_visible: bool,
     pub details_panel_scroll: usize, // Scroll position for details panel
     pub details_panel_max_scroll: usize, // Maximum scroll position for details panel
+    pub details_scroll_item: Option<String>, // ID of the item the details panel scroll position belongs to
+    pub details_scroll_memory: ScrollMemory, // Scroll positions of recently viewed items
+    pub details_refreshed_item: Option<String>, // ID of the selected item when the last sync changed it
+    pub details_focus: Option<usize>, // Index of the focused field in the details panel (None = search box or list has focus)
+    pub list_focused: bool, // Whether keys act on the list instead of typing into the search box
+    pub pending_key: Option<char>, // First key of a two-key sequence waiting for the second (vim scheme)
+    pub focused_field_revealed: bool, // Whether the focused field's hidden value is shown
+    pub details_fullscreen: bool, // Whether the details panel temporarily hides the entry list
+    pub raw_notes: bool, // Whether notes are shown as written instead of rendering their markdown
+    pub layout: LayoutConfig, // Arrangement of the entry list and details panel
+    pub list: ListConfig, // Extra information shown in entry list rows
+    pub accessible: bool, // ASCII symbols, higher contrast and no information conveyed by color alone
     pub password_input_mode: bool,
-    pub password_input: String,
+    pub readonly: bool, // Whether actions that change the vault are disabled (--readonly)
+    pub pick: Option<PickField>, // Field copied by the `pick` launcher, which exits once it is copied
+    pub relocked: bool, // Whether the unlock dialog resumes a session that locked while in use
+    pub session_expired: bool, // Whether the unlock dialog replaces a session token that stopped working
+    pub password_input: TextInput,
     pub unlock_error: Option<String>,
+    pub unlock_failures: u32, // Wrong master passwords in a row
+    pub max_unlock_attempts: u32, // Wrong master passwords before bwtui exits (0 = never)
+    unlock_retry_at: Option<Instant>, // Until when another attempt waits, after repeated failures
     pub offer_save_token: bool,
     pub save_token_response: Option<bool>,
     pub show_not_logged_in_error: bool,
+    pub show_status_history: bool,
+    pub confirm_quit: bool, // Whether quitting waits for confirmation, asked while an operation is in progress
+    pub qr_popup: Option<QrPopup>, // Holds a secret, closed when the vault locks
+    pub copy_as_selected: Option<usize>, // Highlighted entry of the open "copy as" format picker
+    pub local_note_edit: Option<LocalNoteEdit>, // Local note (Alt+N) or tags (Alt+G) being written
+    pub tag_list_selected: Option<usize>, // Highlighted entry of the open tag list (Alt+L)
+    pub active_view: Option<String>, // Name of the saved view whose filters were applied (Alt+E)
+    pub status_history_scroll: usize, // Number of messages scrolled past, newest first
+    // Master password re-prompt state
+    pub reprompt_action: Option<Action>, // Action to resume once the master password is verified
+    pub unlock_in_progress: bool, // Whether a submitted master password is being checked
+    pub reprompt_granted_item: Option<String>, // ID of the item the verified action may access
     pub list_area: Rect,
     pub details_panel_area: Rect,
+    pub click_regions: ClickRegions, // Clickable areas of the last rendered frame
     // TOTP state
     pub current_totp_code: Option<String>,
     pub totp_expires_at: Option<u64>, // Unix timestamp when current TOTP expires
+    pub totp_period: u64, // Validity period of the current TOTP code in seconds
     pub totp_loading: bool, // Whether we're currently fetching a TOTP code
     pub totp_copy_pending: bool, // Whether we're waiting to copy TOTP after fetch
     pub last_totp_fetch: Option<u64>, // Unix timestamp of last TOTP fetch attempt
     pub totp_item_id: Option<String>, // ID of the item that the current TOTP code belongs to
+    pub totp_watch: Option<TotpWatch>, // Item whose code stays in the status bar (Alt+W)
+    pub clipboard_clears_at: Option<Instant>, // When the clipboard auto-clears, shown as a countdown
     // Tab filtering state
     pub active_item_type_filter: Option<ItemType>, // None = all types, Some = specific type
 }
@@ -34,28 +113,92 @@ impl UIState {
             details_panel_visible: false,
             details_panel_scroll: 0,
             details_panel_max_scroll: 0,
+            details_scroll_item: None,
+            details_scroll_memory: ScrollMemory::new(),
+            details_refreshed_item: None,
+            details_focus: None,
+            list_focused: false,
+            pending_key: None,
+            focused_field_revealed: false,
+            details_fullscreen: false,
+            raw_notes: false,
+            layout: LayoutConfig::default(),
+            list: ListConfig::default(),
+            accessible: false,
             password_input_mode: false,
-            password_input: String::new(),
+            readonly: false,
+            pick: None,
+            relocked: false,
+            session_expired: false,
+            password_input: TextInput::new(),
             unlock_error: None,
+            unlock_failures: 0,
+            max_unlock_attempts: 0,
+            unlock_retry_at: None,
             offer_save_token: false,
             save_token_response: None,
             show_not_logged_in_error: false,
+            show_status_history: false,
+            confirm_quit: false,
+            qr_popup: None,
+            copy_as_selected: None,
+            local_note_edit: None,
+            tag_list_selected: None,
+            active_view: None,
+            status_history_scroll: 0,
+            reprompt_action: None,
+            unlock_in_progress: false,
+            reprompt_granted_item: None,
             list_area: Rect::default(),
             details_panel_area: Rect::default(),
+            click_regions: ClickRegions::new(),
             current_totp_code: None,
             totp_expires_at: None,
+            totp_period: crate::totp_util::DEFAULT_PERIOD,
             totp_loading: false,
             totp_copy_pending: false,
             last_totp_fetch: None,
             totp_item_id: None,
+            totp_watch: None,
+            clipboard_clears_at: None,
             active_item_type_filter: None, // Default to showing all types
         }
     }
 
-    pub fn toggle_details_panel(&mut self) {
+    pub fn toggle_details_panel(&mut self, item_id: Option<&str>) {
         self.details_panel_visible = !self.details_panel_visible;
-        // Reset scroll when toggling panel
-        self.details_panel_scroll = 0;
+        // Closing remembers the scroll position and opening restores it, field focus and full-screen mode reset
+        self.reset_details_scroll(item_id.filter(|_| self.details_panel_visible));
+        self.details_fullscreen = false;
+    }
+
+    /// Show the details panel full-screen, or bring the entry list back
+    pub fn toggle_details_fullscreen(&mut self, item_id: Option<&str>) {
+        if !self.details_panel_visible {
+            self.toggle_details_panel(item_id);
+            self.details_fullscreen = true;
+        } else {
+            self.details_fullscreen = !self.details_fullscreen;
+        }
+    }
+
+    /// Move the split between the list and the details panel, returning whether it changed
+    pub fn resize_split(&mut self, delta: i16) -> bool {
+        let list_percent = self.layout.list_percent
+            .saturating_add_signed(delta)
+            .clamp(crate::config::MIN_LIST_PERCENT, crate::config::MAX_LIST_PERCENT);
+        let changed = list_percent != self.layout.list_percent;
+        self.layout.list_percent = list_percent;
+        changed
+    }
+
+    /// Forget the areas of the previous layout, they are recomputed by the next render
+    pub fn handle_resize(&mut self) {
+        self.list_area = Rect::default();
+        self.details_panel_area = Rect::default();
+        self.click_regions.clear();
+        // The panel is taller or shorter now, the next render clamps to the new maximum
+        self.details_panel_scroll = self.details_panel_scroll.min(self.details_panel_max_scroll);
     }
 
     pub fn scroll_details_up(&mut self) {
@@ -78,8 +221,75 @@ impl UIState {
         }
     }
 
-    pub fn reset_details_scroll(&mut self) {
-        self.details_panel_scroll = 0;
+    /// Remember the scroll position of the item shown so far and restore the one of `item_id`
+    pub fn reset_details_scroll(&mut self, item_id: Option<&str>) {
+        if let Some(previous) = self.details_scroll_item.take() {
+            self.details_scroll_memory.remember(previous, self.details_panel_scroll);
+        }
+        self.details_panel_scroll = item_id
+            .and_then(|id| self.details_scroll_memory.recall(id))
+            .unwrap_or(0);
+        self.details_scroll_item = item_id.map(str::to_string);
+        // The focused field belongs to the previously displayed item
+        self.details_focus = None;
+        self.focused_field_revealed = false;
+    }
+
+    /// Move keyboard focus into the details panel, on the first field
+    pub fn focus_details(&mut self, field_count: usize) {
+        if self.details_panel_visible && field_count > 0 {
+            self.details_focus = Some(0);
+            self.focused_field_revealed = false;
+        }
+    }
+
+    /// Which part of the screen receives typed keys
+    pub fn focus(&self) -> Focus {
+        if self.details_focus.is_some() {
+            Focus::Details
+        } else if self.list_focused {
+            Focus::List
+        } else {
+            Focus::Search
+        }
+    }
+
+    /// Send typed keys to the search box
+    pub fn focus_search(&mut self) {
+        self.unfocus_details();
+        self.list_focused = false;
+    }
+
+    /// Send typed keys to the list, so letters can be used as commands
+    pub fn focus_list(&mut self) {
+        self.unfocus_details();
+        self.list_focused = true;
+    }
+
+    /// Return keyboard focus to the search box or list, whichever had it before the details panel
+    pub fn unfocus_details(&mut self) {
+        self.details_focus = None;
+        self.focused_field_revealed = false;
+    }
+
+    /// Focus the next field in the details panel (wraps around)
+    pub fn focus_next_field(&mut self, field_count: usize) {
+        if let Some(index) = self.details_focus {
+            if field_count > 0 {
+                self.details_focus = Some((index + 1) % field_count);
+                self.focused_field_revealed = false;
+            }
+        }
+    }
+
+    /// Focus the previous field in the details panel (wraps around)
+    pub fn focus_previous_field(&mut self, field_count: usize) {
+        if let Some(index) = self.details_focus {
+            if field_count > 0 {
+                self.details_focus = Some(if index == 0 { field_count - 1 } else { index - 1 });
+                self.focused_field_revealed = false;
+            }
+        }
     }
 
     pub fn enter_password_mode(&mut self) {
@@ -88,18 +298,39 @@ impl UIState {
         self.unlock_error = None;
     }
 
+    /// Ask for the master password after the session locked, keeping everything loaded
+    pub fn enter_relock_prompt(&mut self) {
+        self.relocked = true;
+        self.enter_password_mode();
+    }
+
+    /// Return whether the finished unlock resumed a locked session, clearing the flag
+    pub fn take_relocked(&mut self) -> bool {
+        std::mem::take(&mut self.relocked)
+    }
+
     pub fn exit_password_mode(&mut self) {
         self.password_input_mode = false;
+        self.unlock_in_progress = false;
+        self.session_expired = false;
         self.password_input.clear();
         self.unlock_error = None;
     }
 
     pub fn append_password_char(&mut self, c: char) {
-        self.password_input.push(c);
+        self.password_input.insert(c);
     }
 
     pub fn delete_password_char(&mut self) {
-        self.password_input.pop();
+        self.password_input.delete_back();
+    }
+
+    pub fn paste_password(&mut self, text: &str) {
+        self.password_input.insert_str(text);
+    }
+
+    pub fn edit_password(&mut self, edit: TextEdit) {
+        self.password_input.apply(edit);
     }
 
     pub fn clear_password(&mut self) {
@@ -107,7 +338,28 @@ impl UIState {
     }
 
     pub fn get_password(&self) -> String {
-        self.password_input.clone()
+        self.password_input.text().to_string()
+    }
+
+    /// Count a wrong master password, the next attempt waits longer after each one past the second
+    pub fn record_unlock_failure(&mut self, now: Instant) {
+        self.unlock_failures += 1;
+        self.unlock_retry_at = unlock_backoff(self.unlock_failures).map(|delay| now + delay);
+    }
+
+    pub fn reset_unlock_failures(&mut self) {
+        self.unlock_failures = 0;
+        self.unlock_retry_at = None;
+    }
+
+    /// How long until another master password may be tried
+    pub fn unlock_wait(&self, now: Instant) -> Option<Duration> {
+        self.unlock_retry_at.map(|at| at.saturating_duration_since(now)).filter(|wait| !wait.is_zero())
+    }
+
+    /// Whether the wrong master passwords reached the configured maximum
+    pub fn unlock_attempts_exhausted(&self) -> bool {
+        self.max_unlock_attempts > 0 && self.unlock_failures >= self.max_unlock_attempts
     }
 
     pub fn set_unlock_error(&mut self, error: String) {
@@ -132,10 +384,49 @@ impl UIState {
         self.show_not_logged_in_error = true;
     }
 
+    /// Show or hide the status history popup, starting from the newest message
+    pub fn toggle_status_history(&mut self) {
+        self.show_status_history = !self.show_status_history;
+        self.status_history_scroll = 0;
+    }
+
+    pub fn scroll_status_history_up(&mut self) {
+        self.status_history_scroll = self.status_history_scroll.saturating_sub(1);
+    }
+
+    pub fn scroll_status_history_down(&mut self, max_scroll: usize) {
+        if self.status_history_scroll < max_scroll {
+            self.status_history_scroll += 1;
+        }
+    }
+
+    /// Ask for the master password before running an action on a re-prompt protected item
+    pub fn enter_reprompt(&mut self, action: Action) {
+        self.reprompt_action = Some(action);
+        self.enter_password_mode();
+    }
+
+    /// Close the re-prompt dialog, returning the action that was waiting on it
+    pub fn exit_reprompt(&mut self) -> Option<Action> {
+        self.exit_password_mode();
+        self.reprompt_action.take()
+    }
+
+    /// Allow a single re-prompt protected action on the given item
+    pub fn grant_reprompt(&mut self, item_id: String) {
+        self.reprompt_granted_item = Some(item_id);
+    }
+
+    /// Consume the re-prompt grant, returning whether it was for the given item
+    pub fn consume_reprompt_grant(&mut self, item_id: &str) -> bool {
+        self.reprompt_granted_item.take().as_deref() == Some(item_id)
+    }
+
     /// Set the current TOTP code and its expiration time
-    pub fn set_totp_code(&mut self, code: String, expires_at: u64, item_id: String) {
+    pub fn set_totp_code(&mut self, code: String, expires_at: u64, period: u64, item_id: String) {
         self.current_totp_code = Some(code);
         self.totp_expires_at = Some(expires_at);
+        self.totp_period = period;
         self.totp_item_id = Some(item_id);
         self.totp_loading = false;
         self.totp_copy_pending = false;
@@ -180,7 +471,7 @@ impl UIState {
 
     /// Check if the current TOTP code belongs to the given item
     pub fn totp_belongs_to_item(&self, item_id: &str) -> bool {
-        self.totp_item_id.as_ref().map_or(false, |id| id == item_id)
+        self.totp_item_id.as_ref().is_some_and(|id| id == item_id)
     }
 
     /// Check if the current TOTP code is expired
@@ -226,7 +517,7 @@ impl UIState {
     /// Cycle to the next tab in order: All -> Login -> Note -> Card -> Identity -> All
     pub fn cycle_next_tab(&mut self) {
         self.active_item_type_filter = match self.active_item_type_filter {
-            None => Some(ItemType::Login),
+            None | Some(ItemType::Unknown(_)) => Some(ItemType::Login),
             Some(ItemType::Login) => Some(ItemType::SecureNote),
             Some(ItemType::SecureNote) => Some(ItemType::Card),
             Some(ItemType::Card) => Some(ItemType::Identity),
@@ -237,7 +528,7 @@ impl UIState {
     /// Cycle to the previous tab in order: All <- Login <- Note <- Card <- Identity <- All
     pub fn cycle_previous_tab(&mut self) {
         self.active_item_type_filter = match self.active_item_type_filter {
-            None => Some(ItemType::Identity), // Cycle back to Identity
+            None | Some(ItemType::Unknown(_)) => Some(ItemType::Identity), // Cycle back to Identity
             Some(ItemType::Login) => None,
             Some(ItemType::SecureNote) => Some(ItemType::Login),
             Some(ItemType::Card) => Some(ItemType::SecureNote),
@@ -253,3 +544,36 @@ impl Default for UIState {
     }
 }
 
+
+/// Wait before another master password after this many wrong ones in a row: none for the first two,
+/// then doubling from a second up to half a minute
+fn unlock_backoff(failures: u32) -> Option<Duration> {
+    let doublings = failures.checked_sub(3)?;
+    Some(Duration::from_secs(1 << doublings.min(5)).min(Duration::from_secs(30)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlock_backoff() {
+        let delays: Vec<Option<u64>> = (1..=9).map(|failures| unlock_backoff(failures).map(|delay| delay.as_secs())).collect();
+        assert_eq!(delays, vec![None, None, Some(1), Some(2), Some(4), Some(8), Some(16), Some(30), Some(30)]);
+
+        let mut ui = UIState::new();
+        let now = Instant::now();
+        ui.record_unlock_failure(now);
+        assert_eq!(ui.unlock_wait(now), None);
+        ui.record_unlock_failure(now);
+        ui.record_unlock_failure(now);
+        assert_eq!(ui.unlock_wait(now), Some(Duration::from_secs(1)));
+        assert_eq!(ui.unlock_wait(now + Duration::from_secs(1)), None);
+
+        ui.max_unlock_attempts = 3;
+        assert!(ui.unlock_attempts_exhausted());
+        ui.reset_unlock_failures();
+        assert!(!ui.unlock_attempts_exhausted());
+        assert_eq!(ui.unlock_wait(now), None);
+    }
+}
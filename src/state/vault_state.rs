@@ -1,20 +1,121 @@
-use crate::types::VaultItem;
+use crate::local_notes::{HiddenItems, LocalNotes, LocalTags};
+use std::collections::BTreeSet;
+use crate::state::{TextEdit, TextInput};
+use crate::text::fold_case;
+use crate::types::{Collection, EquivalentDomains, Folder, Organization, VaultItem};
 use fuzzy_matcher::FuzzyMatcher;
 use fuzzy_matcher::skim::SkimMatcherV2;
 use ratatui::widgets::ListState;
 
+/// Character positions matched by the filter query within an item's displayed text
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct MatchPositions {
+    pub name: Vec<usize>,
+    pub username: Vec<usize>,
+}
+
+/// Which owner's items are listed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum OwnerFilter {
+    #[default]
+    All,
+    /// Only items that don't belong to an organization
+    Personal,
+    /// Only items of the organization with this id
+    Organization(String),
+}
+
+impl OwnerFilter {
+    pub fn matches(&self, item: &VaultItem) -> bool {
+        match self {
+            OwnerFilter::All => true,
+            OwnerFilter::Personal => item.organization_id.is_none(),
+            OwnerFilter::Organization(id) => item.organization_id.as_ref() == Some(id),
+        }
+    }
+}
+
+/// How a sync changed the vault, compared to the items listed before it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncChanges {
+    pub added: usize,
+    pub updated: usize,
+    pub removed: usize,
+}
+
+impl SyncChanges {
+    /// Items are matched by id, and count as updated when their revision date changed
+    pub fn between(old: &[VaultItem], new: &[VaultItem]) -> Self {
+        let old_revisions: std::collections::HashMap<&str, _> = old.iter()
+            .map(|item| (item.id.as_str(), item.revision_date))
+            .collect();
+        let mut changes = Self::default();
+        for item in new {
+            match old_revisions.get(item.id.as_str()) {
+                None => changes.added += 1,
+                Some(revision_date) if *revision_date != item.revision_date => changes.updated += 1,
+                Some(_) => {}
+            }
+        }
+        let kept = new.iter().filter(|item| old_revisions.contains_key(item.id.as_str())).count();
+        changes.removed = old_revisions.len() - kept;
+        changes
+    }
+
+    /// E.g. "3 items updated, 1 added", None when nothing changed
+    pub fn summary(&self) -> Option<String> {
+        let counts = [(self.updated, "updated"), (self.added, "added"), (self.removed, "removed")];
+        let parts: Vec<String> = counts.iter()
+            .filter(|(count, _)| *count > 0)
+            .enumerate()
+            .map(|(position, (count, change))| match position {
+                0 => format!("{} item{} {}", count, if *count == 1 { "" } else { "s" }, change),
+                _ => format!("{} {}", count, change),
+            })
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+}
+
 /// State related to vault items, filtering, and selection
 #[derive(Debug)]
 pub struct VaultState {
     pub vault_items: Vec<VaultItem>,
-    pub filtered_items: Vec<VaultItem>,
-    pub filter_query: String,
+    /// Indices into `vault_items` of the items matching the current filter, in display order
+    pub filtered_indices: Vec<usize>,
+    pub filter_query: TextInput,
+    /// Case-folded name, username, domain and local note of each item, by index into `vault_items`
+    /// Built when the list is filtered, cleared when the items, the local notes or the case sensitivity change
+    search_texts: Vec<String>,
     pub selected_index: usize,
     pub list_state: ListState,
     pub initial_load_complete: bool,
     pub secrets_available: bool,
+    /// When the listed items were cached, while they are shown from the cache
+    pub cached_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Whether the listed cache is older than the configured maximum age
+    pub cache_expired: bool,
+    /// Whether the list scrolls to keep the selection visible, cleared by scrolling it manually
+    follow_selection: bool,
     fuzzy_enabled: bool,
     case_sensitive: bool,
+    organizations: Vec<Organization>,
+    owner_filter: OwnerFilter,
+    folders: Vec<Folder>,
+    collections: Vec<Collection>,
+    /// Domains of the same site, so searching one finds items of the others
+    equivalent_domains: EquivalentDomains,
+    /// Only list items in the folder with this name (case-insensitive)
+    folder_filter: Option<String>,
+    /// Notes kept on this computer, by item id, searchable like the item's name
+    local_notes: LocalNotes,
+    /// Tags kept on this computer, by item id, filtered on with `tag:` in the query
+    local_tags: LocalTags,
+    /// Items left out of the list and search results unless `show_hidden` is set
+    hidden_items: HiddenItems,
+    show_hidden: bool,
+    /// Item types left out of every tab, set by a saved view
+    hidden_types: Vec<crate::types::ItemType>,
 }
 
 impl VaultState {
@@ -24,47 +125,156 @@ impl VaultState {
         
         Self {
             vault_items: Vec::new(),
-            filtered_items: Vec::new(),
-            filter_query: String::new(),
+            filtered_indices: Vec::new(),
+            filter_query: TextInput::new(),
+            search_texts: Vec::new(),
             selected_index: 0,
             list_state,
             initial_load_complete: false,
             secrets_available: false,
+            cached_at: None,
+            cache_expired: false,
+            follow_selection: true,
             fuzzy_enabled: true,
             case_sensitive: false,
+            organizations: Vec::new(),
+            owner_filter: OwnerFilter::All,
+            folders: Vec::new(),
+            collections: Vec::new(),
+            equivalent_domains: EquivalentDomains::default(),
+            folder_filter: None,
+            local_notes: LocalNotes::new(),
+            local_tags: LocalTags::new(),
+            hidden_items: HiddenItems::new(),
+            show_hidden: false,
+            hidden_types: Vec::new(),
         }
     }
 
     /// Load items from cache (without secrets)
-    pub fn load_cached_items(&mut self, items: Vec<VaultItem>) {
+    pub fn load_cached_items(&mut self, items: Vec<VaultItem>, cached_at: chrono::DateTime<chrono::Utc>, expired: bool) {
         self.vault_items = items;
+        self.search_texts.clear();
         self.apply_filter(None); // No type filter when loading from cache
         self.initial_load_complete = true;
         self.secrets_available = false;
+        self.cached_at = Some(cached_at);
+        self.cache_expired = expired;
     }
 
     /// Load items with full data including secrets
     pub fn load_items_with_secrets(&mut self, items: Vec<VaultItem>) {
         self.vault_items = items;
+        self.search_texts.clear();
         self.apply_filter(None); // No type filter when loading with secrets
         self.initial_load_complete = true;
         self.secrets_available = true;
+        self.cached_at = None;
+        self.cache_expired = false;
+    }
+
+    /// Show items of a first load while more are still arriving
+    /// Their secrets are only treated as available once the load is finished
+    pub fn append_loading_items(&mut self, items: Vec<VaultItem>, type_filter: Option<crate::types::ItemType>) {
+        self.vault_items.extend(items);
+        self.apply_filter(type_filter);
+    }
+
+    /// Mark a progressively displayed first load as complete
+    pub fn finish_loading_items(&mut self) {
+        self.initial_load_complete = true;
+        self.secrets_available = true;
+        self.cached_at = None;
+        self.cache_expired = false;
+    }
+
+    /// Remove an item that was deleted from the vault
+    pub fn remove_item(&mut self, id: &str, type_filter: Option<crate::types::ItemType>) {
+        self.vault_items.retain(|item| item.id != id);
+        self.search_texts.clear();
+        self.apply_filter(type_filter);
+    }
+
+    /// Clear the search and owner filter if needed so the item is listed, and select it
+    /// Returns false if there is no such item
+    pub fn reveal_item(&mut self, id: &str) -> bool {
+        let Some(item_index) = self.vault_items.iter().position(|item| item.id == id) else {
+            return false;
+        };
+
+        self.filter_query.clear();
+        if !self.owner_filter.matches(&self.vault_items[item_index]) {
+            self.owner_filter = OwnerFilter::All;
+        }
+        if !self.matches_folder_filter(&self.vault_items[item_index]) {
+            self.folder_filter = None;
+        }
+        if self.is_hidden(id) {
+            self.show_hidden = true;
+        }
+        self.apply_filter(None);
+
+        match self.filtered_indices.iter().position(|&idx| idx == item_index) {
+            Some(position) => {
+                self.select_index(position);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Drop the items of a first load that failed or restarted
+    pub fn discard_loading_items(&mut self) {
+        self.vault_items.clear();
+        self.search_texts.clear();
+        self.filtered_indices.clear();
+        self.selected_index = 0;
+        self.sync_list_state();
     }
 
     pub fn apply_filter(&mut self, type_filter: Option<crate::types::ItemType>) {
-        // First filter by item type if specified
-        let mut items = if let Some(filter_type) = type_filter {
-            self.vault_items.iter()
-                .filter(|item| item.item_type == filter_type)
-                .cloned()
-                .collect()
-        } else {
-            self.vault_items.clone()
+        self.filter_within(type_filter, None);
+    }
+
+    /// Search again after the query grew, e.g. by typing at its end, only among the items that matched before
+    /// An item matching the longer text always matched the shorter one, so large vaults aren't searched from scratch on every key
+    fn narrow_filter(&mut self, previous_query: &str, type_filter: Option<crate::types::ItemType>) {
+        let (previous_tags, previous_text) = split_tag_terms(previous_query);
+        let (tags, text) = split_tag_terms(self.filter_query.text());
+        if tags != previous_tags || !text.starts_with(previous_text.as_ref()) {
+            return self.apply_filter(type_filter);
+        }
+        // Back in vault order, so equal scores rank the same as a full search
+        let mut previous = std::mem::take(&mut self.filtered_indices);
+        previous.sort_unstable();
+        self.filter_within(type_filter, Some(&previous));
+    }
+
+    /// Filter the items, or only those at the given sorted indices
+    #[tracing::instrument(skip_all, fields(items = within.map_or(self.vault_items.len(), <[usize]>::len)))]
+    fn filter_within(&mut self, type_filter: Option<crate::types::ItemType>, within: Option<&[usize]>) {
+        // Only items added since the texts were last built need theirs, e.g. during a progressive load
+        let missing: Vec<String> = self.vault_items[self.search_texts.len()..].iter()
+            .map(|item| self.get_searchable_text(item))
+            .collect();
+        self.search_texts.extend(missing);
+
+        // First filter by item type if specified, by owner and folder, and by the `tag:` terms of the query
+        let (tags, text) = split_tag_terms(self.filter_query.text());
+        let items: Box<dyn Iterator<Item = (usize, &VaultItem)>> = match within {
+            Some(indices) => Box::new(indices.iter().map(|&idx| (idx, &self.vault_items[idx]))),
+            None => Box::new(self.vault_items.iter().enumerate()),
         };
+        let candidates = items
+            .filter(|(_, item)| type_filter.is_none_or(|filter_type| item.item_type == filter_type))
+            .filter(|(_, item)| self.in_scope(item))
+            .filter(|(_, item)| tags.iter().all(|tag| self.has_tag(item, tag)));
 
-        if self.filter_query.is_empty() {
+        let indices: Vec<usize> = if text.is_empty() {
             // When no text filter is active, show all items with starred items first
-            items.sort_by(|a, b| {
+            let mut indices: Vec<usize> = candidates.map(|(idx, _)| idx).collect();
+            indices.sort_by(|&a, &b| {
+                let (a, b) = (&self.vault_items[a], &self.vault_items[b]);
                 // Sort by favorite status (true before false), then by name
                 match (b.favorite, a.favorite) {
                     (true, false) => std::cmp::Ordering::Greater,
@@ -72,47 +282,50 @@ impl VaultState {
                     _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
                 }
             });
-            self.filtered_items = items;
+            indices
         } else {
-            let matcher = SkimMatcherV2::default();
-            let query = if self.case_sensitive {
-                self.filter_query.clone()
-            } else {
-                self.filter_query.to_lowercase()
+            let matcher = self.fuzzy_matcher();
+            let query = if self.case_sensitive { text.to_string() } else { fold_case(&text) };
+
+            let score = |&(idx, item): &(usize, &VaultItem)| {
+                let searchable_text = &self.search_texts[idx];
+
+                let score = if self.fuzzy_enabled {
+                    matcher.fuzzy_match(searchable_text, &query)
+                } else {
+                    // For non-fuzzy matching, use a simple relevance score
+                    // Higher score if match is earlier in the string
+                    searchable_text.find(&query)
+                        .map(|position| 1000 - position as i64)
+                };
+                // Items of an equivalent domain come after the direct matches
+                score
+                    .or_else(|| self.matches_equivalent_domain(item, &query).then_some(i64::MIN))
+                    .map(|score| (idx, score))
             };
 
             // Collect items with their relevance scores
-            let mut items_with_scores: Vec<(VaultItem, i64)> = items
-                .iter()
-                .filter_map(|item| {
-                    let searchable_text = self.get_searchable_text(item);
-                    
-                    if self.fuzzy_enabled {
-                        matcher.fuzzy_match(&searchable_text, &query)
-                            .map(|score| (item.clone(), score))
-                    } else {
-                        if searchable_text.contains(&query) {
-                            // For non-fuzzy matching, use a simple relevance score
-                            // Higher score if match is earlier in the string
-                            let position = searchable_text.find(&query).unwrap_or(searchable_text.len());
-                            let score = 1000 - position as i64;
-                            Some((item.clone(), score))
-                        } else {
-                            None
-                        }
-                    }
-                })
-                .collect();
+            #[cfg(not(feature = "parallel-search"))]
+            let mut indices_with_scores: Vec<(usize, i64)> = candidates.filter_map(|candidate| score(&candidate)).collect();
+            // Scored on every core, the collected scores keep the vault order
+            #[cfg(feature = "parallel-search")]
+            let mut indices_with_scores: Vec<(usize, i64)> = {
+                use rayon::prelude::*;
+                let candidates: Vec<(usize, &VaultItem)> = candidates.collect();
+                candidates.par_iter().filter_map(score).collect()
+            };
 
-            // Sort by score descending (higher scores = better matches first)
-            items_with_scores.sort_by(|a, b| b.1.cmp(&a.1));
+            // Sort by score descending (higher scores = better matches first), stable so ties stay in vault order
+            indices_with_scores.sort_by_key(|b| std::cmp::Reverse(b.1));
             
-            // Extract just the items
-            self.filtered_items = items_with_scores.into_iter().map(|(item, _)| item).collect();
-        }
+            // Extract just the indices
+            indices_with_scores.into_iter().map(|(idx, _)| idx).collect()
+        };
+
+        self.filtered_indices = indices;
 
         // Reset selection if out of bounds
-        if self.selected_index >= self.filtered_items.len() && !self.filtered_items.is_empty() {
+        if self.selected_index >= self.filtered_len() && !self.filtered_indices.is_empty() {
             self.selected_index = 0;
         }
         
@@ -120,11 +333,71 @@ impl VaultState {
         self.sync_list_state();
     }
 
+    /// The fuzzy matcher, which otherwise ignores case for lowercase queries even when matching is case-sensitive
+    fn fuzzy_matcher(&self) -> SkimMatcherV2 {
+        if self.case_sensitive {
+            SkimMatcherV2::default().respect_case()
+        } else {
+            SkimMatcherV2::default()
+        }
+    }
+
+    /// Find which characters of the item's name and username matched the current query
+    pub fn match_positions(&self, item: &VaultItem) -> MatchPositions {
+        let (_, text) = split_tag_terms(self.filter_query.text());
+        if text.is_empty() {
+            return MatchPositions::default();
+        }
+
+        let searchable_text = self.get_searchable_text(item);
+        let query = if self.case_sensitive { text.to_string() } else { fold_case(&text) };
+
+        // Positions are character indices into the searchable text
+        let positions: Vec<usize> = if self.fuzzy_enabled {
+            self.fuzzy_matcher()
+                .fuzzy_indices(&searchable_text, &query)
+                .map(|(_, indices)| indices)
+                .unwrap_or_default()
+        } else {
+            searchable_text.find(&query)
+                .map(|position| {
+                    let start = searchable_text[..position].chars().count();
+                    (start..start + query.chars().count()).collect()
+                })
+                .unwrap_or_default()
+        };
+
+        // The searchable text starts with the name, followed by a space and the username
+        let name_len = item.name.chars().count();
+        let username_len = item.username().map_or(0, |username| username.chars().count());
+        let mut matches = MatchPositions::default();
+        for position in positions {
+            if position < name_len {
+                matches.name.push(position);
+            } else if position > name_len && position <= name_len + username_len {
+                matches.username.push(position - name_len - 1);
+            }
+        }
+        matches
+    }
+
+    /// Whether the query is part of a domain equivalent to the item's, like "amazon" for an amzn.com login
+    /// Always an exact match, as fuzzy matching the many global domains would find nearly anything
+    fn matches_equivalent_domain(&self, item: &VaultItem, query: &str) -> bool {
+        let Some(domain) = item.domain() else {
+            return false;
+        };
+        self.equivalent_domains
+            .equivalents(&domain)
+            .iter()
+            .any(|equivalent| if self.case_sensitive { equivalent.contains(query) } else { fold_case(equivalent).contains(query) })
+    }
+
     fn get_searchable_text(&self, item: &VaultItem) -> String {
         let mut text = if self.case_sensitive {
             item.name.clone()
         } else {
-            item.name.to_lowercase()
+            fold_case(&item.name)
         };
 
         if let Some(username) = item.username() {
@@ -132,8 +405,7 @@ impl VaultState {
             if self.case_sensitive {
                 text.push_str(username);
             } else {
-                let lowercase = username.to_lowercase();
-                text.push_str(&lowercase);
+                text.push_str(&fold_case(username));
             }
         }
 
@@ -142,29 +414,71 @@ impl VaultState {
             if self.case_sensitive {
                 text.push_str(&domain);
             } else {
-                let lowercase = domain.to_lowercase();
-                text.push_str(&lowercase);
+                text.push_str(&fold_case(&domain));
+            }
+        }
+
+        if let Some(note) = self.local_note(&item.id) {
+            text.push(' ');
+            if self.case_sensitive {
+                text.push_str(note);
+            } else {
+                text.push_str(&fold_case(note));
             }
         }
 
         text
     }
 
+    /// Number of items matching the current filter
+    #[inline]
+    pub fn filtered_len(&self) -> usize {
+        self.filtered_indices.len()
+    }
+
+    /// Item at the given position in the filtered list
+    pub fn filtered_item(&self, position: usize) -> Option<&VaultItem> {
+        self.filtered_indices.get(position).and_then(|&idx| self.vault_items.get(idx))
+    }
+
     pub fn selected_item(&self) -> Option<&VaultItem> {
-        self.filtered_items.get(self.selected_index)
+        self.filtered_item(self.selected_index)
+    }
+
+    /// Range of filtered items visible in a list of the given height
+    /// Scrolls the list just enough to keep the selected item in view, unless it was scrolled manually
+    pub fn visible_window(&mut self, height: usize) -> std::ops::Range<usize> {
+        let len = self.filtered_len();
+        let mut offset = self.list_state.offset();
+
+        if height == 0 || len == 0 {
+            return 0..0;
+        }
+        if !self.follow_selection {
+            // Keep the manual scroll position
+        } else if self.selected_index < offset {
+            offset = self.selected_index;
+        } else if self.selected_index >= offset + height {
+            offset = self.selected_index + 1 - height;
+        }
+        // Don't leave empty rows at the bottom when the list shrank
+        offset = offset.min(len.saturating_sub(height));
+
+        *self.list_state.offset_mut() = offset;
+        offset..(offset + height).min(len)
     }
 
     pub fn select_next(&mut self) {
-        if !self.filtered_items.is_empty() {
-            self.selected_index = (self.selected_index + 1) % self.filtered_items.len();
+        if !self.filtered_indices.is_empty() {
+            self.selected_index = (self.selected_index + 1) % self.filtered_len();
             self.sync_list_state();
         }
     }
 
     pub fn select_previous(&mut self) {
-        if !self.filtered_items.is_empty() {
+        if !self.filtered_indices.is_empty() {
             if self.selected_index == 0 {
-                self.selected_index = self.filtered_items.len() - 1;
+                self.selected_index = self.filtered_len() - 1;
             } else {
                 self.selected_index -= 1;
             }
@@ -173,12 +487,21 @@ impl VaultState {
     }
 
     pub fn select_index(&mut self, index: usize) {
-        if index < self.filtered_items.len() {
+        if index < self.filtered_len() {
             self.selected_index = index;
             self.sync_list_state();
         }
     }
 
+    /// Select the listed item with this id, returning false if it isn't listed
+    pub fn select_item(&mut self, id: &str) -> bool {
+        let position = self.filtered_indices.iter().position(|&idx| self.vault_items[idx].id == id);
+        if let Some(position) = position {
+            self.select_index(position);
+        }
+        position.is_some()
+    }
+
     pub fn page_up(&mut self, page_size: usize) {
         if self.selected_index >= page_size {
             self.selected_index -= page_size;
@@ -189,8 +512,8 @@ impl VaultState {
     }
 
     pub fn page_down(&mut self, page_size: usize) {
-        if !self.filtered_items.is_empty() {
-            self.selected_index = (self.selected_index + page_size).min(self.filtered_items.len() - 1);
+        if !self.filtered_indices.is_empty() {
+            self.selected_index = (self.selected_index + page_size).min(self.filtered_len() - 1);
             self.sync_list_state();
         }
     }
@@ -201,14 +524,36 @@ impl VaultState {
     }
 
     pub fn jump_to_end(&mut self) {
-        if !self.filtered_items.is_empty() {
-            self.selected_index = self.filtered_items.len() - 1;
+        if !self.filtered_indices.is_empty() {
+            self.selected_index = self.filtered_len() - 1;
             self.sync_list_state();
         }
     }
     
+    /// Scroll the list up one row without changing the selection
+    pub fn scroll_list_up(&mut self) {
+        let offset = self.list_state.offset();
+        *self.list_state.offset_mut() = offset.saturating_sub(1);
+        self.follow_selection = false;
+    }
+
+    /// Scroll the list down one row without changing the selection
+    pub fn scroll_list_down(&mut self) {
+        let offset = self.list_state.offset();
+        if offset + 1 < self.filtered_len() {
+            *self.list_state.offset_mut() = offset + 1;
+        }
+        self.follow_selection = false;
+    }
+
+    /// Bring the selected item back into view, e.g. after the list changed size
+    pub fn follow_selection(&mut self) {
+        self.follow_selection = true;
+    }
+
     fn sync_list_state(&mut self) {
-        if self.filtered_items.is_empty() {
+        self.follow_selection = true;
+        if self.filtered_indices.is_empty() {
             self.list_state.select(None);
         } else {
             self.list_state.select(Some(self.selected_index));
@@ -216,19 +561,402 @@ impl VaultState {
     }
 
     pub fn append_filter(&mut self, c: char, type_filter: Option<crate::types::ItemType>) {
-        self.filter_query.push(c);
-        self.apply_filter(type_filter);
+        let previous_query = self.filter_query.text().to_string();
+        self.filter_query.insert(c);
+        self.narrow_filter(&previous_query, type_filter);
     }
 
     pub fn delete_filter_char(&mut self, type_filter: Option<crate::types::ItemType>) {
-        self.filter_query.pop();
+        self.filter_query.delete_back();
         self.apply_filter(type_filter);
     }
 
+    /// Paste text into the search at the cursor, searching once for all of it
+    pub fn paste_filter(&mut self, text: &str, type_filter: Option<crate::types::ItemType>) {
+        let previous_query = self.filter_query.text().to_string();
+        if self.filter_query.insert_str(text) {
+            self.narrow_filter(&previous_query, type_filter);
+        }
+    }
+
+    /// Move the search cursor or delete around it, only searching again if the text changed
+    pub fn edit_filter(&mut self, edit: TextEdit, type_filter: Option<crate::types::ItemType>) {
+        if self.filter_query.apply(edit) {
+            self.apply_filter(type_filter);
+        }
+    }
+
+    /// Clear the search text and the folder filter
     pub fn clear_filter(&mut self, type_filter: Option<crate::types::ItemType>) {
         self.filter_query.clear();
+        self.folder_filter = None;
+        self.apply_filter(type_filter);
+    }
+
+    #[inline]
+    pub fn fuzzy_enabled(&self) -> bool {
+        self.fuzzy_enabled
+    }
+
+    #[inline]
+    pub fn case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    /// Switch between fuzzy and exact substring matching
+    pub fn toggle_fuzzy(&mut self, type_filter: Option<crate::types::ItemType>) {
+        self.fuzzy_enabled = !self.fuzzy_enabled;
+        self.apply_filter(type_filter);
+    }
+
+    /// Switch between case-sensitive and case-insensitive matching
+    pub fn toggle_case_sensitive(&mut self, type_filter: Option<crate::types::ItemType>) {
+        self.case_sensitive = !self.case_sensitive;
+        self.search_texts.clear();
+        self.apply_filter(type_filter);
+    }
+
+    #[inline]
+    pub fn organizations(&self) -> &[Organization] {
+        &self.organizations
+    }
+
+    /// Replace the known organizations, dropping an owner filter for one that no longer exists
+    pub fn set_organizations(&mut self, organizations: Vec<Organization>, type_filter: Option<crate::types::ItemType>) {
+        self.organizations = organizations;
+        if let OwnerFilter::Organization(id) = &self.owner_filter {
+            if self.organization_name(id).is_none() {
+                self.owner_filter = OwnerFilter::All;
+                self.apply_filter(type_filter);
+            }
+        }
+    }
+
+    /// Name of the organization with the given id, if it is known
+    pub fn organization_name(&self, id: &str) -> Option<&str> {
+        self.organizations
+            .iter()
+            .find(|organization| organization.id == id)
+            .map(|organization| organization.name.as_str())
+    }
+
+    #[inline]
+    pub fn folders(&self) -> &[Folder] {
+        &self.folders
+    }
+
+    pub fn set_folders(&mut self, folders: Vec<Folder>, type_filter: Option<crate::types::ItemType>) {
+        self.folders = folders;
+        // Folder names decide which items the folder filter lists
+        if self.folder_filter.is_some() {
+            self.apply_filter(type_filter);
+        }
+    }
+
+    pub fn set_collections(&mut self, collections: Vec<Collection>) {
+        self.collections = collections;
+    }
+
+    pub fn set_equivalent_domains(&mut self, domains: EquivalentDomains, type_filter: Option<crate::types::ItemType>) {
+        self.equivalent_domains = domains;
+        self.apply_filter(type_filter);
+    }
+
+    /// Record an item's master password re-prompt setting
+    pub fn set_item_reprompt(&mut self, id: &str, enabled: bool) {
+        if let Some(item) = self.vault_items.iter_mut().find(|item| item.id == id) {
+            item.reprompt = Some(u8::from(enabled));
+        }
+    }
+
+    /// The known collections an item is shared in, by name
+    pub fn item_collections(&self, item: &VaultItem) -> Vec<&Collection> {
+        let ids = item.collection_ids.as_deref().unwrap_or_default();
+        let mut collections: Vec<&Collection> = self.collections
+            .iter()
+            .filter(|collection| ids.contains(&collection.id))
+            .collect();
+        collections.sort_by_cached_key(|collection| collection.name.to_lowercase());
+        collections
+    }
+
+    /// Every known collection, by organization then name
+    pub fn collections_by_organization(&self) -> Vec<&Collection> {
+        let mut collections: Vec<&Collection> = self.collections.iter().collect();
+        collections.sort_by_cached_key(|collection| {
+            let organization = self.organization_name(&collection.organization_id).unwrap_or_default();
+            (organization.to_lowercase(), collection.name.to_lowercase())
+        });
+        collections
+    }
+
+    /// Put an item in a folder, which may take it out of the folder filter
+    pub fn set_item_folder(&mut self, id: &str, folder_id: Option<String>, type_filter: Option<crate::types::ItemType>) {
+        if let Some(item) = self.vault_items.iter_mut().find(|item| item.id == id) {
+            item.folder_id = folder_id;
+        }
+        self.apply_filter(type_filter);
+    }
+
+    /// Record that an item now belongs to an organization, in the given collections
+    pub fn share_item(&mut self, id: &str, organization_id: &str, collection_ids: Vec<String>, type_filter: Option<crate::types::ItemType>) {
+        if let Some(item) = self.vault_items.iter_mut().find(|item| item.id == id) {
+            item.organization_id = Some(organization_id.to_string());
+            item.collection_ids = Some(collection_ids);
+        }
+        self.apply_filter(type_filter);
+    }
+
+    /// Every folder by name, with its number of items
+    pub fn folder_counts(&self) -> Vec<(&Folder, usize)> {
+        let mut counts: Vec<(&Folder, usize)> = self.folders
+            .iter()
+            .map(|folder| {
+                let count = self.vault_items
+                    .iter()
+                    .filter(|item| item.folder_id.as_deref() == Some(folder.id.as_str()))
+                    .count();
+                (folder, count)
+            })
+            .collect();
+        counts.sort_by_cached_key(|(folder, _)| folder.name.to_lowercase());
+        counts
+    }
+
+    pub fn add_folder(&mut self, folder: Folder) {
+        self.folders.push(folder);
+    }
+
+    /// Give a folder a new name, keeping the folder filter on it
+    pub fn rename_folder(&mut self, id: &str, name: &str) {
+        let Some(folder) = self.folders.iter_mut().find(|folder| folder.id == id) else {
+            return;
+        };
+        if self.folder_filter.as_ref().is_some_and(|filter| filter.eq_ignore_ascii_case(&folder.name)) {
+            self.folder_filter = Some(name.to_string());
+        }
+        folder.name = name.to_string();
+    }
+
+    /// Forget a deleted folder, whose items are no longer in any folder
+    pub fn remove_folder(&mut self, id: &str, type_filter: Option<crate::types::ItemType>) {
+        if let Some(folder) = self.folders.iter().find(|folder| folder.id == id) {
+            if self.folder_filter.as_ref().is_some_and(|filter| filter.eq_ignore_ascii_case(&folder.name)) {
+                self.folder_filter = None;
+            }
+        }
+        self.folders.retain(|folder| folder.id != id);
+        for item in self.vault_items.iter_mut().filter(|item| item.folder_id.as_deref() == Some(id)) {
+            item.folder_id = None;
+        }
+        self.apply_filter(type_filter);
+    }
+
+    /// Replace the search text
+    pub fn set_filter_query(&mut self, query: String, type_filter: Option<crate::types::ItemType>) {
+        self.filter_query.set(query);
+        self.apply_filter(type_filter);
+    }
+
+    pub fn local_notes(&self) -> &LocalNotes {
+        &self.local_notes
+    }
+
+    pub fn local_note(&self, id: &str) -> Option<&str> {
+        self.local_notes.get(id).map(String::as_str)
+    }
+
+    pub fn set_local_notes(&mut self, notes: LocalNotes, type_filter: Option<crate::types::ItemType>) {
+        self.local_notes = notes;
+        self.search_texts.clear();
+        self.apply_filter(type_filter);
+    }
+
+    /// Attach a note to an item, or remove its note when the text is blank
+    pub fn set_local_note(&mut self, id: &str, text: &str, type_filter: Option<crate::types::ItemType>) {
+        match text.trim() {
+            "" => self.local_notes.remove(id),
+            text => self.local_notes.insert(id.to_string(), text.to_string()),
+        };
+        self.search_texts.clear();
+        self.apply_filter(type_filter);
+    }
+
+    pub fn item_tags(&self, id: &str) -> Option<&BTreeSet<String>> {
+        self.local_tags.get(id)
+    }
+
+    pub fn local_tags(&self) -> &LocalTags {
+        &self.local_tags
+    }
+
+    pub fn set_local_tags(&mut self, tags: LocalTags, type_filter: Option<crate::types::ItemType>) {
+        self.local_tags = tags;
+        self.apply_filter(type_filter);
+    }
+
+    /// Replace an item's tags with the ones in `text`, or remove them when it has none
+    pub fn set_item_tags(&mut self, id: &str, text: &str, type_filter: Option<crate::types::ItemType>) {
+        let tags = crate::local_notes::parse_tags(text);
+        if tags.is_empty() {
+            self.local_tags.remove(id);
+        } else {
+            self.local_tags.insert(id.to_string(), tags);
+        }
+        self.apply_filter(type_filter);
+    }
+
+    /// Every tag in use with its number of items, by name
+    pub fn tag_counts(&self) -> Vec<(&str, usize)> {
+        let mut counts: std::collections::BTreeMap<&str, usize> = std::collections::BTreeMap::new();
+        for tag in self.local_tags.values().flatten() {
+            *counts.entry(tag.as_str()).or_default() += 1;
+        }
+        counts.into_iter().collect()
+    }
+
+    fn has_tag(&self, item: &VaultItem, tag: &str) -> bool {
+        self.item_tags(&item.id).is_some_and(|tags| tags.contains(tag))
+    }
+
+    #[inline]
+    pub fn folder_filter(&self) -> Option<&str> {
+        self.folder_filter.as_deref()
+    }
+
+    /// Only list items in the folder with the given name, or all items
+    pub fn set_folder_filter(&mut self, name: Option<String>, type_filter: Option<crate::types::ItemType>) {
+        self.folder_filter = name;
+        self.apply_filter(type_filter);
+    }
+
+    fn matches_folder_filter(&self, item: &VaultItem) -> bool {
+        self.folder_filter.as_ref().is_none_or(|filter| {
+            self.folder_name(item).is_some_and(|name| name.eq_ignore_ascii_case(filter))
+        })
+    }
+
+    /// Whether the item belongs to the selected owner and folder, and isn't hidden
+    pub fn in_scope(&self, item: &VaultItem) -> bool {
+        self.owner_filter.matches(item)
+            && self.matches_folder_filter(item)
+            && !self.hidden_types.contains(&item.item_type)
+            && (self.show_hidden || !self.is_hidden(&item.id))
+    }
+
+    pub fn is_hidden(&self, id: &str) -> bool {
+        self.hidden_items.contains(id)
+    }
+
+    pub fn hidden_items(&self) -> &HiddenItems {
+        &self.hidden_items
+    }
+
+    pub fn set_hidden_items(&mut self, hidden: HiddenItems, type_filter: Option<crate::types::ItemType>) {
+        self.hidden_items = hidden;
+        self.apply_filter(type_filter);
+    }
+
+    /// Hide the item, or list it again if it is hidden, returning whether it is now hidden
+    pub fn toggle_hidden(&mut self, id: &str, type_filter: Option<crate::types::ItemType>) -> bool {
+        if !self.hidden_items.remove(id) {
+            self.hidden_items.insert(id.to_string());
+        }
+        self.apply_filter(type_filter);
+        self.is_hidden(id)
+    }
+
+    /// Number of listed vault items that are hidden, ids of deleted items aside
+    pub fn hidden_count(&self) -> usize {
+        if self.hidden_items.is_empty() {
+            return 0;
+        }
+        self.vault_items.iter().filter(|item| self.is_hidden(&item.id)).count()
+    }
+
+    #[inline]
+    pub fn show_hidden(&self) -> bool {
+        self.show_hidden
+    }
+
+    pub fn toggle_show_hidden(&mut self, type_filter: Option<crate::types::ItemType>) {
+        self.show_hidden = !self.show_hidden;
+        self.apply_filter(type_filter);
+    }
+
+    /// Name of the folder an item is filed in, if any and it is known
+    pub fn folder_name(&self, item: &VaultItem) -> Option<&str> {
+        let folder_id = item.folder_id.as_deref()?;
+        self.folders
+            .iter()
+            .find(|folder| folder.id == folder_id)
+            .map(|folder| folder.name.as_str())
+    }
+
+    /// Display name of the current owner filter
+    pub fn owner_filter_label(&self) -> &str {
+        match &self.owner_filter {
+            OwnerFilter::All => "All",
+            OwnerFilter::Personal => "Personal",
+            OwnerFilter::Organization(id) => self.organization_name(id).unwrap_or("Organization"),
+        }
+    }
+
+    /// Id of the organization with this name (case-insensitive)
+    pub fn organization_id(&self, name: &str) -> Option<&str> {
+        self.organizations
+            .iter()
+            .find(|organization| organization.name.eq_ignore_ascii_case(name))
+            .map(|organization| organization.id.as_str())
+    }
+
+    /// Replace the owner, folder, hidden types and search filters at once, as a saved view does
+    pub fn set_view_filters(
+        &mut self,
+        owner: OwnerFilter,
+        folder: Option<String>,
+        hidden_types: Vec<crate::types::ItemType>,
+        query: String,
+        type_filter: Option<crate::types::ItemType>,
+    ) {
+        self.owner_filter = owner;
+        self.folder_filter = folder;
+        self.hidden_types = hidden_types;
+        self.filter_query.set(query);
         self.apply_filter(type_filter);
     }
+
+    /// Cycle the owner filter through all items, personal items and each organization
+    pub fn cycle_owner_filter(&mut self, type_filter: Option<crate::types::ItemType>) {
+        self.owner_filter = match &self.owner_filter {
+            OwnerFilter::All => OwnerFilter::Personal,
+            OwnerFilter::Personal => self.organizations
+                .first()
+                .map_or(OwnerFilter::All, |organization| OwnerFilter::Organization(organization.id.clone())),
+            OwnerFilter::Organization(id) => self.organizations
+                .iter()
+                .skip_while(|organization| &organization.id != id)
+                .nth(1)
+                .map_or(OwnerFilter::All, |organization| OwnerFilter::Organization(organization.id.clone())),
+        };
+        self.apply_filter(type_filter);
+    }
+}
+
+/// Split the `tag:NAME` terms out of a query, returning the lowercase tag names and the rest of the text
+/// The rest is returned unchanged when there are no tag terms
+fn split_tag_terms(query: &str) -> (Vec<String>, std::borrow::Cow<'_, str>) {
+    let is_tag = |term: &str| term.get(..4).is_some_and(|prefix| prefix.eq_ignore_ascii_case("tag:"));
+    if !query.split_whitespace().any(is_tag) {
+        return (Vec::new(), query.into());
+    }
+    let tags = query.split_whitespace()
+        .filter(|term| is_tag(term))
+        .map(|term| term[4..].trim_start_matches('#').to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    let text: Vec<&str> = query.split_whitespace().filter(|term| !is_tag(term)).collect();
+    (tags, text.join(" ").into())
 }
 
 impl Default for VaultState {
@@ -237,3 +965,556 @@ impl Default for VaultState {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ItemType;
+
+    fn create_test_item(id: &str, name: &str, item_type: ItemType) -> VaultItem {
+        VaultItem {
+            id: id.to_string(),
+            name: name.to_string(),
+            item_type,
+            login: None,
+            card: None,
+            identity: None,
+            notes: None,
+            fields: None,
+            favorite: false,
+            folder_id: None,
+            organization_id: None,
+            revision_date: chrono::Utc::now(),
+            object: None,
+            creation_date: None,
+            deleted_date: None,
+            password_history: None,
+            attachments: None,
+            collection_ids: None,
+            reprompt: None,
+            raw_fields: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_sync_changes() {
+        let mut edited = create_test_item("2", "Edited", ItemType::Login);
+        let old = vec![
+            create_test_item("1", "Kept", ItemType::Login),
+            edited.clone(),
+            create_test_item("3", "Deleted", ItemType::Login),
+        ];
+        edited.revision_date += chrono::Duration::seconds(1);
+        let new = vec![
+            old[0].clone(),
+            edited,
+            create_test_item("4", "New", ItemType::Card),
+            create_test_item("5", "Other", ItemType::Card),
+        ];
+
+        let changes = SyncChanges::between(&old, &new);
+        assert_eq!(changes, SyncChanges { added: 2, updated: 1, removed: 1 });
+        assert_eq!(changes.summary().unwrap(), "1 item updated, 2 added, 1 removed");
+        assert_eq!(SyncChanges { added: 3, ..Default::default() }.summary().unwrap(), "3 items added");
+        assert_eq!(SyncChanges::between(&old, &old).summary(), None);
+    }
+
+    fn create_vault(count: usize) -> VaultState {
+        let mut vault = VaultState::new();
+        let items = (0..count)
+            .map(|i| create_test_item(&i.to_string(), &format!("Item {:04}", i), ItemType::Login))
+            .collect();
+        vault.load_items_with_secrets(items);
+        vault
+    }
+
+    #[test]
+    fn test_visible_window_follows_selection() {
+        let mut vault = create_vault(5000);
+        assert_eq!(vault.visible_window(10), 0..10);
+
+        // Moving past the bottom scrolls just enough to show the selection
+        vault.select_index(15);
+        assert_eq!(vault.visible_window(10), 6..16);
+
+        // Moving above the top scrolls back up
+        vault.select_index(3);
+        assert_eq!(vault.visible_window(10), 3..13);
+
+        // The window never runs past the end of the list
+        vault.jump_to_end();
+        assert_eq!(vault.visible_window(10), 4990..5000);
+    }
+
+    #[test]
+    fn test_visible_window_shrinks_with_list() {
+        let mut vault = create_vault(50);
+        vault.jump_to_end();
+        assert_eq!(vault.visible_window(10), 40..50);
+
+        // Filtering resets the selection and pulls the window back to the start
+        vault.filter_query.set("item 000".to_string());
+        vault.apply_filter(None);
+        assert_eq!(vault.visible_window(10), 0..10);
+
+        vault.filter_query.set("nothing matches".to_string());
+        vault.apply_filter(None);
+        assert_eq!(vault.visible_window(10), 0..0);
+    }
+
+    #[test]
+    fn test_scroll_list_keeps_selection() {
+        let mut vault = create_vault(50);
+        vault.select_index(2);
+        assert_eq!(vault.visible_window(10), 0..10);
+
+        // Scrolling moves the window away from the selection
+        for _ in 0..5 {
+            vault.scroll_list_down();
+        }
+        assert_eq!(vault.visible_window(10), 5..15);
+        assert_eq!(vault.selected_index, 2);
+
+        vault.scroll_list_up();
+        assert_eq!(vault.visible_window(10), 4..14);
+
+        // Moving the selection brings it back into view
+        vault.select_next();
+        assert_eq!(vault.visible_window(10), 3..13);
+    }
+
+    #[test]
+    fn test_resize_brings_selection_back() {
+        let mut vault = create_vault(50);
+        vault.select_index(30);
+        assert_eq!(vault.visible_window(10), 21..31);
+        for _ in 0..10 {
+            vault.scroll_list_up();
+        }
+        assert_eq!(vault.visible_window(10), 11..21);
+
+        // A shorter list after a resize shows the selection again
+        vault.follow_selection();
+        assert_eq!(vault.visible_window(5), 26..31);
+    }
+
+    #[test]
+    fn test_progressive_loading() {
+        let mut vault = VaultState::new();
+        vault.append_loading_items(vec![create_test_item("1", "Beta", ItemType::Login)], None);
+        vault.append_loading_items(vec![create_test_item("2", "Alpha", ItemType::Login)], None);
+
+        // Items are listed as they arrive, but the load isn't done yet
+        assert_eq!(vault.filtered_len(), 2);
+        assert_eq!(vault.selected_item().map(|item| item.name.as_str()), Some("Alpha"));
+        assert!(!vault.initial_load_complete);
+        assert!(!vault.secrets_available);
+
+        vault.finish_loading_items();
+        assert!(vault.initial_load_complete);
+        assert!(vault.secrets_available);
+
+        vault.discard_loading_items();
+        assert_eq!(vault.filtered_len(), 0);
+        assert!(vault.selected_item().is_none());
+    }
+
+    #[test]
+    fn test_typing_narrows_the_previous_results() {
+        let mut vault = VaultState::new();
+        let mut favorite = create_test_item("4", "Mail", ItemType::Login);
+        favorite.favorite = true;
+        vault.load_items_with_secrets(vec![
+            create_test_item("1", "Gmail", ItemType::Login),
+            create_test_item("2", "Bank", ItemType::Login),
+            create_test_item("3", "Mailbox", ItemType::SecureNote),
+            favorite,
+        ]);
+
+        // Each key narrows the results of the last one, which ranks them like a full search
+        let mut full = VaultState::new();
+        full.load_items_with_secrets(vault.vault_items.clone());
+        for c in "mai".chars() {
+            vault.append_filter(c, None);
+            full.set_filter_query(vault.filter_query.text().to_string(), None);
+            assert_eq!(vault.filtered_indices, full.filtered_indices);
+        }
+        assert_eq!(vault.filtered_len(), 3);
+
+        // Typing inside the query doesn't extend it, which can match items the last query didn't
+        vault.fuzzy_enabled = false;
+        vault.set_filter_query("bn".to_string(), None);
+        assert_eq!(vault.filtered_len(), 0);
+        vault.edit_filter(TextEdit::Left, None);
+        vault.append_filter('a', None);
+        assert_eq!(vault.filter_query.text(), "ban");
+        assert_eq!(vault.selected_item().map(|item| item.id.as_str()), Some("2"));
+    }
+
+    #[test]
+    fn test_apply_filter_by_type_sorts_favorites_first() {
+        let mut vault = VaultState::new();
+        let mut favorite = create_test_item("3", "Zebra", ItemType::Login);
+        favorite.favorite = true;
+        vault.load_items_with_secrets(vec![
+            create_test_item("1", "Bank", ItemType::Card),
+            create_test_item("2", "apple", ItemType::Login),
+            favorite,
+        ]);
+
+        vault.apply_filter(Some(ItemType::Login));
+        let names: Vec<&str> = vault.filtered_indices.iter().map(|&idx| vault.vault_items[idx].name.as_str()).collect();
+        assert_eq!(names, vec!["Zebra", "apple"]);
+    }
+
+    #[test]
+    fn test_cycle_owner_filter() {
+        let mut vault = VaultState::new();
+        let mut work = create_test_item("2", "Work", ItemType::Login);
+        work.organization_id = Some("org-1".to_string());
+        let mut shared = create_test_item("3", "Shared", ItemType::Login);
+        shared.organization_id = Some("org-2".to_string());
+        vault.load_items_with_secrets(vec![create_test_item("1", "Home", ItemType::Login), work, shared]);
+        vault.set_organizations(vec![
+            Organization { id: "org-1".to_string(), name: "Acme".to_string() },
+            Organization { id: "org-2".to_string(), name: "Family".to_string() },
+        ], None);
+
+        let mut shown = Vec::new();
+        for _ in 0..4 {
+            vault.cycle_owner_filter(None);
+            let names: Vec<&str> = vault.filtered_indices.iter().map(|&idx| vault.vault_items[idx].name.as_str()).collect();
+            shown.push((vault.owner_filter_label().to_string(), names.join(",")));
+        }
+        assert_eq!(shown, vec![
+            ("Personal".to_string(), "Home".to_string()),
+            ("Acme".to_string(), "Work".to_string()),
+            ("Family".to_string(), "Shared".to_string()),
+            ("All".to_string(), "Home,Shared,Work".to_string()),
+        ]);
+
+        // Filtering by an organization that disappeared falls back to all items
+        vault.cycle_owner_filter(None);
+        vault.cycle_owner_filter(None);
+        vault.set_organizations(Vec::new(), None);
+        assert_eq!(vault.owner_filter, OwnerFilter::All);
+        assert_eq!(vault.filtered_len(), 3);
+    }
+
+    #[test]
+    fn test_folder_changes() {
+        let mut work = create_test_item("1", "Work login", ItemType::Login);
+        work.folder_id = Some("f-1".to_string());
+        let mut vault = VaultState::new();
+        vault.load_items_with_secrets(vec![work, create_test_item("2", "Bank", ItemType::Login)]);
+        vault.set_folders(vec![Folder { id: "f-1".to_string(), name: "Work".to_string() }], None);
+        vault.add_folder(Folder { id: "f-2".to_string(), name: "archive".to_string() });
+
+        let counts: Vec<(&str, usize)> = vault.folder_counts()
+            .into_iter()
+            .map(|(folder, count)| (folder.name.as_str(), count))
+            .collect();
+        assert_eq!(counts, vec![("archive", 0), ("Work", 1)]);
+
+        // The folder filter follows a renamed folder
+        vault.set_folder_filter(Some("work".to_string()), None);
+        vault.rename_folder("f-1", "Job");
+        assert_eq!(vault.folder_filter(), Some("Job"));
+        assert_eq!(vault.filtered_len(), 1);
+
+        // Deleting a folder moves its items out of it and drops the filter on it
+        vault.remove_folder("f-1", None);
+        assert_eq!(vault.folder_filter(), None);
+        assert_eq!(vault.vault_items[0].folder_id, None);
+        assert_eq!(vault.filtered_len(), 2);
+        assert_eq!(vault.folders().len(), 1);
+    }
+
+    #[test]
+    fn test_share_item() {
+        let mut vault = VaultState::new();
+        vault.load_items_with_secrets(vec![create_test_item("1", "Mail", ItemType::Login), create_test_item("2", "Bank", ItemType::Login)]);
+        vault.set_organizations(vec![
+            Organization { id: "org-1".to_string(), name: "Zeta".to_string() },
+            Organization { id: "org-2".to_string(), name: "Acme".to_string() },
+        ], None);
+        let collection = |id: &str, organization_id: &str, name: &str| Collection {
+            id: id.to_string(),
+            organization_id: organization_id.to_string(),
+            name: name.to_string(),
+        };
+        vault.set_collections(vec![collection("c-1", "org-1", "Ops"), collection("c-2", "org-2", "Sales"), collection("c-3", "org-1", "Dev")]);
+        let names: Vec<&str> = vault.collections_by_organization().iter().map(|collection| collection.name.as_str()).collect();
+        assert_eq!(names, vec!["Sales", "Dev", "Ops"]);
+
+        // A shared item leaves the personal items
+        vault.cycle_owner_filter(None);
+        assert_eq!(vault.filtered_len(), 2);
+        vault.share_item("1", "org-1", vec!["c-1".to_string()], None);
+        assert_eq!(vault.filtered_len(), 1);
+        let mail = &vault.vault_items[0];
+        assert_eq!(mail.organization_id.as_deref(), Some("org-1"));
+        assert_eq!(vault.item_collections(mail).len(), 1);
+    }
+
+    #[test]
+    fn test_equivalent_domains_match() {
+        let mut vault = VaultState::new();
+        let mut shop = create_test_item("1", "Shop", ItemType::Login);
+        shop.login = Some(crate::types::LoginData {
+            username: None,
+            password: None,
+            totp: None,
+            uris: Some(vec![crate::types::Uri { uri: "https://www.amzn.com/login".to_string(), match_type: None }]),
+            password_revision_date: None,
+        });
+        vault.load_items_with_secrets(vec![shop, create_test_item("2", "Amazon notes", ItemType::SecureNote)]);
+        vault.set_filter_query("amazon".to_string(), None);
+        assert_eq!(vault.filtered_len(), 1);
+
+        // The login of the equivalent domain comes after the direct match
+        let domains = EquivalentDomains::new(vec![vec!["amazon.com".to_string(), "amzn.com".to_string()]]);
+        vault.set_equivalent_domains(domains, None);
+        assert_eq!(vault.filtered_len(), 2);
+        assert_eq!(vault.filtered_item(1).unwrap().name, "Shop");
+
+        vault.toggle_fuzzy(None);
+        assert_eq!(vault.filtered_len(), 2);
+    }
+
+    #[test]
+    fn test_folder_filter() {
+        let mut vault = VaultState::new();
+        let mut work = create_test_item("2", "Work", ItemType::Login);
+        work.folder_id = Some("f-1".to_string());
+        vault.load_items_with_secrets(vec![create_test_item("1", "Home", ItemType::Login), work]);
+
+        // Folder names may arrive after the filter is set
+        vault.set_folder_filter(Some("work".to_string()), None);
+        assert_eq!(vault.filtered_len(), 0);
+        vault.set_folders(vec![Folder { id: "f-1".to_string(), name: "Work".to_string() }], None);
+        assert_eq!(vault.selected_item().map(|item| item.name.as_str()), Some("Work"));
+
+        // Clearing the search also clears the folder filter
+        vault.clear_filter(None);
+        assert_eq!(vault.folder_filter(), None);
+        assert_eq!(vault.filtered_len(), 2);
+    }
+
+    #[test]
+    fn test_search_texts_follow_the_items() {
+        let mut vault = VaultState::new();
+        vault.append_loading_items(vec![create_test_item("1", "Home Mail", ItemType::Login)], None);
+        vault.append_loading_items(vec![create_test_item("2", "Work Mail", ItemType::Login)], None);
+        assert_eq!(vault.search_texts, ["home mail", "work mail"]);
+
+        vault.remove_item("1", None);
+        vault.set_filter_query("Mail".to_string(), None);
+        assert_eq!(vault.search_texts, ["work mail"]);
+        assert_eq!(vault.filtered_len(), 1);
+        vault.toggle_case_sensitive(None);
+        assert_eq!(vault.search_texts, ["Work Mail"]);
+        assert_eq!(vault.filtered_len(), 1);
+
+        vault.load_items_with_secrets(vec![create_test_item("3", "Bank", ItemType::Login)]);
+        assert_eq!(vault.search_texts, ["Bank"]);
+    }
+
+    #[test]
+    fn test_case_sensitive_fuzzy_matching() {
+        let mut vault = VaultState::new();
+        vault.load_items_with_secrets(vec![create_test_item("1", "GitHub", ItemType::Login)]);
+        vault.set_filter_query("github".to_string(), None);
+        assert_eq!(vault.filtered_len(), 1);
+
+        vault.toggle_case_sensitive(None);
+        assert!(vault.fuzzy_enabled());
+        assert_eq!(vault.filtered_len(), 0);
+        assert_eq!(vault.match_positions(&vault.vault_items[0]), MatchPositions::default());
+
+        vault.set_filter_query("GitHub".to_string(), None);
+        assert_eq!(vault.filtered_len(), 1);
+    }
+
+    #[test]
+    fn test_local_notes_are_searchable() {
+        let mut vault = VaultState::new();
+        vault.load_items_with_secrets(vec![
+            create_test_item("1", "Home", ItemType::Login),
+            create_test_item("2", "Work", ItemType::Login),
+        ]);
+        vault.set_local_note("2", "  Use the VPN first ", None);
+        assert_eq!(vault.local_note("2"), Some("Use the VPN first"));
+
+        vault.set_filter_query("vpn".to_string(), None);
+        assert_eq!(vault.selected_item().map(|item| item.name.as_str()), Some("Work"));
+        // The note isn't highlighted as part of the name
+        assert_eq!(vault.match_positions(vault.selected_item().unwrap()), MatchPositions::default());
+
+        // A blank note removes it
+        vault.set_local_note("2", " ", None);
+        assert_eq!(vault.local_note("2"), None);
+        assert_eq!(vault.filtered_len(), 0);
+    }
+
+    #[test]
+    fn test_tag_terms_filter_items() {
+        let mut vault = VaultState::new();
+        vault.load_items_with_secrets(vec![
+            create_test_item("1", "Home mail", ItemType::Login),
+            create_test_item("2", "Work mail", ItemType::Login),
+            create_test_item("3", "Work VPN", ItemType::Login),
+        ]);
+        vault.set_item_tags("2", "work, mail", None);
+        vault.set_item_tags("3", "#Work", None);
+        assert_eq!(vault.tag_counts(), [("mail", 1), ("work", 2)]);
+
+        vault.set_filter_query("TAG:work".to_string(), None);
+        assert_eq!(vault.filtered_len(), 2);
+        // Tag terms combine with each other and with the text, which alone is highlighted
+        vault.set_filter_query("tag:work vpn".to_string(), None);
+        assert_eq!(vault.selected_item().map(|item| item.id.as_str()), Some("3"));
+        assert_eq!(vault.match_positions(vault.selected_item().unwrap()).name, [5, 6, 7]);
+        vault.set_filter_query("tag:work tag:mail".to_string(), None);
+        assert_eq!(vault.filtered_len(), 1);
+        vault.set_filter_query("tag:home".to_string(), None);
+        assert_eq!(vault.filtered_len(), 0);
+
+        // Removing an item's last tag forgets it
+        vault.set_item_tags("3", " , ", None);
+        assert!(vault.item_tags("3").is_none());
+        assert_eq!(split_tag_terms("tag: mail"), (Vec::new(), "mail".into()));
+    }
+
+    #[test]
+    fn test_view_filters() {
+        let mut vault = VaultState::new();
+        vault.load_items_with_secrets(vec![
+            create_test_item("1", "Work mail", ItemType::Login),
+            create_test_item("2", "Work card", ItemType::Card),
+            create_test_item("3", "Home mail", ItemType::Login),
+        ]);
+        vault.set_view_filters(OwnerFilter::All, None, vec![ItemType::Card], "work".to_string(), None);
+        assert_eq!(vault.filtered_len(), 1);
+        assert_eq!(vault.selected_item().map(|item| item.id.as_str()), Some("1"));
+
+        vault.set_view_filters(OwnerFilter::All, None, Vec::new(), String::new(), None);
+        assert_eq!(vault.filtered_len(), 3);
+    }
+
+    #[test]
+    fn test_hidden_items() {
+        let mut vault = VaultState::new();
+        vault.load_items_with_secrets(vec![
+            create_test_item("1", "Mail", ItemType::Login),
+            create_test_item("2", "Imported 1", ItemType::Login),
+            create_test_item("3", "Imported 2", ItemType::Login),
+        ]);
+        assert!(vault.toggle_hidden("2", None));
+        assert!(vault.toggle_hidden("3", None));
+        assert_eq!(vault.filtered_len(), 1);
+        assert_eq!(vault.hidden_count(), 2);
+
+        // Hidden items aren't found by searching either
+        vault.set_filter_query("imported".to_string(), None);
+        assert_eq!(vault.filtered_len(), 0);
+        vault.toggle_show_hidden(None);
+        assert_eq!(vault.filtered_len(), 2);
+
+        vault.toggle_show_hidden(None);
+        assert!(!vault.toggle_hidden("3", None));
+        assert_eq!(vault.filtered_len(), 1);
+
+        // Revealing a hidden item shows the hidden items
+        assert!(vault.reveal_item("2"));
+        assert!(vault.show_hidden());
+    }
+
+    #[test]
+    fn test_reveal_item_clears_filters() {
+        let mut vault = create_vault(20);
+        vault.vault_items[15].organization_id = Some("org-1".to_string());
+        vault.cycle_owner_filter(None); // Personal items only
+        vault.filter_query.set("item 0001".to_string());
+        vault.apply_filter(None);
+
+        assert!(vault.reveal_item("15"));
+        assert_eq!(vault.owner_filter, OwnerFilter::All);
+        assert!(vault.filter_query.is_empty());
+        assert_eq!(vault.selected_item().map(|item| item.id.as_str()), Some("15"));
+
+        assert!(!vault.reveal_item("missing"));
+
+        vault.remove_item("15", None);
+        assert_eq!(vault.filtered_len(), 19);
+        assert!(!vault.reveal_item("15"));
+    }
+
+    #[test]
+    fn test_match_positions_split_name_and_username() {
+        let mut vault = VaultState::new();
+        let mut item = create_test_item("1", "GitHub", ItemType::Login);
+        item.login = Some(crate::types::LoginData {
+            username: Some("octocat".to_string()),
+            password: None,
+            totp: None,
+            uris: None,
+            password_revision_date: None,
+        });
+        vault.load_items_with_secrets(vec![item.clone()]);
+
+        // No query, nothing to highlight
+        assert_eq!(vault.match_positions(&item), MatchPositions::default());
+
+        vault.filter_query.set("hub".to_string());
+        vault.fuzzy_enabled = false;
+        let matches = vault.match_positions(&item);
+        assert_eq!(matches.name, vec![3, 4, 5]);
+        assert!(matches.username.is_empty());
+
+        vault.filter_query.set("cat".to_string());
+        let matches = vault.match_positions(&item);
+        assert!(matches.name.is_empty());
+        assert_eq!(matches.username, vec![4, 5, 6]);
+
+        // Fuzzy matches can span both
+        vault.fuzzy_enabled = true;
+        vault.filter_query.set("gocat".to_string());
+        let matches = vault.match_positions(&item);
+        assert_eq!(matches.name, vec![0]);
+        assert_eq!(matches.username, vec![0, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_filter_with_non_ascii_text() {
+        let mut vault = VaultState::new();
+        let items = vec![
+            create_test_item("1", "İstanbul Café", ItemType::Login),
+            create_test_item("2", "Straße", ItemType::Login),
+            create_test_item("3", "東京銀行", ItemType::Login),
+        ];
+        vault.load_items_with_secrets(items.clone());
+        vault.fuzzy_enabled = false;
+
+        for c in "CAFÉ".chars() {
+            vault.append_filter(c, None);
+        }
+        assert_eq!(vault.filtered_len(), 1);
+        // 'İ' lowercases to two chars, highlights must still line up with the name
+        assert_eq!(vault.match_positions(&items[0]).name, vec![9, 10, 11, 12]);
+
+        vault.clear_filter(None);
+        for c in "ße".chars() {
+            vault.append_filter(c, None);
+        }
+        assert_eq!(vault.match_positions(&items[1]).name, vec![4, 5]);
+
+        vault.clear_filter(None);
+        for c in "銀行".chars() {
+            vault.append_filter(c, None);
+        }
+        assert_eq!(vault.filtered_len(), 1);
+        vault.delete_filter_char(None);
+        assert_eq!(vault.filter_query.text(), "銀");
+    }
+}
@@ -1,11 +1,20 @@
 use crate::error::Result;
 use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture},
+    event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use std::io::Stdout;
 
+/// Restores the terminal when dropped, including while unwinding from a panic
+pub struct TerminalGuard;
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        ensure_cleanup();
+    }
+}
+
 /// Setup the terminal for TUI mode
 pub fn setup() -> Result<Stdout> {
     enable_raw_mode().map_err(|e| {
@@ -14,7 +23,7 @@ pub fn setup() -> Result<Stdout> {
         e
     })?;
     let mut stdout = std::io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture).map_err(|e| {
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste).map_err(|e| {
         let error_msg = format!("Failed to setup terminal: {}", e);
         crate::logger::Logger::error(&error_msg);
         e
@@ -30,7 +39,7 @@ pub fn cleanup() -> Result<()> {
         crate::logger::Logger::error(&error_msg);
         e
     })?;
-    execute!(std::io::stdout(), LeaveAlternateScreen, DisableMouseCapture).map_err(|e| {
+    execute!(std::io::stdout(), LeaveAlternateScreen, DisableMouseCapture, DisableBracketedPaste).map_err(|e| {
         let error_msg = format!("Failed to cleanup terminal: {}", e);
         crate::logger::Logger::error(&error_msg);
         e
@@ -44,8 +53,44 @@ pub fn ensure_cleanup() {
     if let Err(e) = disable_raw_mode() {
         crate::logger::Logger::warn(&format!("Failed to disable raw mode during cleanup: {}", e));
     }
-    if let Err(e) = execute!(std::io::stdout(), LeaveAlternateScreen, DisableMouseCapture) {
+    if let Err(e) = execute!(std::io::stdout(), LeaveAlternateScreen, DisableMouseCapture, DisableBracketedPaste) {
         crate::logger::Logger::warn(&format!("Failed to cleanup terminal: {}", e));
     }
 }
 
+
+/// Restore the terminal and stop the process, returning once it is continued (e.g. with `fg`)
+#[cfg(unix)]
+pub fn suspend() -> Result<()> {
+    cleanup()?;
+    crate::logger::Logger::info("Suspending");
+    // Stops with the default SIGTSTP action even though the signal is also watched
+    signal_hook::low_level::emulate_default_handler(signal_hook::consts::SIGTSTP)?;
+    crate::logger::Logger::info("Resumed");
+    Ok(())
+}
+
+/// Take the terminal back after the process was stopped, the shell may have reset its modes
+pub fn resume() -> Result<()> {
+    setup()?;
+    Ok(())
+}
+
+/// Restore the terminal before the panic message is printed, so it is readable and the shell usable
+/// The process exits afterwards: a panic in a background task would otherwise leave the UI running on a restored terminal
+pub fn install_panic_hook(clear_clipboard: bool) {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        ensure_cleanup();
+        if clear_clipboard {
+            match crate::clipboard::clear_last_copied() {
+                Ok(true) => eprintln!("bwtui crashed, the clipboard was cleared"),
+                Ok(false) => {}
+                Err(e) => eprintln!("bwtui crashed and failed to clear the clipboard: {}", e),
+            }
+        }
+        crate::logger::Logger::error(&format!("Panic: {}", info));
+        default_hook(info);
+        std::process::exit(101);
+    }));
+}
@@ -0,0 +1,76 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Number of user-perceived characters, e.g. for masking typed input
+pub fn grapheme_count(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+/// Lowercase character by character, keeping character indices aligned with the original text
+/// (`str::to_lowercase` turns some characters like 'İ' into several)
+pub fn fold_case(text: &str) -> String {
+    text.chars().map(|c| c.to_lowercase().next().unwrap_or(c)).collect()
+}
+
+/// The end of the text that fits in `width` terminal cells
+pub fn tail_to_width(text: &str, width: usize) -> &str {
+    let mut used = 0;
+    let mut start = text.len();
+    for (index, grapheme) in text.grapheme_indices(true).rev() {
+        used += grapheme.width();
+        if used > width {
+            break;
+        }
+        start = index;
+    }
+    &text[start..]
+}
+
+/// The start of the text that fits in `width` terminal cells
+pub fn head_to_width(text: &str, width: usize) -> &str {
+    let mut used = 0;
+    let mut end = 0;
+    for (index, grapheme) in text.grapheme_indices(true) {
+        used += grapheme.width();
+        if used > width {
+            break;
+        }
+        end = index + grapheme.len();
+    }
+    &text[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grapheme_count() {
+        assert_eq!(grapheme_count("straße"), 6);
+        assert_eq!(grapheme_count("cafe\u{301}"), 4);
+        assert_eq!(grapheme_count("パスワード"), 5);
+    }
+
+    #[test]
+    fn test_fold_case_keeps_char_indices() {
+        assert_eq!(fold_case("Straße"), "straße");
+        assert_eq!(fold_case("ÉCOLE"), "école");
+        assert_eq!(fold_case("İstanbul").chars().count(), "İstanbul".chars().count());
+    }
+
+    #[test]
+    fn test_tail_to_width() {
+        assert_eq!(tail_to_width("hello", 10), "hello");
+        assert_eq!(tail_to_width("hello", 3), "llo");
+        // CJK characters take two cells each
+        assert_eq!(tail_to_width("東京都", 4), "京都");
+        assert_eq!(tail_to_width("東京都", 5), "京都");
+    }
+
+    #[test]
+    fn test_head_to_width() {
+        assert_eq!(head_to_width("hello", 10), "hello");
+        assert_eq!(head_to_width("hello", 3), "hel");
+        assert_eq!(head_to_width("東京都", 3), "東");
+    }
+}
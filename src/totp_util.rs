@@ -0,0 +1,364 @@
+use crate::error::{BwError, Result};
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use sha2::{Sha256, Sha512};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Default number of seconds a TOTP code stays valid
+pub const DEFAULT_PERIOD: u64 = 30;
+
+/// Default number of digits in a generated TOTP code
+const DEFAULT_DIGITS: u32 = 6;
+
+/// Maximum number of digits supported by the official clients
+const MAX_DIGITS: u32 = 10;
+
+/// Number of characters in a Steam Guard code
+const STEAM_DIGITS: u32 = 5;
+
+/// Alphabet used by Steam Guard codes
+const STEAM_ALPHABET: &[u8] = b"23456789BCDFGHJKMNPQRTVWXY";
+
+/// HMAC algorithm used to derive the code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TotpAlgorithm {
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+/// Parsed TOTP configuration from a raw secret, otpauth:// URI or steam:// secret
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TotpParams {
+    key: Vec<u8>,
+    pub digits: u32,
+    pub period: u64,
+    pub algorithm: TotpAlgorithm,
+    pub steam: bool,
+}
+
+/// A generated TOTP code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TotpCode {
+    pub code: String,
+    /// Unix timestamp at which the code expires
+    pub expires_at: u64,
+    /// Validity period of the code in seconds
+    pub period: u64,
+}
+
+impl TotpParams {
+    /// Parse a TOTP secret as stored in a Bitwarden login
+    pub fn parse(secret: &str) -> Result<Self> {
+        let secret = secret.trim();
+        let lowercase = secret.to_lowercase();
+
+        let params = if lowercase.starts_with("otpauth://") {
+            Self::parse_otpauth(secret)?
+        } else if lowercase.starts_with("steam://") {
+            Self {
+                key: decode_base32(&secret["steam://".len()..])?,
+                digits: STEAM_DIGITS,
+                period: DEFAULT_PERIOD,
+                algorithm: TotpAlgorithm::Sha1,
+                steam: true,
+            }
+        } else {
+            Self {
+                key: decode_base32(secret)?,
+                digits: DEFAULT_DIGITS,
+                period: DEFAULT_PERIOD,
+                algorithm: TotpAlgorithm::Sha1,
+                steam: false,
+            }
+        };
+
+        if params.key.is_empty() {
+            return Err(BwError::ParseError("TOTP secret is empty".to_string()));
+        }
+
+        Ok(params)
+    }
+
+    /// Parse an otpauth://totp/... URI, honouring digits, period and algorithm
+    fn parse_otpauth(uri: &str) -> Result<Self> {
+        let mut params = Self {
+            key: Vec::new(),
+            digits: DEFAULT_DIGITS,
+            period: DEFAULT_PERIOD,
+            algorithm: TotpAlgorithm::Sha1,
+            steam: false,
+        };
+
+        let query = uri.split_once('?').map(|(_, query)| query).unwrap_or("");
+        for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+            match key.to_lowercase().as_str() {
+                "secret" => params.key = decode_base32(value)?,
+                "digits" => {
+                    params.digits = value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|digits| (1..=MAX_DIGITS).contains(digits))
+                        .ok_or_else(|| BwError::ParseError(format!("Invalid TOTP digits: {}", value)))?;
+                }
+                "period" => {
+                    params.period = value
+                        .parse::<u64>()
+                        .ok()
+                        .filter(|period| *period > 0)
+                        .ok_or_else(|| BwError::ParseError(format!("Invalid TOTP period: {}", value)))?;
+                }
+                "algorithm" => {
+                    params.algorithm = match value.to_uppercase().as_str() {
+                        "SHA1" => TotpAlgorithm::Sha1,
+                        "SHA256" => TotpAlgorithm::Sha256,
+                        "SHA512" => TotpAlgorithm::Sha512,
+                        _ => {
+                            return Err(BwError::ParseError(format!(
+                                "Unsupported TOTP algorithm: {}",
+                                value
+                            )))
+                        }
+                    };
+                }
+                // Issuer and other parameters don't affect the code
+                _ => {}
+            }
+        }
+
+        // Steam secrets may also be expressed as otpauth URIs with an encoder hint
+        if uri.to_lowercase().contains("encoder=steam") {
+            params.steam = true;
+            params.digits = STEAM_DIGITS;
+        }
+
+        Ok(params)
+    }
+
+    /// Generate the code for a given Unix timestamp
+    pub fn generate_at(&self, timestamp: u64) -> Result<TotpCode> {
+        let counter = timestamp / self.period;
+        let binary = hotp(&self.key, counter, self.algorithm)?;
+
+        let code = if self.steam {
+            let mut value = binary;
+            (0..self.digits)
+                .map(|_| {
+                    let c = STEAM_ALPHABET[(value % STEAM_ALPHABET.len() as u32) as usize] as char;
+                    value /= STEAM_ALPHABET.len() as u32;
+                    c
+                })
+                .collect()
+        } else {
+            let value = binary as u64 % 10u64.pow(self.digits);
+            format!("{:0width$}", value, width = self.digits as usize)
+        };
+
+        Ok(TotpCode {
+            code,
+            expires_at: (counter + 1) * self.period,
+            period: self.period,
+        })
+    }
+}
+
+/// Generate the current TOTP code for a secret
+pub fn generate_totp(secret: &str) -> Result<TotpCode> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    generate_totp_at(secret, now)
+}
+
+/// Generate the TOTP code for a secret at a given Unix timestamp
+pub fn generate_totp_at(secret: &str, timestamp: u64) -> Result<TotpCode> {
+    TotpParams::parse(secret)?.generate_at(timestamp)
+}
+
+/// An otpauth:// URI for enrolling the secret in an authenticator app
+/// Secrets already stored as otpauth URIs are returned unchanged
+pub fn otpauth_uri(secret: &str, issuer: &str, account: &str) -> Result<String> {
+    let secret = secret.trim();
+    let params = TotpParams::parse(secret)?;
+    if secret.to_lowercase().starts_with("otpauth://") {
+        return Ok(secret.to_string());
+    }
+
+    let key: String = secret
+        .get(if params.steam { "steam://".len() } else { 0 }..)
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '='))
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    let mut uri = format!(
+        "otpauth://totp/{}:{}?secret={}&issuer={}",
+        percent_encode(issuer),
+        percent_encode(account),
+        key,
+        percent_encode(issuer)
+    );
+    if params.steam {
+        uri.push_str("&encoder=steam");
+    }
+    Ok(uri)
+}
+
+/// Percent-encode everything but unreserved URI characters
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Compute the truncated HOTP value (RFC 4226) for the given key and counter
+fn hotp(key: &[u8], counter: u64, algorithm: TotpAlgorithm) -> Result<u32> {
+    let message = counter.to_be_bytes();
+    let hash = match algorithm {
+        TotpAlgorithm::Sha1 => hmac_digest::<Hmac<Sha1>>(key, &message)?,
+        TotpAlgorithm::Sha256 => hmac_digest::<Hmac<Sha256>>(key, &message)?,
+        TotpAlgorithm::Sha512 => hmac_digest::<Hmac<Sha512>>(key, &message)?,
+    };
+
+    // Dynamic truncation
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    Ok(((hash[offset] as u32 & 0x7f) << 24)
+        | ((hash[offset + 1] as u32) << 16)
+        | ((hash[offset + 2] as u32) << 8)
+        | (hash[offset + 3] as u32))
+}
+
+/// Compute an HMAC digest with the given MAC implementation
+fn hmac_digest<M: Mac + hmac::digest::KeyInit>(key: &[u8], message: &[u8]) -> Result<Vec<u8>> {
+    let mut mac = <M as Mac>::new_from_slice(key)
+        .map_err(|e| BwError::ParseError(format!("Invalid TOTP key: {}", e)))?;
+    mac.update(message);
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+/// Decode an RFC 4648 base32 string, ignoring case, spaces and padding
+fn decode_base32(input: &str) -> Result<Vec<u8>> {
+    let mut output = Vec::with_capacity(input.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for c in input.chars() {
+        let value = match c.to_ascii_uppercase() {
+            c @ 'A'..='Z' => c as u32 - 'A' as u32,
+            c @ '2'..='7' => c as u32 - '2' as u32 + 26,
+            ' ' | '-' | '=' => continue,
+            _ => {
+                return Err(BwError::ParseError(format!(
+                    "Invalid character in TOTP secret: {:?}",
+                    c
+                )))
+            }
+        };
+
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Base32 encodings of the RFC 6238 test secrets
+    const RFC_SECRET_SHA1: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+    const RFC_SECRET_SHA256: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZA";
+    const RFC_SECRET_SHA512: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNA";
+
+    #[test]
+    fn test_rfc6238_vectors() {
+        assert_eq!(generate_totp_at(RFC_SECRET_SHA1, 59).unwrap().code, "287082");
+        assert_eq!(generate_totp_at(RFC_SECRET_SHA1, 1111111109).unwrap().code, "081804");
+        assert_eq!(generate_totp_at(RFC_SECRET_SHA1, 1234567890).unwrap().code, "005924");
+    }
+
+    #[test]
+    fn test_expiry_is_next_period_boundary() {
+        let totp = generate_totp_at(RFC_SECRET_SHA1, 59).unwrap();
+        assert_eq!(totp.expires_at, 60);
+        assert_eq!(totp.period, 30);
+
+        let totp = generate_totp_at(RFC_SECRET_SHA1, 60).unwrap();
+        assert_eq!(totp.expires_at, 90);
+    }
+
+    #[test]
+    fn test_secret_formatting_is_ignored() {
+        let spaced = "gezd gnbv gy3t qojq gezd gnbv gy3t qojq";
+        assert_eq!(generate_totp_at(spaced, 59).unwrap().code, "287082");
+    }
+
+    #[test]
+    fn test_otpauth_uri_secret() {
+        let uri = format!("otpauth://totp/Example:alice?secret={}&issuer=Example", RFC_SECRET_SHA1);
+        assert_eq!(generate_totp_at(&uri, 59).unwrap().code, "287082");
+    }
+
+    #[test]
+    fn test_otpauth_digits_and_algorithms() {
+        let uri = format!("otpauth://totp/Example?secret={}&digits=8&algorithm=SHA256", RFC_SECRET_SHA256);
+        assert_eq!(generate_totp_at(&uri, 59).unwrap().code, "46119246");
+
+        let uri = format!("otpauth://totp/Example?secret={}&digits=8&algorithm=sha512", RFC_SECRET_SHA512);
+        assert_eq!(generate_totp_at(&uri, 59).unwrap().code, "90693936");
+    }
+
+    #[test]
+    fn test_otpauth_uri() {
+        assert_eq!(
+            otpauth_uri("jbsw y3dp ehpk 3pxp", "My Bank", "alice@example.com").unwrap(),
+            "otpauth://totp/My%20Bank:alice%40example.com?secret=JBSWY3DPEHPK3PXP&issuer=My%20Bank"
+        );
+        let uri = format!("otpauth://totp/Example:alice?secret={}&issuer=Example", RFC_SECRET_SHA1);
+        assert_eq!(otpauth_uri(&uri, "Ignored", "ignored").unwrap(), uri);
+        assert!(otpauth_uri("steam://JBSWY3DPEHPK3PXP", "Steam", "alice").unwrap().ends_with("secret=JBSWY3DPEHPK3PXP&issuer=Steam&encoder=steam"));
+        assert!(otpauth_uri("not base32!", "Example", "alice").is_err());
+    }
+
+    #[test]
+    fn test_otpauth_custom_period() {
+        let uri = format!("otpauth://totp/Example?secret={}&digits=8&period=60", RFC_SECRET_SHA1);
+        let totp = generate_totp_at(&uri, 1111111109).unwrap();
+        assert_eq!(totp.code, "19360094");
+        assert_eq!(totp.period, 60);
+        assert_eq!(totp.expires_at, 1111111140);
+    }
+
+    #[test]
+    fn test_steam_secret() {
+        let secret = format!("steam://{}", RFC_SECRET_SHA1);
+        let totp = generate_totp_at(&secret, 1111111109).unwrap();
+        assert_eq!(totp.code, "PY4YB");
+
+        let uri = format!("otpauth://totp/Steam:alice?secret={}&encoder=steam", RFC_SECRET_SHA1);
+        assert_eq!(generate_totp_at(&uri, 1111111109).unwrap().code, "PY4YB");
+    }
+
+    #[test]
+    fn test_invalid_secret() {
+        assert!(generate_totp_at("not a secret!", 59).is_err());
+        assert!(generate_totp_at("", 59).is_err());
+        assert!(generate_totp_at("otpauth://totp/Example", 59).is_err());
+        assert!(generate_totp_at("steam://", 59).is_err());
+
+        let uri = format!("otpauth://totp/Example?secret={}&algorithm=MD5", RFC_SECRET_SHA1);
+        assert!(generate_totp_at(&uri, 59).is_err());
+        let uri = format!("otpauth://totp/Example?secret={}&period=0", RFC_SECRET_SHA1);
+        assert!(generate_totp_at(&uri, 59).is_err());
+    }
+}
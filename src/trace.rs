@@ -0,0 +1,16 @@
+use crate::error::{BwError, Result};
+use tracing_chrome::{ChromeLayerBuilder, FlushGuard};
+use tracing_subscriber::prelude::*;
+
+/// Record spans to a Chrome trace file (chrome://tracing, Perfetto or a flamegraph converter)
+/// The file is complete once the returned guard is dropped
+pub fn start(path: &str) -> Result<FlushGuard> {
+    let file = std::fs::File::create(path).map_err(|e| {
+        BwError::CommandFailed(format!("Failed to create trace file {}: {}", path, e))
+    })?;
+    let (layer, guard) = ChromeLayerBuilder::new().writer(file).include_args(true).build();
+    tracing::subscriber::set_global_default(tracing_subscriber::registry().with(layer))
+        .map_err(|e| BwError::CommandFailed(format!("Failed to start tracing: {}", e)))?;
+    crate::logger::Logger::info(&format!("Writing performance trace to {}", path));
+    Ok(guard)
+}
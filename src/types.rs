@@ -45,6 +45,9 @@ pub struct VaultItem {
     #[serde(default, skip_serializing)]
     #[allow(dead_code)]
     pub reprompt: Option<u8>,
+    /// Fields not parsed above, shown for item types this version doesn't know
+    #[serde(flatten, skip_serializing)]
+    pub raw_fields: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -53,6 +56,8 @@ pub enum ItemType {
     SecureNote,
     Card,
     Identity,
+    /// A type this version doesn't know about yet, keeping the raw type number
+    Unknown(u8),
 }
 
 impl From<u8> for ItemType {
@@ -62,7 +67,7 @@ impl From<u8> for ItemType {
             2 => ItemType::SecureNote,
             3 => ItemType::Card,
             4 => ItemType::Identity,
-            _ => ItemType::Login, // Default to Login for unknown types
+            other => ItemType::Unknown(other),
         }
     }
 }
@@ -77,6 +82,7 @@ impl serde::Serialize for ItemType {
             ItemType::SecureNote => 2u8,
             ItemType::Card => 3u8,
             ItemType::Identity => 4u8,
+            ItemType::Unknown(value) => *value,
         };
         serializer.serialize_u8(value)
     }
@@ -109,8 +115,88 @@ pub struct LoginData {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Uri {
     pub uri: String,
-    #[serde(rename = "match")]
-    pub match_type: Option<serde_json::Value>,
+    /// None uses the account's default match detection
+    #[serde(rename = "match", default)]
+    pub match_type: Option<UriMatchType>,
+}
+
+impl Uri {
+    /// Whether the URI identifies a site that can be shown or opened
+    /// "Never" URIs are excluded from matching and regular expressions aren't addresses
+    pub fn is_addressable(&self) -> bool {
+        !matches!(self.match_type, Some(UriMatchType::Never | UriMatchType::RegularExpression))
+            && !self.uri.trim().is_empty()
+    }
+}
+
+/// How a login URI is matched against websites
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UriMatchType {
+    Domain,
+    Host,
+    StartsWith,
+    Exact,
+    RegularExpression,
+    Never,
+    /// A match type this version doesn't know about yet, keeping the raw number
+    Unknown(u8),
+}
+
+impl UriMatchType {
+    /// Short label for display
+    pub fn label(&self) -> &'static str {
+        match self {
+            UriMatchType::Domain => "base domain",
+            UriMatchType::Host => "host",
+            UriMatchType::StartsWith => "starts with",
+            UriMatchType::Exact => "exact",
+            UriMatchType::RegularExpression => "regex",
+            UriMatchType::Never => "never",
+            UriMatchType::Unknown(_) => "unknown",
+        }
+    }
+}
+
+impl From<u8> for UriMatchType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => UriMatchType::Domain,
+            1 => UriMatchType::Host,
+            2 => UriMatchType::StartsWith,
+            3 => UriMatchType::Exact,
+            4 => UriMatchType::RegularExpression,
+            5 => UriMatchType::Never,
+            other => UriMatchType::Unknown(other),
+        }
+    }
+}
+
+impl serde::Serialize for UriMatchType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let value = match self {
+            UriMatchType::Domain => 0u8,
+            UriMatchType::Host => 1u8,
+            UriMatchType::StartsWith => 2u8,
+            UriMatchType::Exact => 3u8,
+            UriMatchType::RegularExpression => 4u8,
+            UriMatchType::Never => 5u8,
+            UriMatchType::Unknown(value) => *value,
+        };
+        serializer.serialize_u8(value)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for UriMatchType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = u8::deserialize(deserializer)?;
+        Ok(UriMatchType::from(value))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -161,7 +247,299 @@ pub struct CustomField {
     pub name: Option<String>,
     pub value: Option<String>,
     #[serde(rename = "type")]
-    pub field_type: Option<u8>,
+    pub field_type: Option<CustomFieldType>,
+    /// For linked fields, the item field the value comes from
+    #[serde(default)]
+    pub linked_id: Option<u16>,
+}
+
+/// Kind of a custom field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CustomFieldType {
+    Text,
+    Hidden,
+    Boolean,
+    Linked,
+    /// A field type this version doesn't know about yet, keeping the raw number
+    Unknown(u8),
+}
+
+impl From<u8> for CustomFieldType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => CustomFieldType::Text,
+            1 => CustomFieldType::Hidden,
+            2 => CustomFieldType::Boolean,
+            3 => CustomFieldType::Linked,
+            other => CustomFieldType::Unknown(other),
+        }
+    }
+}
+
+impl serde::Serialize for CustomFieldType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let value = match self {
+            CustomFieldType::Text => 0u8,
+            CustomFieldType::Hidden => 1u8,
+            CustomFieldType::Boolean => 2u8,
+            CustomFieldType::Linked => 3u8,
+            CustomFieldType::Unknown(value) => *value,
+        };
+        serializer.serialize_u8(value)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for CustomFieldType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = u8::deserialize(deserializer)?;
+        Ok(CustomFieldType::from(value))
+    }
+}
+
+/// The value of a custom field, resolved for display and copying
+#[derive(Debug, Clone, PartialEq)]
+pub enum CustomFieldValue {
+    Text(String),
+    Hidden(String),
+    Boolean(bool),
+    /// Value of another field of the item, with that field's label
+    Linked { target: &'static str, value: String, secret: bool },
+}
+
+impl CustomFieldValue {
+    /// The value as copied to the clipboard
+    pub fn text(&self) -> String {
+        match self {
+            CustomFieldValue::Text(value) | CustomFieldValue::Hidden(value) => value.clone(),
+            CustomFieldValue::Boolean(value) => value.to_string(),
+            CustomFieldValue::Linked { value, .. } => value.clone(),
+        }
+    }
+
+    /// Whether the value is masked until revealed
+    pub fn is_secret(&self) -> bool {
+        match self {
+            CustomFieldValue::Hidden(_) => true,
+            CustomFieldValue::Linked { secret, .. } => *secret,
+            CustomFieldValue::Text(_) | CustomFieldValue::Boolean(_) => false,
+        }
+    }
+}
+
+/// An organization the user is a member of, as listed by `bw list organizations`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Organization {
+    pub id: String,
+    pub name: String,
+}
+
+/// A folder items can be filed in, as listed by `bw list folders`
+/// Nested folders are named by their path, e.g. "Work/Infra"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Folder {
+    pub id: String,
+    pub name: String,
+}
+
+/// A collection organization items are shared in, as listed by `bw list collections`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Collection {
+    pub id: String,
+    pub organization_id: String,
+    pub name: String,
+}
+
+/// A member of an organization, as listed by `bw list org-members`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct OrgMember {
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub email: String,
+}
+
+impl OrgMember {
+    /// Name and email, or just the email if the member has no name
+    pub fn label(&self) -> String {
+        match self.name.as_deref().filter(|name| !name.is_empty()) {
+            Some(name) => format!("{} <{}>", name, self.email),
+            None => self.email.clone(),
+        }
+    }
+}
+
+/// Member or group given access to a collection
+#[derive(Debug, Deserialize)]
+pub struct AccessGrant {
+    pub id: String,
+}
+
+/// Who a collection is shared with, as given by `bw get org-collection`
+#[derive(Debug, Default, Deserialize)]
+pub struct CollectionDetails {
+    #[serde(default)]
+    pub users: Vec<AccessGrant>,
+    #[serde(default)]
+    pub groups: Vec<AccessGrant>,
+}
+
+/// Who can access a collection: members given access directly, and how many groups are
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CollectionAccess {
+    pub members: Vec<OrgMember>,
+    pub groups: usize,
+}
+
+impl CollectionAccess {
+    /// Look up the members a collection is shared with, sorted by label
+    pub fn new(details: CollectionDetails, members: Vec<OrgMember>) -> Self {
+        let mut members: Vec<OrgMember> = members
+            .into_iter()
+            .filter(|member| details.users.iter().any(|grant| grant.id == member.id))
+            .collect();
+        members.sort_by_cached_key(|member| member.label().to_lowercase());
+        Self { members, groups: details.groups.len() }
+    }
+}
+
+/// Equivalent domain settings of the account, as given by the server's /settings/domains
+/// Only the api backend can fetch them, bw has no command to list them
+#[cfg_attr(not(feature = "api-backend"), allow(dead_code))]
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DomainSettings {
+    #[serde(default)]
+    pub equivalent_domains: Option<Vec<Vec<String>>>, // Groups the user added
+    #[serde(default)]
+    pub global_equivalent_domains: Option<Vec<GlobalDomains>>,
+}
+
+/// A group of equivalent domains known to the server, which the user may have excluded
+#[cfg_attr(not(feature = "api-backend"), allow(dead_code))]
+#[derive(Debug, Deserialize)]
+pub struct GlobalDomains {
+    #[serde(default)]
+    pub domains: Vec<String>,
+    #[serde(default)]
+    pub excluded: bool,
+}
+
+/// Groups of domains that belong to the same site, like amazon.com and amzn.com
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EquivalentDomains {
+    groups: Vec<Vec<String>>,
+}
+
+impl EquivalentDomains {
+    /// Domains are kept in lowercase, groups of a single domain are dropped
+    pub fn new(groups: Vec<Vec<String>>) -> Self {
+        let groups = groups
+            .into_iter()
+            .map(|group| group.iter().map(|domain| domain.trim().to_lowercase()).filter(|domain| !domain.is_empty()).collect::<Vec<_>>())
+            .filter(|group| group.len() > 1)
+            .collect();
+        Self { groups }
+    }
+
+    /// Bitwarden's global groups, for backends that can't read the account's settings
+    pub fn global() -> Self {
+        Self::new(
+            crate::global_domains::GROUPS
+                .iter()
+                .map(|group| group.iter().map(|domain| domain.to_string()).collect())
+                .collect(),
+        )
+    }
+
+    /// The user's own groups and the global ones they didn't exclude
+    #[cfg_attr(not(feature = "api-backend"), allow(dead_code))]
+    pub fn from_settings(settings: DomainSettings) -> Self {
+        let mut groups = settings.equivalent_domains.unwrap_or_default();
+        groups.extend(
+            settings.global_equivalent_domains
+                .into_iter()
+                .flatten()
+                .filter(|global| !global.excluded)
+                .map(|global| global.domains),
+        );
+        Self::new(groups)
+    }
+
+    /// Domains equivalent to a host name, which matches a domain or any of its subdomains
+    /// The domain the host belongs to is left out, for search and matching items by URI
+    pub fn equivalents(&self, host: &str) -> Vec<&str> {
+        let host = host.split(':').next().unwrap_or(host).to_lowercase();
+        let belongs_to = |domain: &str| host == domain || host.ends_with(&format!(".{}", domain));
+        let mut equivalents: Vec<&str> = self.groups
+            .iter()
+            .filter(|group| group.iter().any(|domain| belongs_to(domain)))
+            .flatten()
+            .map(String::as_str)
+            .filter(|domain| !belongs_to(domain))
+            .collect();
+        equivalents.sort_unstable();
+        equivalents.dedup();
+        equivalents
+    }
+}
+
+impl CardData {
+    /// Get the expiry date formatted as MM/YY
+    pub fn expiry(&self) -> Option<String> {
+        let month: u32 = self.exp_month.as_deref()?.trim().parse().ok()?;
+        let year = self.exp_year.as_deref()?.trim();
+        if !(1..=12).contains(&month) || year.len() < 2 || !year.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        Some(format!("{:02}/{}", month, &year[year.len() - 2..]))
+    }
+
+    /// Get a single line with the number, expiry and CVV for quick form filling
+    pub fn autofill_line(&self) -> Option<String> {
+        let mut parts = vec![self.number.as_deref()?.trim().to_string()];
+        parts.extend(self.expiry());
+        parts.extend(self.code.as_deref().map(|code| code.trim().to_string()));
+        Some(parts.join(" "))
+    }
+}
+
+impl IdentityData {
+    /// Get the full name (title, first, middle and last name)
+    pub fn full_name(&self) -> Option<String> {
+        join_present(&[&self.title, &self.first_name, &self.middle_name, &self.last_name], " ")
+    }
+
+    /// Get the postal address formatted over multiple lines
+    pub fn formatted_address(&self) -> Option<String> {
+        let region = join_present(&[&self.state, &self.postal_code], " ");
+        let locality = join_present(&[&self.city, &region], ", ");
+        join_present(
+            &[&self.address1, &self.address2, &self.address3, &locality, &self.country],
+            "\n",
+        )
+    }
+}
+
+/// Join the non-empty values with a separator, returning None if all are empty
+fn join_present(values: &[&Option<String>], separator: &str) -> Option<String> {
+    let parts: Vec<&str> = values
+        .iter()
+        .filter_map(|value| value.as_deref())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .collect();
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(separator))
+    }
 }
 
 impl VaultItem {
@@ -170,12 +548,17 @@ impl VaultItem {
         self.login.as_ref().and_then(|l| l.username.as_deref())
     }
 
+    /// The URI that best identifies the login's site: the first one that is not a
+    /// "never" or regular expression match, falling back to the first URI
+    pub fn primary_uri(&self) -> Option<&Uri> {
+        let uris = self.login.as_ref()?.uris.as_ref()?;
+        uris.iter().find(|uri| uri.is_addressable()).or_else(|| uris.first())
+    }
+
     /// Get the domain from URIs
     pub fn domain(&self) -> Option<String> {
-        self.login
-            .as_ref()
-            .and_then(|l| l.uris.as_ref())
-            .and_then(|uris| uris.first())
+        self.primary_uri()
+            .filter(|uri| uri.match_type != Some(UriMatchType::RegularExpression))
             .map(|uri| {
                 // Extract domain from URI
                 uri.uri
@@ -188,29 +571,161 @@ impl VaultItem {
             })
     }
 
+    /// Resolve a custom field's value: booleans are parsed and linked fields read the field they point to
+    /// Returns None when there is nothing to show
+    pub fn custom_field_value(&self, field: &CustomField) -> Option<CustomFieldValue> {
+        match field.field_type {
+            Some(CustomFieldType::Linked) => {
+                let (target, value, secret) = self.linked_field(field.linked_id?)?;
+                Some(CustomFieldValue::Linked { target, value: value?, secret })
+            }
+            Some(CustomFieldType::Hidden) => Some(CustomFieldValue::Hidden(field.value.clone()?)),
+            Some(CustomFieldType::Boolean) => match field.value.as_deref()?.trim().to_lowercase().as_str() {
+                "true" => Some(CustomFieldValue::Boolean(true)),
+                "false" | "" => Some(CustomFieldValue::Boolean(false)),
+                _ => Some(CustomFieldValue::Text(field.value.clone()?)),
+            },
+            _ => Some(CustomFieldValue::Text(field.value.clone()?)),
+        }
+    }
+
+    /// Label, value and whether it is secret of the item field a linked custom field points to
+    fn linked_field(&self, linked_id: u16) -> Option<(&'static str, Option<String>, bool)> {
+        match linked_id {
+            100..=199 => {
+                let login = self.login.as_ref()?;
+                match linked_id {
+                    100 => Some(("Username", login.username.clone(), false)),
+                    101 => Some(("Password", login.password.clone(), true)),
+                    _ => None,
+                }
+            }
+            300..=399 => {
+                let card = self.card.as_ref()?;
+                match linked_id {
+                    300 => Some(("Cardholder name", card.card_holder_name.clone(), false)),
+                    301 => Some(("Expiration month", card.exp_month.clone(), false)),
+                    302 => Some(("Expiration year", card.exp_year.clone(), false)),
+                    303 => Some(("Security code", card.code.clone(), true)),
+                    304 => Some(("Brand", card.brand.clone(), false)),
+                    305 => Some(("Number", card.number.clone(), true)),
+                    _ => None,
+                }
+            }
+            400..=499 => {
+                let identity = self.identity.as_ref()?;
+                match linked_id {
+                    400 => Some(("Title", identity.title.clone(), false)),
+                    401 => Some(("Middle name", identity.middle_name.clone(), false)),
+                    402 => Some(("Address 1", identity.address1.clone(), false)),
+                    403 => Some(("Address 2", identity.address2.clone(), false)),
+                    404 => Some(("Address 3", identity.address3.clone(), false)),
+                    405 => Some(("City", identity.city.clone(), false)),
+                    406 => Some(("State", identity.state.clone(), false)),
+                    407 => Some(("Postal code", identity.postal_code.clone(), false)),
+                    408 => Some(("Country", identity.country.clone(), false)),
+                    410 => Some(("Email", identity.email.clone(), false)),
+                    411 => Some(("Phone", identity.phone.clone(), false)),
+                    412 => Some(("SSN", identity.ssn.clone(), true)),
+                    413 => Some(("Username", identity.username.clone(), false)),
+                    414 => Some(("Passport number", identity.passport_number.clone(), true)),
+                    415 => Some(("License number", identity.license_number.clone(), true)),
+                    416 => Some(("First name", identity.first_name.clone(), false)),
+                    417 => Some(("Last name", identity.last_name.clone(), false)),
+                    418 => Some(("Full name", identity.full_name(), false)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
     /// Get the card brand for display
     pub fn card_brand(&self) -> Option<&str> {
         self.card.as_ref().and_then(|c| c.brand.as_deref())
     }
 
+    /// Whether the master password must be re-entered before using this item's secrets
+    pub fn requires_reprompt(&self) -> bool {
+        self.reprompt == Some(1)
+    }
+
     /// Get the identity email for display
     pub fn identity_email(&self) -> Option<&str> {
         self.identity.as_ref().and_then(|i| i.email.as_deref())
     }
 
+    /// When the login password was last changed
+    /// The CLI only reports a password revision date after the first change, so fall back to the item's
+    pub fn password_changed_at(&self) -> Option<DateTime<Utc>> {
+        let login = self.login.as_ref()?;
+        Some(login.password_revision_date.unwrap_or(self.revision_date))
+    }
+
+    /// Whether the login has a password older than the given number of days (0 = never stale)
+    pub fn is_password_stale(&self, now: DateTime<Utc>, max_age_days: u32) -> bool {
+        if max_age_days == 0 || self.login.as_ref().and_then(|l| l.password.as_ref()).is_none() {
+            return false;
+        }
+        self.password_changed_at()
+            .is_some_and(|changed_at| now - changed_at > chrono::Duration::days(i64::from(max_age_days)))
+    }
+
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_collection_access_lists_granted_members() {
+        let details: CollectionDetails = serde_json::from_str(
+            r#"{"id":"c-1","name":"Ops","users":[{"id":"m-2","readOnly":false},{"id":"m-3"}],"groups":[{"id":"g-1"}]}"#,
+        ).unwrap();
+        let members: Vec<OrgMember> = serde_json::from_str(
+            r#"[{"object":"org-member","id":"m-1","name":"Carol","email":"carol@example.com"},
+                {"object":"org-member","id":"m-2","name":"Bob","email":"bob@example.com"},
+                {"object":"org-member","id":"m-3","name":null,"email":"alice@example.com"}]"#,
+        ).unwrap();
+
+        let access = CollectionAccess::new(details, members);
+        let labels: Vec<String> = access.members.iter().map(OrgMember::label).collect();
+        assert_eq!(labels, vec!["alice@example.com", "Bob <bob@example.com>"]);
+        assert_eq!(access.groups, 1);
+
+        // Older CLI versions only list the groups
+        let details: CollectionDetails = serde_json::from_str(r#"{"id":"c-1","groups":[]}"#).unwrap();
+        assert_eq!(CollectionAccess::new(details, Vec::new()), CollectionAccess::default());
+    }
+
+    #[test]
+    fn test_equivalent_domains() {
+        let settings: DomainSettings = serde_json::from_str(
+            r#"{"equivalentDomains":[["intranet.example","Example.org"]],
+                "globalEquivalentDomains":[{"type":2,"domains":["amazon.com","amzn.com"],"excluded":false},
+                                           {"type":3,"domains":["apple.com","icloud.com"],"excluded":true}],
+                "object":"domains"}"#,
+        ).unwrap();
+        let domains = EquivalentDomains::from_settings(settings);
+
+        assert_eq!(domains.equivalents("www.amazon.com:443"), vec!["amzn.com"]);
+        assert_eq!(domains.equivalents("example.org"), vec!["intranet.example"]);
+        assert!(domains.equivalents("icloud.com").is_empty());
+        assert!(domains.equivalents("notamazon.com").is_empty());
+
+        // The built-in copy of the global groups
+        let global = EquivalentDomains::global();
+        assert!(global.equivalents("www.amazon.com").contains(&"amzn.com"));
+        assert!(global.equivalents("github.com").is_empty());
+    }
+
     #[test]
     fn test_item_type_conversion() {
         assert_eq!(ItemType::from(1), ItemType::Login);
         assert_eq!(ItemType::from(2), ItemType::SecureNote);
         assert_eq!(ItemType::from(3), ItemType::Card);
         assert_eq!(ItemType::from(4), ItemType::Identity);
-        assert_eq!(ItemType::from(99), ItemType::Login); // Default for unknown types
+        assert_eq!(ItemType::from(99), ItemType::Unknown(99));
     }
 
     #[test]
@@ -233,6 +748,27 @@ mod tests {
         assert_eq!(card, ItemType::Card);
     }
 
+    #[test]
+    fn test_unknown_item_type_keeps_raw_fields() {
+        let item: VaultItem = serde_json::from_str(
+            r#"{"id": "1", "name": "Server", "type": 5, "favorite": false, "revisionDate": "2024-01-01T00:00:00Z",
+                "login": null, "notes": null, "sshKey": {"publicKey": "ssh-ed25519 AAAA"}}"#,
+        ).unwrap();
+
+        assert_eq!(item.item_type, ItemType::Unknown(5));
+        assert_eq!(serde_json::to_string(&item.item_type).unwrap(), "5");
+        assert_eq!(item.raw_fields["sshKey"]["publicKey"], "ssh-ed25519 AAAA");
+        // Parsed fields are not duplicated
+        assert!(!item.raw_fields.contains_key("name"));
+    }
+
+    #[test]
+    fn test_organization_deserialization() {
+        let json = r#"[{"object":"organization","id":"org-1","name":"Acme","status":2,"type":2,"enabled":true}]"#;
+        let organizations: Vec<Organization> = serde_json::from_str(json).unwrap();
+        assert_eq!(organizations, vec![Organization { id: "org-1".to_string(), name: "Acme".to_string() }]);
+    }
+
     #[test]
     fn test_username_extraction() {
         let item = VaultItem {
@@ -261,11 +797,36 @@ mod tests {
             attachments: None,
             collection_ids: None,
             reprompt: None,
+            raw_fields: Default::default(),
         };
         
         assert_eq!(item.username(), Some("user@example.com"));
     }
 
+    #[test]
+    fn test_password_staleness() {
+        let now = chrono::Utc::now();
+        let mut item: VaultItem = serde_json::from_str(
+            r#"{"id": "1", "name": "Test", "type": 1, "favorite": false, "revisionDate": "2024-01-01T00:00:00Z",
+                "login": {"username": "user", "password": "secret", "totp": null, "uris": null}}"#,
+        ).unwrap();
+
+        // Without a password revision date the item's revision date is used
+        assert_eq!(item.password_changed_at(), Some(item.revision_date));
+        item.revision_date = now - chrono::Duration::days(400);
+        assert!(item.is_password_stale(now, 365));
+        assert!(!item.is_password_stale(now, 0));
+
+        let login = item.login.as_mut().unwrap();
+        login.password_revision_date = Some(now - chrono::Duration::days(30));
+        assert!(!item.is_password_stale(now, 365));
+        assert!(item.is_password_stale(now, 7));
+
+        // Items without a password are never stale
+        item.login.as_mut().unwrap().password = None;
+        assert!(!item.is_password_stale(now, 7));
+    }
+
     #[test]
     fn test_username_none_when_no_login() {
         let item = VaultItem {
@@ -288,6 +849,7 @@ mod tests {
             attachments: None,
             collection_ids: None,
             reprompt: None,
+            raw_fields: Default::default(),
         };
         
         assert_eq!(item.username(), None);
@@ -324,6 +886,7 @@ mod tests {
             attachments: None,
             collection_ids: None,
             reprompt: None,
+            raw_fields: Default::default(),
         };
         
         assert_eq!(item.domain(), Some("example.com".to_string()));
@@ -360,6 +923,7 @@ mod tests {
             attachments: None,
             collection_ids: None,
             reprompt: None,
+            raw_fields: Default::default(),
         };
         
         assert_eq!(item.domain(), Some("example.org".to_string()));
@@ -396,11 +960,72 @@ mod tests {
             attachments: None,
             collection_ids: None,
             reprompt: None,
+            raw_fields: Default::default(),
         };
         
         assert_eq!(item.domain(), Some("example.com".to_string()));
     }
 
+    #[test]
+    fn test_domain_respects_match_type() {
+        let uri = |uri: &str, match_type: Option<UriMatchType>| Uri { uri: uri.to_string(), match_type };
+        let mut item: VaultItem = serde_json::from_value(serde_json::json!({
+            "id": "1", "name": "Test", "type": 1, "favorite": false,
+            "revisionDate": "2024-01-01T00:00:00Z",
+            "login": { "uris": [
+                { "uri": "https://old.example.com", "match": 5 },
+                { "uri": "^https://.*\\.example\\.com", "match": 4 },
+                { "uri": "https://login.example.com/sso", "match": 3 },
+                { "uri": "https://example.com", "match": null },
+            ] },
+        })).unwrap();
+        assert_eq!(item.primary_uri().map(|uri| uri.uri.as_str()), Some("https://login.example.com/sso"));
+        assert_eq!(item.domain(), Some("login.example.com".to_string()));
+
+        // Without an addressable URI a "never" URI still names the site, a regular expression doesn't
+        item.login.as_mut().unwrap().uris = Some(vec![uri("https://old.example.com", Some(UriMatchType::Never))]);
+        assert_eq!(item.domain(), Some("old.example.com".to_string()));
+        item.login.as_mut().unwrap().uris = Some(vec![uri("^https://.*", Some(UriMatchType::RegularExpression))]);
+        assert_eq!(item.domain(), None);
+    }
+
+    #[test]
+    fn test_custom_field_values() {
+        let item: VaultItem = serde_json::from_value(serde_json::json!({
+            "id": "1", "name": "Test", "type": 1, "favorite": false,
+            "revisionDate": "2024-01-01T00:00:00Z",
+            "login": { "username": "alice", "password": "hunter2" },
+            "fields": [
+                { "name": "note", "value": "plain", "type": 0 },
+                { "name": "pin", "value": "1234", "type": 1 },
+                { "name": "admin", "value": "True", "type": 2 },
+                { "name": "user", "value": null, "type": 3, "linkedId": 100 },
+                { "name": "pass", "value": null, "type": 3, "linkedId": 101 },
+                { "name": "cvv", "value": null, "type": 3, "linkedId": 303 },
+            ],
+        })).unwrap();
+        let values: Vec<_> = item.fields.as_ref().unwrap().iter().map(|field| item.custom_field_value(field)).collect();
+
+        assert_eq!(values[0], Some(CustomFieldValue::Text("plain".to_string())));
+        assert_eq!(values[1], Some(CustomFieldValue::Hidden("1234".to_string())));
+        assert_eq!(values[2], Some(CustomFieldValue::Boolean(true)));
+        assert_eq!(values[3], Some(CustomFieldValue::Linked { target: "Username", value: "alice".to_string(), secret: false }));
+        assert!(values[4].as_ref().is_some_and(|value| value.is_secret() && value.text() == "hunter2"));
+        // Linked to a card field on a login
+        assert_eq!(values[5], None);
+    }
+
+    #[test]
+    fn test_uri_match_type_serialization() {
+        let uri: Uri = serde_json::from_str(r#"{"uri": "https://example.com", "match": 1}"#).unwrap();
+        assert_eq!(uri.match_type, Some(UriMatchType::Host));
+        let uri: Uri = serde_json::from_str(r#"{"uri": "https://example.com", "match": 9}"#).unwrap();
+        assert_eq!(uri.match_type, Some(UriMatchType::Unknown(9)));
+        assert_eq!(serde_json::to_value(&uri).unwrap()["match"], 9);
+        let uri: Uri = serde_json::from_str(r#"{"uri": "https://example.com"}"#).unwrap();
+        assert_eq!(uri.match_type, None);
+    }
+
     #[test]
     fn test_card_brand_extraction() {
         let item = VaultItem {
@@ -430,6 +1055,7 @@ mod tests {
             attachments: None,
             collection_ids: None,
             reprompt: None,
+            raw_fields: Default::default(),
         };
         
         assert_eq!(item.card_brand(), Some("Visa"));
@@ -475,9 +1101,91 @@ mod tests {
             attachments: None,
             collection_ids: None,
             reprompt: None,
+            raw_fields: Default::default(),
         };
         
         assert_eq!(item.identity_email(), Some("person@example.com"));
     }
-}
 
+    fn create_identity() -> IdentityData {
+        IdentityData {
+            title: Some("Dr".to_string()),
+            first_name: Some("Jane".to_string()),
+            middle_name: None,
+            last_name: Some("Doe".to_string()),
+            address1: Some("1 Main St".to_string()),
+            address2: Some("".to_string()),
+            address3: None,
+            city: Some("Springfield".to_string()),
+            state: Some("IL".to_string()),
+            postal_code: Some("62701".to_string()),
+            country: Some("US".to_string()),
+            phone: None,
+            email: None,
+            ssn: None,
+            license_number: None,
+            passport_number: None,
+            username: None,
+        }
+    }
+
+    #[test]
+    fn test_identity_full_name() {
+        let mut identity = create_identity();
+        assert_eq!(identity.full_name(), Some("Dr Jane Doe".to_string()));
+
+        identity.title = None;
+        identity.first_name = None;
+        identity.last_name = None;
+        assert_eq!(identity.full_name(), None);
+    }
+
+    #[test]
+    fn test_identity_formatted_address() {
+        let mut identity = create_identity();
+        assert_eq!(
+            identity.formatted_address(),
+            Some("1 Main St\nSpringfield, IL 62701\nUS".to_string())
+        );
+
+        identity.city = None;
+        identity.state = None;
+        assert_eq!(identity.formatted_address(), Some("1 Main St\n62701\nUS".to_string()));
+    }
+
+    fn create_card() -> CardData {
+        CardData {
+            brand: Some("Visa".to_string()),
+            card_holder_name: None,
+            number: Some("4111111111111111".to_string()),
+            exp_month: Some("3".to_string()),
+            exp_year: Some("2027".to_string()),
+            code: Some("123".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_card_expiry() {
+        let mut card = create_card();
+        assert_eq!(card.expiry(), Some("03/27".to_string()));
+
+        card.exp_year = Some("27".to_string());
+        assert_eq!(card.expiry(), Some("03/27".to_string()));
+
+        card.exp_month = Some("13".to_string());
+        assert_eq!(card.expiry(), None);
+    }
+
+    #[test]
+    fn test_card_autofill_line() {
+        let mut card = create_card();
+        assert_eq!(card.autofill_line(), Some("4111111111111111 03/27 123".to_string()));
+
+        card.exp_month = None;
+        card.code = None;
+        assert_eq!(card.autofill_line(), Some("4111111111111111".to_string()));
+
+        card.number = None;
+        assert_eq!(card.autofill_line(), None);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::state::AppState;
+use crate::ui::layout::centered_rect;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+pub fn render(frame: &mut Frame, state: &AppState) {
+    let area = centered_rect(60, 30, frame.area());
+
+    // Clear the entire dialog area first
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(" Quit? ")
+        .style(Style::default().bg(Color::Black));
+
+    frame.render_widget(block.clone(), area);
+
+    let inner = block.inner(area);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),     // Message
+            Constraint::Length(2),  // Options
+        ])
+        .split(inner);
+
+    // The operation may have finished while the question was open
+    let message = match state.operation_in_progress() {
+        Some(operation) => format!("{} is still in progress.\n\nQuit anyway? It will be stopped.", operation),
+        None => "The operation has finished.\n\nQuit now?".to_string(),
+    };
+    let message = Paragraph::new(message)
+        .style(Style::default().fg(Color::White).bg(Color::Black))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(message, chunks[0]);
+
+    let options = Paragraph::new("Press Y to quit, N to keep working")
+        .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD).bg(Color::Black))
+        .alignment(Alignment::Center);
+    frame.render_widget(options, chunks[1]);
+}
@@ -0,0 +1,71 @@
+use crate::state::AppState;
+use crate::ui::symbols;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Width of the format picker, enough for the labels and the help text
+const DIALOG_WIDTH: u16 = 44;
+
+pub fn render(frame: &mut Frame, state: &AppState) {
+    let formats = state.copy_formats();
+    let selected = state.ui.copy_as_selected.unwrap_or(0);
+    let symbols = symbols::symbols(state);
+
+    let screen = frame.area();
+    let width = DIALOG_WIDTH.min(screen.width);
+    let height = (formats.len() as u16 + 4).min(screen.height); // Borders, a blank line and the help text
+    let area = Rect::new(
+        screen.x + (screen.width - width) / 2,
+        screen.y + (screen.height - height) / 2,
+        width,
+        height,
+    );
+
+    // Clear the entire dialog area first
+    frame.render_widget(Clear, area);
+
+    let title = state.selected_item().map_or("Copy as".to_string(), |item| format!("Copy {} as", item.name));
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(" {} ", title))
+        .style(Style::default().bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),     // Formats
+            Constraint::Length(1),  // Help text
+        ])
+        .split(inner);
+
+    let lines: Vec<Line> = formats
+        .iter()
+        .enumerate()
+        .map(|(index, format)| {
+            let is_selected = index == selected;
+            let style = if is_selected {
+                Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            Line::from(vec![
+                Span::styled(if is_selected { symbols.selected } else { "  " }, style),
+                Span::styled(format!("{}. {}", index + 1, format.label()), style),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).style(Style::default().bg(Color::Black)), chunks[0]);
+
+    let help = Paragraph::new("Enter or 1-9 to copy, Esc to close")
+        .style(Style::default().fg(Color::DarkGray).bg(Color::Black))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[1]);
+}
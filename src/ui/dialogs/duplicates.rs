@@ -0,0 +1,120 @@
+use crate::state::AppState;
+use crate::ui::layout::centered_rect;
+use crate::ui::symbols;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+pub fn render(frame: &mut Frame, state: &AppState) {
+    let area = centered_rect(80, 70, frame.area());
+
+    // Clear the entire dialog area first
+    frame.render_widget(Clear, area);
+
+    let duplicates = &state.duplicates;
+    let symbols = symbols::symbols(state);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(" Possible Duplicates ({} groups) ", duplicates.groups.len()))
+        .style(Style::default().bg(Color::Black));
+
+    frame.render_widget(block.clone(), area);
+
+    // Split into content area
+    let inner = block.inner(area);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),     // Groups
+            Constraint::Length(1),  // Help text
+        ])
+        .split(inner);
+
+    // A header line per group, followed by its items
+    let mut lines: Vec<Line> = Vec::new();
+    let mut selected_line = 0;
+    let mut item_position = 0;
+    for group in &duplicates.groups {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}: ", group.kind.label()), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(group.key.as_str(), Style::default().fg(Color::White)),
+            Span::styled(format!(" ({})", group.item_ids.len()), Style::default().fg(Color::DarkGray)),
+        ]));
+
+        for item_id in &group.item_ids {
+            let is_selected = item_position == duplicates.selected;
+            if is_selected {
+                selected_line = lines.len();
+            }
+            item_position += 1;
+
+            let style = if is_selected {
+                Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let muted = if is_selected { style } else { Style::default().fg(Color::DarkGray) };
+
+            let item = state.vault.vault_items.iter().find(|item| &item.id == item_id);
+            let mut spans = vec![
+                Span::styled(if is_selected { format!("  {}", symbols.selected) } else { "    ".to_string() }, style),
+                Span::styled(item.map_or(item_id.as_str(), |item| item.name.as_str()), style),
+            ];
+            if let Some(username) = item.and_then(|item| item.username()) {
+                spans.push(Span::styled(format!(" ({})", username), muted));
+            }
+            if let Some(folder) = item.and_then(|item| state.vault.folder_name(item)) {
+                spans.push(Span::styled(format!("  {} {}", symbols::folder_icon(symbols::icon_set(state)), folder), muted));
+            }
+            if let Some(revision_date) = item.map(|item| item.revision_date) {
+                spans.push(Span::styled(format!("  edited {}", revision_date.format("%Y-%m-%d")), muted));
+            }
+            lines.push(Line::from(spans));
+        }
+    }
+
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled("No duplicates found", Style::default().fg(Color::DarkGray))));
+    }
+
+    // Keep the selected item in view
+    let height = chunks[0].height as usize;
+    let scroll = (selected_line + 1).saturating_sub(height);
+    let groups = Paragraph::new(lines)
+        .style(Style::default().bg(Color::Black))
+        .scroll((scroll as u16, 0));
+    frame.render_widget(groups, chunks[0]);
+
+    // Help text, or the pending delete confirmation
+    let (help, style) = if duplicates.confirm_delete {
+        let name = duplicates
+            .selected_item_id()
+            .and_then(|id| state.vault.vault_items.iter().find(|item| item.id == id))
+            .map_or("this item", |item| item.name.as_str());
+        (format!("Move '{}' to trash? (y/n)", name), Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+    } else if duplicates.deleting {
+        ("Moving item to trash...".to_string(), Style::default().fg(Color::Yellow))
+    } else if state.ui.readonly {
+        (
+            format!("{} to select, Enter to show item, Esc or Alt+D to close", symbols.up_down),
+            Style::default().fg(Color::DarkGray),
+        )
+    } else {
+        (
+            format!("{} to select, Enter to show item, Del to move to trash, Esc or Alt+D to close", symbols.up_down),
+            Style::default().fg(Color::DarkGray),
+        )
+    };
+    let help = Paragraph::new(help)
+        .style(style.bg(Color::Black))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[1]);
+}
@@ -0,0 +1,140 @@
+use crate::state::{AppState, FolderEdit};
+use crate::ui::layout::centered_rect;
+use crate::ui::symbols;
+use crate::ui::widgets::text_input;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+pub fn render(frame: &mut Frame, state: &AppState) {
+    let area = centered_rect(60, 60, frame.area());
+
+    // Clear the entire dialog area first
+    frame.render_widget(Clear, area);
+
+    let folders = &state.folders;
+    let counts = state.vault.folder_counts();
+    let symbols = symbols::symbols(state);
+    let moving = folders.moving.as_ref().map(|item_id| {
+        state.vault.vault_items
+            .iter()
+            .find(|item| &item.id == item_id)
+            .map_or("item", |item| item.name.as_str())
+    });
+    let title = match moving {
+        Some(name) => format!(" Move '{}' to Folder ", name),
+        None => format!(" Folders ({}) ", counts.len()),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(title)
+        .style(Style::default().bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),     // Folders
+            Constraint::Length(if folders.edit.is_some() { 3 } else { 0 }), // Name input
+            Constraint::Length(2),  // Help text, or the pending delete confirmation
+        ])
+        .split(inner);
+
+    // The move picker lists "No folder" first
+    let mut rows: Vec<(&str, Option<usize>)> = Vec::new();
+    if moving.is_some() {
+        rows.push(("No folder", None));
+    }
+    rows.extend(counts.iter().map(|(folder, count)| (folder.name.as_str(), Some(*count))));
+
+    let lines: Vec<Line> = if rows.is_empty() {
+        vec![Line::styled("No folders yet, press n to create one", Style::default().fg(Color::DarkGray))]
+    } else {
+        rows
+            .iter()
+            .enumerate()
+            .map(|(index, (name, count))| {
+                let is_selected = index == folders.selected;
+                let style = if is_selected {
+                    Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                let muted = if is_selected { style } else { Style::default().fg(Color::DarkGray) };
+                let mut spans = vec![
+                    Span::styled(if is_selected { symbols.selected } else { "  " }, style),
+                    Span::styled(*name, style),
+                ];
+                if let Some(count) = count {
+                    spans.push(Span::styled(format!(" ({})", count), muted));
+                }
+                Line::from(spans)
+            })
+            .collect()
+    };
+
+    // Keep the selected folder in view
+    let scroll = (folders.selected + 1).saturating_sub(chunks[0].height as usize);
+    let list = Paragraph::new(lines)
+        .style(Style::default().bg(Color::Black))
+        .scroll((scroll as u16, 0));
+    frame.render_widget(list, chunks[0]);
+
+    if let Some(edit) = folders.edit.as_ref() {
+        let input_width = chunks[1].width.saturating_sub(2) as usize;
+        let (name, cursor) = text_input::visible(&folders.input, input_width, None);
+        let input = Paragraph::new(name)
+            .style(Style::default().fg(Color::Yellow).bg(Color::Black))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Yellow))
+                    .title(if *edit == FolderEdit::Create { " New Folder " } else { " Rename Folder " })
+                    .style(Style::default().bg(Color::Black)),
+            );
+        frame.render_widget(input, chunks[1]);
+        frame.set_cursor_position((chunks[1].x + 1 + cursor, chunks[1].y + 1));
+    }
+
+    let (help, style) = if folders.confirm_delete {
+        let (name, count) = state
+            .selected_folder()
+            .map_or(("this folder", 0), |(folder, count)| (folder.name.as_str(), count));
+        let question = match count {
+            0 => format!("Delete folder '{}'? (y/n)", name),
+            1 => format!("'{}' still holds 1 item, which will be left without a folder. Delete it? (y/n)", name),
+            _ => format!("'{}' still holds {} items, which will be left without a folder. Delete it? (y/n)", name, count),
+        };
+        (question, Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+    } else if folders.busy {
+        ("Saving the folder change...".to_string(), Style::default().fg(Color::Yellow))
+    } else if folders.edit.is_some() {
+        ("Enter to save, Esc to cancel".to_string(), Style::default().fg(Color::DarkGray))
+    } else if moving.is_some() {
+        (
+            format!("{} to select, Enter to move the item there, Esc to cancel", symbols.up_down),
+            Style::default().fg(Color::DarkGray),
+        )
+    } else if state.ui.readonly {
+        (
+            format!("{} to select, Enter to filter by folder, Esc or Alt+K to close", symbols.up_down),
+            Style::default().fg(Color::DarkGray),
+        )
+    } else {
+        (
+            format!("{} to select, Enter to filter, n to create, r to rename, Del to delete, Esc to close", symbols.up_down),
+            Style::default().fg(Color::DarkGray),
+        )
+    };
+    let help = Paragraph::new(help)
+        .style(style.bg(Color::Black))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(help, chunks[2]);
+}
@@ -0,0 +1,77 @@
+use crate::state::{Annotation, AppState};
+use crate::ui::layout::{centered_rect, with_min_height};
+use crate::ui::widgets::text_input;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Rows needed for the borders, instructions, input and help text
+const MIN_HEIGHT: u16 = 10;
+
+pub fn render(frame: &mut Frame, state: &AppState) {
+    let Some(edit) = state.ui.local_note_edit.as_ref() else {
+        return;
+    };
+    let area = with_min_height(centered_rect(60, 30, frame.area()), MIN_HEIGHT, frame.area());
+
+    // Clear the entire dialog area first
+    frame.render_widget(Clear, area);
+
+    let (title, instructions, help) = match edit.annotation {
+        Annotation::Note => (
+            "Local Note",
+            "Only stored on this computer, never synced. Shown in the details panel and found by searching.",
+            "Enter to save (empty removes the note), Esc to cancel",
+        ),
+        Annotation::Tags => (
+            "Local Tags",
+            "Separated by commas or spaces. Only stored on this computer, never synced. Search with tag:NAME.",
+            "Enter to save (empty removes the tags), Esc to cancel",
+        ),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(" {}: {} ", title, edit.item_name))
+        .style(Style::default().bg(Color::Black));
+
+    frame.render_widget(block.clone(), area);
+
+    let inner = block.inner(area);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),  // Instructions
+            Constraint::Length(3),  // Note input
+            Constraint::Min(0),     // Spacing
+            Constraint::Length(1),  // Help text
+        ])
+        .split(inner);
+
+    let instructions = Paragraph::new(instructions)
+        .style(Style::default().fg(Color::White).bg(Color::Black))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(instructions, chunks[0]);
+
+    let input_width = chunks[1].width.saturating_sub(2) as usize;
+    let (note, cursor) = text_input::visible(&edit.input, input_width, None);
+    let input = Paragraph::new(note)
+        .style(Style::default().fg(Color::Yellow).bg(Color::Black))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title(if edit.annotation == Annotation::Note { " Note " } else { " Tags " })
+                .style(Style::default().bg(Color::Black)),
+        );
+    frame.render_widget(input, chunks[1]);
+    frame.set_cursor_position((chunks[1].x + 1 + cursor, chunks[1].y + 1));
+
+    let help = Paragraph::new(help)
+        .style(Style::default().fg(Color::DarkGray).bg(Color::Black))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[3]);
+}
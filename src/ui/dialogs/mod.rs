@@ -1,4 +1,15 @@
 pub mod password;
+pub mod confirm_quit;
 pub mod save_token;
 pub mod not_logged_in;
+pub mod status_history;
+pub mod sync_status;
+pub mod qr_code;
+pub mod copy_as;
+pub mod local_note;
+pub mod tag_list;
 
+pub mod duplicates;
+pub mod folders;
+pub mod share;
+pub mod totp_dashboard;
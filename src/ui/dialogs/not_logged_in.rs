@@ -32,7 +32,7 @@ pub fn render(frame: &mut Frame) {
         .split(inner);
     
     // Message
-    let message_text = vec![
+    let message_text = [
         "Your Bitwarden vault is not logged in.",
         "",
         "Please run the following command to log in:",
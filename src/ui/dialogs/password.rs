@@ -1,5 +1,7 @@
 use crate::state::AppState;
-use crate::ui::layout::centered_rect;
+use crate::ui::layout::{centered_rect, with_min_height};
+use crate::ui::symbols;
+use crate::ui::widgets::text_input;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
     style::{Color, Style},
@@ -9,6 +11,7 @@ use ratatui::{
 };
 
 #[cfg(test)]
+#[allow(clippy::items_after_test_module)]
 mod tests {
     use crate::state::AppState;
 
@@ -37,19 +40,79 @@ mod tests {
         state.exit_password_mode();
         assert!(!state.password_input_mode());
     }
+
+    #[test]
+    fn test_reprompt_mode() {
+        let mut state = AppState::new();
+        state.enter_reprompt(crate::events::Action::CopyCardAll);
+        assert!(state.password_input_mode());
+        assert!(state.reprompt_active());
+
+        // Leaving the re-prompt hands back the pending action
+        let action = state.exit_reprompt();
+        assert!(matches!(action, Some(crate::events::Action::CopyCardAll)));
+        assert!(!state.password_input_mode());
+        assert!(!state.reprompt_active());
+
+        // A grant is only valid once, and only for its item
+        state.grant_reprompt("1".to_string());
+        assert!(!state.consume_reprompt_grant("2"));
+        state.grant_reprompt("1".to_string());
+        assert!(state.consume_reprompt_grant("1"));
+        assert!(!state.consume_reprompt_grant("1"));
+    }
+
+    #[test]
+    fn test_relock_prompt() {
+        let mut state = AppState::new();
+        state.enter_relock_prompt();
+        assert!(state.password_input_mode());
+        assert!(!state.reprompt_active());
+
+        // The flag is only reported once
+        assert!(state.ui.take_relocked());
+        assert!(!state.ui.take_relocked());
+    }
 }
 
+/// Rows needed for the borders, instructions, password input and help text, without an error
+const MIN_HEIGHT: u16 = 11;
+
 pub fn render(frame: &mut Frame, state: &AppState) {
-    let area = centered_rect(60, 40, frame.area());
+    // Error message if any, with the count of wrong passwords
+    // Terminals don't report Caps Lock, so a wrong password only suggests checking it
+    let mut error_lines = Vec::new();
+    if let Some(error) = state.ui.unlock_error.as_ref().filter(|error| !error.is_empty()) {
+        error_lines.push(Line::styled(error.clone(), Style::default().fg(Color::Red)));
+    }
+    let failures = state.ui.unlock_failures;
+    if failures > 0 {
+        let mut attempts = format!("{} wrong attempt{}", failures, if failures == 1 { "" } else { "s" });
+        if state.ui.max_unlock_attempts > 0 {
+            attempts.push_str(&format!(", {} left before exiting", state.ui.max_unlock_attempts.saturating_sub(failures)));
+        }
+        error_lines.push(Line::styled(attempts, Style::default().fg(Color::Yellow)));
+        error_lines.push(Line::styled(
+            "Is Caps Lock on? Check the keyboard layout",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    // The dialog grows so the error and attempt count fit
+    let min_height = MIN_HEIGHT + error_lines.len() as u16;
+    let area = with_min_height(centered_rect(60, 40, frame.area()), min_height, frame.area());
     
     // Clear the entire dialog area first
     frame.render_widget(Clear, area);
     
+    // The same dialog verifies the master password for re-prompt protected items
+    let reprompt = state.reprompt_active();
+    let busy = state.unlock_in_progress();
+    
     // Clear the background
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Cyan))
-        .title(" Unlock Vault ")
+        .title(if reprompt { " Master Password Re-prompt " } else { " Unlock Vault " })
         .style(Style::default().bg(Color::Black));
     
     frame.render_widget(block.clone(), area);
@@ -69,10 +132,14 @@ pub fn render(frame: &mut Frame, state: &AppState) {
         .split(inner);
     
     // Instructions
-    let instruction_text = if state.sync.syncing {
-        format!("{} Unlocking vault...", state.sync.spinner())
-    } else {
-        "Enter your master password to unlock the vault:".to_string()
+    let instruction_text = match (busy, reprompt) {
+        (true, true) if state.ui.accessible => "Verifying master password...".to_string(),
+        (true, true) => "⏳ Verifying master password...".to_string(),
+        (true, false) => format!("{} Unlocking vault...", state.sync_spinner()),
+        (false, true) => "This item is protected. Enter your master password to continue:".to_string(),
+        (false, false) if state.ui.relocked => "The vault was locked. Enter your master password to continue:".to_string(),
+        (false, false) if state.ui.session_expired => "Your saved session expired. Enter your master password to unlock the vault:".to_string(),
+        (false, false) => "Enter your master password to unlock the vault:".to_string(),
     };
     let instructions = Paragraph::new(instruction_text)
         .style(Style::default().fg(Color::White).bg(Color::Black))
@@ -80,13 +147,15 @@ pub fn render(frame: &mut Frame, state: &AppState) {
     frame.render_widget(instructions, chunks[0]);
     
     // Password input box
-    let password_display = "•".repeat(state.ui.password_input.len());
-    let password_style = if state.sync.syncing {
+    let input_width = chunks[2].width.saturating_sub(2) as usize;
+    let mask = symbols::symbols(state).mask_char;
+    let (password_display, cursor) = text_input::visible(&state.ui.password_input, input_width, Some(mask));
+    let password_style = if busy {
         Style::default().fg(Color::DarkGray).bg(Color::Black)
     } else {
         Style::default().fg(Color::Yellow).bg(Color::Black)
     };
-    let password_border_style = if state.sync.syncing {
+    let password_border_style = if busy {
         Style::default().fg(Color::DarkGray)
     } else {
         Style::default().fg(Color::Yellow)
@@ -98,7 +167,7 @@ pub fn render(frame: &mut Frame, state: &AppState) {
         .style(Style::default().bg(Color::Black));
 
     // Add clear password shortcut on the right when there's text and not syncing
-    if !state.ui.password_input.is_empty() && !state.sync.syncing {
+    if !state.ui.password_input.is_empty() && !busy {
         password_block = password_block.title(Line::from(" ^X:Clear ").alignment(Alignment::Right));
     }
 
@@ -106,24 +175,29 @@ pub fn render(frame: &mut Frame, state: &AppState) {
         .style(password_style)
         .block(password_block);
     frame.render_widget(password_widget, chunks[2]);
+    if !busy {
+        frame.set_cursor_position((chunks[2].x + 1 + cursor, chunks[2].y + 1));
+    }
     
     // Error message if any
-    if let Some(error) = &state.ui.unlock_error {
-        let error_widget = Paragraph::new(error.as_str())
-            .style(Style::default().fg(Color::Red).bg(Color::Black))
-            .wrap(Wrap { trim: false });
-        frame.render_widget(error_widget, chunks[4]);
-    }
+    let error_widget = Paragraph::new(error_lines)
+        .style(Style::default().bg(Color::Black))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(error_widget, chunks[4]);
     
     // Help text
-    let help_text = if state.sync.syncing {
-        "Please wait while the vault is being unlocked..."
+    let wait = state.ui.unlock_wait(std::time::Instant::now());
+    let help_text = if busy && reprompt {
+        "Please wait while the master password is being verified...".to_string()
+    } else if busy {
+        "Please wait while the vault is being unlocked...".to_string()
+    } else if let Some(wait) = wait {
+        format!("Wait {}s before trying again, Esc to cancel", wait.as_secs_f64().ceil())
     } else {
-        "Press Enter to submit, Esc to cancel"
+        "Press Enter to submit, Esc to cancel".to_string()
     };
     let help = Paragraph::new(help_text)
         .style(Style::default().fg(Color::DarkGray).bg(Color::Black))
         .alignment(Alignment::Center);
     frame.render_widget(help, chunks[5]);
 }
-
@@ -0,0 +1,63 @@
+use crate::state::QrPopup;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+pub fn render(frame: &mut Frame, popup: &QrPopup) {
+    let code_width = popup.lines.iter().map(|line| line.width()).max().unwrap_or(0) as u16;
+    let code_height = popup.lines.len() as u16;
+
+    // Sized to the code plus borders and the help line, a cropped code can't be scanned
+    let screen = frame.area();
+    let width = (code_width + 2).max(popup.title.width() as u16 + 4).min(screen.width);
+    let height = (code_height + 3).min(screen.height);
+    let area = Rect::new(
+        screen.x + (screen.width - width) / 2,
+        screen.y + (screen.height - height) / 2,
+        width,
+        height,
+    );
+
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(" {} ", popup.title))
+        .style(Style::default().bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),     // Code
+            Constraint::Length(1),  // Help text
+        ])
+        .split(inner);
+
+    if chunks[0].width < code_width || chunks[0].height < code_height {
+        let message = Paragraph::new("Enlarge the terminal to show the QR code")
+            .style(Style::default().fg(Color::Yellow).bg(Color::Black))
+            .alignment(Alignment::Center);
+        frame.render_widget(message, chunks[0]);
+    } else {
+        // Scanners expect dark modules on a light background
+        let lines: Vec<Line> = popup.lines.iter().map(|line| Line::from(line.as_str())).collect();
+        let code = Paragraph::new(lines)
+            .style(Style::default().fg(Color::Black).bg(Color::White))
+            .alignment(Alignment::Center);
+        let code_area = Rect::new(chunks[0].x + (chunks[0].width - code_width) / 2, chunks[0].y, code_width, code_height);
+        frame.render_widget(code, code_area);
+    }
+
+    let help = Paragraph::new("Esc to close")
+        .style(Style::default().fg(Color::DarkGray).bg(Color::Black))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[1]);
+}
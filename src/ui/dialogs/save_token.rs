@@ -33,7 +33,7 @@ pub fn render(frame: &mut Frame, _state: &AppState) {
         .split(inner);
     
     // Message
-    let message_text = vec![
+    let message_text = [
         "Vault unlocked successfully!",
         "",
         "Would you like to save the session token securely?",
@@ -0,0 +1,106 @@
+use crate::state::AppState;
+use crate::ui::layout::centered_rect;
+use crate::ui::symbols;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+pub fn render(frame: &mut Frame, state: &AppState) {
+    let area = centered_rect(60, 60, frame.area());
+
+    // Clear the entire dialog area first
+    frame.render_widget(Clear, area);
+
+    let share = &state.share;
+    let collections = state.vault.collections_by_organization();
+    let symbols = symbols::symbols(state);
+    let item_name = share
+        .item_id
+        .as_ref()
+        .and_then(|id| state.vault.vault_items.iter().find(|item| &item.id == id))
+        .map_or("item", |item| item.name.as_str());
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(" Share '{}' ", item_name))
+        .style(Style::default().bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),     // Collections
+            Constraint::Length(2),  // Help text, or the pending confirmation
+        ])
+        .split(inner);
+
+    // Collections come sorted by organization, each organization starting with its name
+    let mut lines: Vec<Line> = Vec::new();
+    let mut selected_line = 0;
+    let mut organization = None;
+    for (index, collection) in collections.iter().enumerate() {
+        if organization != Some(collection.organization_id.as_str()) {
+            organization = Some(collection.organization_id.as_str());
+            let name = state.vault.organization_name(&collection.organization_id).unwrap_or("Organization");
+            lines.push(Line::styled(name.to_string(), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)));
+        }
+        let is_selected = index == share.selected;
+        if is_selected {
+            selected_line = lines.len();
+        }
+        let style = if is_selected {
+            Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let marker = if share.chosen.contains(&collection.id) { "[x] " } else { "[ ] " };
+        lines.push(Line::from(vec![
+            Span::styled(if is_selected { symbols.selected } else { "  " }, style),
+            Span::styled(marker, style),
+            Span::styled(collection.name.as_str(), style),
+        ]));
+    }
+
+    // Keep the selected collection in view
+    let scroll = (selected_line + 1).saturating_sub(chunks[0].height as usize);
+    let list = Paragraph::new(lines)
+        .style(Style::default().bg(Color::Black))
+        .scroll((scroll as u16, 0));
+    frame.render_widget(list, chunks[0]);
+
+    let (help, style) = if share.confirm {
+        let chosen: Vec<&str> = collections
+            .iter()
+            .filter(|collection| share.chosen.contains(&collection.id))
+            .map(|collection| collection.name.as_str())
+            .collect();
+        let organization = state
+            .share_target()
+            .and_then(|(_, organization_id, _)| state.vault.organization_name(&organization_id).map(str::to_string))
+            .unwrap_or_else(|| "the organization".to_string());
+        let question = format!(
+            "Share '{}' with {} ({})? The organization will own it (y/n)",
+            item_name,
+            organization,
+            chosen.join(", ")
+        );
+        (question, Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+    } else if share.busy {
+        ("Sharing the item...".to_string(), Style::default().fg(Color::Yellow))
+    } else {
+        (
+            format!("{} to select, Space to choose, Enter to share, Esc or Alt+U to close", symbols.up_down),
+            Style::default().fg(Color::DarkGray),
+        )
+    };
+    let help = Paragraph::new(help)
+        .style(style.bg(Color::Black))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(help, chunks[1]);
+}
@@ -0,0 +1,74 @@
+use crate::state::{AppState, MessageLevel};
+use crate::ui::layout::centered_rect;
+use crate::ui::symbols;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+pub fn render(frame: &mut Frame, state: &AppState) {
+    let area = centered_rect(80, 60, frame.area());
+
+    // Clear the entire dialog area first
+    frame.render_widget(Clear, area);
+
+    // Clear the background
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(" Status History ({}) ", state.status_history.len()))
+        .style(Style::default().bg(Color::Black));
+
+    frame.render_widget(block.clone(), area);
+
+    // Split into content area
+    let inner = block.inner(area);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),     // Messages
+            Constraint::Length(1),  // Help text
+        ])
+        .split(inner);
+
+    // Messages, newest first
+    let lines: Vec<Line> = if state.status_history.is_empty() {
+        vec![Line::from(Span::styled("No messages yet", Style::default().fg(Color::DarkGray)))]
+    } else {
+        state.status_history
+            .newest_first()
+            .skip(state.ui.status_history_scroll)
+            .map(|entry| {
+                let level_style = match entry.level {
+                    MessageLevel::Info => Style::default().fg(Color::Cyan),
+                    MessageLevel::Success => Style::default().fg(Color::Green),
+                    MessageLevel::Warning => Style::default().fg(Color::Yellow),
+                    MessageLevel::Error => Style::default().fg(Color::Red),
+                };
+                Line::from(vec![
+                    Span::styled(entry.time.format("%H:%M:%S ").to_string(), Style::default().fg(Color::DarkGray)),
+                    Span::styled(format!("{:<6}", entry.level.label()), level_style.add_modifier(Modifier::BOLD)),
+                    // The level is already spelled out
+                    Span::styled(
+                        if state.ui.accessible { symbols::strip_status_glyph(&entry.text) } else { entry.text.as_str() },
+                        Style::default().fg(Color::White),
+                    ),
+                ])
+            })
+            .collect()
+    };
+
+    let messages = Paragraph::new(lines)
+        .style(Style::default().bg(Color::Black))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(messages, chunks[0]);
+
+    // Help text
+    let help = Paragraph::new(format!("{} to scroll, Esc or Ctrl+G to close", symbols::symbols(state).up_down))
+        .style(Style::default().fg(Color::DarkGray).bg(Color::Black))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[1]);
+}
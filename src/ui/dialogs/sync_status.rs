@@ -0,0 +1,109 @@
+use crate::state::{AppState, SyncOutcome};
+use crate::ui::layout::centered_rect;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+pub fn render(frame: &mut Frame, state: &AppState) {
+    let area = centered_rect(70, 60, frame.area());
+
+    // Clear the entire dialog area first
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(" Sync Status ")
+        .style(Style::default().bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),     // Summary and recent syncs
+            Constraint::Length(1),  // Help text
+        ])
+        .split(inner);
+
+    let label = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let sync = &state.sync;
+    let mut lines = vec![Line::from(vec![
+        Span::styled("Status: ", label),
+        if state.syncing() {
+            Span::styled(format!("{} {}", state.sync_spinner(), sync.activity("Syncing")), Style::default().fg(Color::Yellow))
+        } else {
+            Span::styled("Idle", Style::default().fg(Color::White))
+        },
+    ])];
+    if let Some(cached_at) = state.vault.cached_at {
+        let cached_at = cached_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M");
+        lines.push(Line::from(vec![
+            Span::styled("Showing: ", label),
+            Span::styled(format!("the cache from {}", cached_at), Style::default().fg(Color::Yellow)),
+        ]));
+    }
+    lines.push(Line::from(vec![
+        Span::styled("Last successful sync: ", label),
+        match sync.last_success() {
+            Some(record) => Span::styled(
+                format!("{} ({})", record.time.format("%H:%M:%S"), describe(&record.outcome)),
+                Style::default().fg(Color::Green),
+            ),
+            None => Span::styled("none yet", Style::default().fg(Color::DarkGray)),
+        },
+    ]));
+    if let Some(record) = sync.last_failure() {
+        lines.push(Line::from(vec![
+            Span::styled("Last error: ", label),
+            Span::styled(format!("{} {}", record.time.format("%H:%M:%S"), describe(&record.outcome)), Style::default().fg(Color::Red)),
+        ]));
+    }
+
+    // Every finished sync of this session, newest first
+    lines.push(Line::from(""));
+    lines.push(Line::styled("Recent syncs", label));
+    if sync.newest_first().next().is_none() {
+        lines.push(Line::styled("No sync finished yet", Style::default().fg(Color::DarkGray)));
+    }
+    for record in sync.newest_first() {
+        let (mark, style) = match (&record.outcome, state.ui.accessible) {
+            (SyncOutcome::Synced { .. }, false) => ("✓", Style::default().fg(Color::Green)),
+            (SyncOutcome::Synced { .. }, true) => ("OK", Style::default().fg(Color::Green)),
+            (SyncOutcome::Failed(_), false) => ("✗", Style::default().fg(Color::Red)),
+            (SyncOutcome::Failed(_), true) => ("FAILED", Style::default().fg(Color::Red)),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(record.time.format("%H:%M:%S ").to_string(), Style::default().fg(Color::DarkGray)),
+            Span::styled(format!("{} ", mark), style.add_modifier(Modifier::BOLD)),
+            Span::styled(describe(&record.outcome), Style::default().fg(Color::White)),
+        ]));
+    }
+
+    let summary = Paragraph::new(lines)
+        .style(Style::default().bg(Color::Black))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(summary, chunks[0]);
+
+    let help = if state.syncing() { "Esc or Alt+Y to close" } else { "r to sync now, Esc or Alt+Y to close" };
+    let help = Paragraph::new(help)
+        .style(Style::default().fg(Color::DarkGray).bg(Color::Black))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[1]);
+}
+
+/// E.g. "312 items, 2 items updated" or the error
+fn describe(outcome: &SyncOutcome) -> String {
+    match outcome {
+        SyncOutcome::Synced { items, changes: None } => format!("{} items loaded", items),
+        SyncOutcome::Synced { items, changes: Some(changes) } => match changes.summary() {
+            Some(summary) => format!("{} items, {}", items, summary),
+            None => format!("{} items, no changes", items),
+        },
+        SyncOutcome::Failed(error) => error.clone(),
+    }
+}
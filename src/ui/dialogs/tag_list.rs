@@ -0,0 +1,78 @@
+use crate::state::AppState;
+use crate::ui::symbols;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Width of the tag list, enough for the help text
+const DIALOG_WIDTH: u16 = 44;
+
+pub fn render(frame: &mut Frame, state: &AppState) {
+    let tags = state.vault.tag_counts();
+    let selected = state.ui.tag_list_selected.unwrap_or(0);
+    let symbols = symbols::symbols(state);
+
+    let screen = frame.area();
+    let width = DIALOG_WIDTH.min(screen.width);
+    let height = (tags.len() as u16 + 4).min(screen.height); // Borders, a blank line and the help text
+    let area = Rect::new(
+        screen.x + (screen.width - width) / 2,
+        screen.y + (screen.height - height) / 2,
+        width,
+        height,
+    );
+
+    // Clear the entire dialog area first
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(" Tags ({}) ", tags.len()))
+        .style(Style::default().bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),     // Tags
+            Constraint::Length(1),  // Help text
+        ])
+        .split(inner);
+
+    let lines: Vec<Line> = tags
+        .iter()
+        .enumerate()
+        .map(|(index, (tag, count))| {
+            let is_selected = index == selected;
+            let style = if is_selected {
+                Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let muted = if is_selected { style } else { Style::default().fg(Color::DarkGray) };
+            Line::from(vec![
+                Span::styled(if is_selected { symbols.selected } else { "  " }, style),
+                Span::styled(format!("#{}", tag), style),
+                Span::styled(format!(" ({})", count), muted),
+            ])
+        })
+        .collect();
+
+    // Keep the selected tag in view
+    let scroll = (selected + 1).saturating_sub(chunks[0].height as usize);
+    let list = Paragraph::new(lines)
+        .style(Style::default().bg(Color::Black))
+        .scroll((scroll as u16, 0));
+    frame.render_widget(list, chunks[0]);
+
+    let help = Paragraph::new("Enter to filter by tag, Esc to close")
+        .style(Style::default().fg(Color::DarkGray).bg(Color::Black))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[1]);
+}
@@ -0,0 +1,98 @@
+use crate::state::AppState;
+use crate::ui::layout::centered_rect;
+use crate::ui::symbols;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// Width of the code and countdown columns, e.g. "123456  12s"
+const CODE_COLUMNS: usize = 16;
+
+pub fn render(frame: &mut Frame, state: &AppState) {
+    let area = centered_rect(80, 70, frame.area());
+
+    // Clear the entire dialog area first
+    frame.render_widget(Clear, area);
+
+    let dashboard = &state.totp_dashboard;
+    let symbols = symbols::symbols(state);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(" TOTP Codes ({}) ", dashboard.rows.len()))
+        .style(Style::default().bg(Color::Black));
+
+    frame.render_widget(block.clone(), area);
+
+    let inner = block.inner(area);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),     // Codes
+            Constraint::Length(1),  // Help text
+        ])
+        .split(inner);
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    // The item name and username take what the codes leave
+    let label_width = (inner.width as usize).saturating_sub(CODE_COLUMNS + 4);
+
+    let mut lines: Vec<Line> = Vec::new();
+    for (position, row) in dashboard.rows.iter().enumerate() {
+        let is_selected = position == dashboard.selected;
+        let style = if is_selected {
+            Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let muted = if is_selected { style } else { Style::default().fg(Color::DarkGray) };
+
+        let label = match &row.username {
+            Some(username) => format!("{} ({})", row.name, username),
+            None => row.name.clone(),
+        };
+        let label = crate::text::head_to_width(&label, label_width);
+        let padding = " ".repeat(label_width.saturating_sub(label.width()));
+
+        let mut spans = vec![
+            Span::styled(if is_selected { format!("  {}", symbols.selected) } else { "    ".to_string() }, style),
+            Span::styled(format!("{}{}", label, padding), style),
+        ];
+        match &row.code {
+            Some(totp) => {
+                let remaining = totp.expires_at.saturating_sub(now);
+                // Codes about to change are shown in a warning color, like the status bar countdown
+                let code_style = if is_selected || remaining > 5 { style } else { style.fg(Color::Yellow) };
+                spans.push(Span::styled(format!("{:>10}", totp.code), code_style));
+                spans.push(Span::styled(format!("{:>5}s", remaining), muted));
+            }
+            None => spans.push(Span::styled(format!("{:>16}", "unavailable"), muted)),
+        }
+        lines.push(Line::from(spans));
+    }
+
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled("No items with a TOTP secret", Style::default().fg(Color::DarkGray))));
+    }
+
+    // Keep the selected row in view
+    let height = chunks[0].height as usize;
+    let scroll = (dashboard.selected + 1).saturating_sub(height);
+    let codes = Paragraph::new(lines)
+        .style(Style::default().bg(Color::Black))
+        .scroll((scroll as u16, 0));
+    frame.render_widget(codes, chunks[0]);
+
+    let help = Paragraph::new(format!("{} to select, Enter to copy the code, Esc or Alt+T to close", symbols.up_down))
+        .style(Style::default().fg(Color::DarkGray).bg(Color::Black))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[1]);
+}
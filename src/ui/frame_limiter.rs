@@ -0,0 +1,51 @@
+use std::time::{Duration, Instant};
+
+/// Spaces out redraws so a burst of events, like a held key, draws at most one frame per interval
+#[derive(Debug)]
+pub struct FrameLimiter {
+    interval: Duration,
+    last_frame: Option<Instant>,
+}
+
+impl FrameLimiter {
+    pub fn new(max_fps: u32) -> Self {
+        Self {
+            interval: Duration::from_secs(1) / max_fps.max(1),
+            last_frame: None,
+        }
+    }
+
+    /// When the next frame may be drawn
+    pub fn next_frame(&self) -> Option<Instant> {
+        self.last_frame.map(|last_frame| last_frame + self.interval)
+    }
+
+    /// Whether a frame may be drawn now
+    pub fn due(&self, now: Instant) -> bool {
+        self.next_frame().is_none_or(|next_frame| now >= next_frame)
+    }
+
+    pub fn drawn(&mut self, now: Instant) {
+        self.last_frame = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frames_are_spaced_out() {
+        let mut limiter = FrameLimiter::new(50);
+        let start = Instant::now();
+        assert!(limiter.due(start));
+
+        limiter.drawn(start);
+        assert!(!limiter.due(start + Duration::from_millis(10)));
+        assert_eq!(limiter.next_frame(), Some(start + Duration::from_millis(20)));
+        assert!(limiter.due(start + Duration::from_millis(20)));
+
+        // 0 can't mean no frames at all
+        assert_eq!(FrameLimiter::new(0).interval, Duration::from_secs(1));
+    }
+}
@@ -1,4 +1,132 @@
-use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use crate::config::{LayoutConfig, LayoutMode};
+use crate::state::AppState;
+use ratatui::layout::{Constraint, Direction, Layout, Margin, Rect};
+use ratatui::style::Style;
+use ratatui::widgets::{Block, Borders};
+
+/// Smallest terminal width the main screen is drawn at
+pub const MIN_WIDTH: u16 = 40;
+
+/// Smallest terminal height the main screen is drawn at (search box, tabs, a few rows and the status bar)
+pub const MIN_HEIGHT: u16 = 12;
+
+/// Whether the terminal is too small to draw the main screen
+pub fn is_too_small(area: Rect) -> bool {
+    area.width < MIN_WIDTH || area.height < MIN_HEIGHT
+}
+
+/// Areas of the main screen
+pub struct ScreenAreas {
+    pub search: Rect,
+    pub tabs: Rect,
+    pub main: Rect,
+    pub status: Rect,
+}
+
+/// Areas of the entry list and details panel, empty when hidden
+pub struct MainAreas {
+    pub list: Rect,
+    pub details: Rect,
+}
+
+/// Split the screen into the search box, tab bar, main area and status bar
+/// The compact layout has a one-line search box and no tab bar
+pub fn screen_areas(area: Rect, status_bar_height: u16, compact: bool) -> ScreenAreas {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(search_height(compact)),
+            Constraint::Length(if compact { 0 } else { 3 }), // Tab bar
+            Constraint::Min(0),                    // Entry list and details
+            Constraint::Length(status_bar_height), // Status bar (dynamic height)
+        ])
+        .split(area);
+
+    ScreenAreas {
+        search: chunks[0],
+        tabs: chunks[1],
+        main: chunks[2],
+        status: chunks[3],
+    }
+}
+
+/// Split the screen of the `pick` launcher, which has no tab bar or details panel
+pub fn picker_areas(area: Rect, status_bar_height: u16, compact: bool) -> ScreenAreas {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(search_height(compact)),
+            Constraint::Min(0),                    // Entry list
+            Constraint::Length(status_bar_height), // Status bar (dynamic height)
+        ])
+        .split(area);
+
+    ScreenAreas {
+        search: chunks[0],
+        tabs: Rect::default(),
+        main: chunks[1],
+        status: chunks[2],
+    }
+}
+
+fn search_height(compact: bool) -> u16 {
+    if compact { 1 } else { 3 }
+}
+
+/// Block of the entry list and details panel, which only keeps its title line in the compact layout
+pub fn panel_block<'a>(compact: bool, style: Style) -> Block<'a> {
+    if compact {
+        // Without borders the title carries the color
+        Block::default().borders(Borders::NONE).title_style(style)
+    } else {
+        Block::default().borders(Borders::ALL).border_style(style)
+    }
+}
+
+/// Where the content of the entry list or details panel goes, inside its borders or below its title
+pub fn panel_inner(area: Rect, compact: bool) -> Rect {
+    if compact {
+        let y = area.y.saturating_add(1).min(area.bottom());
+        Rect::new(area.x, y, area.width, area.bottom() - y)
+    } else {
+        area.inner(Margin::new(1, 1))
+    }
+}
+
+/// Whether the entry list and details panel should be stacked for the given terminal width
+pub fn is_stacked(layout: &LayoutConfig, width: u16) -> bool {
+    match layout.mode {
+        LayoutMode::Auto => width < layout.stack_below_width,
+        LayoutMode::SideBySide => false,
+        LayoutMode::Stacked => true,
+    }
+}
+
+/// Split the main area between the entry list and the details panel
+pub fn main_areas(area: Rect, terminal_width: u16, state: &AppState) -> MainAreas {
+    if state.details_fullscreen() {
+        return MainAreas { list: Rect::default(), details: area };
+    }
+    if !state.details_panel_visible() {
+        return MainAreas { list: area, details: Rect::default() };
+    }
+
+    let layout = &state.ui.layout;
+    let direction = if is_stacked(layout, terminal_width) {
+        Direction::Vertical
+    } else {
+        Direction::Horizontal
+    };
+    let chunks = Layout::default()
+        .direction(direction)
+        .constraints([
+            Constraint::Percentage(layout.list_percent),       // Entry list
+            Constraint::Percentage(100 - layout.list_percent), // Details panel
+        ])
+        .split(area);
+
+    MainAreas { list: chunks[0], details: chunks[1] }
+}
 
 /// Helper function to create a centered rect
 pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
@@ -21,3 +149,70 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
+/// Grow a centered rect to at least `min_height` rows, staying centered within `r`
+pub fn with_min_height(area: Rect, min_height: u16, r: Rect) -> Rect {
+    let height = area.height.max(min_height).min(r.height);
+    Rect { y: r.y + (r.height - height) / 2, height, ..area }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_layout_stacks_narrow_terminals() {
+        let layout = LayoutConfig::default();
+        assert!(is_stacked(&layout, 80));
+        assert!(!is_stacked(&layout, 120));
+
+        let forced = LayoutConfig { mode: LayoutMode::SideBySide, ..LayoutConfig::default() };
+        assert!(!is_stacked(&forced, 40));
+        let forced = LayoutConfig { mode: LayoutMode::Stacked, ..LayoutConfig::default() };
+        assert!(is_stacked(&forced, 200));
+    }
+
+    #[test]
+    fn test_is_too_small() {
+        assert!(!is_too_small(Rect::new(0, 0, MIN_WIDTH, MIN_HEIGHT)));
+        assert!(is_too_small(Rect::new(0, 0, MIN_WIDTH - 1, 50)));
+        assert!(is_too_small(Rect::new(0, 0, 200, MIN_HEIGHT - 1)));
+        assert!(is_too_small(Rect::default()));
+    }
+
+    #[test]
+    fn test_with_min_height() {
+        let screen = Rect::new(0, 0, 80, 24);
+        assert_eq!(with_min_height(Rect::new(16, 7, 48, 9), 11, screen), Rect::new(16, 6, 48, 11));
+        assert_eq!(with_min_height(Rect::new(16, 2, 48, 20), 11, screen), Rect::new(16, 2, 48, 20));
+        assert_eq!(with_min_height(Rect::new(0, 0, 10, 2), 11, Rect::new(0, 0, 10, 5)), Rect::new(0, 0, 10, 5));
+    }
+
+    #[test]
+    fn test_main_areas() {
+        let area = Rect::new(0, 6, 80, 20);
+        let mut state = AppState::new();
+
+        // Without details the list takes the whole area
+        let areas = main_areas(area, 80, &state);
+        assert_eq!(areas.list, area);
+        assert!(areas.details.is_empty());
+
+        // Narrow terminal: list above details
+        state.toggle_details_panel();
+        let areas = main_areas(area, 80, &state);
+        assert_eq!(areas.list, Rect::new(0, 6, 80, 10));
+        assert_eq!(areas.details, Rect::new(0, 16, 80, 10));
+
+        // Wide terminal: side by side
+        let area = Rect::new(0, 6, 160, 20);
+        let areas = main_areas(area, 160, &state);
+        assert_eq!(areas.list, Rect::new(0, 6, 80, 20));
+        assert_eq!(areas.details, Rect::new(80, 6, 80, 20));
+
+        state.toggle_details_fullscreen();
+        let areas = main_areas(area, 160, &state);
+        assert!(areas.list.is_empty());
+        assert_eq!(areas.details, area);
+    }
+}
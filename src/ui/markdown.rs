@@ -0,0 +1,141 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+/// Render notes with basic markdown: headings, bold and italic text, lists, code spans and fenced code blocks
+/// Anything else, including markers without a closing one, is shown as written
+pub fn render(text: &str, bullet: &str) -> Vec<Line<'static>> {
+    let text_style = Style::default().fg(Color::White);
+    let code_style = Style::default().fg(Color::Yellow);
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            lines.push(Line::from(Span::styled(line.to_string(), code_style)));
+            continue;
+        }
+
+        if let Some((level, heading)) = heading(trimmed) {
+            let mut style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+            if level == 1 {
+                style = style.add_modifier(Modifier::UNDERLINED);
+            }
+            lines.push(Line::from(inline(heading, style, code_style)));
+            continue;
+        }
+
+        let indent = &line[..line.len() - trimmed.len()];
+        if let Some(item) = ["- ", "* ", "+ "].iter().find_map(|marker| trimmed.strip_prefix(marker)) {
+            let mut spans = vec![Span::styled(format!("{}{} ", indent, bullet), Style::default().fg(Color::Cyan))];
+            spans.extend(inline(item, text_style, code_style));
+            lines.push(Line::from(spans));
+        } else if let Some((number, item)) = numbered_item(trimmed) {
+            let mut spans = vec![Span::styled(format!("{}{} ", indent, number), Style::default().fg(Color::Cyan))];
+            spans.extend(inline(item, text_style, code_style));
+            lines.push(Line::from(spans));
+        } else {
+            lines.push(Line::from(inline(line, text_style, code_style)));
+        }
+    }
+    lines
+}
+
+/// Level and text of a `# Heading` line
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let text = line[level..].strip_prefix(' ')?;
+    Some((level, text.trim_end_matches('#').trim_end()))
+}
+
+/// Number (with its dot) and text of a `1. Item` line
+fn numbered_item(line: &str) -> Option<(&str, &str)> {
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits == 0 {
+        return None;
+    }
+    let text = line[digits..].strip_prefix(". ")?;
+    Some((&line[..digits + 1], text))
+}
+
+/// Split a line into spans for `**bold**`, `*italic*` and `` `code` ``
+/// Underscores are left alone, they are common in secrets and identifiers
+fn inline(text: &str, style: Style, code_style: Style) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let emphasis = match c {
+            '`' => Some(("`", code_style)),
+            '*' if rest.starts_with("**") => Some(("**", style.add_modifier(Modifier::BOLD))),
+            '*' => Some(("*", style.add_modifier(Modifier::ITALIC))),
+            _ => None,
+        };
+        if let Some((marker, marked_style)) = emphasis {
+            // Only a closing marker around text that doesn't start or end with a space makes it markup
+            let inner = rest[marker.len()..].find(marker).map(|end| &rest[marker.len()..marker.len() + end]);
+            if let Some(inner) = inner.filter(|inner| !inner.is_empty() && inner.trim() == *inner) {
+                if !plain.is_empty() {
+                    spans.push(Span::styled(std::mem::take(&mut plain), style));
+                }
+                spans.push(Span::styled(inner.to_string(), marked_style));
+                rest = &rest[marker.len() * 2 + inner.len()..];
+                continue;
+            }
+        }
+        plain.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    if !plain.is_empty() || spans.is_empty() {
+        spans.push(Span::styled(plain, style));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(line: &Line) -> String {
+        line.spans.iter().map(|span| span.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_blocks() {
+        let lines = render("# Server\n- host: *db*\n  2. port\n```\n# not a heading\n```\nplain", "•");
+        let texts: Vec<String> = lines.iter().map(text).collect();
+        assert_eq!(texts, vec!["Server", "• host: db", "  2. port", "# not a heading", "plain"]);
+        assert!(lines[0].spans[0].style.add_modifier.contains(Modifier::UNDERLINED));
+        assert_eq!(lines[3].spans[0].style.fg, Some(Color::Yellow));
+    }
+
+    #[test]
+    fn test_inline_markup() {
+        let style = Style::default();
+        let code = Style::default().fg(Color::Yellow);
+        let spans = inline("run `ls -l` as **root**", style, code);
+        let runs: Vec<(&str, Style)> = spans.iter().map(|span| (span.content.as_ref(), span.style)).collect();
+        assert_eq!(runs, vec![
+            ("run ", style),
+            ("ls -l", code),
+            (" as ", style),
+            ("root", style.add_modifier(Modifier::BOLD)),
+        ]);
+
+        // Unclosed or empty markers and underscores stay as written
+        let spans = inline("p*ss **word my_secret_key ``", style, code);
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].content, "p*ss **word my_secret_key ``");
+    }
+}
@@ -1,14 +1,16 @@
 pub mod widgets;
 pub mod dialogs;
+pub mod frame_limiter;
 pub mod layout;
+pub mod markdown;
+pub mod symbols;
+
+#[cfg(test)]
+mod snapshot_tests;
 
 use crate::error::Result;
 use crate::state::AppState;
-use ratatui::{
-    backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout},
-    Terminal,
-};
+use ratatui::{backend::CrosstermBackend, layout::Rect, Frame, Terminal};
 use std::io::Stdout;
 
 pub struct UI {
@@ -22,57 +24,103 @@ impl UI {
         Ok(Self { terminal })
     }
 
-    pub fn render(&mut self, state: &mut AppState) -> Result<()> {
-        self.terminal.draw(|frame| {
-            let status_bar_height = widgets::status_bar::calculate_height(frame.area().width, state);
-            
-            let chunks = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Length(3),              // Search box
-                    Constraint::Length(3),              // Tab bar
-                    Constraint::Min(0),                 // Entry list and details
-                    Constraint::Length(status_bar_height), // Status bar (dynamic height)
-                ])
-                .split(frame.area());
-
-            widgets::search_box::render(frame, chunks[0], state);
-            widgets::tab_bar::render(frame, chunks[1], state);
-            
-            // Split the middle section horizontally if details panel is visible
-            if state.details_panel_visible() {
-                let main_chunks = Layout::default()
-                    .direction(Direction::Horizontal)
-                    .constraints([
-                        Constraint::Percentage(50),     // Entry list
-                        Constraint::Percentage(50),     // Details panel
-                    ])
-                    .split(chunks[2]);
-                
-                state.ui.list_area = main_chunks[0];
-                state.ui.details_panel_area = main_chunks[1];
-                widgets::entry_list::render(frame, main_chunks[0], state);
-                widgets::details::render(frame, main_chunks[1], state);
-            } else {
-                state.ui.list_area = chunks[2];
-                state.ui.details_panel_area = ratatui::layout::Rect::default();
-                widgets::entry_list::render(frame, chunks[2], state);
-            }
-            
-            widgets::status_bar::render(frame, chunks[3], state);
-
-            // Render password input dialog, save token prompt, or not logged in error on top if active
-            if state.password_input_mode() {
-                dialogs::password::render(frame, state);
-            } else if state.offer_save_token() {
-                dialogs::save_token::render(frame, state);
-            } else if state.show_not_logged_in_error() {
-                dialogs::not_logged_in::render(frame);
-            }
-        })?;
+    /// Forget what is on screen so the next render redraws everything
+    pub fn clear(&mut self) -> Result<()> {
+        self.terminal.clear()?;
+        Ok(())
+    }
 
+    #[tracing::instrument(skip_all)]
+    pub fn render(&mut self, state: &mut AppState) -> Result<()> {
+        self.terminal.draw(|frame| draw(frame, state))?;
         Ok(())
     }
 }
 
+/// Draw the whole screen into a frame, on any ratatui backend
+pub fn draw(frame: &mut Frame, state: &mut AppState) {
+    // Widgets register their clickable areas while rendering
+    state.ui.click_regions.clear();
+
+    // Nothing fits below the minimum size, so only explain how to fix it
+    if layout::is_too_small(frame.area()) {
+        state.ui.list_area = Default::default();
+        state.ui.details_panel_area = Default::default();
+        widgets::too_small::render(frame);
+        return;
+    }
+
+    let status_bar_height = widgets::status_bar::calculate_height(frame.area().width, state);
+    let picker = state.ui.pick.is_some();
+    let compact = state.ui.layout.compact;
+    let screen = if picker {
+        layout::picker_areas(frame.area(), status_bar_height, compact)
+    } else {
+        layout::screen_areas(frame.area(), status_bar_height, compact)
+    };
+
+    widgets::search_box::render(frame, screen.search, state);
+    if !picker && !compact {
+        widgets::tab_bar::render(frame, screen.tabs, state);
+    }
+
+    // Split the middle section between the list and the details panel, the picker only has the list
+    let main = if picker {
+        layout::MainAreas { list: screen.main, details: Rect::default() }
+    } else {
+        layout::main_areas(screen.main, frame.area().width, state)
+    };
+    state.ui.list_area = main.list;
+    state.ui.details_panel_area = main.details;
+    if !main.list.is_empty() {
+        widgets::entry_list::render(frame, main.list, state);
+    }
+    if !main.details.is_empty() {
+        widgets::details::render(frame, main.details, state);
+    }
+
+    widgets::status_bar::render(frame, screen.status, state);
 
+    // Dialogs cover the panels, so clicks must not reach them
+    if state.dialog_open() {
+        state.ui.click_regions.clear();
+    }
+
+    // Render password input dialog, save token prompt, or not logged in error on top if active
+    if state.password_input_mode() {
+        dialogs::password::render(frame, state);
+    } else if state.offer_save_token() {
+        dialogs::save_token::render(frame, state);
+    } else if state.show_not_logged_in_error() {
+        dialogs::not_logged_in::render(frame);
+    } else if state.show_status_history() {
+        dialogs::status_history::render(frame, state);
+    } else if state.show_sync_details() {
+        dialogs::sync_status::render(frame, state);
+    } else if state.show_duplicates() {
+        dialogs::duplicates::render(frame, state);
+    } else if state.show_folders() {
+        dialogs::folders::render(frame, state);
+    } else if state.share_open() {
+        dialogs::share::render(frame, state);
+    } else if state.show_totp_dashboard() {
+        dialogs::totp_dashboard::render(frame, state);
+    } else if let Some(popup) = state.qr_popup() {
+        dialogs::qr_code::render(frame, popup);
+    } else if state.copy_as_open() {
+        dialogs::copy_as::render(frame, state);
+    } else if state.local_note_open() {
+        dialogs::local_note::render(frame, state);
+    } else if state.tag_list_open() {
+        dialogs::tag_list::render(frame, state);
+    }
+
+    // Asked over whatever else is open, like the password dialog during an unlock
+    if state.confirm_quit() {
+        dialogs::confirm_quit::render(frame, state);
+    }
+
+    if state.ui.accessible {
+        symbols::raise_contrast(frame.buffer_mut());
+    }
+}
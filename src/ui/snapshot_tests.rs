@@ -0,0 +1,409 @@
+//! Rendering tests on a TestBackend, comparing the drawn text with the files in src/ui/snapshots
+//! Run with UPDATE_SNAPSHOTS=1 to write the files after an intended change, then review the diff
+
+use crate::events::Action;
+use crate::state::{AccessLookup, Annotation, AppState, DetailField, MessageLevel, SyncChanges, SyncOutcome};
+use crate::types::{
+    Collection, CollectionAccess, CustomField, CustomFieldType, Folder, ItemType, LoginData, OrgMember, Organization, Uri, VaultItem,
+};
+use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
+use std::path::PathBuf;
+use unicode_width::UnicodeWidthStr;
+
+/// Draw the whole screen at the given size and return it as text
+fn render(state: &mut AppState, width: u16, height: u16) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|frame| super::draw(frame, state)).unwrap();
+    buffer_text(terminal.backend().buffer())
+}
+
+/// The symbols of a buffer, one line per row without trailing spaces
+fn buffer_text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    (area.top()..area.bottom())
+        .map(|y| {
+            let mut row = String::new();
+            let mut x = area.left();
+            while x < area.right() {
+                // Wide characters also cover the next cell(s)
+                let symbol = buffer[(x, y)].symbol();
+                row.push_str(symbol);
+                x += (symbol.width() as u16).max(1);
+            }
+            row.trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n"
+}
+
+/// Compare with the stored snapshot, or store it when UPDATE_SNAPSHOTS is set
+fn assert_snapshot(name: &str, actual: &str) {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "src", "ui", "snapshots", &format!("{}.snap", name)].iter().collect();
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("Missing snapshot {}, run the tests with UPDATE_SNAPSHOTS=1", path.display()));
+    assert!(expected == actual, "Snapshot {} differs\n--- expected\n{}\n--- actual\n{}", name, expected, actual);
+}
+
+fn login(id: &str, name: &str, username: &str) -> VaultItem {
+    VaultItem {
+        id: id.to_string(),
+        name: name.to_string(),
+        item_type: ItemType::Login,
+        login: Some(LoginData {
+            username: Some(username.to_string()),
+            password: Some("hunter2".to_string()),
+            totp: None,
+            uris: Some(vec![Uri { uri: format!("https://{}.example.com", id), match_type: None }]),
+            password_revision_date: None,
+        }),
+        card: None,
+        identity: None,
+        notes: None,
+        fields: None,
+        favorite: false,
+        folder_id: None,
+        organization_id: None,
+        // Ages are shown relative to now, a fresh item always reads "0d"
+        revision_date: chrono::Utc::now(),
+        object: None,
+        creation_date: None,
+        deleted_date: None,
+        password_history: None,
+        attachments: None,
+        collection_ids: None,
+        reprompt: None,
+        raw_fields: Default::default(),
+    }
+}
+
+fn vault_state() -> AppState {
+    let mut mail = login("mail", "Mail", "alice@example.com");
+    mail.favorite = true;
+    mail.notes = Some("Recovery codes are in the safe, ".repeat(4).trim_end().to_string());
+    mail.fields = Some(vec![CustomField {
+        name: Some("PIN".to_string()),
+        value: Some("1234".to_string()),
+        field_type: Some(CustomFieldType::Hidden),
+        linked_id: None,
+    }]);
+
+    let mut state = AppState::new();
+    state.load_items_with_secrets(vec![mail, login("git", "GitHub", "alice"), login("bank", "Bank", "alice.smith")]);
+    state.toggle_details_panel();
+    state
+}
+
+#[test]
+fn test_main_screen() {
+    let mut state = vault_state();
+    assert_snapshot("main_screen", &render(&mut state, 100, 24));
+}
+
+#[test]
+fn test_narrow_screen_stacks_panels() {
+    let mut state = vault_state();
+    assert_snapshot("narrow_screen", &render(&mut state, 60, 30));
+}
+
+#[test]
+fn test_compact_layout() {
+    let mut state = vault_state();
+    state.ui.layout.compact = true;
+    state.set_item_type_filter(Some(ItemType::Login));
+    assert_snapshot("compact_layout", &render(&mut state, 60, 16));
+
+    // Rows are clickable right below the list title
+    assert!(matches!(state.ui.click_regions.action_at(5, 2), Some(Action::SelectIndexAndShowDetails(0))));
+}
+
+#[test]
+fn test_sync_changed_selected_item() {
+    let mut state = vault_state();
+    state.select_index(2);
+    let mut items = state.vault.vault_items.clone();
+    let git = items.iter_mut().find(|item| item.id == "git").unwrap();
+    git.login.as_mut().unwrap().username = Some("alice.smith".to_string());
+    git.revision_date += chrono::Duration::seconds(1);
+    items.push(login("aws", "AWS", "alice"));
+
+    // The new item is listed first, the edited one stays selected
+    let changes = state.load_items_with_secrets(items);
+    assert_eq!(changes.summary().unwrap(), "1 item updated, 1 added");
+    assert_eq!(state.selected_item().unwrap().id, "git");
+    assert_snapshot("sync_changed_selected_item", &render(&mut state, 100, 24));
+
+    state.select_next();
+    assert!(!state.details_refreshed());
+}
+
+#[test]
+fn test_status_bar_message() {
+    let mut state = vault_state();
+    state.set_status("✓ Copied password to clipboard", MessageLevel::Success);
+    let screen = render(&mut state, 100, 24);
+    let status_bar: Vec<&str> = screen.lines().skip_while(|line| !line.contains("Copied password")).collect();
+    assert_snapshot("status_bar_message", &(status_bar.join("\n") + "\n"));
+}
+
+#[test]
+fn test_quick_copy_numbers() {
+    let mut state = vault_state();
+    state.set_filter_query("a".to_string());
+    assert_snapshot("quick_copy_numbers", &render(&mut state, 100, 24));
+}
+
+#[test]
+fn test_picker() {
+    let mut state = vault_state();
+    state.ui.pick = Some(crate::args::PickField::Password);
+    state.set_filter_query("a".to_string());
+    assert_snapshot("picker", &render(&mut state, 80, 16));
+}
+
+#[test]
+fn test_too_small_screen() {
+    let mut state = vault_state();
+    assert_snapshot("too_small_screen", &render(&mut state, 30, 8));
+    assert!(state.ui.click_regions.action_at(5, 5).is_none());
+}
+
+#[test]
+fn test_password_dialog() {
+    let mut state = vault_state();
+    state.enter_password_mode();
+    for c in "secret".chars() {
+        state.ui.password_input.insert(c);
+    }
+    assert_snapshot("password_dialog", &render(&mut state, 80, 24));
+}
+
+#[test]
+fn test_password_dialog_after_wrong_attempts() {
+    let mut state = vault_state();
+    state.enter_password_mode();
+    state.ui.max_unlock_attempts = 5;
+    for _ in 0..2 {
+        state.ui.record_unlock_failure(std::time::Instant::now());
+    }
+    state.set_unlock_error("Invalid master password".to_string());
+    assert_snapshot("password_dialog_wrong_attempts", &render(&mut state, 80, 30));
+}
+
+#[test]
+fn test_copy_as_dialog() {
+    let mut state = vault_state();
+    state.open_copy_as();
+    assert_snapshot("copy_as_dialog", &render(&mut state, 100, 24));
+}
+
+#[test]
+fn test_local_note() {
+    let mut state = vault_state();
+    state.open_local_note(Annotation::Note);
+    state.local_note_input().unwrap().insert_str("Use the VPN first");
+    assert_eq!(state.save_local_note(), Some(Annotation::Note));
+    assert_snapshot("local_note_details", &render(&mut state, 100, 24));
+
+    state.open_local_note(Annotation::Note);
+    assert_snapshot("local_note_dialog", &render(&mut state, 100, 24));
+}
+
+#[test]
+fn test_local_tags() {
+    let mut state = vault_state();
+    state.open_local_note(Annotation::Tags);
+    state.local_note_input().unwrap().insert_str("Work, #vpn");
+    assert_eq!(state.save_local_note(), Some(Annotation::Tags));
+    // The same tags in another order don't change anything
+    state.open_local_note(Annotation::Tags);
+    assert_eq!(state.local_note_input().unwrap().text(), "vpn, work");
+    state.local_note_input().unwrap().set("work vpn".to_string());
+    assert_eq!(state.save_local_note(), None);
+    state.select_index(2);
+    state.open_local_note(Annotation::Tags);
+    state.local_note_input().unwrap().insert_str("work");
+    state.save_local_note();
+    assert_snapshot("local_tags_list", &render(&mut state, 100, 24));
+
+    state.open_tag_list();
+    state.select_next_tag();
+    assert_snapshot("tag_list_dialog", &render(&mut state, 100, 24));
+
+    state.filter_by_selected_tag();
+    assert!(!state.tag_list_open());
+    assert_eq!(state.vault.filter_query.text(), "tag:work");
+    assert_eq!(state.vault.filtered_len(), 2);
+}
+
+#[test]
+fn test_hidden_items() {
+    let mut state = vault_state();
+    state.select_index(1);
+    assert!(state.toggle_item_hidden());
+    assert_eq!(state.vault.filtered_len(), 2);
+
+    state.toggle_show_hidden();
+    state.select_index(1);
+    assert_snapshot("hidden_items_shown", &render(&mut state, 100, 24));
+}
+
+#[test]
+fn test_folder_screen() {
+    let mut state = vault_state();
+    for item in state.vault.vault_items.iter_mut().filter(|item| item.id != "git") {
+        item.folder_id = Some("personal".to_string());
+    }
+    state.set_folders(vec![
+        Folder { id: "personal".to_string(), name: "Personal".to_string() },
+        Folder { id: "archive".to_string(), name: "Archive".to_string() },
+    ]);
+    state.toggle_folders();
+    state.select_next_folder();
+    state.request_delete_folder();
+    assert_snapshot("folder_screen_delete", &render(&mut state, 100, 24));
+
+    // Enter lists the folder's items
+    state.folders.confirm_delete = false;
+    state.filter_by_selected_folder();
+    assert!(!state.show_folders());
+    assert_eq!(state.vault.filtered_len(), 2);
+}
+
+#[test]
+fn test_collection_access_details() {
+    let mut state = vault_state();
+    state.set_organizations(vec![Organization { id: "acme".to_string(), name: "Acme".to_string() }]);
+    let bank = state.vault.vault_items.iter_mut().find(|item| item.id == "bank").unwrap();
+    bank.organization_id = Some("acme".to_string());
+    bank.collection_ids = Some(vec!["finance".to_string(), "ops".to_string()]);
+    state.set_collections(vec![
+        Collection { id: "finance".to_string(), organization_id: "acme".to_string(), name: "Finance".to_string() },
+        Collection { id: "ops".to_string(), organization_id: "acme".to_string(), name: "Ops".to_string() },
+    ]);
+    state.select_index(1);
+    assert_eq!(state.start_access_lookup().len(), 2);
+
+    let member = |name: &str| OrgMember { id: name.to_lowercase(), name: Some(name.to_string()), email: format!("{}@acme.example", name.to_lowercase()) };
+    state.collection_access.store("finance", AccessLookup::Loaded(CollectionAccess { members: vec![member("Alice"), member("Bob")], groups: 2 }));
+    state.collection_access.store("ops", AccessLookup::Failed("You do not have permission".to_string()));
+    assert_snapshot("collection_access_details", &render(&mut state, 100, 24));
+}
+
+#[test]
+fn test_status_bar_stacks_messages() {
+    let mut state = vault_state();
+    state.set_status("✗ Sync failed", MessageLevel::Error);
+    state.set_status("✓ Copied password to clipboard", MessageLevel::Success);
+    state.set_status("Syncing...", MessageLevel::Info);
+    // Progress is replaced rather than stacked
+    state.set_status("Checking vault status...", MessageLevel::Info);
+    let screen = render(&mut state, 100, 24);
+    let status_bar: Vec<&str> = screen.lines().skip_while(|line| !line.contains("Sync failed")).collect();
+    assert_snapshot("status_bar_stacked", &(status_bar.join("\n") + "\n"));
+
+    // The error outlasts the newer messages
+    let earlier = std::time::Instant::now() - std::time::Duration::from_secs(4);
+    for status in state.stacked_status.iter_mut().chain(state.status_message.as_mut()) {
+        status.timestamp = earlier;
+    }
+    state.expire_old_status();
+    assert_eq!(state.status_message.as_ref().map(|status| status.text.as_str()), Some("✗ Sync failed"));
+    assert!(state.stacked_status.is_empty());
+}
+
+#[test]
+fn test_confirm_quit_dialog() {
+    let mut state = vault_state();
+    state.start_sync();
+    state.ui.confirm_quit = true;
+    assert_snapshot("confirm_quit_dialog", &render(&mut state, 100, 24));
+}
+
+#[test]
+fn test_sync_indicator_opens_sync_status() {
+    let mut state = vault_state();
+    state.sync.record(SyncOutcome::Failed("bw sync timed out after 30s".to_string()));
+    state.sync.record(SyncOutcome::Synced { items: 3, changes: Some(SyncChanges { added: 1, ..SyncChanges::default() }) });
+    state.start_sync();
+    let screen = render(&mut state, 100, 24);
+
+    // The indicator sits at the end of the list's top border
+    let (row, line) = screen.lines().enumerate().find(|(_, line)| line.contains("Syncing...")).unwrap();
+    let column = line[..line.find("Syncing...").unwrap()].width() as u16;
+    let action = state.ui.click_regions.action_at(column, row as u16).unwrap();
+    assert!(matches!(action, Action::ToggleSyncDetails));
+
+    crate::actions::handle_ui(&action, &mut state);
+    let screen = render(&mut state, 100, 24);
+    assert!(screen.contains("Sync Status"), "{}", screen);
+    assert!(screen.contains("3 items, 1 item added"), "{}", screen);
+    assert!(screen.contains("Last error:"), "{}", screen);
+}
+
+#[test]
+fn test_reprompt_item() {
+    let mut state = vault_state();
+    state.set_item_reprompt("bank", true);
+    state.select_index(1);
+    assert_snapshot("reprompt_item", &render(&mut state, 100, 24));
+}
+
+#[test]
+fn test_share_dialog() {
+    let mut state = vault_state();
+    state.set_organizations(vec![
+        Organization { id: "acme".to_string(), name: "Acme".to_string() },
+        Organization { id: "globex".to_string(), name: "Globex".to_string() },
+    ]);
+    state.set_collections(vec![
+        Collection { id: "ops".to_string(), organization_id: "acme".to_string(), name: "Ops".to_string() },
+        Collection { id: "finance".to_string(), organization_id: "acme".to_string(), name: "Finance".to_string() },
+        Collection { id: "sales".to_string(), organization_id: "globex".to_string(), name: "Sales".to_string() },
+    ]);
+    state.select_index(1);
+    state.open_share();
+    state.toggle_share_collection();
+    state.select_next_share_collection();
+    state.toggle_share_collection();
+    state.request_share();
+    assert_snapshot("share_dialog", &render(&mut state, 100, 24));
+}
+
+#[test]
+fn test_details_scroll_ends_at_last_line() {
+    let mut state = vault_state();
+    render(&mut state, 100, 24);
+    for _ in 0..50 {
+        state.scroll_details_down();
+    }
+    let screen = render(&mut state, 100, 24);
+
+    // Scrolled down all the way, the last wrapped line of the notes sits right above the bottom border
+    let rows: Vec<&str> = screen.lines().collect();
+    let bottom = rows.iter().position(|row| row.contains("Shift+")).unwrap();
+    assert!(rows[bottom - 1].contains("PIN"), "{}", screen);
+    assert_eq!(state.ui.details_panel_scroll, state.ui.details_panel_max_scroll);
+}
+
+#[test]
+fn test_click_regions_follow_wrapped_notes() {
+    // The wrapped notes push the custom field down, its click region must follow
+    let mut state = vault_state();
+    let screen = render(&mut state, 100, 40);
+    let pin_row = screen.lines().position(|line| line.contains("PIN")).unwrap() as u16;
+    let pin_column = screen.lines().nth(pin_row as usize).unwrap().find("PIN").unwrap() as u16;
+
+    assert!(matches!(
+        state.ui.click_regions.action_at(pin_column, pin_row),
+        Some(Action::CopyDetailField(DetailField::CustomField(0)))
+    ));
+    // Dialogs block clicks on the panels
+    state.enter_password_mode();
+    render(&mut state, 100, 40);
+    assert!(state.ui.click_regions.action_at(pin_column, pin_row).is_none());
+}
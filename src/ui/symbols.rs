@@ -0,0 +1,211 @@
+use crate::config::IconSet;
+use crate::state::{AppState, MessageLevel};
+use crate::types::ItemType;
+use ratatui::buffer::Buffer;
+use ratatui::style::Color;
+use unicode_width::UnicodeWidthStr;
+
+/// Glyphs drawn by the widgets and dialogs
+pub struct Symbols {
+    pub selected: &'static str,
+    pub favorite: &'static str,
+    pub bullet: &'static str,
+    pub mask: &'static str,
+    pub mask_char: char,
+    pub card_mask: &'static str,
+    pub cvv_mask: &'static str,
+    pub linked: &'static str,
+    pub up_down: &'static str,
+    pub separator: &'static str,
+    pub gauge_filled: &'static str,
+    pub gauge_empty: &'static str,
+    pub scroll_up: &'static str,
+    pub scroll_down: &'static str,
+    pub scroll_track: &'static str,
+    pub scroll_thumb: &'static str,
+}
+
+pub const UNICODE: Symbols = Symbols {
+    selected: "► ",
+    favorite: "★ ",
+    bullet: "•",
+    mask: "••••••••",
+    mask_char: '•',
+    card_mask: "••••-••••-••••-••••",
+    cvv_mask: "•••",
+    linked: "→",
+    up_down: "↑↓",
+    separator: "·",
+    gauge_filled: "█",
+    gauge_empty: "░",
+    scroll_up: "↑",
+    scroll_down: "↓",
+    scroll_track: "│",
+    scroll_thumb: "█",
+};
+
+/// Plain ASCII replacements, read correctly by screen readers and any terminal font
+pub const ASCII: Symbols = Symbols {
+    selected: "> ",
+    favorite: "* ",
+    bullet: "-",
+    mask: "********",
+    mask_char: '*',
+    card_mask: "****-****-****-****",
+    cvv_mask: "***",
+    linked: "->",
+    up_down: "Up/Down",
+    separator: "|",
+    gauge_filled: "#",
+    gauge_empty: "-",
+    scroll_up: "^",
+    scroll_down: "v",
+    scroll_track: "|",
+    scroll_thumb: "#",
+};
+
+/// Status message prefixes that only make sense as pictures
+const STATUS_GLYPHS: [&str; 5] = ["✓", "✗", "⏳", "⚠", "⟳"];
+
+/// The glyphs for the current accessibility setting
+pub fn symbols(state: &AppState) -> &'static Symbols {
+    if state.ui.accessible {
+        &ASCII
+    } else {
+        &UNICODE
+    }
+}
+
+/// The configured icon set, always ASCII in accessibility mode
+pub fn icon_set(state: &AppState) -> IconSet {
+    if state.ui.accessible {
+        IconSet::Ascii
+    } else {
+        state.ui.list.icons
+    }
+}
+
+/// Icon shown before an item's name in the entry list
+pub fn item_type_icon(item_type: ItemType, icons: IconSet) -> &'static str {
+    match (icons, item_type) {
+        (IconSet::Emoji, ItemType::Login) => "🔑",
+        (IconSet::Emoji, ItemType::SecureNote) => "📝",
+        (IconSet::Emoji, ItemType::Card) => "💳",
+        (IconSet::Emoji, ItemType::Identity) => "👤",
+        (IconSet::Emoji, ItemType::Unknown(_)) => "❔",
+        (IconSet::NerdFont, ItemType::Login) => "\u{f084}", // nf-fa-key
+        (IconSet::NerdFont, ItemType::SecureNote) => "\u{f249}", // nf-fa-sticky_note
+        (IconSet::NerdFont, ItemType::Card) => "\u{f09d}", // nf-fa-credit_card
+        (IconSet::NerdFont, ItemType::Identity) => "\u{f007}", // nf-fa-user
+        (IconSet::NerdFont, ItemType::Unknown(_)) => "\u{f128}", // nf-fa-question
+        (IconSet::Ascii, ItemType::Login) => "[Login]",
+        (IconSet::Ascii, ItemType::SecureNote) => "[Note]",
+        (IconSet::Ascii, ItemType::Card) => "[Card]",
+        (IconSet::Ascii, ItemType::Identity) => "[Identity]",
+        (IconSet::Ascii, ItemType::Unknown(_)) => "[Unknown]",
+    }
+}
+
+/// The item type icon padded to the widest icon of the set, so names line up
+pub fn item_type_icon_column(item_type: ItemType, icons: IconSet) -> String {
+    let width = [ItemType::Login, ItemType::SecureNote, ItemType::Card, ItemType::Identity, ItemType::Unknown(0)]
+        .into_iter()
+        .map(|item_type| item_type_icon(item_type, icons).width())
+        .max()
+        .unwrap_or(0);
+    let icon = item_type_icon(item_type, icons);
+    format!("{}{}", icon, " ".repeat(width - icon.width()))
+}
+
+/// Icon shown before folder names
+pub fn folder_icon(icons: IconSet) -> &'static str {
+    match icons {
+        IconSet::Emoji => "📁",
+        IconSet::NerdFont => "\u{f07b}", // nf-fa-folder
+        IconSet::Ascii => "Folder:",
+    }
+}
+
+/// Icon shown after the names of items that ask for the master password again
+pub fn reprompt_icon(icons: IconSet) -> &'static str {
+    match icons {
+        IconSet::Emoji => "🔒",
+        IconSet::NerdFont => "\u{f023}", // nf-fa-lock
+        IconSet::Ascii => "[re-prompt]",
+    }
+}
+
+/// Drop the glyph a message starts with
+pub fn strip_status_glyph(text: &str) -> &str {
+    STATUS_GLYPHS
+        .iter()
+        .find_map(|glyph| text.strip_prefix(glyph))
+        .map(str::trim_start)
+        .unwrap_or(text)
+}
+
+/// A status message as displayed, with its level spelled out instead of a glyph in accessibility mode
+pub fn status_text(text: &str, level: MessageLevel, accessible: bool) -> String {
+    if accessible {
+        format!("{}: {}", level.label(), strip_status_glyph(text))
+    } else {
+        text.to_string()
+    }
+}
+
+/// Brighten dimmed text so it stays readable with low vision or low contrast terminals
+pub fn raise_contrast(buffer: &mut Buffer) {
+    for cell in buffer.content.iter_mut() {
+        match cell.fg {
+            Color::DarkGray => cell.fg = Color::Gray,
+            Color::Gray => cell.fg = Color::White,
+            _ => {}
+        }
+        if cell.bg == Color::DarkGray {
+            cell.bg = Color::Blue;
+            cell.fg = Color::White;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ITEM_TYPES: [ItemType; 5] = [ItemType::Login, ItemType::SecureNote, ItemType::Card, ItemType::Identity, ItemType::Unknown(9)];
+
+    #[test]
+    fn test_ascii_symbols_are_ascii() {
+        let ascii = [
+            ASCII.selected, ASCII.favorite, ASCII.bullet, ASCII.mask, ASCII.card_mask,
+            ASCII.cvv_mask, ASCII.linked, ASCII.up_down, ASCII.separator, ASCII.gauge_filled,
+            ASCII.gauge_empty, ASCII.scroll_up, ASCII.scroll_down, ASCII.scroll_track, ASCII.scroll_thumb,
+        ];
+        assert!(ascii.iter().all(|symbol| symbol.is_ascii()));
+        assert!(ASCII.mask_char.is_ascii());
+        for item_type in ITEM_TYPES {
+            assert!(item_type_icon(item_type, IconSet::Ascii).is_ascii());
+        }
+        assert!(folder_icon(IconSet::Ascii).is_ascii());
+        assert!(reprompt_icon(IconSet::Ascii).is_ascii());
+    }
+
+    #[test]
+    fn test_icon_columns_have_the_same_width() {
+        for icons in [IconSet::Emoji, IconSet::NerdFont, IconSet::Ascii] {
+            let widths: Vec<usize> = ITEM_TYPES.iter().map(|item_type| item_type_icon_column(*item_type, icons).width()).collect();
+            assert!(widths.iter().all(|width| *width == widths[0]), "{:?}: {:?}", icons, widths);
+        }
+        // Emoji take two cells
+        assert_eq!(item_type_icon_column(ItemType::Login, IconSet::Emoji).width(), 2);
+        assert_eq!(item_type_icon_column(ItemType::SecureNote, IconSet::Ascii), "[Note]    ");
+    }
+
+    #[test]
+    fn test_status_text() {
+        assert_eq!(status_text("✓ Password copied", MessageLevel::Success, true), "OK: Password copied");
+        assert_eq!(status_text("⏳ Please wait", MessageLevel::Warning, true), "WARN: Please wait");
+        assert_eq!(status_text("Vault synced", MessageLevel::Info, true), "INFO: Vault synced");
+        assert_eq!(status_text("✗ Failed", MessageLevel::Error, false), "✗ Failed");
+    }
+}
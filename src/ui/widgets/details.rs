@@ -1,39 +1,89 @@
-use crate::state::AppState;
-use crate::ui::widgets::clickable::{Clickable, is_click_in_area};
-use crossterm::event::MouseEvent;
+use crate::state::{AccessLookup, AppState, DetailField};
+use crate::types::{CustomFieldValue, UriMatchType};
+use crate::ui::layout::{panel_block, panel_inner};
+use crate::ui::symbols::{self, Symbols};
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap, Scrollbar, ScrollbarOrientation, ScrollbarState},
+    widgets::{Paragraph, Wrap, Scrollbar, ScrollbarOrientation, ScrollbarState},
     Frame,
 };
 
 pub fn render(frame: &mut Frame, area: Rect, state: &mut AppState) {
+    let compact = state.ui.layout.compact;
     if let Some(item) = state.selected_item() {
-        // Generate all content lines
+        // Generate all content lines, remembering which line shows each focusable field
         let mut lines = Vec::new();
+        let mut field_lines: Vec<(DetailField, usize)> = Vec::new();
+        let symbols = symbols::symbols(state);
         
         // Title/Name
         lines.push(Line::from(vec![
             Span::styled("Name: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
             Span::styled(&item.name, Style::default().fg(Color::White)),
         ]));
+        if let Some(organization_id) = &item.organization_id {
+            let organization = state.vault.organization_name(organization_id).unwrap_or("Unknown organization");
+            lines.push(Line::from(vec![
+                Span::styled("Organization: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+                Span::styled(organization, Style::default().fg(Color::Magenta)),
+            ]));
+            render_collections(&mut lines, item, state);
+        }
+        if let Some(folder) = state.vault.folder_name(item) {
+            lines.push(Line::from(vec![
+                Span::styled("Folder: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+                Span::styled(folder, Style::default().fg(Color::White)),
+            ]));
+        }
+        // Written with Alt+N and Alt+G, kept on this computer
+        if let Some(note) = state.vault.local_note(&item.id) {
+            lines.push(Line::from(vec![
+                Span::styled("Local note: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+                Span::styled(note, Style::default().fg(Color::Yellow)),
+                Span::styled(" [M-N:Edit]", Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        if let Some(tags) = state.vault.item_tags(&item.id) {
+            let tags: Vec<String> = tags.iter().map(|tag| format!("#{}", tag)).collect();
+            lines.push(Line::from(vec![
+                Span::styled("Tags: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+                Span::styled(tags.join(" "), Style::default().fg(Color::LightBlue)),
+                Span::styled(" [M-G:Edit]", Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        if item.requires_reprompt() {
+            lines.push(Line::from(vec![
+                Span::styled("Master password re-prompt: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+                Span::styled("on", Style::default().fg(Color::Yellow)),
+                Span::styled(" [M-R:Turn off]", Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        if state.vault.is_hidden(&item.id) {
+            lines.push(Line::from(vec![
+                Span::styled("Hidden from the list and search", Style::default().fg(Color::DarkGray)),
+                Span::styled(" [M-H:Unhide]", Style::default().fg(Color::DarkGray)),
+            ]));
+        }
         lines.push(Line::from(""));
         
         // Render type-specific content
         match item.item_type {
             crate::types::ItemType::Login => {
-                render_login_details(&mut lines, item, state);
+                render_login_details(&mut lines, &mut field_lines, item, state);
             }
             crate::types::ItemType::SecureNote => {
                 render_secure_note_details(&mut lines, item, state);
             }
             crate::types::ItemType::Card => {
-                render_card_details(&mut lines, item, state);
+                render_card_details(&mut lines, &mut field_lines, item, state);
             }
             crate::types::ItemType::Identity => {
-                render_identity_details(&mut lines, item, state);
+                render_identity_details(&mut lines, &mut field_lines, item, state);
+            }
+            crate::types::ItemType::Unknown(value) => {
+                render_unknown_details(&mut lines, item, value, state);
             }
         }
         
@@ -46,11 +96,19 @@ pub fn render(frame: &mut Frame, area: Rect, state: &mut AppState) {
             ]));
         } else if let Some(notes) = &item.notes {
             if !notes.is_empty() {
-                lines.push(Line::from(Span::styled("Notes: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))));
+                let view = if state.ui.raw_notes { "[M-M:Formatted]" } else { "[M-M:Raw]" };
+                lines.push(Line::from(vec![
+                    Span::styled("Notes: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+                    Span::styled(view, Style::default().fg(Color::DarkGray)),
+                ]));
                 
-                // Split notes by newlines and display all lines
-                for line in notes.lines() {
-                    lines.push(Line::from(Span::styled(line, Style::default().fg(Color::White))));
+                // Render the markdown of structured notes, or show them as written
+                if state.ui.raw_notes {
+                    for line in notes.lines() {
+                        lines.push(Line::from(Span::styled(line, Style::default().fg(Color::White))));
+                    }
+                } else {
+                    lines.extend(crate::ui::markdown::render(notes, symbols.bullet));
                 }
             }
         }
@@ -68,48 +126,73 @@ pub fn render(frame: &mut Frame, area: Rect, state: &mut AppState) {
                 lines.push(Line::from(""));
                 lines.push(Line::from(Span::styled("Custom Fields: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))));
                 
-                for field in fields.iter() {
-                    if let (Some(name), Some(value)) = (&field.name, &field.value) {
-                        if !name.is_empty() && !value.is_empty() {
-                            lines.push(Line::from(vec![
-                                Span::styled("  • ", Style::default().fg(Color::DarkGray)),
+                for (index, field) in fields.iter().enumerate() {
+                    if let (Some(name), Some(value)) = (&field.name, item.custom_field_value(field)) {
+                        if !name.is_empty() && !value.text().is_empty() {
+                            let revealed = state.revealed_detail_field() == Some(DetailField::CustomField(index));
+                            let mut spans = vec![
+                                Span::styled(format!("  {} ", symbols.bullet), Style::default().fg(Color::DarkGray)),
                                 Span::styled(format!("{}: ", name), Style::default().fg(Color::Cyan)),
-                                Span::styled(value, Style::default().fg(Color::White)),
-                            ]));
+                            ];
+                            spans.extend(custom_field_value_spans(value, revealed, symbols));
+                            lines.push(Line::from(spans));
+                            field_lines.push((DetailField::CustomField(index), lines.len() - 1));
                         }
                     }
                 }
             }
         }
         
-        // Calculate the actual content height after wrapping
-        let available_width = area.width.saturating_sub(2); // Account for borders
-        let available_height = area.height.saturating_sub(2); // Account for borders
+        let inner = panel_inner(area, compact);
         
-        // Calculate how many lines the content will actually take after wrapping
-        let content_height = lines.iter().map(|line| {
-            let line_width = line.width() as u16;
-            if line_width > available_width {
-                (line_width / available_width) + 1
-            } else {
-                1
+        // Highlight the focused field, if any
+        let focused_line = state.focused_detail_field().and_then(|focused| {
+            field_lines.iter().find(|(field, _)| *field == focused).map(|(_, index)| *index)
+        });
+        if let Some(index) = focused_line {
+            lines[index].style = Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD);
+            // The highlight is only a color, mark the line too
+            if state.ui.accessible {
+                lines[index].spans.insert(0, Span::raw(symbols.selected));
             }
-        }).sum::<u16>() as usize;
+        }
         
-        let max_visible_lines = available_height as usize;
+        // Calculate how many rows each line will actually take after wrapping
+        let wrapped_heights = |width: u16| -> Vec<usize> {
+            lines
+                .iter()
+                .map(|line| Paragraph::new(line.clone()).wrap(Wrap { trim: false }).line_count(width).max(1))
+                .collect()
+        };
+        let max_visible_lines = inner.height as usize;
+        let mut text_width = inner.width;
+        let mut line_heights = wrapped_heights(text_width);
         
         // Determine if scrollbar will be shown
-        let scrollbar_visible = content_height > max_visible_lines;
+        let scrollbar_visible = line_heights.iter().sum::<usize>() > max_visible_lines;
+        
+        // Without borders the scrollbar takes the last column, so the text wraps one column earlier
+        if compact && scrollbar_visible {
+            text_width = text_width.saturating_sub(1);
+            line_heights = wrapped_heights(text_width);
+        }
+        let line_rows = |index: usize| -> usize { line_heights[..index].iter().sum() };
+        let content_height = line_heights.iter().sum::<usize>();
+        let focused_row = focused_line.map(line_rows);
         
         // Create the block with conditional scroll shortcut
-        let mut block = Block::default()
-            .borders(Borders::ALL)
-            .title(" Details ")
-            .border_style(Style::default().fg(Color::Cyan));
+        let color = if state.details_focused() { Color::Yellow } else { Color::Cyan };
+        let mut block = panel_block(compact, Style::default().fg(color)).title(" Details ");
+        if state.details_refreshed() {
+            block = block.title(Span::styled(" Updated by the last sync ", Style::default().fg(Color::Black).bg(Color::Green)));
+        }
         
-        // Add scroll shortcut at bottom when scrollbar is visible
-        if scrollbar_visible {
-            block = block.title_bottom(Line::from(" Shift+↑↓:Scroll "));
+        // Show field navigation shortcuts while focused, scroll shortcut when scrollbar is visible
+        // (the compact layout leaves them to the status bar)
+        if !compact && state.details_focused() {
+            block = block.title_bottom(Line::from(format!(" {}:Field Enter:Copy Esc:Back ", symbols.up_down)));
+        } else if !compact && scrollbar_visible {
+            block = block.title_bottom(Line::from(format!(" Shift+{}:Scroll ", symbols.up_down)));
         }
         
         // Create the paragraph
@@ -117,180 +200,128 @@ pub fn render(frame: &mut Frame, area: Rect, state: &mut AppState) {
             .block(block)
             .wrap(Wrap { trim: false });
         
-        // Calculate maximum scroll position based on actual content height
-        // Allow some overscroll to ensure scrollbar reaches the bottom
-        let max_scroll = if content_height > max_visible_lines {
-            content_height - max_visible_lines + 3
-        } else {
-            0
-        };
+        // The last row of the content ends at the bottom of the panel when scrolled down all the way
+        let max_scroll = content_height.saturating_sub(max_visible_lines);
         
         // Get current scroll position and clamp it
-        let scroll_offset = state.ui.details_panel_scroll.min(max_scroll);
+        let mut scroll_offset = state.ui.details_panel_scroll.min(max_scroll);
+        
+        // Keep the focused field visible
+        if let Some(row) = focused_row {
+            if row < scroll_offset {
+                scroll_offset = row;
+            } else if row >= scroll_offset + max_visible_lines {
+                scroll_offset = (row + 1).saturating_sub(max_visible_lines).min(max_scroll);
+            }
+        }
         
         // Apply scrolling to the paragraph
         let scrolled_paragraph = paragraph.scroll((scroll_offset as u16, 0));
         
         // Render the paragraph
-        frame.render_widget(scrolled_paragraph, area);
+        let text_area = Rect { width: area.width - (inner.width - text_width), ..area };
+        frame.render_widget(scrolled_paragraph, text_area);
         
         // Render scrollbar if content overflows
-        if content_height > max_visible_lines {
-            // The scrollbar should represent the total content height
-            // With overscroll, we can use the scroll offset directly
-            let mut scrollbar_state = ScrollbarState::new(content_height)
+        if scrollbar_visible {
+            // Positions run from the top to max_scroll, and the thumb covers the visible share of the content
+            let mut scrollbar_state = ScrollbarState::new(max_scroll + 1)
+                .viewport_content_length(max_visible_lines)
                 .position(scroll_offset);
             
             let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
-                .begin_symbol(Some("↑"))
-                .end_symbol(Some("↓"))
-                .track_symbol(Some("│"))
-                .thumb_symbol("█");
+                .begin_symbol(Some(symbols.scroll_up))
+                .end_symbol(Some(symbols.scroll_down))
+                .track_symbol(Some(symbols.scroll_track))
+                .thumb_symbol(symbols.scroll_thumb);
             
-            frame.render_stateful_widget(scrollbar, area, &mut scrollbar_state);
+            // Over the right border, or the last column below the title in the compact layout
+            let scrollbar_area = if compact { inner } else { area };
+            frame.render_stateful_widget(scrollbar, scrollbar_area, &mut scrollbar_state);
+        }
+        
+        // Register each field's rows that are visible after scrolling as a click region
+        for (field, index) in &field_lines {
+            let top = line_rows(*index) as i64 - scroll_offset as i64;
+            let bottom = (top + line_heights[*index] as i64).min(max_visible_lines as i64);
+            let top = top.max(0);
+            if top < bottom {
+                let region = Rect::new(inner.x, inner.y + top as u16, text_width, (bottom - top) as u16);
+                state.ui.click_regions.push(region, field_click_action(*field, state));
+            }
         }
-        
+
         // Update state with the calculated max scroll after rendering
         state.set_details_max_scroll(max_scroll);
+        state.ui.details_panel_scroll = scroll_offset;
     } else {
-        // No item selected
-        let paragraph = Paragraph::new("No item selected")
+        // No item selected, or none loaded yet
+        let message = if !state.initial_load_complete() && state.syncing() {
+            format!("{} Loading vault...", state.sync_spinner())
+        } else {
+            "No item selected".to_string()
+        };
+        let paragraph = Paragraph::new(message)
             .style(Style::default().fg(Color::DarkGray))
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title(" Details ")
-                    .border_style(Style::default().fg(Color::DarkGray)),
-            );
+            .block(panel_block(compact, Style::default().fg(Color::DarkGray)).title(" Details "));
         
         frame.render_widget(paragraph, area);
     }
 }
 
 
-/// Details panel click handler
-pub struct DetailsClickHandler;
+/// Action triggered by clicking a field in the details panel
+fn field_click_action(field: DetailField, state: &AppState) -> crate::events::Action {
+    // Clicking a TOTP that hasn't been loaded yet fetches it
+    if field == DetailField::Totp && state.current_totp_code().is_none() {
+        return crate::events::Action::FetchTotp;
+    }
+    crate::events::Action::CopyDetailField(field)
+}
 
-impl Clickable for DetailsClickHandler {
-    fn handle_click(&self, mouse: MouseEvent, state: &AppState, area: Rect) -> Option<crate::events::Action> {
-        if !is_click_in_area(mouse, area) {
-            return None;
-        }
+/// The collections an organization item is shared in, with who can access them once looked up with Alt+A
+fn render_collections<'a>(lines: &mut Vec<Line<'a>>, item: &crate::types::VaultItem, state: &'a AppState) {
+    let collections = state.vault.item_collections(item);
+    if collections.is_empty() {
+        return;
+    }
+    let names: Vec<&str> = collections.iter().map(|collection| collection.name.as_str()).collect();
+    lines.push(Line::from(vec![
+        Span::styled("Collections: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::styled(names.join(", "), Style::default().fg(Color::Magenta)),
+        Span::styled(" [M-A:Access]", Style::default().fg(Color::DarkGray)),
+    ]));
 
-        let selected_item = state.selected_item()?;
-        let login = selected_item.login.as_ref()?;
-        
-        // Calculate relative position within the details panel
-        let relative_y = mouse.row.saturating_sub(area.y);
-        let relative_x = mouse.column.saturating_sub(area.x);
-        
-        // Account for border (1 line at top)
-        if relative_y == 0 {
-            return None;
-        }
-        
-        // Adjust for scroll offset
-        let scroll_offset = state.ui.details_panel_scroll;
-        let content_line = (relative_y - 1) as usize + scroll_offset;
-        
-        // Generate the same content structure as the render function to find clickable areas
-        let mut lines = Vec::new();
-        
-        // Title/Name (2 lines: label + blank)
-        lines.push(Line::from(""));
-        lines.push(Line::from(""));
-        
-        // Username section
-        if login.username.is_some() {
-            lines.push(Line::from("")); // Username line
-            lines.push(Line::from("")); // Blank line
-        } else {
-            lines.push(Line::from("")); // Username line (no button)
-            lines.push(Line::from("")); // Blank line
-        }
-        
-        // Password section
-        if login.password.is_some() {
-            lines.push(Line::from("")); // Password line
-            lines.push(Line::from("")); // Blank line
-        } else {
-            lines.push(Line::from("")); // Password line (no button)
-            lines.push(Line::from("")); // Blank line
-        }
-        
-        // TOTP section
-        if login.totp.is_some() {
-            lines.push(Line::from("")); // TOTP line
-            lines.push(Line::from("")); // Blank line
-        } else {
-            lines.push(Line::from("")); // TOTP line (no button)
-            lines.push(Line::from("")); // Blank line
-        }
-        
-        // Check if we're clicking on a clickable line
-        let mut current_line = 0;
-        
-        // Name (2 lines: label + blank)
-        current_line += 2;
-        
-        // Username section
-        if login.username.is_some() {
-            if content_line == current_line {
-                // Calculate approximate position of [^U] at end of line
-                let username_len = login.username.as_ref().unwrap().len() as u16;
-                let shortcut_start = 10 + username_len + 2; // After "Username: " + username + " ["
-                let shortcut_end = shortcut_start + 3; // "[^U]" is 4 characters
-                
-                if relative_x >= shortcut_start && relative_x <= shortcut_end {
-                    return Some(crate::events::Action::CopyUsername);
-                }
+    for collection in collections {
+        let access = match state.collection_access.get(&collection.id) {
+            None => continue,
+            Some(AccessLookup::Loading) => {
+                Span::styled(format!("{} Loading...", state.sync_spinner()), Style::default().fg(Color::Yellow))
             }
-            current_line += 2; // label + blank
-        } else {
-            current_line += 2; // label + blank (no button)
-        }
-        
-        // Password section
-        if login.password.is_some() {
-            if content_line == current_line {
-                // Calculate approximate position of [^P] at end of line
-                let shortcut_start = 20; // After "Password: •••••••• ["
-                let shortcut_end = shortcut_start + 3; // "[^P]" is 4 characters
-                
-                if relative_x >= shortcut_start && relative_x <= shortcut_end {
-                    return Some(crate::events::Action::CopyPassword);
+            Some(AccessLookup::Failed(error)) => Span::styled(format!("✗ {}", error), Style::default().fg(Color::Red)),
+            Some(AccessLookup::Loaded(access)) => {
+                let mut who: Vec<String> = access.members.iter().map(|member| member.label()).collect();
+                match access.groups {
+                    0 => {}
+                    1 => who.push("1 group".to_string()),
+                    groups => who.push(format!("{} groups", groups)),
                 }
-            }
-            current_line += 2; // label + blank
-        } else {
-            current_line += 2; // label + blank (no button)
-        }
-        
-        // TOTP section
-        if login.totp.is_some() {
-            if content_line == current_line {
-                // Check if we have a TOTP code displayed
-                if state.current_totp_code().is_some() {
-                    // Calculate approximate position of [^T] at end of line
-                    let shortcut_start = 19; // After "TOTP: 123456 (Xs) ["
-                    let shortcut_end = shortcut_start + 3; // "[^T]" is 4 characters
-                    
-                    if relative_x >= shortcut_start && relative_x <= shortcut_end {
-                        return Some(crate::events::Action::CopyTotp);
-                    }
+                if who.is_empty() {
+                    Span::styled("Only the organization's managers", Style::default().fg(Color::DarkGray))
                 } else {
-                    // No TOTP code displayed, clicking anywhere on the line should fetch it
-                    return Some(crate::events::Action::FetchTotp);
+                    Span::styled(who.join(", "), Style::default().fg(Color::White))
                 }
             }
-        }
-        
-        None
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {}: ", collection.name), Style::default().fg(Color::Cyan)),
+            access,
+        ]));
     }
 }
 
 /// Render login-specific details
-fn render_login_details<'a>(lines: &mut Vec<Line<'a>>, item: &'a crate::types::VaultItem, state: &AppState) {
+fn render_login_details<'a>(lines: &mut Vec<Line<'a>>, field_lines: &mut Vec<(DetailField, usize)>, item: &'a crate::types::VaultItem, state: &AppState) {
     if let Some(login) = &item.login {
         // Username
         if let Some(username) = &login.username {
@@ -299,6 +330,7 @@ fn render_login_details<'a>(lines: &mut Vec<Line<'a>>, item: &'a crate::types::V
                 Span::styled(username, Style::default().fg(Color::White)),
                 Span::styled(" [^U]", Style::default().fg(Color::DarkGray)),
             ]));
+            field_lines.push((DetailField::Username, lines.len() - 1));
         } else {
             lines.push(Line::from(vec![
                 Span::styled("Username: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
@@ -313,17 +345,33 @@ fn render_login_details<'a>(lines: &mut Vec<Line<'a>>, item: &'a crate::types::V
                 Span::styled(format!("{} Loading...", state.sync_spinner()), Style::default().fg(Color::Yellow)),
             ]));
         } else if login.password.is_some() {
-            lines.push(Line::from(vec![
+            let mut spans = vec![
                 Span::styled("Password: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
-                Span::styled("••••••••", Style::default().fg(Color::Yellow)),
+                Span::styled(symbols::symbols(state).mask, Style::default().fg(Color::Yellow)),
                 Span::styled(" [^P]", Style::default().fg(Color::DarkGray)),
-            ]));
+            ];
+            if let Some(changed_at) = item.password_changed_at() {
+                let now = chrono::Utc::now();
+                let stale = item.is_password_stale(now, state.ui.list.stale_password_days);
+                let age_style = if stale {
+                    Style::default().fg(Color::LightRed)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                };
+                // Spell out the stale highlight when colors aren't relied on
+                let suffix = if stale && state.ui.accessible { ", stale" } else { "" };
+                spans.push(Span::styled(format!(" (pw {} old{})", format_age(now - changed_at), suffix), age_style));
+            }
+            lines.push(Line::from(spans));
         } else {
             lines.push(Line::from(vec![
                 Span::styled("Password: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
                 Span::styled("(none)", Style::default().fg(Color::DarkGray)),
             ]));
         }
+        if login.password.is_some() {
+            field_lines.push((DetailField::Password, lines.len() - 1));
+        }
         
         // TOTP (or loading)
         if !state.secrets_available() {
@@ -342,7 +390,9 @@ fn render_login_details<'a>(lines: &mut Vec<Line<'a>>, item: &'a crate::types::V
                     lines.push(Line::from(vec![
                         Span::styled("TOTP: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
                         Span::styled(code.clone(), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
-                        Span::styled(format!(" ({}s)", remaining), Style::default().fg(Color::DarkGray)),
+                        Span::raw(" "),
+                        Span::styled(totp_gauge(remaining, state.totp_period(), symbols::symbols(state)), totp_gauge_style(remaining)),
+                        Span::styled(format!(" {}s", remaining), Style::default().fg(Color::DarkGray)),
                         Span::styled(" [^T]", Style::default().fg(Color::DarkGray)),
                     ]));
                 } else {
@@ -364,17 +414,29 @@ fn render_login_details<'a>(lines: &mut Vec<Line<'a>>, item: &'a crate::types::V
                 Span::styled("(none)", Style::default().fg(Color::DarkGray)),
             ]));
         }
+        if login.totp.is_some() {
+            field_lines.push((DetailField::Totp, lines.len() - 1));
+        }
         lines.push(Line::from(""));
         
         // URIs
         if let Some(uris) = &login.uris {
             if !uris.is_empty() {
                 lines.push(Line::from(Span::styled("URIs: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))));
-                for uri in uris.iter() {
-                    lines.push(Line::from(vec![
-                        Span::styled("  • ", Style::default().fg(Color::DarkGray)),
+                for (index, uri) in uris.iter().enumerate() {
+                    let mut spans = vec![
+                        Span::styled(format!("  {} ", symbols::symbols(state).bullet), Style::default().fg(Color::DarkGray)),
                         Span::styled(&uri.uri, Style::default().fg(Color::Blue)),
-                    ]));
+                    ];
+                    // The default detection isn't worth a label
+                    if let Some(match_type) = uri.match_type.filter(|match_type| *match_type != UriMatchType::Domain) {
+                        spans.push(Span::styled(
+                            format!(" ({})", match_type.label()),
+                            Style::default().fg(Color::DarkGray),
+                        ));
+                    }
+                    lines.push(Line::from(spans));
+                    field_lines.push((DetailField::Uri(index), lines.len() - 1));
                 }
                 lines.push(Line::from(""));
             }
@@ -382,6 +444,131 @@ fn render_login_details<'a>(lines: &mut Vec<Line<'a>>, item: &'a crate::types::V
     }
 }
 
+/// Render a custom field's value according to its type, masking secrets unless revealed
+fn custom_field_value_spans(value: CustomFieldValue, revealed: bool, symbols: &Symbols) -> Vec<Span<'static>> {
+    let secret_spans = |value: String| {
+        if revealed {
+            vec![Span::styled(value, Style::default().fg(Color::Yellow))]
+        } else {
+            vec![
+                Span::styled(symbols.mask, Style::default().fg(Color::Yellow)),
+                Span::styled(" [Space]", Style::default().fg(Color::DarkGray)),
+            ]
+        }
+    };
+
+    match value {
+        CustomFieldValue::Text(value) => vec![Span::styled(value, Style::default().fg(Color::White))],
+        CustomFieldValue::Hidden(value) => secret_spans(value),
+        CustomFieldValue::Boolean(true) => vec![Span::styled("[x] yes", Style::default().fg(Color::Green))],
+        CustomFieldValue::Boolean(false) => vec![Span::styled("[ ] no", Style::default().fg(Color::DarkGray))],
+        CustomFieldValue::Linked { target, value, secret } => {
+            let mut spans = vec![Span::styled(format!("{} {}: ", symbols.linked, target), Style::default().fg(Color::DarkGray))];
+            if secret {
+                spans.extend(secret_spans(value));
+            } else {
+                spans.push(Span::styled(value, Style::default().fg(Color::White)));
+            }
+            spans
+        }
+    }
+}
+
+/// Width of the TOTP countdown gauge in cells
+const TOTP_GAUGE_WIDTH: u64 = 10;
+
+/// Build a countdown gauge showing how much of the TOTP period remains
+fn totp_gauge(remaining: u64, period: u64, symbols: &Symbols) -> String {
+    let period = period.max(1);
+    let filled = (remaining.min(period) * TOTP_GAUGE_WIDTH).div_ceil(period);
+    format!(
+        "{}{}",
+        symbols.gauge_filled.repeat(filled as usize),
+        symbols.gauge_empty.repeat((TOTP_GAUGE_WIDTH - filled) as usize)
+    )
+}
+
+/// Show the raw fields of an item type this version doesn't know how to display
+fn render_unknown_details<'a>(lines: &mut Vec<Line<'a>>, item: &'a crate::types::VaultItem, item_type: u8, state: &AppState) {
+    lines.push(Line::from(vec![
+        Span::styled("Type: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::styled(format!("Unknown ({})", item_type), Style::default().fg(Color::Yellow)),
+    ]));
+    lines.push(Line::from(Span::styled(
+        "This item type isn't supported yet, its fields are shown as reported by the CLI",
+        Style::default().fg(Color::DarkGray),
+    )));
+    lines.push(Line::from(""));
+
+    if !state.secrets_available() {
+        // Raw fields aren't cached, they may contain secrets
+        lines.push(Line::from(vec![
+            Span::styled("Fields: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{} Loading...", state.sync_spinner()), Style::default().fg(Color::Yellow)),
+        ]));
+        lines.push(Line::from(""));
+        return;
+    }
+
+    for (name, value) in raw_field_lines(&item.raw_fields) {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}: ", name), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(value, Style::default().fg(Color::White)),
+        ]));
+    }
+    lines.push(Line::from(""));
+}
+
+/// Flatten raw JSON fields into (dotted name, value) pairs, skipping empty values
+fn raw_field_lines(fields: &serde_json::Map<String, serde_json::Value>) -> Vec<(String, String)> {
+    fn collect(prefix: &str, value: &serde_json::Value, out: &mut Vec<(String, String)>) {
+        match value {
+            serde_json::Value::Null => {}
+            serde_json::Value::Object(map) => {
+                for (key, value) in map {
+                    let name = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                    collect(&name, value, out);
+                }
+            }
+            serde_json::Value::Array(values) => {
+                for (index, value) in values.iter().enumerate() {
+                    collect(&format!("{}[{}]", prefix, index), value, out);
+                }
+            }
+            serde_json::Value::String(text) if text.is_empty() => {}
+            serde_json::Value::String(text) => out.push((prefix.to_string(), text.clone())),
+            other => out.push((prefix.to_string(), other.to_string())),
+        }
+    }
+
+    let mut lines = Vec::new();
+    for (key, value) in fields {
+        collect(key, value, &mut lines);
+    }
+    lines
+}
+
+/// Describe an age in its largest whole unit, e.g. "3d", "5mo" or "2y"
+fn format_age(age: chrono::Duration) -> String {
+    let days = age.num_days().max(0);
+    if days >= 365 {
+        format!("{}y", days / 365)
+    } else if days >= 30 {
+        format!("{}mo", days / 30)
+    } else {
+        format!("{}d", days)
+    }
+}
+
+/// Color the TOTP gauge by urgency
+fn totp_gauge_style(remaining: u64) -> Style {
+    match remaining {
+        0..=5 => Style::default().fg(Color::Red),
+        6..=10 => Style::default().fg(Color::Yellow),
+        _ => Style::default().fg(Color::Green),
+    }
+}
+
 /// Render secure note-specific details
 fn render_secure_note_details<'a>(_lines: &mut Vec<Line<'a>>, _item: &'a crate::types::VaultItem, _state: &AppState) {
     // Secure notes only have name and notes, which are handled in the common section
@@ -389,7 +576,7 @@ fn render_secure_note_details<'a>(_lines: &mut Vec<Line<'a>>, _item: &'a crate::
 }
 
 /// Render card-specific details
-fn render_card_details<'a>(lines: &mut Vec<Line<'a>>, item: &'a crate::types::VaultItem, state: &AppState) {
+fn render_card_details<'a>(lines: &mut Vec<Line<'a>>, field_lines: &mut Vec<(DetailField, usize)>, item: &'a crate::types::VaultItem, state: &AppState) {
     if let Some(card) = &item.card {
         // Brand
         if let Some(brand) = &card.brand {
@@ -416,7 +603,7 @@ fn render_card_details<'a>(lines: &mut Vec<Line<'a>>, item: &'a crate::types::Va
         } else if card.number.is_some() {
             lines.push(Line::from(vec![
                 Span::styled("Number: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
-                Span::styled("••••-••••-••••-••••", Style::default().fg(Color::Yellow)),
+                Span::styled(symbols::symbols(state).card_mask, Style::default().fg(Color::Yellow)),
                 Span::styled(" [^N]", Style::default().fg(Color::DarkGray)),
             ]));
         } else {
@@ -425,9 +612,19 @@ fn render_card_details<'a>(lines: &mut Vec<Line<'a>>, item: &'a crate::types::Va
                 Span::styled("(none)", Style::default().fg(Color::DarkGray)),
             ]));
         }
+        if card.number.is_some() {
+            field_lines.push((DetailField::CardNumber, lines.len() - 1));
+        }
         
         // Expiry
-        if let (Some(month), Some(year)) = (&card.exp_month, &card.exp_year) {
+        if let Some(expiry) = card.expiry() {
+            lines.push(Line::from(vec![
+                Span::styled("Expiry: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+                Span::styled(expiry, Style::default().fg(Color::White)),
+                Span::styled(" [^Y]", Style::default().fg(Color::DarkGray)),
+            ]));
+            field_lines.push((DetailField::CardExpiry, lines.len() - 1));
+        } else if let (Some(month), Some(year)) = (&card.exp_month, &card.exp_year) {
             lines.push(Line::from(vec![
                 Span::styled("Expiry: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
                 Span::styled(format!("{}/{}", month, year), Style::default().fg(Color::White)),
@@ -443,7 +640,7 @@ fn render_card_details<'a>(lines: &mut Vec<Line<'a>>, item: &'a crate::types::Va
         } else if card.code.is_some() {
             lines.push(Line::from(vec![
                 Span::styled("CVV: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
-                Span::styled("•••", Style::default().fg(Color::Yellow)),
+                Span::styled(symbols::symbols(state).cvv_mask, Style::default().fg(Color::Yellow)),
                 Span::styled(" [^M]", Style::default().fg(Color::DarkGray)),
             ]));
         } else {
@@ -452,61 +649,37 @@ fn render_card_details<'a>(lines: &mut Vec<Line<'a>>, item: &'a crate::types::Va
                 Span::styled("(none)", Style::default().fg(Color::DarkGray)),
             ]));
         }
+        if card.code.is_some() {
+            field_lines.push((DetailField::CardCvv, lines.len() - 1));
+        }
         lines.push(Line::from(""));
     }
 }
 
 /// Render identity-specific details
-fn render_identity_details<'a>(lines: &mut Vec<Line<'a>>, item: &'a crate::types::VaultItem, _state: &AppState) {
+fn render_identity_details<'a>(lines: &mut Vec<Line<'a>>, field_lines: &mut Vec<(DetailField, usize)>, item: &'a crate::types::VaultItem, _state: &AppState) {
     if let Some(identity) = &item.identity {
         // Name section
-        let mut name_parts = Vec::new();
-        if let Some(title) = &identity.title {
-            name_parts.push(title.clone());
-        }
-        if let Some(first) = &identity.first_name {
-            name_parts.push(first.clone());
-        }
-        if let Some(middle) = &identity.middle_name {
-            name_parts.push(middle.clone());
-        }
-        if let Some(last) = &identity.last_name {
-            name_parts.push(last.clone());
-        }
-        
-        if !name_parts.is_empty() {
-            lines.push(Line::from(Span::styled("Name: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))));
-            lines.push(Line::from(Span::styled(name_parts.join(" "), Style::default().fg(Color::White))));
+        if let Some(full_name) = identity.full_name() {
+            lines.push(Line::from(vec![
+                Span::styled("Name: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+                Span::styled(full_name, Style::default().fg(Color::White)),
+                Span::styled(" [^F]", Style::default().fg(Color::DarkGray)),
+            ]));
+            field_lines.push((DetailField::IdentityName, lines.len() - 1));
             lines.push(Line::from(""));
         }
         
         // Address section
-        let mut address_parts = Vec::new();
-        if let Some(addr1) = &identity.address1 {
-            address_parts.push(addr1.clone());
-        }
-        if let Some(addr2) = &identity.address2 {
-            address_parts.push(addr2.clone());
-        }
-        if let Some(addr3) = &identity.address3 {
-            address_parts.push(addr3.clone());
-        }
-        if let Some(city) = &identity.city {
-            address_parts.push(city.clone());
-        }
-        if let Some(state) = &identity.state {
-            address_parts.push(state.clone());
-        }
-        if let Some(postal) = &identity.postal_code {
-            address_parts.push(postal.clone());
-        }
-        if let Some(country) = &identity.country {
-            address_parts.push(country.clone());
-        }
-        
-        if !address_parts.is_empty() {
-            lines.push(Line::from(Span::styled("Address: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))));
-            lines.push(Line::from(Span::styled(address_parts.join(", "), Style::default().fg(Color::White))));
+        if let Some(address) = identity.formatted_address() {
+            lines.push(Line::from(vec![
+                Span::styled("Address: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+                Span::styled("[M-P]", Style::default().fg(Color::DarkGray)),
+            ]));
+            field_lines.push((DetailField::IdentityAddress, lines.len() - 1));
+            for address_line in address.lines() {
+                lines.push(Line::from(Span::styled(address_line.to_string(), Style::default().fg(Color::White))));
+            }
             lines.push(Line::from(""));
         }
         
@@ -515,13 +688,17 @@ fn render_identity_details<'a>(lines: &mut Vec<Line<'a>>, item: &'a crate::types
             lines.push(Line::from(vec![
                 Span::styled("Phone: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
                 Span::styled(phone, Style::default().fg(Color::White)),
+                Span::styled(" [^O]", Style::default().fg(Color::DarkGray)),
             ]));
+            field_lines.push((DetailField::IdentityPhone, lines.len() - 1));
         }
         if let Some(email) = &identity.email {
             lines.push(Line::from(vec![
                 Span::styled("Email: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
                 Span::styled(email, Style::default().fg(Color::White)),
+                Span::styled(" [M-I]", Style::default().fg(Color::DarkGray)),
             ]));
+            field_lines.push((DetailField::IdentityEmail, lines.len() - 1));
         }
         if let Some(username) = &identity.username {
             lines.push(Line::from(vec![
@@ -554,3 +731,29 @@ fn render_identity_details<'a>(lines: &mut Vec<Line<'a>>, item: &'a crate::types
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raw_field_lines() {
+        let fields: serde_json::Map<String, serde_json::Value> = serde_json::from_str(
+            r#"{"sshKey": {"publicKey": "ssh-ed25519 AAAA", "keyFingerprint": "", "privateKey": null}, "tags": ["a", 2]}"#,
+        ).unwrap();
+        assert_eq!(raw_field_lines(&fields), vec![
+            ("sshKey.publicKey".to_string(), "ssh-ed25519 AAAA".to_string()),
+            ("tags[0]".to_string(), "a".to_string()),
+            ("tags[1]".to_string(), "2".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(chrono::Duration::hours(5)), "0d");
+        assert_eq!(format_age(chrono::Duration::days(29)), "29d");
+        assert_eq!(format_age(chrono::Duration::days(45)), "1mo");
+        assert_eq!(format_age(chrono::Duration::days(800)), "2y");
+        // Clock skew can make revision dates lie in the future
+        assert_eq!(format_age(chrono::Duration::days(-3)), "0d");
+    }
+}
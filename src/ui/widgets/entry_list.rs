@@ -1,56 +1,88 @@
 use crate::state::AppState;
-use crate::ui::widgets::clickable::{Clickable, is_click_in_area};
-use crossterm::event::MouseEvent;
+use crate::types::ItemType;
+use crate::ui::layout::{panel_block, panel_inner};
+use crate::ui::symbols::{self, folder_icon, item_type_icon_column, reprompt_icon};
+use unicode_width::UnicodeWidthStr;
 use ratatui::{
     layout::{Alignment, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem},
+    widgets::{List, ListItem, ListState},
     Frame,
 };
 
+/// Number of top search results that can be copied with Alt+1..9
+const QUICK_COPY_COUNT: usize = 9;
+
 pub fn render(frame: &mut Frame, area: Rect, state: &mut AppState) {
-    let items: Vec<ListItem> = state
-        .vault.filtered_items
+    let compact = state.ui.layout.compact;
+    let inner = panel_inner(area, compact);
+
+    // Only build rows for the visible window so huge vaults stay responsive
+    let window = state.vault.visible_window(inner.height as usize);
+    let window_start = window.start;
+    let window_len = window.len();
+    let now = chrono::Utc::now();
+    let symbols = symbols::symbols(state);
+    let accessible = state.ui.accessible;
+    let icons = symbols::icon_set(state);
+    let quick_copy = state.quick_copy_available();
+
+    let mut items: Vec<ListItem> = state
+        .vault.filtered_indices[window]
         .iter()
         .enumerate()
-        .map(|(idx, item)| {
+        .map(|(offset, &item_index)| {
+            let idx = window_start + offset;
+            let item = &state.vault.vault_items[item_index];
             let is_selected = idx == state.vault.selected_index;
+            let is_stale = item.is_password_stale(now, state.ui.list.stale_password_days);
             
             let style = if is_selected {
                 Style::default()
                     .fg(Color::Black)
                     .bg(Color::Cyan)
                     .add_modifier(Modifier::BOLD)
+            } else if is_stale {
+                // Warn about passwords older than the configured threshold
+                Style::default().fg(Color::LightRed)
             } else {
                 Style::default().fg(Color::White)
             };
 
             // Build display text
-            let mut spans = vec![
-                Span::styled(
-                    if is_selected { "► " } else { "  " },
-                    style,
-                ),
-            ];
+            let mut spans = Vec::new();
+
+            // Number the top search results, for copying their password with Alt+1..9
+            if quick_copy {
+                let number = if idx < QUICK_COPY_COUNT { format!("{} ", idx + 1) } else { "  ".to_string() };
+                spans.push(Span::styled(number, Style::default().fg(Color::DarkGray)));
+            }
+
+            spans.push(Span::styled(
+                if is_selected { symbols.selected } else { "  " },
+                style,
+            ));
 
             // Add favorite indicator
             if item.favorite {
-                spans.push(Span::styled("★ ", Style::default().fg(Color::Yellow)));
+                spans.push(Span::styled(symbols.favorite, Style::default().fg(Color::Yellow)));
             }
 
             // Add type indicator
-            let type_indicator = match item.item_type {
-                crate::types::ItemType::Login => "🔑",
-                crate::types::ItemType::SecureNote => "📝",
-                crate::types::ItemType::Card => "💳",
-                crate::types::ItemType::Identity => "👤",
-            };
-            spans.push(Span::styled(type_indicator, Style::default().fg(Color::Yellow)));
+            spans.push(Span::styled(item_type_icon_column(item.item_type, icons), Style::default().fg(Color::Yellow)));
             spans.push(Span::styled(" ", style));
 
+            // Highlight the characters that matched the search query
+            let matches = state.vault.match_positions(item);
+            let highlight_style = if is_selected {
+                style.add_modifier(Modifier::UNDERLINED)
+            } else {
+                style.fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            };
+
             // Add item name
-            spans.push(Span::styled(&item.name, style));
+            spans.extend(highlight_matches(&item.name, &matches.name, style, highlight_style));
 
             // Add type-specific subtitle
             let subtitle = match item.item_type {
@@ -66,12 +98,67 @@ pub fn render(frame: &mut Frame, area: Rect, state: &mut AppState) {
                 crate::types::ItemType::Identity => {
                     item.identity_email().map(|e| format!("({})", e))
                 }
+                crate::types::ItemType::Unknown(value) => {
+                    Some(format!("(type {})", value))
+                }
             };
 
             if let Some(subtitle) = subtitle {
+                let subtitle_style = if is_selected {
+                    Style::default().fg(Color::Black).bg(Color::Cyan)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                };
+                // Only the login subtitle (the username) is part of the searchable text,
+                // shifted by one for the opening parenthesis
+                let subtitle_matches: Vec<usize> = if item.item_type == crate::types::ItemType::Login {
+                    matches.username.iter().map(|position| position + 1).collect()
+                } else {
+                    Vec::new()
+                };
+                spans.push(Span::styled(" ", style));
+                spans.extend(highlight_matches(&subtitle, &subtitle_matches, subtitle_style, highlight_style));
+            }
+
+            // Add TOTP indicator
+            if item.login.as_ref().and_then(|l| l.totp.as_ref()).is_some() {
+                spans.push(Span::styled(" ", style));
+                spans.push(Span::styled(
+                    "[2FA]",
+                    if is_selected {
+                        Style::default().fg(Color::Black).bg(Color::Cyan)
+                    } else {
+                        Style::default().fg(Color::Green)
+                    },
+                ));
+            }
+
+            // Items that ask for the master password again
+            if item.requires_reprompt() {
+                spans.push(Span::styled(" ", style));
+                spans.push(Span::styled(reprompt_icon(icons), Style::default().fg(Color::Yellow)));
+            }
+
+            // Add local tag chips
+            if let Some(tags) = state.vault.item_tags(&item.id) {
+                for tag in tags {
+                    spans.push(Span::styled(" ", style));
+                    spans.push(Span::styled(
+                        format!("#{}", tag),
+                        if is_selected {
+                            Style::default().fg(Color::Black).bg(Color::Cyan)
+                        } else {
+                            Style::default().fg(Color::LightBlue)
+                        },
+                    ));
+                }
+            }
+
+            // Hidden items are only listed while hidden items are shown
+            if state.vault.is_hidden(&item.id) {
                 spans.push(Span::styled(" ", style));
                 spans.push(Span::styled(
-                    subtitle,
+                    "[hidden]",
                     if is_selected {
                         Style::default().fg(Color::Black).bg(Color::Cyan)
                     } else {
@@ -80,52 +167,119 @@ pub fn render(frame: &mut Frame, area: Rect, state: &mut AppState) {
                 ));
             }
 
-            // Add TOTP indicator
-            if item.login.as_ref().and_then(|l| l.totp.as_ref()).is_some() {
+            // The stale highlight is only a color, spell it out
+            if is_stale && accessible {
+                spans.push(Span::styled(" ", style));
+                spans.push(Span::styled("[old password]", style));
+            }
+
+            // Add organization badge
+            if let Some(organization_id) = &item.organization_id {
+                let organization = state.vault.organization_name(organization_id).unwrap_or("Org");
                 spans.push(Span::styled(" ", style));
                 spans.push(Span::styled(
-                    "[2FA]",
+                    format!("[{}]", organization),
                     if is_selected {
                         Style::default().fg(Color::Black).bg(Color::Cyan)
                     } else {
-                        Style::default().fg(Color::Green)
+                        Style::default().fg(Color::Magenta)
                     },
                 ));
             }
 
+            // Add folder suffix
+            if state.ui.list.show_folder {
+                if let Some(folder) = state.vault.folder_name(item) {
+                    spans.push(Span::styled(
+                        format!("  {} {}", folder_icon(icons), folder),
+                        if is_selected {
+                            Style::default().fg(Color::Black).bg(Color::Cyan)
+                        } else {
+                            Style::default().fg(Color::DarkGray)
+                        },
+                    ));
+                }
+            }
+
             ListItem::new(Line::from(spans))
         })
         .collect();
 
-    let title = if !state.initial_load_complete() {
+    // Placeholder rows until the first items arrive, so the layout is visible right away
+    // Screen readers would read the placeholder glyphs, the title already says the vault is loading
+    if !accessible && !state.initial_load_complete() && state.syncing() && state.vault.vault_items.is_empty() {
+        items = skeleton_rows(inner.height as usize, state.sync.animation_frame());
+    }
+
+    let title = if !state.initial_load_complete() && !state.vault.vault_items.is_empty() {
+        // Items are shown as they arrive during the initial load
+        format!(
+            " {} {} ({} items so far) ",
+            state.sync_spinner(),
+            state.sync.activity("Loading vault"),
+            state.vault.vault_items.len()
+        )
+    } else if !state.initial_load_complete() {
         // Show spinner during initial load
-        format!(" {} Loading vault... ", state.sync_spinner())
-    } else if state.vault.filtered_items.is_empty() {
+        format!(" {} {} ", state.sync_spinner(), state.sync.activity("Loading vault"))
+    } else if state.vault.filtered_indices.is_empty() {
         " No entries found ".to_string()
     } else {
+        let cache_age = match state.vault.cached_at {
+            Some(cached_at) if !state.secrets_available() => format!(
+                "{} {}cache from {} ",
+                symbols.separator,
+                if state.vault.cache_expired { "expired " } else { "" },
+                crate::cache::describe_age(cached_at, now)
+            ),
+            _ => String::new(),
+        };
         format!(
-            " Vault Entries ({}/{}) ",
-            state.vault.filtered_items.len(),
-            state.vault.vault_items.len()
+            " {} ({}/{}) {}",
+            list_name(state),
+            state.vault.filtered_len(),
+            state.vault.vault_items.len(),
+            cache_age
         )
     };
 
-    let title_style = if state.syncing() || !state.initial_load_complete() {
+    let title_style = if state.vault.cache_expired {
+        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+    } else if state.syncing() || !state.initial_load_complete() {
         Style::default().fg(Color::Cyan)
     } else {
         Style::default().fg(Color::White)
     };
 
     // Create the block with conditional right-aligned syncing indicator
-    let mut block = Block::default()
-        .borders(Borders::ALL)
-        .title(title)
-        .title_bottom(Line::from(" ↑↓:Navigate "))
-        .border_style(title_style);
+    let mut block = panel_block(compact, title_style).title(title);
+
+    // The compact layout leaves the hints to the status bar, to keep every row for entries
+    if !compact {
+        block = block.title_bottom(Line::from(if let Some(field) = state.ui.pick {
+            format!(" {}:Navigate Enter:Copy {} ", symbols.up_down, field.label())
+        } else if state.focus() == crate::state::Focus::List {
+            format!(" {} j/k:Navigate /:Search ", symbols.up_down)
+        } else {
+            format!(" {}:Navigate Tab:Focus list ", symbols.up_down)
+        }));
+    }
+
+    // Show the first key of a sequence until the second one is typed, like vim's showcmd
+    if let Some(key) = state.ui.pending_key {
+        let key = Line::from(format!(" {} ", key)).alignment(Alignment::Right);
+        block = if compact { block.title(key) } else { block.title_bottom(key) };
+    }
 
     // Add syncing indicator on the right when syncing (but not during initial load)
     if state.syncing() && state.initial_load_complete() {
-        block = block.title(Line::from(format!(" {} Syncing... ", state.sync_spinner())).alignment(Alignment::Right));
+        let indicator = format!(" {} {} ", state.sync_spinner(), state.sync.activity("Syncing"));
+        // Clicking it opens the sync status popup, the title ends before the corner when there are borders
+        let width = (indicator.width() as u16).min(area.width);
+        let corner = if compact { 0 } else { 1 };
+        let indicator_area = Rect::new(area.right().saturating_sub(width + corner).max(area.x), area.y, width, 1);
+        state.ui.click_regions.push(indicator_area, crate::events::Action::ToggleSyncDetails);
+        block = block.title(Line::from(indicator).alignment(Alignment::Right));
     }
 
     let list = List::new(items).block(block)
@@ -136,38 +290,113 @@ pub fn render(frame: &mut Frame, area: Rect, state: &mut AppState) {
                 .add_modifier(Modifier::BOLD),
         );
 
-    frame.render_stateful_widget(list, area, &mut state.vault.list_state);
+    // The rows are already scrolled, so the list only needs the selection within the window
+    // (which can be out of view after scrolling with the mouse wheel)
+    let mut window_state = ListState::default().with_selected(
+        state.vault.list_state.selected()
+            .filter(|selected| (window_start..window_start + window_len).contains(selected))
+            .map(|selected| selected - window_start),
+    );
+    frame.render_stateful_widget(list, area, &mut window_state);
+
+    // Each visible row selects its item when clicked
+    for row in 0..window_len {
+        let row_area = Rect::new(inner.x, inner.y + row as u16, inner.width, 1);
+        state.ui.click_regions.push(row_area, crate::events::Action::SelectIndexAndShowDetails(window_start + row));
+    }
 }
 
-/// Entry list click handler
-pub struct EntryListClickHandler;
+/// Name of the listed items, the compact layout has no tab bar showing the selected tab
+fn list_name(state: &AppState) -> &'static str {
+    if !state.ui.layout.compact {
+        return "Vault Entries";
+    }
+    match state.ui.active_item_type_filter {
+        Some(ItemType::Login) => "Logins",
+        Some(ItemType::SecureNote) => "Notes",
+        Some(ItemType::Card) => "Cards",
+        Some(ItemType::Identity) => "Identities",
+        None | Some(ItemType::Unknown(_)) => "Vault Entries",
+    }
+}
 
-impl Clickable for EntryListClickHandler {
-    fn handle_click(&self, mouse: MouseEvent, state: &AppState, area: Rect) -> Option<crate::events::Action> {
-        if !is_click_in_area(mouse, area) {
-            return None;
-        }
+/// Dimmed placeholder rows of varying length, with a highlight sweeping down the list
+fn skeleton_rows(count: usize, animation_frame: u8) -> Vec<ListItem<'static>> {
+    const NAME_WIDTHS: [usize; 5] = [14, 22, 10, 18, 26];
+    const SUBTITLE_WIDTHS: [usize; 3] = [12, 0, 16];
 
-        // Calculate relative position within the list
-        let relative_y = mouse.row - area.y;
-        
-        // Account for the border (1 line at top)
-        if relative_y > 0 {
-            let item_index_in_view = (relative_y - 1) as usize;
-            
-            // Get the current scroll offset from the list state
-            let scroll_offset = state.vault.list_state.offset();
-            
-            // Calculate the absolute index in the filtered list
-            let absolute_index = scroll_offset + item_index_in_view;
-            
-            // Only select if it's a valid item
-            if absolute_index < state.vault.filtered_items.len() {
-                return Some(crate::events::Action::SelectIndexAndShowDetails(absolute_index));
+    (0..count)
+        .map(|row| {
+            let color = if row % 8 == animation_frame as usize {
+                Color::Gray
+            } else {
+                Color::DarkGray
+            };
+            let style = Style::default().fg(color);
+            let mut spans = vec![
+                Span::raw("  "),
+                Span::styled("░░", style),
+                Span::raw(" "),
+                Span::styled("▒".repeat(NAME_WIDTHS[row % NAME_WIDTHS.len()]), style),
+            ];
+            let subtitle_width = SUBTITLE_WIDTHS[row % SUBTITLE_WIDTHS.len()];
+            if subtitle_width > 0 {
+                spans.push(Span::raw(" "));
+                spans.push(Span::styled("░".repeat(subtitle_width), style));
             }
+            ListItem::new(Line::from(spans))
+        })
+        .collect()
+}
+
+/// Split text into spans, styling the characters at the given positions with the highlight style
+fn highlight_matches<'a>(text: &str, positions: &[usize], style: Style, highlight_style: Style) -> Vec<Span<'a>> {
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let mut current_highlighted = false;
+
+    for (idx, c) in text.chars().enumerate() {
+        let highlighted = positions.contains(&idx);
+        if highlighted != current_highlighted && !current.is_empty() {
+            let run_style = if current_highlighted { highlight_style } else { style };
+            spans.push(Span::styled(std::mem::take(&mut current), run_style));
         }
-        
-        None
+        current_highlighted = highlighted;
+        current.push(c);
     }
+
+    if !current.is_empty() {
+        let run_style = if current_highlighted { highlight_style } else { style };
+        spans.push(Span::styled(current, run_style));
+    }
+    spans
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_matches_groups_runs() {
+        let style = Style::default();
+        let highlight = Style::default().fg(Color::Yellow);
+        let spans = highlight_matches("GitHub", &[0, 1, 3], style, highlight);
+
+        let runs: Vec<(&str, Style)> = spans.iter().map(|span| (span.content.as_ref(), span.style)).collect();
+        assert_eq!(runs, vec![("Gi", highlight), ("t", style), ("H", highlight), ("ub", style)]);
+    }
+
+    #[test]
+    fn test_skeleton_rows_fill_the_list() {
+        let rows = skeleton_rows(10, 3);
+        assert_eq!(rows.len(), 10);
+        assert_eq!(skeleton_rows(0, 3).len(), 0);
+    }
+
+    #[test]
+    fn test_highlight_matches_without_positions() {
+        let spans = highlight_matches("GitHub", &[], Style::default(), Style::default().fg(Color::Yellow));
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].content, "GitHub");
+    }
+}
@@ -4,4 +4,6 @@ pub mod status_bar;
 pub mod details;
 pub mod clickable;
 pub mod tab_bar;
+pub mod text_input;
 
+pub mod too_small;
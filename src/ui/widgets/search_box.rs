@@ -1,4 +1,5 @@
-use crate::state::AppState;
+use crate::state::{AppState, Focus};
+use crate::ui::widgets::text_input;
 use ratatui::{
     layout::{Alignment, Rect},
     style::{Color, Style},
@@ -14,19 +15,50 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
         Style::default().fg(Color::Yellow)
     };
 
+    // Show the active matching mode
+    let mode = if state.vault.fuzzy_enabled() { "Fuzzy" } else { "Exact" };
+    let case = if state.vault.case_sensitive() { "Aa" } else { "aa" };
+    let compact_mode = format!(" {} {}", mode, case);
+
+    // Keep the cursor of a long query in view, it is where typing happens
+    // The compact layout has a single line, with the matching mode on the right instead of borders
+    let compact = state.ui.layout.compact;
+    let reserved = if compact { compact_mode.len() } else { 2 } + 2; // Borders or mode, and "> "
+    let query_width = (area.width as usize).saturating_sub(reserved);
+    let (query, cursor) = text_input::visible(&state.vault.filter_query, query_width, None);
     let filter_text = if state.vault.filter_query.is_empty() {
-        "Type to search...".to_string()
+        let placeholder = if state.focus() == Focus::Search { "Type to search..." } else { "Press / to search..." };
+        placeholder.to_string()
     } else {
-        format!("> {}", state.vault.filter_query)
+        format!("> {}", query)
     };
 
+    // The terminal cursor shows where typing goes, unless a dialog has the keyboard
+    if state.focus() == Focus::Search && !state.dialog_open() && !area.is_empty() {
+        let (x, y) = if compact { (area.x, area.y) } else { (area.x + 1, area.y + 1) };
+        let offset = if state.vault.filter_query.is_empty() { 0 } else { 2 + cursor };
+        frame.set_cursor_position((x + offset, y));
+    }
+
+    if compact {
+        frame.render_widget(Paragraph::new(filter_text).style(style), area);
+        frame.render_widget(
+            Paragraph::new(compact_mode).style(Style::default().fg(Color::DarkGray)).alignment(Alignment::Right),
+            area,
+        );
+        return;
+    }
+
     let mut block = Block::default()
         .borders(Borders::ALL)
-        .title(" Search ")
+        .title(format!(" Search [{} {}] ", mode, case))
+        .title_bottom(Line::from(" M-F:Fuzzy/Exact M-C:Case "))
         .border_style(style);
 
-    // Add clear search shortcut on the right when there's text
-    if !state.vault.filter_query.is_empty() {
+    // Add the quick copy and clear search shortcuts on the right when there's text
+    if state.quick_copy_available() {
+        block = block.title(Line::from(" M-1..9:Copy password ^X:Clear search ").alignment(Alignment::Right));
+    } else if !state.vault.filter_query.is_empty() {
         block = block.title(Line::from(" ^X:Clear search ").alignment(Alignment::Right));
     }
 
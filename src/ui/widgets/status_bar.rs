@@ -1,7 +1,9 @@
 use crate::state::{AppState, MessageLevel};
 use crate::types::ItemType;
+use crate::ui::symbols;
+use unicode_width::UnicodeWidthStr;
 use ratatui::{
-    layout::{Alignment, Rect},
+    layout::{Alignment, Constraint, Layout, Rect},
     style::{Color, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap},
@@ -21,7 +23,17 @@ fn get_copy_shortcuts_for_item_type(item_type: Option<ItemType>) -> Vec<&'static
         Some(ItemType::Card) => {
             vec![
                 "^N:Card Number",
+                "^Y:Expiry",
                 "^M:CVV",
+                "^B:Copy All",
+            ]
+        }
+        Some(ItemType::Identity) => {
+            vec![
+                "^F:Name",
+                "M-I:Email",
+                "^O:Phone",
+                "M-P:Address",
             ]
         }
         _ => {
@@ -34,6 +46,22 @@ fn get_copy_shortcuts_for_item_type(item_type: Option<ItemType>) -> Vec<&'static
 fn get_all_shortcuts(state: &AppState) -> Vec<&'static str> {
     let mut shortcuts = vec![];
     
+    // Field navigation replaces the usual shortcuts while the details panel has focus
+    if state.details_focused() {
+        let field = if state.ui.accessible { "Up/Down:Field" } else { "↑↓:Field" };
+        shortcuts.extend([field, "Enter:Copy", "M-Q:QR Code", "Esc:Back", "^Q:Quit"]);
+        return shortcuts;
+    }
+    
+    // The picker only copies one field, shown in the list
+    if state.ui.pick.is_some() {
+        if state.syncing() {
+            shortcuts.push("^C:Cancel Sync");
+        }
+        shortcuts.extend(["Enter:Copy", "Esc:Cancel"]);
+        return shortcuts;
+    }
+
     // Add copy shortcuts based on selected item type
     let copy_shortcuts = if let Some(item) = state.selected_item() {
         get_copy_shortcuts_for_item_type(Some(item.item_type))
@@ -43,9 +71,15 @@ fn get_all_shortcuts(state: &AppState) -> Vec<&'static str> {
     
     shortcuts.extend(copy_shortcuts);
     
+    // Offer to cancel a sync that takes too long
+    if state.syncing() {
+        shortcuts.push("^C:Cancel Sync");
+    }
+    
     // Add other common shortcuts
     shortcuts.extend(vec![
         "^D:Details",
+        "^G:History",
         "^R:Refresh",
         "^L:Lock&Quit",
         "^Q:Quit",
@@ -56,16 +90,15 @@ fn get_all_shortcuts(state: &AppState) -> Vec<&'static str> {
 
 pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
     let status_text = if let Some(status_msg) = &state.status_message {
-        let style = match status_msg.level {
-            MessageLevel::Info => Style::default().fg(Color::Cyan),
-            MessageLevel::Success => Style::default().fg(Color::Green),
-            MessageLevel::Warning => Style::default().fg(Color::Yellow),
-            MessageLevel::Error => Style::default().fg(Color::Red),
-        };
+        // Earlier messages still being shown stack above the newest, the compact bar only has room for it
+        let stacked = if state.ui.layout.compact { 0 } else { state.stacked_status.len() };
+        let lines: Vec<Line> = state.stacked_status.iter()
+            .skip(state.stacked_status.len() - stacked)
+            .chain(std::iter::once(status_msg))
+            .map(|status| Line::styled(symbols::status_text(&status.text, status.level, state.ui.accessible), level_style(status.level)))
+            .collect();
 
-        Paragraph::new(status_msg.text.as_str())
-            .style(style)
-            .alignment(Alignment::Left)
+        Paragraph::new(lines).alignment(Alignment::Left)
     } else {
         // Show dynamic keybindings with wrapping support
         let bindings = get_all_shortcuts(state);
@@ -78,26 +111,143 @@ pub fn render(frame: &mut Frame, area: Rect, state: &AppState) {
             }
         }
 
-        Paragraph::new(Line::from(spans))
+        let paragraph = Paragraph::new(Line::from(spans))
             .style(Style::default().fg(Color::DarkGray))
-            .alignment(Alignment::Center)
-            .wrap(Wrap { trim: false })
+            .alignment(Alignment::Center);
+        // The compact status bar is a single line, cut off instead of wrapped
+        if state.ui.layout.compact { paragraph } else { paragraph.wrap(Wrap { trim: false }) }
     };
 
-    let block = Block::default()
+    let separator = symbols::symbols(state).separator;
+    let clipboard = state.clipboard_clear_seconds(std::time::Instant::now());
+    let countdown = countdown_text(clipboard, state.displayed_totp_seconds(), separator);
+    // The view context stays visible when a status message replaces the shortcuts
+    let context = context_text(state, crate::profile::current(), separator);
+    let context_style = Style::default().fg(Color::Cyan);
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let watch = watch_text(state, now);
+    let watch_style = Style::default().fg(Color::Black).bg(Color::Magenta);
+
+    // Without borders the context starts the line, the watched code and countdowns take its end
+    if state.ui.layout.compact {
+        let context = format!("{} ", context);
+        let watch = watch.map(|text| format!(" {} ", text)).unwrap_or_default();
+        let countdown = countdown.map(|text| format!(" {}", text)).unwrap_or_default();
+        let [context_area, text_area, watch_area, countdown_area] = Layout::horizontal([
+            Constraint::Length(context.width() as u16),
+            Constraint::Min(0),
+            Constraint::Length(watch.width() as u16),
+            Constraint::Length(countdown.width() as u16),
+        ]).areas(area);
+        frame.render_widget(Paragraph::new(context).style(context_style), context_area);
+        frame.render_widget(status_text, text_area);
+        frame.render_widget(Paragraph::new(watch).style(watch_style), watch_area);
+        frame.render_widget(Paragraph::new(countdown).style(Style::default().fg(Color::Yellow)), countdown_area);
+        return;
+    }
+
+    let mut block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::DarkGray));
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(Span::styled(format!(" {} ", context), context_style));
+
+    // Live countdowns on the right of the top border, after the watched code
+    if let Some(text) = watch {
+        block = block.title(Line::from(Span::styled(format!(" {} ", text), watch_style)).alignment(Alignment::Right));
+    }
+    if let Some(text) = countdown {
+        block = block.title(Line::from(Span::styled(format!(" {} ", text), Style::default().fg(Color::Yellow))).alignment(Alignment::Right));
+    }
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
     frame.render_widget(status_text, inner);
 }
 
+fn level_style(level: MessageLevel) -> Style {
+    match level {
+        MessageLevel::Info => Style::default().fg(Color::Cyan),
+        MessageLevel::Success => Style::default().fg(Color::Green),
+        MessageLevel::Warning => Style::default().fg(Color::Yellow),
+        MessageLevel::Error => Style::default().fg(Color::Red),
+    }
+}
+
+/// Seconds until the clipboard clears and until the displayed TOTP code changes
+fn countdown_text(clipboard_secs: Option<u64>, totp_secs: Option<u64>, separator: &str) -> Option<String> {
+    let parts: Vec<String> = [
+        clipboard_secs.map(|secs| format!("Clipboard clears in {}s", secs)),
+        totp_secs.map(|secs| format!("TOTP {}s", secs)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    (!parts.is_empty()).then(|| parts.join(&format!(" {} ", separator)))
+}
+
+/// The watched item's TOTP code and its remaining seconds, e.g. "GitHub 123456 (12s)"
+fn watch_text(state: &AppState, now: u64) -> Option<String> {
+    let watch = state.ui.totp_watch.as_ref()?;
+    let code = match (&watch.code, state.watched_totp_seconds(now)) {
+        (Some(totp), Some(seconds)) => format!("{} ({}s)", totp.code, seconds),
+        // Until the next code is generated or fetched
+        _ => "...".to_string(),
+    };
+    Some(format!("{} {}", watch.name, code))
+}
+
+/// Tab, owner and folder filters, list order, where the items come from and the profile
+fn context_text(state: &AppState, profile: Option<&str>, separator: &str) -> String {
+    let tab = match state.ui.get_active_filter() {
+        Some(ItemType::Login) => "Logins",
+        Some(ItemType::SecureNote) => "Notes",
+        Some(ItemType::Card) => "Cards",
+        Some(ItemType::Identity) => "Identities",
+        None | Some(ItemType::Unknown(_)) => "All",
+    };
+    let owner = state.vault.owner_filter_label();
+    // Matches are listed best first, otherwise favorites come first and then names
+    let order = if state.vault.filter_query.is_empty() { "By name" } else { "By match" };
+    // Items from the cache are shown until bw answers, and stay if it can't be reached
+    let hidden = match state.vault.hidden_count() {
+        0 => None,
+        count if state.vault.show_hidden() => Some(format!("{} hidden shown", count)),
+        count => Some(format!("{} hidden", count)),
+    };
+    let source = if state.vault.cached_at.is_some() {
+        Some("Offline")
+    } else {
+        state.secrets_available().then_some("Online")
+    };
+
+    let parts: Vec<String> = [
+        state.ui.active_view.as_ref().map(|view| format!("View: {}", view)),
+        Some(tab.to_string()),
+        (owner != "All").then(|| format!("Owner: {}", owner)),
+        state.vault.folder_filter().map(|folder| format!("Folder: {}", folder)),
+        Some(order.to_string()),
+        hidden,
+        source.map(str::to_string),
+        profile.map(|profile| format!("Profile: {}", profile)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    parts.join(&format!(" {} ", separator))
+}
+
 /// Calculate the height needed for the status bar
 pub fn calculate_height(width: u16, state: &AppState) -> u16 {
-    // If there's a status message, use fixed height
+    if state.ui.layout.compact {
+        return 1;
+    }
+
+    // A status message takes one line, and one more for each earlier message stacked above it
     if state.status_message.is_some() {
-        return 3;
+        return 3 + state.stacked_status.len() as u16;
     }
     
     // Calculate height needed for dynamic keybindings
@@ -110,7 +260,7 @@ pub fn calculate_height(width: u16, state: &AppState) -> u16 {
     let mut lines_needed = 1;
     
     for (i, binding) in bindings.iter().enumerate() {
-        let binding_width = binding.chars().count();
+        let binding_width = binding.width();
         let separator_width = if i < bindings.len() - 1 { 3 } else { 0 }; // " | "
         let total_width = binding_width + separator_width;
         
@@ -126,3 +276,52 @@ pub fn calculate_height(width: u16, state: &AppState) -> u16 {
     lines_needed as u16 + 2
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_countdown_text() {
+        assert_eq!(countdown_text(None, None, "·"), None);
+        assert_eq!(countdown_text(Some(12), None, "·").unwrap(), "Clipboard clears in 12s");
+        assert_eq!(countdown_text(None, Some(5), "·").unwrap(), "TOTP 5s");
+        assert_eq!(countdown_text(Some(3), Some(25), "|").unwrap(), "Clipboard clears in 3s | TOTP 25s");
+    }
+
+    #[test]
+    fn test_watch_text() {
+        let mut state = AppState::new();
+        assert_eq!(watch_text(&state, 100), None);
+
+        let code = crate::state::FetchedTotp { code: "123456".to_string(), expires_at: 120, period: 30 };
+        state.ui.totp_watch = Some(crate::state::TotpWatch { item_id: "1".to_string(), name: "GitHub".to_string(), code: Some(code), fetching: false });
+        assert_eq!(watch_text(&state, 100).unwrap(), "GitHub 123456 (20s)");
+        // An expired code waits for the next one
+        assert_eq!(watch_text(&state, 120).unwrap(), "GitHub ...");
+    }
+
+    #[test]
+    fn test_context_text() {
+        let mut state = AppState::new();
+        assert_eq!(context_text(&state, None, "·"), "All · By name");
+
+        state.set_item_type_filter(Some(ItemType::Card));
+        state.set_folder_filter(Some("Work".to_string()));
+        state.set_filter_query("visa".to_string());
+        state.vault.cached_at = Some(chrono::Utc::now());
+        assert_eq!(context_text(&state, Some("home"), "|"), "Cards | Folder: Work | By match | Offline | Profile: home");
+
+        let view = crate::config::ViewConfig {
+            name: "Work".to_string(),
+            item_type: Some(crate::args::TabArg::Login),
+            hide_types: Vec::new(),
+            owner: Some("Personal".to_string()),
+            folder: None,
+            query: None,
+        };
+        state.apply_view(Some(&view));
+        assert_eq!(context_text(&state, None, "·"), "View: Work · Logins · Owner: Personal · By name · Offline");
+        state.apply_view(None);
+        assert_eq!(context_text(&state, None, "·"), "All · By name · Offline");
+    }
+}
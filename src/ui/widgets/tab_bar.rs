@@ -1,5 +1,6 @@
 use crate::state::AppState;
 use crate::types::ItemType;
+use crate::ui::symbols;
 use ratatui::{
     layout::Rect,
     style::{Color, Style, Stylize},
@@ -27,7 +28,8 @@ enum TabType {
 impl TabType {
     fn from_item_type(item_type: Option<ItemType>) -> Self {
         match item_type {
-            None => TabType::All,
+            // Unknown types have no tab of their own and are only listed under All
+            None | Some(ItemType::Unknown(_)) => TabType::All,
             Some(ItemType::Login) => TabType::Login,
             Some(ItemType::SecureNote) => TabType::SecureNote,
             Some(ItemType::Card) => TabType::Card,
@@ -36,28 +38,34 @@ impl TabType {
     }
 
     fn get_count(&self, state: &AppState) -> usize {
+        // Counts only include the items of the selected owner and folder
+        let items = state.vault.vault_items.iter().filter(|item| state.vault.in_scope(item));
         match self {
-            TabType::All => state.vault.vault_items.len(),
-            TabType::Login => state.vault.vault_items.iter()
+            TabType::All => items.count(),
+            TabType::Login => items
                 .filter(|item| item.item_type == ItemType::Login)
                 .count(),
-            TabType::SecureNote => state.vault.vault_items.iter()
+            TabType::SecureNote => items
                 .filter(|item| item.item_type == ItemType::SecureNote)
                 .count(),
-            TabType::Card => state.vault.vault_items.iter()
+            TabType::Card => items
                 .filter(|item| item.item_type == ItemType::Card)
                 .count(),
-            TabType::Identity => state.vault.vault_items.iter()
+            TabType::Identity => items
                 .filter(|item| item.item_type == ItemType::Identity)
                 .count(),
         }
     }
 
-    fn title(&self, state: &AppState) -> Line<'static> {
+    fn title(&self, state: &AppState, active: bool) -> Line<'static> {
         let count = self.get_count(state);
-        format!("{} ({})", self, count)
-            .fg(Color::White)
-            .into()
+        // The active tab is only highlighted by color, bracket it too
+        let title = if active && state.ui.accessible {
+            format!("[{} ({})]", self, count)
+        } else {
+            format!("{} ({})", self, count)
+        };
+        title.fg(Color::White).into()
     }
 
     fn highlight_style(&self) -> Style {
@@ -73,7 +81,7 @@ pub fn render(frame: &mut Frame, area: Rect, state: &mut AppState) {
     
     // Create tab titles with counts
     let titles: Vec<Line> = TabType::iter()
-        .map(|tab| tab.title(state))
+        .map(|tab| tab.title(state, tab == current_tab))
         .collect();
     
     // Get the selected tab index
@@ -81,12 +89,18 @@ pub fn render(frame: &mut Frame, area: Rect, state: &mut AppState) {
         .position(|tab| tab == current_tab)
         .unwrap_or(0);
     
+    let mut scope = format!(" M-O Owner: {} ", state.vault.owner_filter_label());
+    if let Some(folder) = state.vault.folder_filter() {
+        scope.push_str(&format!("{} Folder: {} ", symbols::symbols(state).separator, folder));
+    }
+
     // Create the Tabs widget
     let tabs = Tabs::new(titles)
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .title(" Item Types ")
+                .title(Line::from(scope).right_aligned())
         )
         .select(selected_index)
         .highlight_style(current_tab.highlight_style())
@@ -0,0 +1,64 @@
+use crate::state::TextInput;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// The part of the input that fits in `width` cells with the cursor in view, and the cursor's column in it
+/// With a mask, each typed character is shown as one mask character
+pub fn visible(input: &TextInput, width: usize, mask: Option<char>) -> (String, u16) {
+    let shown = |text: &str| match mask {
+        Some(mask) => mask.to_string().repeat(crate::text::grapheme_count(text)),
+        None => text.to_string(),
+    };
+    let before = shown(&input.text()[..input.cursor()]);
+    let after = shown(&input.text()[input.cursor()..]);
+
+    // Keep a cell free for the cursor at the end of the text
+    let before = crate::text::tail_to_width(&before, width.saturating_sub(1));
+    let column = before.width();
+    let mut visible = before.to_string();
+    let mut used = column;
+    for grapheme in after.graphemes(true) {
+        used += grapheme.width();
+        if used > width {
+            break;
+        }
+        visible.push_str(grapheme);
+    }
+    (visible, column as u16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::TextEdit;
+
+    fn input(text: &str) -> TextInput {
+        let mut input = TextInput::new();
+        input.set(text.to_string());
+        input
+    }
+
+    #[test]
+    fn test_visible_keeps_cursor_in_view() {
+        let mut text = input("github.com");
+        assert_eq!(visible(&text, 20, None), ("github.com".to_string(), 10));
+        // Typing at the end scrolls the start of the text away
+        assert_eq!(visible(&text, 5, None), (".com".to_string(), 4));
+
+        text.apply(TextEdit::Home);
+        assert_eq!(visible(&text, 5, None), ("githu".to_string(), 0));
+        text.apply(TextEdit::Right);
+        text.apply(TextEdit::Right);
+        assert_eq!(visible(&text, 5, Some('*')), ("*****".to_string(), 2));
+    }
+
+    #[test]
+    fn test_visible_wide_characters() {
+        let mut text = input("東京都");
+        text.apply(TextEdit::Left);
+        assert_eq!(visible(&text, 4, None), ("京都".to_string(), 2));
+        // The character after the cursor doesn't fit in the one cell left
+        assert_eq!(visible(&text, 3, None), ("京".to_string(), 2));
+        assert_eq!(visible(&text, 4, Some('*')), ("***".to_string(), 2));
+    }
+}
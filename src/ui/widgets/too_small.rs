@@ -0,0 +1,47 @@
+use crate::ui::layout::{MIN_HEIGHT, MIN_WIDTH};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+
+/// Screen shown instead of the main screen when the terminal is below the minimum size
+pub fn render(frame: &mut Frame) {
+    let area = frame.area();
+    let lines = vec![
+        Line::styled("Terminal too small", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+        Line::from(format!("{}x{}, needs {}x{}", area.width, area.height, MIN_WIDTH, MIN_HEIGHT)),
+        Line::styled("Ctrl+Q to quit", Style::default().fg(Color::DarkGray)),
+    ];
+
+    // Center the message vertically when there is room for it
+    let [message] = Layout::vertical([Constraint::Length(lines.len() as u16)])
+        .flex(ratatui::layout::Flex::Center)
+        .areas(area);
+    let paragraph = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_renders_at_any_size() {
+        for (width, height) in [(0, 0), (1, 1), (10, 2), (39, 30), (80, 11)] {
+            let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+            terminal.draw(render).unwrap();
+        }
+
+        let mut terminal = Terminal::new(TestBackend::new(30, 5)).unwrap();
+        terminal.draw(render).unwrap();
+        let text: String = terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("Terminal too small"));
+        assert!(text.contains("30x5, needs 40x12"));
+    }
+}